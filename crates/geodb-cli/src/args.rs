@@ -1,10 +1,15 @@
-use clap::{Parser, Subcommand};
+use crate::table::TableArgs;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use geodb_core::{CountryOrder, ExportLevel};
+use std::path::PathBuf;
 
 /// CLI arguments for geodb-cli
 #[derive(Debug, Parser)]
 #[command(
     name = "geodb",
     version,
+    disable_version_flag = true,
+    arg_required_else_help = true,
     about = "CLI for querying and inspecting the geodb-core geographic database"
 )]
 pub struct CliArgs {
@@ -16,33 +21,507 @@ pub struct CliArgs {
     #[arg(short = 'f', long = "filter", global = true)]
     pub filter: Option<String>,
 
+    /// Config file (default: ./geodb.toml if present)
+    #[arg(short = 'c', long = "config", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Show additional details (e.g. city aliases and regions from city_meta.json)
+    #[arg(short = 'v', long = "verbose", global = true)]
+    pub verbose: bool,
+
+    /// Language for state, country and region names (e.g. de, fr); uses
+    /// state_translations.json, region_translations.json and the dataset's
+    /// country translations
+    #[arg(long = "lang", global = true)]
+    pub lang: Option<String>,
+
+    /// Leave malformed countries, states and cities of the input dataset
+    /// out (listed on stderr) instead of failing; bypasses the binary cache
+    #[arg(long = "skip-invalid", global = true)]
+    pub skip_invalid: bool,
+
+    /// Corrections (added, renamed or deleted cities, coordinates, aliases)
+    /// applied on top of the loaded dataset, e.g. before `build`; entries
+    /// that don't match are listed on stderr
+    #[arg(long = "overrides", global = true)]
+    pub overrides: Option<PathBuf>,
+
+    /// Additional city meta file (aliases, regions) applied after the
+    /// bundled one if that is loaded; repeatable. The `sources` column of
+    /// cities, list-cities and resolve shows which file each value came from
+    #[arg(long = "city-meta", global = true)]
+    pub city_meta: Vec<PathBuf>,
+
+    /// Output format (default: text, for `export` jsonl); a command that
+    /// can't write the format rejects it and lists the ones it can
+    #[arg(long, value_enum, global = true)]
+    pub format: Option<OutputFormat>,
+
+    /// Print version
+    #[arg(short = 'V', action = ArgAction::Version)]
+    pub short_version: Option<bool>,
+
+    /// Print the code and dataset versions (crate version and features,
+    /// dataset file and version, data fingerprint and counts) for bug
+    /// reports; loads the database, so --input and --filter apply
+    #[arg(long = "version")]
+    pub version: bool,
+
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Show a summary of the database contents
-    Stats,
+    ///
+    /// `--format json` prints a versioned report (counts, dataset file,
+    /// fingerprint and version, build date) for release tooling; `--format
+    /// csv` prints the counts and dataset version
+    Stats {
+        /// Write the report to this file instead of stdout
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+
+        /// Dataset version recorded in the report (e.g. 2024.06.01)
+        #[arg(long)]
+        dataset_version: Option<String>,
+    },
+
+    /// Show the resolved dataset and cache files and which one a load would
+    /// use, or that the embedded database is used
+    Paths,
 
-    /// List all countries
-    Countries,
+    /// Show the size breakdown of a binary database (countries, states,
+    /// cities, strings, largest countries) without loading it as a whole
+    Inspect {
+        /// Binary database (`*.bin`, optionally sealed or gzip-compressed)
+        path: PathBuf,
+
+        /// Number of largest countries to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// List all countries as a table
+    ///
+    /// Columns: name, iso2, iso3, capital, region, subregion, phone_code,
+    /// currency, currency_name, currency_symbol, numeric_code, tld,
+    /// nationality, population, gdp, area, density, states, cities
+    /// (default: name, iso2)
+    Countries {
+        #[command(flatten)]
+        table: TableArgs,
+    },
 
     /// Lookup a country by ISO2 or ISO3 code
+    ///
+    /// `--format json|jsonl|csv` prints every column of `countries` for it
     Country {
         /// ISO2 or ISO3 code (e.g. DE, USA)
         code: String,
     },
 
-    /// List all states for a given country
+    /// Show the current local time of a country, in its primary timezone
+    ///
+    /// Uses the standard UTC offset of the dataset; daylight saving time
+    /// isn't applied
+    LocalTime {
+        /// ISO2 or ISO3 code (e.g. DE, USA)
+        code: String,
+    },
+
+    /// List all states for a given country as a table
+    ///
+    /// Columns: name, code, full_code, country, iso2, cities, lat, lng
+    /// (default: name, code, cities)
     States {
         /// ISO2 code of the country
         iso2: String,
+
+        #[command(flatten)]
+        table: TableArgs,
     },
 
-    /// Search for cities containing a substring
+    /// Search for cities containing a substring, listed as a table
+    ///
+    /// Columns: name, state, country, iso2, lat, lng, timezone, aliases,
+    /// regions, sources (default: name, state, country; --verbose adds
+    /// aliases, regions)
     Cities {
         /// Substring to search (case-insensitive)
         query: String,
+
+        #[command(flatten)]
+        table: TableArgs,
+    },
+
+    /// List the cities of a country, or of one of its states, as a table
+    ///
+    /// Columns: name, state, country, iso2, population, lat, lng, timezone,
+    /// aliases, regions, sources (default: name, state; --verbose adds
+    /// aliases, regions). The dataset has no city populations, so `population` and
+    /// --min-population refer to the country
+    ListCities {
+        /// ISO2 or ISO3 code of the country
+        #[arg(long)]
+        country: String,
+
+        /// State code (CA), full code (US-CA) or name; all states if omitted
+        #[arg(long)]
+        state: Option<String>,
+
+        /// List nothing unless the country has at least this many inhabitants
+        #[arg(long)]
+        min_population: Option<i64>,
+
+        #[command(flatten)]
+        table: TableArgs,
+    },
+
+    /// Resolve "city, country" or "city, state, country" to the full
+    /// administrative path of every matching city
+    ///
+    /// Columns: path, id, city, state, state_code, country, iso2, iso3,
+    /// lat, lng, timezone, sources (default: path, id, lat, lng). The country is an
+    /// ISO2/ISO3 code or a name, the state a code, full code or name
+    Resolve {
+        /// Place to resolve, e.g. "Geneva, CH" or "Springfield, IL, US"
+        place: String,
+
+        #[command(flatten)]
+        table: TableArgs,
+    },
+
+    /// List the distinct timezones, currencies or regions with counts, as a
+    /// table
+    ///
+    /// Columns: value, count (timezones count cities, currencies and
+    /// regions count countries)
+    List {
+        /// What to list
+        #[arg(value_enum)]
+        kind: ListKind,
+
+        #[command(flatten)]
+        table: TableArgs,
+    },
+
+    /// Check city_meta.json for ambiguous aliases (exits non-zero on conflicts)
+    AuditAliases {
+        /// Meta file to check (default: the bundled city_meta.json);
+        /// repeatable, to also find conflicts between files
+        #[arg(long)]
+        meta: Vec<PathBuf>,
+    },
+
+    /// Check countries' listed timezones against their cities' timezones
+    /// (exits non-zero on cities using unlisted zones)
+    AuditTimezones {
+        /// Also fail on listed zones no city uses
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Check each country's state and city counts, and its cities' share
+    /// with coordinates and timezones, against a benchmark of minimums
+    /// (exits non-zero on shortfalls)
+    ///
+    /// Without --benchmark, only the counts are reported; --write-benchmark
+    /// turns the current counts into a benchmark for the next dataset update
+    AuditCoverage {
+        /// Benchmark JSON to check against
+        #[arg(long, conflicts_with = "write_benchmark")]
+        benchmark: Option<PathBuf>,
+
+        /// Write a benchmark from the current counts to this file
+        #[arg(long)]
+        write_benchmark: Option<PathBuf>,
+
+        /// How far below the current counts the written minimums are
+        /// (0.05 = 5%)
+        #[arg(long, default_value_t = 0.05, requires = "write_benchmark")]
+        tolerance: f64,
+    },
+
+    /// Ranked search across countries, states, cities and phone codes
+    Search {
+        /// Query to search; omit when using --stdin
+        #[arg(required_unless_present = "stdin")]
+        query: Option<String>,
+
+        /// Read one query per line from stdin (the database is loaded once)
+        #[arg(long, conflicts_with = "query")]
+        stdin: bool,
+
+        /// Maximum number of hits per query (default: [search] limit, or 10)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Skip this many of the best hits, e.g. `--offset 10` for page two
+        /// (default: [search] offset, or 0)
+        #[arg(long)]
+        offset: Option<usize>,
+
+        /// Drop hits scoring below this (default: [search] min_score)
+        #[arg(long, allow_negative_numbers = true)]
+        min_score: Option<i32>,
+
+        /// Also match names this many typos off, e.g. "berln" (default:
+        /// [search] max_edit_distance, 0 = off)
+        #[arg(long)]
+        max_edit_distance: Option<usize>,
     },
+
+    /// Write a binary database with only the cities a query log matched
+    Prune {
+        /// Query log with one query per line (blank lines and `#` comments are skipped)
+        log: PathBuf,
+
+        /// Output file for the pruned binary database
+        #[arg(short = 'o', long)]
+        output: PathBuf,
+
+        /// Hits per query that count as matched (like `search --limit`)
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+    },
+
+    /// Build the binary database from the input dataset (honours --filter),
+    /// or a Rust module of a small subset
+    #[command(group = clap::ArgGroup::new("target").required(true).multiple(true))]
+    Build {
+        /// Write the plain binary database to this file
+        #[arg(short = 'o', long, group = "target")]
+        output: Option<PathBuf>,
+
+        /// Publish a versioned release bundle (artifact, SHA256SUMS,
+        /// provenance.json, index.json) into <DIR>/<version>/
+        #[arg(long, value_name = "DIR", group = "target")]
+        release_bundle: Option<PathBuf>,
+
+        /// Bundle version (default: today's date as YYYY.MM.DD)
+        #[arg(long, requires = "release_bundle")]
+        bundle_version: Option<String>,
+
+        /// Sign the bundle artifact with an ed25519 key file
+        /// (32 raw bytes or 64 hex characters)
+        #[arg(long, value_name = "FILE", requires = "release_bundle")]
+        signing_key: Option<PathBuf>,
+
+        /// Replace an existing bundle of the same version
+        #[arg(long, requires = "release_bundle")]
+        overwrite: bool,
+
+        /// Drop all latitudes and longitudes (name and code search keep
+        /// working); recorded in provenance.json
+        #[arg(long)]
+        strip_coords: bool,
+
+        /// Order of the countries in the artifact: dataset, iso2 or name
+        /// (name and iso2 stay stable when upstream reorders its entries)
+        #[arg(long, default_value_t = CountryOrder::Dataset)]
+        country_order: CountryOrder,
+
+        /// Put a country index in front of the artifact, so
+        /// `GeoDb::load_mmap` opens it without decoding (still readable
+        /// by every other loader)
+        #[arg(long, requires = "output")]
+        indexed: bool,
+
+        /// Write a Rust module with `static` arrays of the countries (down
+        /// to --rust-level) to compile into an application, no
+        /// deserialization at runtime
+        #[arg(long, value_name = "FILE", group = "target")]
+        rust_source: Option<PathBuf>,
+
+        /// How deep --rust-source goes: countries, states or cities
+        #[arg(long, default_value_t = ExportLevel::Countries, requires = "rust_source")]
+        rust_level: ExportLevel,
+
+        /// Download the upstream dataset to --input (default: the bundled
+        /// dataset's path) first; it replaces the file only once verified
+        /// and parsed, and the binary caches are rebuilt
+        #[cfg(feature = "fetch")]
+        #[arg(long)]
+        download: bool,
+
+        /// Where --download fetches the dataset from
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "URL", default_value = geodb_core::fetch::DATASET_URL, requires = "download")]
+        download_url: String,
+
+        /// Expected SHA-256 (hex) of the downloaded dataset
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "HEX", requires = "download")]
+        sha256: Option<String>,
+
+        /// Proxy for --download (e.g. http://proxy:3128); without it the
+        /// HTTP_PROXY / HTTPS_PROXY environment variables apply
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "URL", requires = "download")]
+        proxy: Option<String>,
+
+        /// Start --download over instead of continuing an interrupted one
+        #[cfg(feature = "fetch")]
+        #[arg(long, requires = "download")]
+        no_resume: bool,
+    },
+
+    /// List countries, states and cities added, removed or renamed since an
+    /// older dataset (honours --filter)
+    Diff {
+        /// Older dataset (.json.gz) to compare the input dataset against
+        old: PathBuf,
+
+        /// Append the changes to this changelog file (created if missing)
+        #[arg(long, requires = "dataset_version")]
+        changelog: Option<PathBuf>,
+
+        /// Version of the input dataset, recorded in the changelog
+        #[arg(long)]
+        dataset_version: Option<String>,
+
+        /// Version of the older dataset (default: the changelog's latest)
+        #[arg(long)]
+        previous_version: Option<String>,
+    },
+
+    /// Stream all countries, states or cities to stdout, or write all of
+    /// them as CSV files
+    ///
+    /// The default `--format jsonl` streams NDJSON; `--format csv` writes
+    /// countries.csv, states.csv and cities.csv into --output-dir; an edited
+    /// copy loads again with `--input <DIR>`
+    Export {
+        /// Entities to export: countries, states or cities (jsonl only)
+        #[arg(long, default_value_t = ExportLevel::Cities)]
+        level: ExportLevel,
+
+        /// Directory for the CSV files (created if missing; required with
+        /// `--format csv`)
+        #[arg(short = 'o', long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+    },
+
+    /// Write a deterministic miniature source-format JSON (e.g. for test fixtures)
+    ///
+    /// Reads the input dataset (honours --filter) and keeps a seeded sample of
+    /// countries, all of their states and a few cities per state.
+    Sample {
+        /// Countries to keep
+        #[arg(long, default_value_t = 3)]
+        countries: usize,
+
+        /// Cities to keep per state
+        #[arg(long, default_value_t = 5)]
+        cities_per_state: usize,
+
+        /// Seed; the same seed always yields the same fixture
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Output JSON file (default: stdout)
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Generate a city meta file with aliases from GeoNames dumps
+    ImportGeonames {
+        /// GeoNames places dump (e.g. cities15000.txt or DE.txt)
+        #[arg(long)]
+        places: PathBuf,
+
+        /// GeoNames alternateNamesV2.txt
+        #[arg(long = "alternate-names")]
+        alternate_names: PathBuf,
+
+        /// Comma-separated language codes to keep (e.g. de,fr); all if omitted
+        #[arg(long)]
+        languages: Option<String>,
+
+        /// Keep only names flagged as preferred
+        #[arg(long)]
+        preferred_only: bool,
+
+        /// Maximum aliases per city
+        #[arg(long)]
+        max_aliases: Option<usize>,
+
+        /// Existing meta file to merge into (e.g. the bundled city_meta.json)
+        #[arg(long)]
+        merge: Option<PathBuf>,
+
+        /// Output meta file
+        #[arg(short = 'o', long)]
+        output: PathBuf,
+    },
+}
+
+/// `--format` values, shared by all commands; each command accepts the
+/// ones in [`Commands::formats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text: aligned tables, summaries and reports
+    #[value(alias = "table")]
+    Text,
+    /// Pretty-printed JSON (listings: an array of objects keyed by column)
+    Json,
+    /// Compact JSON, one value per line (listings: one object per row,
+    /// `search`: one array per query)
+    #[value(alias = "ndjson")]
+    Jsonl,
+    /// Comma-separated values with a header line
+    Csv,
+    /// One MessagePack array per query (binary, concatenated for --stdin)
+    #[cfg(feature = "msgpack")]
+    Msgpack,
+}
+
+impl Commands {
+    /// The `--format` values this command can write; the first is its
+    /// default.
+    pub fn formats(&self) -> &'static [OutputFormat] {
+        use OutputFormat::*;
+        match self {
+            Self::Stats { .. } => &[Text, Json, Csv],
+            Self::Countries { .. }
+            | Self::Country { .. }
+            | Self::States { .. }
+            | Self::Cities { .. }
+            | Self::ListCities { .. }
+            | Self::Resolve { .. }
+            | Self::List { .. } => &[Text, Json, Jsonl, Csv],
+            Self::Search { .. } => &[
+                Text,
+                Json,
+                Jsonl,
+                #[cfg(feature = "msgpack")]
+                Msgpack,
+            ],
+            Self::Export { .. } => &[Jsonl, Csv],
+            Self::Inspect { .. }
+            | Self::AuditTimezones { .. }
+            | Self::AuditCoverage { .. }
+            | Self::Diff { .. } => &[Text, Json],
+            Self::Paths
+            | Self::LocalTime { .. }
+            | Self::AuditAliases { .. }
+            | Self::Prune { .. }
+            | Self::Build { .. }
+            | Self::Sample { .. }
+            | Self::ImportGeonames { .. } => &[Text],
+        }
+    }
+}
+
+/// What the `list` command enumerates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListKind {
+    /// IANA timezones of the cities
+    Timezones,
+    /// Currency codes of the countries
+    Currencies,
+    /// Regions (continents) of the countries
+    Regions,
 }
@@ -0,0 +1,63 @@
+//! `geodb.toml` configuration.
+//!
+//! Lets deployments tune ranking and output without recompiling:
+//!
+//! ```toml
+//! [search]
+//! language = "de"   # analyzer: simple (default), ascii, de, tr
+//! include_phone = false   # no country hits for calling codes ("41")
+//! min_score = 40   # drop weak "contains" matches
+//! max_edit_distance = 1   # "berln" finds Berlin
+//! limit = 20   # hits per query of `search` (default 10)
+//!
+//! [search.scoring]
+//! alias_penalty = 10   # rank "Genf" → Geneva further below name matches
+//!
+//! [search.session]
+//! preferred_countries = ["CH"]   # rank Swiss hits slightly higher
+//!
+//! [search.expansions]
+//! st = "saint"
+//! ft = "fort"
+//!
+//! [view]
+//! city_meta = true
+//! translations = false   # leave country and state translations out of JSON
+//! ```
+//!
+//! All sections and fields are optional.
+use anyhow::Context;
+use geodb_core::{SearchOptions, ViewOptions};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Default config file, looked up in the working directory.
+pub const DEFAULT_CONFIG_FILE: &str = "geodb.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub search: SearchOptions,
+    pub view: ViewOptions,
+}
+
+impl Config {
+    /// Load `path`, or `./geodb.toml` if present, or fall back to defaults.
+    ///
+    /// An explicitly given path must exist.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let path = match path {
+            Some(p) => p.to_path_buf(),
+            None => {
+                let default = PathBuf::from(DEFAULT_CONFIG_FILE);
+                if !default.is_file() {
+                    return Ok(Self::default());
+                }
+                default
+            }
+        };
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading config {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("parsing config {}", path.display()))
+    }
+}
@@ -8,22 +8,131 @@
 //! Usage examples
 //! --------------
 //!
-//! - Show overall stats
+//! - Show overall stats, or write a JSON report for release tooling
+//!   (schema version, counts, dataset file and version, build date)
 //!   $ geodb stats
+//!   $ geodb stats --format json --output stats.json --dataset-version 2024.06.01
 //!
-//! - List all countries (optionally with a filter)
+//! - List all countries (optionally with a filter). The listings
+//!   (`countries`, `states`, `cities`, `list`) print tables; pick columns with
+//!   `--columns`, order with `--sort <column>[:asc|:desc]`, cut with `--limit`
 //!   $ geodb countries
 //!   $ geodb --filter=US,DE countries
+//!   $ geodb countries --sort area
+//!   $ geodb countries --columns name,iso2,population --sort population:desc --limit 20
 //!
-//! - Show details for a country by code (ISO2 or ISO3, case-insensitive)
+//! - Machine-readable output: the global `--format text|json|jsonl|csv`
+//!   applies to every command that can write it (listings and `country`
+//!   key JSON objects by column name; `stats` takes text, json and csv,
+//!   `search` adds msgpack, the audits, `diff` and `inspect` take json);
+//!   other formats are rejected with the ones the command supports
+//!   $ geodb cities berlin --format json | jq -r '.[].state'
+//!   $ geodb country de --format json
+//!
+//! - Show details for a country by code (ISO2 or ISO3, case-insensitive;
+//!   `--verbose` adds measurement system, date format and first weekday)
 //!   $ geodb country us
 //!   $ geodb country deu
 //!
-//! - List states/regions for a country (by ISO2)
+//! - List states/regions for a country (by ISO2); `--lang` picks translated
+//!   names where available (also for country names, regions and subregions
+//!   in `countries` and `country`)
 //!   $ geodb states US
+//!   $ geodb --lang fr states CH
+//!
+//! - List the cities of a country or one of its states (state by code,
+//!   full code or name), e.g. sorted by name
+//!   $ geodb list-cities --country US --state CA --sort name
+//!   $ geodb list-cities --country DE --state Bavaria --format csv
+//!
+//! - Resolve "city, country" or "city, state, country" to the full path
+//!   with ids, codes and coordinates (the country by code or name)
+//!   $ geodb resolve "Geneva, CH"
+//!   $ geodb resolve "Springfield, IL, US" --format json
+//!
+//! - List the distinct timezones, currencies or regions with counts
+//!   $ geodb list timezones --sort count
+//!   $ geodb -f DE,AT,CH list currencies
 //!
-//! - Search cities by substring
+//! - Search cities by substring (`--verbose` adds aliases and regions)
 //!   $ geodb cities berlin
+//!   $ geodb --verbose cities münster
+//!
+//! - Ranked search; `--stdin` reads one query per line (bulk geocoding),
+//!   `--limit`/`--offset` page through the hits
+//!   $ geodb search "berlin de"
+//!   $ geodb search san --limit 10 --offset 10 --min-score 40
+//!   $ printf 'berlin\nparis\n' | geodb search --stdin --format jsonl
+//!   $ geodb search berlin --format msgpack > berlin.msgpack   # feature `msgpack`
+//!
+//! - Build a small database with only the cities a query log matched
+//!   (countries and states are kept)
+//!   $ geodb prune queries.log -o kiosk.bin
+//!
+//! - Build the binary database, or publish a versioned release bundle
+//!   (artifact, SHA256SUMS, provenance.json and index.json in
+//!   `dist/<version>/`, optionally signed), optionally without coordinates
+//!   or with the countries in a fixed order
+//!   $ geodb -f DE,AT,CH build -o dach.bin
+//!   $ geodb build -o kiosk.bin --strip-coords
+//!   $ geodb build -o geodb.bin --country-order iso2
+//!   $ geodb build --release-bundle dist --bundle-version 2024.06.01 --signing-key release.key
+//!   $ geodb build -o geodb.bin --indexed   # for GeoDb::load_mmap
+//!   $ geodb build -o geodb.bin --download --sha256 <hex>   # feature `fetch`
+//!   $ geodb build -o geodb.bin --download --proxy http://proxy:3128
+//!   $ geodb -f DE,AT,CH build --rust-source src/geodata.rs --rust-level states
+//!
+//! - List what changed since an older dataset, and record it in a
+//!   changelog for `GeoDb::changes_since`
+//!   $ geodb diff old.json.gz
+//!   $ geodb diff old.json.gz --changelog changelog.json --dataset-version 2024.06.01 \
+//!   --previous-version 2024.05.01
+//!
+//! - Stream all cities (or `--level countries|states`) as NDJSON
+//!   $ geodb export --format jsonl --level cities | jq -c 'select(.country_iso2 == "DE")'
+//!
+//! - Export to CSV for corrections in a spreadsheet, and build from the
+//!   edited files (`--input` takes the directory)
+//!   $ geodb -f DE export --format csv -o corrections/
+//!   $ geodb -i corrections/ build -o geodb.bin
+//!
+//! - Apply local corrections (add, rename or delete cities, fix
+//!   coordinates, add aliases) on top of the dataset, e.g. baked into a build
+//!   $ geodb --overrides overrides.json build -o geodb.bin
+//!
+//! - Generate city aliases from GeoNames dumps (optionally merged into an
+//!   existing meta file)
+//!   $ geodb -f DE import-geonames --places DE.txt --alternate-names alternateNamesV2.txt \
+//!   --languages de,en -o city_meta.json
+//!
+//! - Check city_meta.json for ambiguous aliases (`--meta` repeatable, to
+//!   check several meta files together)
+//!   $ geodb audit-aliases
+//!
+//! - Show where a city's aliases and corrections came from when stacking
+//!   meta and overrides files
+//!   $ geodb -v --city-meta extra_meta.json --overrides overrides.json \
+//!   resolve "Geneva, CH" --columns path,sources
+//!
+//! - Check country timezones against city timezones (`--strict` also fails
+//!   on listed zones no city uses)
+//!   $ geodb audit-timezones --format json > tz-report.json
+//!
+//! - Check per-country state and city counts against a benchmark taken
+//!   from an earlier dataset (exits non-zero on shortfalls, e.g. in CI)
+//!   $ geodb -i old.json.gz audit-coverage --write-benchmark coverage.json --tolerance 0.05
+//!   $ geodb -i new.json.gz audit-coverage --benchmark coverage.json --format json
+//!
+//! - Show which dataset and cache files would be used
+//!   $ geodb paths
+//!
+//! - Print the code and dataset versions for a bug report (`-V` prints
+//!   just the CLI version)
+//!   $ geodb -f DE --version
+//!
+//! - Show what a binary database is made of (bytes per level, strings,
+//!   largest countries), e.g. after an artifact grew
+//!   $ geodb inspect dist/2024.06.01/geodb.bin --top 5
 //!
 //! Data source
 //! -----------
@@ -31,18 +140,52 @@
 //! By default, the CLI loads the compressed dataset bundled with the
 //! `geodb-core` crate and automatically caches a binary version next to it
 //! for fast subsequent runs. Use `--input <path>` to point to a custom
-//! `.json.gz` dataset and `--filter <ISO2,ISO2,...>` to restrict loading to
-//! specific countries for speed.
+//! `.json.gz` dataset (or a directory written by `export --format csv`) and
+//! `--filter <ISO2,ISO2,...>` to restrict loading to specific countries for
+//! speed.
+//!
+//! Search expansions and view options can be set in a `geodb.toml` (in the
+//! working directory, or via `--config <path>`); see `config.rs`.
+//!
+//! Built with `--features embed-data`, the CLI carries the prebuilt database
+//! inside the binary and uses it unless `--input` is given (not with the
+//! `districts` feature: the embedded database has no districts).
 //!
 //! See also: the repository README for more details and examples.
 mod args;
+mod config;
+mod table;
 
-use crate::args::{CliArgs, Commands};
-use clap::Parser;
-use geodb_core::{GeoDb, StandardBackend};
+use crate::args::{CliArgs, Commands, ListKind, OutputFormat};
+use crate::config::Config;
+use crate::table::{Cell, Table};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, FromArgMatches, ValueEnum};
+use geodb_core::alias::CityMetaFile;
+use geodb_core::bundle::{self, BundleOptions};
+use geodb_core::fixture::{sample_source_dataset, FixtureOptions};
+use geodb_core::geonames::GeonamesImportOptions;
+use geodb_core::inspect::{inspect_bytes, ArtifactReport, Compression};
+use geodb_core::signed::SigningKey;
+use geodb_core::{
+    render_hits_with, ChangeSet, Changelog, City, CityMetaIndex, Country, CountryLocaleTable,
+    CountrySort, CoverageBenchmark, DatasetSource, GeoDb, GeoError, LoadSource, Overrides,
+    PlaceQuery, RegionTranslationTable, ResponseFormat, SmartItem, StandardBackend, State,
+    StateTranslationTable, TimezoneIssueKind,
+};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
 
 fn main() -> anyhow::Result<()> {
-    let args = CliArgs::parse();
+    let matches = CliArgs::command().get_matches();
+    let args = CliArgs::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let config = Config::load(args.config.as_deref())?;
+
+    // With `embed-data`, no --input means: use the database compiled into the binary
+    #[cfg(all(feature = "embed-data", not(feature = "districts")))]
+    let use_embedded = args.input.is_none();
+    #[cfg(not(all(feature = "embed-data", not(feature = "districts"))))]
+    let use_embedded = false;
 
     // Determine input file (default JSON.gz inside geodb-core)
     let input_path = args.input.unwrap_or_else(|| {
@@ -58,63 +201,1197 @@ fn main() -> anyhow::Result<()> {
             .collect()
     });
 
-    // Load DB (with filter if any)
     let filter_slice = iso_filter.as_deref();
-    let db = GeoDb::<StandardBackend>::load_from_path(&input_path, filter_slice)?;
 
-    match args.command {
-        Commands::Stats => {
-            let stats = db.stats();
-            println!("Database statistics:");
-            println!("  Countries: {}", stats.countries);
-            println!("  States/Regions: {}", stats.states);
-            println!("  Cities: {}", stats.cities);
+    // Extended `--version`: describes the data as well, so it loads the database
+    if args.version {
+        let db = if use_embedded {
+            load_embedded(filter_slice)?
+        } else {
+            load_dataset(&input_path, filter_slice, args.skip_invalid)?
+        };
+        let source = (!use_embedded).then(|| DatasetSource::from_path(&input_path));
+        print_version(&db, source.as_ref());
+        return Ok(());
+    }
+    let Some(command) = args.command else {
+        CliArgs::command()
+            .error(ErrorKind::MissingSubcommand, "a subcommand is required")
+            .exit()
+    };
+    let formats = command.formats();
+    let format = match args.format {
+        None => formats[0],
+        Some(format) if formats.contains(&format) => format,
+        Some(format) => {
+            let name = |f: &OutputFormat| f.to_possible_value().expect("no skipped values");
+            let supported: Vec<_> = formats
+                .iter()
+                .map(|f| name(f).get_name().to_owned())
+                .collect();
+            CliArgs::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "`{}` can't write --format {} (supported: {})",
+                        matches.subcommand_name().unwrap_or_default(),
+                        name(&format).get_name(),
+                        supported.join(", ")
+                    ),
+                )
+                .exit()
+        }
+    };
+
+    // Diagnostics only; don't load (and possibly rebuild) the database
+    if let Commands::Paths = command {
+        print_paths(&input_path, filter_slice, use_embedded);
+        return Ok(());
+    }
+
+    // Reads an artifact on its own, independent of --input
+    if let Commands::Inspect { path, top } = &command {
+        let bytes =
+            std::fs::read(path).map_err(|e| anyhow::anyhow!("reading {}: {e}", path.display()))?;
+        let report = inspect_bytes::<StandardBackend>(&bytes)?;
+        let mut out = io::stdout().lock();
+        if format == OutputFormat::Json {
+            serde_json::to_writer_pretty(&mut out, &report)?;
+            writeln!(out)?;
+        } else {
+            write_inspect(&mut out, &path.display().to_string(), &report, *top)?;
+        }
+        return Ok(());
+    }
+
+    // Works on the source JSON itself, not on a built database
+    if let Commands::Sample {
+        countries,
+        cities_per_state,
+        seed,
+        output,
+    } = &command
+    {
+        let options = FixtureOptions {
+            countries: *countries,
+            cities_per_state: *cities_per_state,
+            seed: *seed,
+        };
+        let fixture = sample_source_dataset(&input_path, filter_slice, &options)?;
+        let sampled = fixture.as_array().map_or(0, Vec::len);
+        match output {
+            Some(path) => {
+                let mut out = BufWriter::new(std::fs::File::create(path)?);
+                serde_json::to_writer_pretty(&mut out, &fixture)?;
+                writeln!(out)?;
+                out.flush()?;
+                eprintln!("Wrote {} ({sampled} countries)", path.display());
+            }
+            None => {
+                let mut out = io::stdout().lock();
+                serde_json::to_writer_pretty(&mut out, &fixture)?;
+                writeln!(out)?;
+            }
+        }
+        return Ok(());
+    }
+
+    // Replace the dataset before loading it; the load then hits the fresh cache
+    #[cfg(feature = "fetch")]
+    if let Commands::Build {
+        download: true,
+        download_url,
+        sha256,
+        proxy,
+        no_resume,
+        ..
+    } = &command
+    {
+        if use_embedded {
+            anyhow::bail!("--download needs --input when the database is embedded");
+        }
+        let options = geodb_core::fetch::DownloadOptions {
+            proxy: proxy.clone(),
+            expected_sha256: sha256.clone(),
+            resume: !no_resume,
+            ..Default::default()
+        };
+        download_dataset(&input_path, download_url, options, filter_slice)?;
+    }
+
+    // Load DB (with filter if any)
+    let mut db = if use_embedded {
+        load_embedded(filter_slice)?
+    } else {
+        load_dataset(&input_path, filter_slice, args.skip_invalid)?
+    };
+
+    // Verbose output includes city aliases/regions from the bundled city meta
+    if args.verbose || config.view.city_meta {
+        match CityMetaIndex::load_default() {
+            Ok(index) => {
+                db.apply_city_meta(&index);
+            }
+            Err(e) => eprintln!("warning: could not load city meta: {e}"),
+        }
+    }
+    for path in &args.city_meta {
+        db.apply_city_meta(&CityMetaIndex::load_from_path(path)?);
+    }
+    if let Some(path) = &args.overrides {
+        let overrides = Overrides::load_from_path(path)?;
+        for skipped in db.apply_overrides(&overrides) {
+            eprintln!("warning: skipped {skipped}");
+        }
+    }
+    if args.verbose {
+        match CountryLocaleTable::load_default() {
+            Ok(table) => {
+                db.apply_country_locale(&table);
+            }
+            Err(e) => eprintln!("warning: could not load country locale: {e}"),
+        }
+    }
+    if args.lang.is_some() {
+        match StateTranslationTable::load_default() {
+            Ok(table) => {
+                db.apply_state_translations(&table);
+            }
+            Err(e) => eprintln!("warning: could not load state translations: {e}"),
+        }
+        match RegionTranslationTable::load_default() {
+            Ok(table) => {
+                db.apply_region_translations(&table);
+            }
+            Err(e) => eprintln!("warning: could not load region translations: {e}"),
+        }
+    }
+
+    match command {
+        Commands::Paths | Commands::Inspect { .. } | Commands::Sample { .. } => {
+            unreachable!("handled before loading")
+        }
+
+        Commands::Stats {
+            output,
+            dataset_version,
+        } => {
+            let mut source = if use_embedded {
+                DatasetSource::default()
+            } else {
+                DatasetSource::from_path(&input_path)
+            };
+            source.version = dataset_version;
+            let report = db.dataset_stats(source, filter_slice.unwrap_or_default());
+
+            let mut out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(BufWriter::new(std::fs::File::create(path)?)),
+                None => Box::new(io::stdout().lock()),
+            };
+            match format {
+                OutputFormat::Json => {
+                    serde_json::to_writer_pretty(&mut out, &report)?;
+                    writeln!(out)?;
+                }
+                OutputFormat::Csv => {
+                    writeln!(out, "countries,states,cities,dataset_version")?;
+                    writeln!(
+                        out,
+                        "{},{},{},{}",
+                        report.countries,
+                        report.states,
+                        report.cities,
+                        report.dataset.version.as_deref().unwrap_or_default()
+                    )?;
+                }
+                _ => {
+                    writeln!(out, "Database statistics:")?;
+                    writeln!(out, "  Countries: {}", report.countries)?;
+                    writeln!(out, "  States/Regions: {}", report.states)?;
+                    writeln!(out, "  Cities: {}", report.cities)?;
+                    if let Some(version) = &report.dataset.version {
+                        writeln!(out, "  Dataset version: {version}")?;
+                    }
+                }
+            }
+            out.flush()?;
+            if let Some(path) = output {
+                eprintln!("Wrote {}", path.display());
+            }
         }
 
-        Commands::Countries => {
-            for c in db.countries() {
-                println!("{} ({})", c.name(), c.iso2());
+        Commands::Countries { table } => {
+            let lang = args.lang.as_deref();
+            let mut rows = db.countries_sorted(CountrySort::Name);
+            if let Some(lang) = lang {
+                rows.sort_by_cached_key(|c| c.localized_name(lang).to_lowercase());
             }
+            country_table(rows, lang).print(&table, format)?;
         }
 
         Commands::Country { code } => match db.find_country_by_code(&code) {
+            Some(c) if format != OutputFormat::Text => {
+                country_table(vec![c], args.lang.as_deref()).print_record(format)?
+            }
             Some(c) => {
                 println!("Country: {}", c.name());
                 println!("ISO2: {}", c.iso2());
                 println!("ISO3: {}", c.iso3());
+                if let Some(numeric) = c.numeric_code() {
+                    println!("Numeric code: {numeric}");
+                }
+                if let Some(native) = c.native_name() {
+                    println!("Native name: {native}");
+                }
                 println!("Capital: {:?}", c.capital());
                 println!("Phone Code: {}", c.phone_code());
-                println!("Currency: {}", c.currency());
-                println!("Region: {}", c.region());
+                match (c.currency_name(), c.currency_symbol()) {
+                    (Some(name), Some(symbol)) => {
+                        println!("Currency: {} ({name}, {symbol})", c.currency())
+                    }
+                    (Some(extra), None) | (None, Some(extra)) => {
+                        println!("Currency: {} ({extra})", c.currency())
+                    }
+                    (None, None) => println!("Currency: {}", c.currency()),
+                }
+                if let Some(tld) = c.tld() {
+                    println!("TLD: {tld}");
+                }
+                let lang = args.lang.as_deref();
+                println!(
+                    "Region: {}",
+                    lang.map_or(c.region(), |l| c.region_localized(l))
+                );
+                let subregion = match lang {
+                    Some(lang) => c.subregion_localized(lang),
+                    None => c.subregion(),
+                };
+                if let Some(subregion) = subregion {
+                    println!("Subregion: {subregion}");
+                }
+                if let Some(nationality) = c.nationality() {
+                    println!("Nationality: {nationality}");
+                }
                 println!("Population: {:?}", c.population());
-                println!("States: {}", c.states().len());
+                if let Some(gdp) = c.gdp() {
+                    println!("GDP: {gdp}");
+                }
+                if let Some(area) = c.area() {
+                    println!("Area: {area} km²");
+                }
+                if let Some(density) = c.density() {
+                    println!("Density: {density:.1} /km²");
+                }
+                if let Some(tz) = c.primary_timezone() {
+                    match c
+                        .primary_timezone_entry()
+                        .and_then(|e| e.gmt_offset_name.as_deref())
+                    {
+                        Some(offset) => println!("Timezone: {tz} ({offset})"),
+                        None => println!("Timezone: {tz}"),
+                    }
+                }
+                if let Some(locale) = c.locale() {
+                    println!("Measurement: {:?}", locale.measurement_system);
+                    println!("Date format: {}", locale.date_format);
+                    println!("First day of week: {:?}", locale.first_day_of_week);
+                }
+                println!("States: {}", c.state_count());
             }
             None => {
                 eprintln!("No country found for: {code}");
             }
         },
 
-        Commands::States { iso2 } => match db.find_country_by_iso2(&iso2) {
+        Commands::LocalTime { code } => {
+            let Some(c) = db.find_country_by_code(&code) else {
+                anyhow::bail!("No country found for: {code}");
+            };
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let (Some(tz), Some(time)) = (c.primary_timezone(), c.local_time_at(now)) else {
+                anyhow::bail!("No timezone with a UTC offset known for {}", c.name());
+            };
+            let offset = c
+                .primary_timezone_entry()
+                .and_then(|e| e.gmt_offset_name.as_deref())
+                .unwrap_or("");
+            println!("{}: {time} {tz} ({offset}, standard time)", c.name());
+        }
+
+        Commands::States { iso2, table } => match db.find_country_by_iso2(&iso2) {
             Some(c) => {
-                println!("States in {}:", c.name());
-                for s in c.states() {
-                    println!("- {}", s.name());
+                let lang = args.lang.as_deref();
+                let name = move |s: &State<StandardBackend>| match lang {
+                    Some(lang) => s.localized_name(lang).to_string(),
+                    None => s.name().to_string(),
+                };
+                let mut rows: Vec<&State<StandardBackend>> = c.states().iter().collect();
+                if lang.is_some() {
+                    rows.sort_by_cached_key(|s| name(s).to_lowercase());
                 }
+                Table::new(rows, &["name", "code", "cities"])
+                    .text("name", move |s| Cell::text(name(s)))
+                    .text("code", |s| Cell::opt_text(Some(s.state_code())))
+                    .text("full_code", |s| Cell::opt_text(s.full_code.as_deref()))
+                    .text("country", |_| Cell::text(c.name()))
+                    .text("iso2", |_| Cell::text(c.iso2()))
+                    .number("cities", |s| Cell::Int(s.city_count() as i64))
+                    .number("lat", |s| Cell::opt_float(s.latitude, 4))
+                    .number("lng", |s| Cell::opt_float(s.longitude, 4))
+                    .print(&table, format)?;
             }
             None => eprintln!("Country {iso2} not found"),
         },
 
-        Commands::Cities { query } => {
+        Commands::Cities { query, table } => {
             let matches = db.find_cities_by_substring(&query);
-            if matches.is_empty() {
+            if matches.is_empty() && format == OutputFormat::Text {
                 println!("No cities found matching: {query}");
             } else {
-                for (city, state, country) in matches {
-                    println!("{} — {}, {}", city.name(), state.name(), country.name());
+                let defaults: &[&str] = if args.verbose {
+                    &["name", "state", "country", "aliases", "regions"]
+                } else {
+                    &["name", "state", "country"]
+                };
+                let join = |items: Vec<&str>| Cell::opt_text(Some(&items.join(", ")));
+                Table::new(matches, defaults)
+                    .text("name", |r| Cell::text(r.0.name()))
+                    .text("state", |r| Cell::text(r.1.name()))
+                    .text("country", |r| Cell::text(r.2.name()))
+                    .text("iso2", |r| Cell::text(r.2.iso2()))
+                    .number("lat", |r| Cell::opt_float(r.0.latitude, 4))
+                    .number("lng", |r| Cell::opt_float(r.0.longitude, 4))
+                    .text("timezone", |r| Cell::opt_text(r.0.timezone.as_deref()))
+                    .text("aliases", move |r| join(r.0.aliases().collect()))
+                    .text("regions", move |r| join(r.0.regions().collect()))
+                    .text("sources", |r| sources_cell(r.0))
+                    .print(&table, format)?;
+            }
+        }
+
+        Commands::ListCities {
+            country,
+            state,
+            min_population,
+            table,
+        } => {
+            let Some(c) = db.find_country_by_code(&country) else {
+                eprintln!("Country {country} not found");
+                return Ok(());
+            };
+            let states = match &state {
+                Some(state) => match db.find_state(&country, state) {
+                    Some(s) => std::slice::from_ref(s),
+                    None => {
+                        eprintln!("State {state} not found in {}", c.name());
+                        return Ok(());
+                    }
+                },
+                None => db.states_for_country(&country),
+            };
+            let populated =
+                min_population.is_none_or(|min| c.population().is_some_and(|p| p >= min));
+            let rows: Vec<_> = states
+                .iter()
+                .filter(|_| populated)
+                .flat_map(|s| s.cities().iter().map(move |city| (city, s)))
+                .collect();
+            if rows.is_empty() && format == OutputFormat::Text {
+                println!("No cities found");
+                return Ok(());
+            }
+            let defaults: &[&str] = if args.verbose {
+                &["name", "state", "aliases", "regions"]
+            } else {
+                &["name", "state"]
+            };
+            let join = |items: Vec<&str>| Cell::opt_text(Some(&items.join(", ")));
+            Table::new(rows, defaults)
+                .text("name", |r| Cell::text(r.0.name()))
+                .text("state", |r| Cell::text(r.1.name()))
+                .text("country", |_| Cell::text(c.name()))
+                .text("iso2", |_| Cell::text(c.iso2()))
+                .number("population", |_| Cell::opt_int(c.population()))
+                .number("lat", |r| Cell::opt_float(r.0.latitude, 4))
+                .number("lng", |r| Cell::opt_float(r.0.longitude, 4))
+                .text("timezone", |r| Cell::opt_text(r.0.timezone.as_deref()))
+                .text("aliases", move |r| join(r.0.aliases().collect()))
+                .text("regions", move |r| join(r.0.regions().collect()))
+                .text("sources", |r| sources_cell(r.0))
+                .print(&table, format)?;
+        }
+
+        Commands::Resolve { place, table } => {
+            let Some(query) = PlaceQuery::parse(&place) else {
+                anyhow::bail!(
+                    "expected \"city, country\" or \"city, state, country\", got {place:?}"
+                );
+            };
+            let paths = db.resolve_place(&query);
+            if paths.is_empty() {
+                match db.find_country_by_code_or_name(&query.country) {
+                    None => anyhow::bail!("Country {} not found", query.country),
+                    Some(c) => match &query.state {
+                        Some(state) if db.find_state(c.iso2(), state).is_none() => {
+                            anyhow::bail!("State {state} not found in {}", c.name())
+                        }
+                        _ => anyhow::bail!("No city {} found for {place:?}", query.city),
+                    },
+                }
+            }
+            // cities listed directly on a country hang off a nameless state
+            let rows: Vec<_> = paths
+                .into_iter()
+                .map(|p| {
+                    let id = p.city.and_then(|city| db.city_id(city));
+                    (p, id, p.state.filter(|s| !s.is_synthetic()))
+                })
+                .collect();
+            Table::new(rows, &["path", "id", "lat", "lng"])
+                .text("path", |r| Cell::text(r.0.to_string()))
+                .text("id", |r| {
+                    Cell::opt_text(r.1.map(|id| id.to_string()).as_deref())
+                })
+                .text("city", |r| Cell::opt_text(r.0.city.map(|c| c.name())))
+                .text("state", |r| Cell::opt_text(r.2.map(|s| s.name())))
+                .text("state_code", |r| {
+                    Cell::opt_text(r.2.map(|s| s.state_code()))
+                })
+                .text("country", |r| Cell::text(r.0.country.name()))
+                .text("iso2", |r| Cell::text(r.0.country.iso2()))
+                .text("iso3", |r| Cell::text(r.0.country.iso3()))
+                .number("lat", |r| {
+                    Cell::opt_float(r.0.city.and_then(|c| c.latitude), 4)
+                })
+                .number("lng", |r| {
+                    Cell::opt_float(r.0.city.and_then(|c| c.longitude), 4)
+                })
+                .text("timezone", |r| {
+                    Cell::opt_text(r.0.city.and_then(|c| c.timezone.as_deref()))
+                })
+                .text("sources", |r| match r.0.city {
+                    Some(city) => sources_cell(city),
+                    None => Cell::Empty,
+                })
+                .print(&table, format)?;
+        }
+
+        Commands::List { kind, table } => {
+            let rows = match kind {
+                ListKind::Timezones => db.distinct_timezones(),
+                ListKind::Currencies => db.distinct_currencies(),
+                ListKind::Regions => db.distinct_regions(),
+            };
+            Table::new(rows, &["value", "count"])
+                .text("value", |r| Cell::text(r.value))
+                .number("count", |r| Cell::Int(r.count as i64))
+                .print(&table, format)?;
+        }
+
+        Commands::AuditAliases { meta } => {
+            let mut index = CityMetaIndex::default();
+            for path in &meta {
+                index.merge(CityMetaIndex::load_from_path(path)?);
+            }
+            if meta.is_empty() {
+                index = CityMetaIndex::load_default()?;
+            }
+            let conflicts = db.audit_city_aliases(&index);
+            if conflicts.is_empty() {
+                println!("No alias conflicts in {} entries", index.entries.len());
+            } else {
+                for c in &conflicts {
+                    let fmt = |r: &(String, String, String)| format!("{} ({}, {})", r.2, r.1, r.0);
+                    println!("{:?}: \"{}\"", c.kind, c.alias);
+                    for (owner, source) in c.owners.iter().zip(&c.sources) {
+                        println!("  alias of:     {}  [{source}]", fmt(owner));
+                    }
+                    for other in &c.shadowed {
+                        println!("  also a city:  {}", fmt(other));
+                    }
+                    println!("  suggestion:   {}", c.suggestion);
+                }
+                anyhow::bail!("{} alias conflict(s)", conflicts.len());
+            }
+        }
+
+        Commands::AuditTimezones { strict } => {
+            let issues = db.audit_timezones();
+            if format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&issues)?);
+            } else if issues.is_empty() {
+                println!("No timezone issues in {} countries", db.country_count());
+            } else {
+                for issue in &issues {
+                    println!("{:?}: {issue}", issue.kind);
+                    println!("  suggestion:   {}", issue.suggestion);
+                }
+            }
+            let failing = issues
+                .iter()
+                .filter(|i| strict || i.kind == TimezoneIssueKind::CityZoneNotListed)
+                .count();
+            if failing > 0 {
+                anyhow::bail!("{failing} timezone issue(s)");
+            }
+        }
+
+        Commands::AuditCoverage {
+            benchmark,
+            write_benchmark,
+            tolerance,
+        } => {
+            if let Some(path) = write_benchmark {
+                let benchmark = CoverageBenchmark::from_db(&db, tolerance);
+                let mut out = BufWriter::new(std::fs::File::create(&path)?);
+                serde_json::to_writer_pretty(&mut out, &benchmark)?;
+                writeln!(out)?;
+                out.flush()?;
+                eprintln!(
+                    "Wrote {} ({} countries)",
+                    path.display(),
+                    benchmark.countries.len()
+                );
+                return Ok(());
+            }
+            let mut benchmark = match benchmark {
+                Some(path) => CoverageBenchmark::load_from_path(path)?,
+                None => CoverageBenchmark::default(),
+            };
+            if let Some(filter) = filter_slice {
+                benchmark.retain_iso2(filter);
+            }
+            let report = db.audit_coverage(&benchmark);
+            if format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else if benchmark.countries.is_empty() {
+                for c in &report.countries {
+                    println!(
+                        "{}: {} states, {} cities ({} with coordinates, {} with a timezone)",
+                        c.iso2, c.states, c.cities, c.cities_with_coords, c.cities_with_timezone
+                    );
+                }
+            } else if report.passed() {
+                println!(
+                    "Coverage of {} countries meets the benchmark",
+                    benchmark.countries.len()
+                );
+            } else {
+                for issue in &report.issues {
+                    println!("{:?}: {issue}", issue.kind);
+                }
+            }
+            if !report.passed() {
+                anyhow::bail!("{} coverage issue(s)", report.issues.len());
+            }
+        }
+
+        Commands::Search {
+            query,
+            stdin,
+            limit,
+            offset,
+            min_score,
+            max_edit_distance,
+        } => {
+            let mut config = config;
+            if limit.is_some() {
+                config.search.limit = limit;
+            }
+            // unlike the library, the CLI never prints every hit by default
+            config.search.limit.get_or_insert(10);
+            if let Some(offset) = offset {
+                config.search.offset = offset;
+            }
+            if min_score.is_some() {
+                config.search.min_score = min_score;
+            }
+            if let Some(max) = max_edit_distance {
+                config.search.max_edit_distance = max;
+            }
+            let mut out = BufWriter::new(io::stdout().lock());
+            if stdin {
+                for line in io::stdin().lock().lines() {
+                    let line = line?;
+                    // Keep output lines aligned with input lines, even for blanks
+                    write_search(&mut out, &db, line.trim(), &config, format)?;
+                }
+            } else {
+                let query = query.as_deref().unwrap_or("");
+                write_search(&mut out, &db, query, &config, format)?;
+            }
+            out.flush()?;
+        }
+
+        Commands::Prune { log, output, limit } => {
+            let text = std::fs::read_to_string(&log)?;
+            let queries = text
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'));
+            let pruned = db.pruned_for_queries(queries, &config.search, limit);
+            let bytes = pruned.to_bytes()?;
+            std::fs::write(&output, &bytes)?;
+
+            let (before, after) = (db.stats(), pruned.stats());
+            println!(
+                "Kept {} of {} cities ({} countries, {} states)",
+                after.cities, before.cities, after.countries, after.states
+            );
+            println!("Wrote {} ({} bytes)", output.display(), bytes.len());
+        }
+
+        Commands::Build {
+            output,
+            release_bundle,
+            bundle_version,
+            signing_key,
+            overwrite,
+            strip_coords,
+            country_order,
+            indexed,
+            rust_source,
+            rust_level,
+            ..
+        } => {
+            db.sort_countries(country_order);
+            if let Some(output) = &output {
+                let stripped = strip_coords.then(|| db.without_coordinates());
+                let artifact = stripped.as_ref().unwrap_or(&db);
+                let bytes = if indexed {
+                    artifact.to_indexed_bytes()?
+                } else {
+                    artifact.to_bytes()?
+                };
+                std::fs::write(output, &bytes)?;
+                println!("Wrote {} ({} bytes)", output.display(), bytes.len());
+            }
+            if let Some(path) = &rust_source {
+                let stripped = strip_coords.then(|| db.without_coordinates());
+                let source = stripped.as_ref().unwrap_or(&db);
+                let stats = source.write_rust_source(std::fs::File::create(path)?, rust_level)?;
+                println!(
+                    "Wrote {} ({} countries, {} states, {} cities)",
+                    path.display(),
+                    stats.countries,
+                    stats.states,
+                    stats.cities
+                );
+            }
+            if let Some(root) = &release_bundle {
+                let mut options = match bundle_version {
+                    Some(version) => BundleOptions::new(version),
+                    None => BundleOptions::dated(),
+                }
+                .with_filter(filter_slice.unwrap_or_default())
+                .with_overwrite(overwrite)
+                .with_strip_coords(strip_coords);
+                if !use_embedded {
+                    options = options.with_source(&input_path);
+                }
+                if let Some(path) = &signing_key {
+                    options = options.with_signing_key(read_signing_key(path)?);
+                }
+                let index = db.write_release_bundle(root, &options)?;
+                let dir = root.join(&index.version);
+                for artifact in &index.artifacts {
+                    println!(
+                        "Wrote {} ({} bytes, sha256 {}{})",
+                        dir.join(&artifact.file).display(),
+                        artifact.size,
+                        artifact.sha256,
+                        if artifact.signed { ", signed" } else { "" }
+                    );
+                }
+                println!("Index: {}", dir.join(bundle::INDEX_FILE).display());
+            }
+        }
+
+        Commands::Diff {
+            old,
+            changelog,
+            dataset_version,
+            previous_version,
+        } => {
+            let old_db = load_dataset(&old, filter_slice, args.skip_invalid)?;
+            let changes = old_db.diff(&db);
+            if format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&changes)?);
+            } else {
+                for change in &changes {
+                    println!("{change}");
+                }
+                println!("{} change(s)", changes.len());
+            }
+
+            if let (Some(path), Some(version)) = (changelog, dataset_version) {
+                let mut log = if path.exists() {
+                    Changelog::load_from_path(&path)?
+                } else {
+                    Changelog::new()
+                };
+                let previous =
+                    match previous_version.or_else(|| log.latest_version().map(String::from)) {
+                        Some(previous) => previous,
+                        None => anyhow::bail!("--previous-version is required for a new changelog"),
+                    };
+                log.push(ChangeSet {
+                    version,
+                    previous,
+                    changes,
+                })?;
+                log.save_to_path(&path)?;
+                eprintln!("Updated {} ({} versions)", path.display(), log.sets.len());
+            }
+        }
+
+        Commands::Export { level, output_dir } => {
+            let result = match (format, output_dir) {
+                (OutputFormat::Csv, Some(dir)) => {
+                    let stats = db.export_csv_dir(&dir)?;
+                    eprintln!(
+                        "Wrote {} ({} countries, {} states, {} cities)",
+                        dir.display(),
+                        stats.countries,
+                        stats.states,
+                        stats.cities
+                    );
+                    return Ok(());
+                }
+                (OutputFormat::Csv, None) => CliArgs::command()
+                    .error(
+                        ErrorKind::MissingRequiredArgument,
+                        "`export --format csv` needs --output-dir",
+                    )
+                    .exit(),
+                _ => db.export_ndjson(io::stdout().lock(), level),
+            };
+            match result {
+                Ok(written) => eprintln!("Exported {written} {level}"),
+                // the reader went away (e.g. `| head`); not an error for a stream
+                Err(GeoError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Commands::ImportGeonames {
+            places,
+            alternate_names,
+            languages,
+            preferred_only,
+            max_aliases,
+            merge,
+            output,
+        } => {
+            let mut options = GeonamesImportOptions::default().with_preferred_only(preferred_only);
+            if let Some(languages) = languages {
+                options = options.with_languages(
+                    languages
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty()),
+                );
+            }
+            options.max_aliases = max_aliases;
+
+            let import = db.import_geonames_aliases(
+                io::BufReader::new(std::fs::File::open(&places)?),
+                io::BufReader::new(std::fs::File::open(&alternate_names)?),
+                &options,
+            )?;
+            println!(
+                "Matched {} GeoNames places ({} unmatched); {} aliases for {} cities",
+                import.matched_places,
+                import.unmatched_places,
+                import.aliases,
+                import.entries.len()
+            );
+
+            let cities = match merge {
+                Some(path) => {
+                    let mut file: CityMetaFile = serde_json::from_slice(&std::fs::read(&path)?)?;
+                    import.merge_into(&mut file.cities);
+                    file.cities
+                }
+                None => import.entries,
+            };
+            let json = serde_json::to_string_pretty(&CityMetaFile { cities })?;
+            std::fs::write(&output, json)?;
+            println!("Wrote {}", output.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// The `sources` column: where a city's aliases, regions and overridden
+/// fields came from.
+fn sources_cell(city: &City<StandardBackend>) -> Cell {
+    let sources: Vec<String> = city.provenance().iter().map(|p| p.to_string()).collect();
+    Cell::opt_text(Some(&sources.join("; ")))
+}
+
+/// The columns of `countries` (and of `country --format json|csv`); `lang`
+/// picks translated names, regions and subregions.
+fn country_table<'a>(
+    rows: Vec<&'a Country<StandardBackend>>,
+    lang: Option<&'a str>,
+) -> Table<'a, &'a Country<StandardBackend>> {
+    Table::new(rows, &["name", "iso2"])
+        .text("name", move |c| match lang {
+            Some(lang) => Cell::text(c.localized_name(lang)),
+            None => Cell::text(c.name()),
+        })
+        .text("iso2", |c| Cell::text(c.iso2()))
+        .text("iso3", |c| Cell::opt_text(Some(c.iso3())))
+        .text("capital", |c| Cell::opt_text(c.capital()))
+        .text("region", move |c| match lang {
+            Some(lang) => Cell::opt_text(Some(c.region_localized(lang))),
+            None => Cell::opt_text(Some(c.region())),
+        })
+        .text("subregion", move |c| match lang {
+            Some(lang) => Cell::opt_text(c.subregion_localized(lang)),
+            None => Cell::opt_text(c.subregion()),
+        })
+        .text("phone_code", |c| Cell::opt_text(Some(c.phone_code())))
+        .text("currency", |c| Cell::opt_text(Some(c.currency())))
+        .text("currency_name", |c| Cell::opt_text(c.currency_name()))
+        .text("currency_symbol", |c| Cell::opt_text(c.currency_symbol()))
+        .text("numeric_code", |c| Cell::opt_text(c.numeric_code()))
+        .text("tld", |c| Cell::opt_text(c.tld()))
+        .text("nationality", |c| Cell::opt_text(c.nationality()))
+        .number("population", |c| Cell::opt_int(c.population()))
+        .number("gdp", |c| Cell::opt_int(c.gdp()))
+        .number("area", |c| Cell::opt_float(c.area(), 0))
+        .number("density", |c| Cell::opt_float(c.density(), 1))
+        .number("states", |c| Cell::Int(c.state_count() as i64))
+        .number("cities", |c| Cell::Int(c.city_count() as i64))
+}
+
+/// Load the dataset at `path` (or the CSV files in the directory `path`);
+/// with `skip_invalid`, malformed entries of a JSON dataset are left out
+/// and listed on stderr.
+fn load_dataset(
+    path: impl AsRef<Path>,
+    filter: Option<&[&str]>,
+    skip_invalid: bool,
+) -> anyhow::Result<GeoDb<StandardBackend>> {
+    if path.as_ref().is_dir() {
+        let mut db = GeoDb::<StandardBackend>::load_csv_dir(&path)?;
+        if let Some(filter) = filter.filter(|f| !f.is_empty()) {
+            db = db.filtered_by_iso2(filter);
+        }
+        return Ok(db);
+    }
+    if !skip_invalid {
+        return Ok(GeoDb::<StandardBackend>::load_from_path(&path, filter)?);
+    }
+    let (db, skipped) = GeoDb::<StandardBackend>::load_from_path_skipping_invalid(path, filter)?;
+    for entity in &skipped {
+        eprintln!("warning: skipped {entity}");
+    }
+    if !skipped.is_empty() {
+        eprintln!("warning: skipped {} invalid entries", skipped.len());
+    }
+    Ok(db)
+}
+
+#[cfg(feature = "fetch")]
+fn download_dataset(
+    path: &str,
+    url: &str,
+    options: geodb_core::fetch::DownloadOptions,
+    filter: Option<&[&str]>,
+) -> anyhow::Result<()> {
+    use geodb_core::fetch::{Downloader, Progress};
+
+    let downloader = Downloader::new(options)?;
+    eprintln!("Downloading {url}");
+    // one line per tenth of the file, or per MiB when the size is unknown
+    let mut shown = None;
+    let progress = |p: Progress| {
+        let step = match p.total {
+            Some(total) if total > 0 => p.downloaded * 10 / total,
+            _ => p.downloaded >> 20,
+        };
+        if shown.is_some_and(|s| s >= step) {
+            return;
+        }
+        if shown.is_none() && p.downloaded > 0 {
+            eprintln!("  resuming at {} bytes", p.downloaded);
+        }
+        shown = Some(step);
+        match p.total {
+            Some(total) => eprintln!("  {:>3}%  {} of {total} bytes", step * 10, p.downloaded),
+            None => eprintln!("  {} bytes", p.downloaded),
+        }
+    };
+    let (db, report) = downloader.refresh_dataset(url, path, filter, progress)?;
+    let stats = db.stats();
+    eprintln!(
+        "Replaced {} ({} bytes, sha256 {}; {} countries, {} states, {} cities)",
+        report.path.display(),
+        report.bytes,
+        report.sha256,
+        stats.countries,
+        stats.states,
+        stats.cities
+    );
+    Ok(())
+}
+
+#[cfg(all(feature = "embed-data", not(feature = "districts")))]
+fn load_embedded(filter: Option<&[&str]>) -> anyhow::Result<GeoDb<StandardBackend>> {
+    let mut db = GeoDb::<StandardBackend>::load_embedded()?;
+    if let Some(filter) = filter.filter(|f| !f.is_empty()) {
+        db.countries.retain(|c| {
+            filter
+                .iter()
+                .any(|iso2| c.iso2().eq_ignore_ascii_case(iso2))
+        });
+    }
+    Ok(db)
+}
+
+#[cfg(not(all(feature = "embed-data", not(feature = "districts"))))]
+fn load_embedded(_filter: Option<&[&str]>) -> anyhow::Result<GeoDb<StandardBackend>> {
+    anyhow::bail!("geodb-cli was built without an embedded database")
+}
+
+fn write_search(
+    out: &mut impl Write,
+    db: &GeoDb<StandardBackend>,
+    query: &str,
+    config: &Config,
+    format: OutputFormat,
+) -> anyhow::Result<()> {
+    let hits = db.smart_search_with(query, &config.search);
+
+    match format {
+        OutputFormat::Text => {
+            for hit in &hits {
+                let label = match hit.item {
+                    SmartItem::Country(c) => format!("country  {} ({})", c.name(), c.iso2()),
+                    SmartItem::State { country, state } => {
+                        format!("state    {}, {}", state.name(), country.name())
+                    }
+                    // cities listed directly on the country have no state to show
+                    SmartItem::City {
+                        country,
+                        state,
+                        city,
+                    } if state.is_synthetic() => {
+                        format!("city     {} — {}", city.name(), country.name())
+                    }
+                    SmartItem::City {
+                        country,
+                        state,
+                        city,
+                    } => format!(
+                        "city     {} — {}, {}",
+                        city.name(),
+                        state.name(),
+                        country.name()
+                    ),
+                    #[cfg(feature = "districts")]
+                    SmartItem::District {
+                        country,
+                        state,
+                        district,
+                    } => format!(
+                        "district {}, {}, {}",
+                        district.name(),
+                        state.name(),
+                        country.name()
+                    ),
+                };
+                match hit.alias {
+                    Some(alias) => {
+                        writeln!(out, "{:>3}  {label} (also known as {alias})", hit.score)?
+                    }
+                    None => writeln!(out, "{:>3}  {label}", hit.score)?,
                 }
             }
         }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(
+                &mut *out,
+                &render_hits_with(&hits, ResponseFormat::Native, config.view),
+            )?;
+            writeln!(out)?;
+        }
+        OutputFormat::Jsonl => {
+            serde_json::to_writer(
+                &mut *out,
+                &render_hits_with(&hits, ResponseFormat::Native, config.view),
+            )?;
+            writeln!(out)?;
+        }
+        #[cfg(feature = "msgpack")]
+        OutputFormat::Msgpack => {
+            geodb_core::msgpack::write(
+                out,
+                &render_hits_with(&hits, ResponseFormat::Native, config.view),
+            )?;
+        }
+        OutputFormat::Csv => unreachable!("not among the search formats"),
     }
+    Ok(())
+}
+
+/// Read an ed25519 signing key: 32 raw bytes, or 64 hex characters
+/// (surrounding whitespace ignored).
+fn read_signing_key(path: &Path) -> anyhow::Result<SigningKey> {
+    let raw = std::fs::read(path)?;
+    let text = String::from_utf8_lossy(&raw);
+    let hex = text.trim();
+    let bytes: Vec<u8> = if hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        (0..32)
+            .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16))
+            .collect::<Result<_, _>>()?
+    } else {
+        raw
+    };
+    let key: [u8; 32] = bytes.try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "{}: expected 32 raw bytes or 64 hex characters",
+            path.display()
+        )
+    })?;
+    Ok(SigningKey::from_bytes(&key))
+}
+
+fn write_inspect(
+    out: &mut impl Write,
+    name: &str,
+    report: &ArtifactReport,
+    top: usize,
+) -> anyhow::Result<()> {
+    let share = |bytes: u64| 100.0 * bytes as f64 / report.payload_size.max(1) as f64;
 
+    writeln!(out, "File: {name} ({} bytes)", report.size)?;
+    match report.compression {
+        Compression::None => writeln!(out, "Compression: none")?,
+        Compression::Gzip => writeln!(out, "Compression: gzip")?,
+    }
+    match &report.envelope {
+        Some(env) => writeln!(
+            out,
+            "Envelope: v{}, {}, sha256 {}",
+            env.version,
+            if env.signed { "signed" } else { "unsigned" },
+            env.sha256
+        )?,
+        None => writeln!(out, "Envelope: none")?,
+    }
+    writeln!(out, "Payload: {} bytes", report.payload_size)?;
+    writeln!(
+        out,
+        "Entities: {} countries, {} states, {} cities",
+        report.countries, report.states, report.cities
+    )?;
+
+    writeln!(out, "Sections:")?;
+    let sections = &report.sections;
+    for (label, bytes) in [
+        ("countries", sections.countries),
+        ("states", sections.states),
+        ("cities", sections.cities),
+        ("trailing", sections.trailing),
+    ] {
+        writeln!(out, "  {label:<10} {bytes:>12}  {:>5.1} %", share(bytes))?;
+    }
+    writeln!(
+        out,
+        "  {:<10} {:>12}  {:>5.1} %  (inline, part of the above)",
+        "strings",
+        sections.strings,
+        share(sections.strings)
+    )?;
+
+    let mut largest: Vec<_> = report.per_country.iter().collect();
+    largest.sort_by_key(|c| std::cmp::Reverse(c.bytes));
+    if top > 0 && !largest.is_empty() {
+        writeln!(out, "Largest countries:")?;
+        for c in largest.into_iter().take(top) {
+            writeln!(
+                out,
+                "  {}  {:>12}  {:>5.1} %  (states {}, cities {})  {}",
+                c.iso2,
+                c.bytes,
+                share(c.bytes),
+                c.states,
+                c.cities,
+                c.name
+            )?;
+        }
+    }
     Ok(())
 }
+
+fn print_version(db: &GeoDb<StandardBackend>, source: Option<&DatasetSource>) {
+    let info = db
+        .version_info()
+        .with_binding(concat!("geodb-cli ", env!("CARGO_PKG_VERSION")));
+    println!("{}", info.binding.as_deref().unwrap_or_default());
+    println!(
+        "geodb-core {} (features: {})",
+        info.crate_version,
+        info.features.join(", ")
+    );
+    match source {
+        Some(source) => {
+            let file = source.file.as_deref().unwrap_or("?");
+            match &source.fingerprint {
+                Some(fp) => println!("Dataset:  {file}, fingerprint {fp}"),
+                None => println!("Dataset:  {file} (missing, loaded from the binary cache)"),
+            }
+        }
+        None => println!("Dataset:  embedded"),
+    }
+    println!(
+        "Version:  {}",
+        info.dataset_version.as_deref().unwrap_or("unversioned")
+    );
+    println!(
+        "Data:     {}, {} countries, {} states, {} cities",
+        info.data_fingerprint, info.countries, info.states, info.cities
+    );
+}
+
+fn print_paths(input_path: &str, filter: Option<&[&str]>, embedded: bool) {
+    println!(
+        "Data dir: {}",
+        GeoDb::<StandardBackend>::default_data_dir().display()
+    );
+    if embedded {
+        println!("Dataset:  embedded in the binary (pass --input to use a file)");
+        println!("Loader:   embedded database, no cache");
+        return;
+    }
+
+    let status = GeoDb::<StandardBackend>::cache_status(input_path, filter);
+    println!("Dataset:  {}", status.dataset_path.display());
+    match (status.dataset_size, &status.dataset_fingerprint) {
+        (Some(size), Some(fp)) => println!("          {size} bytes, fingerprint {fp}"),
+        (Some(size), None) => println!("          {size} bytes"),
+        _ => println!("          (missing)"),
+    }
+    println!("Cache:    {}", status.cache_path.display());
+    if status.cache_exists {
+        let age = if status.cache_fresh {
+            "fresh"
+        } else {
+            "stale (older than dataset)"
+        };
+        println!("          exists, {age}");
+    } else {
+        println!("          (missing, created on first load)");
+    }
+    match status.source {
+        LoadSource::BinaryCache => println!("Loader:   binary cache"),
+        LoadSource::Source => println!("Loader:   dataset (.json.gz), then write cache"),
+    }
+}
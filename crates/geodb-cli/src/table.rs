@@ -0,0 +1,420 @@
+//! Tables for the listing commands (`countries`, `states`, `cities`,
+//! `list`) and the `country` lookup.
+//!
+//! Each command declares the columns it can show; [`TableArgs`] picks which
+//! of them are printed (`--columns`), how rows are ordered (`--sort`), how
+//! many are kept (`--limit`); the global `--format` picks how they are
+//! written (aligned text, a JSON array of objects keyed by column name, one
+//! such object per line, or CSV). Sorting works on any declared column,
+//! shown or not.
+use crate::args::OutputFormat;
+use clap::Args;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// Table options shared by the listing commands.
+#[derive(Debug, Clone, Default, Args)]
+pub struct TableArgs {
+    /// Comma-separated columns to show, e.g. name,iso2,population
+    /// (an unknown name lists the available ones)
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// Sort by a column, e.g. population:desc; numeric columns default to
+    /// largest first, text columns to A-Z
+    #[arg(long)]
+    pub sort: Option<SortSpec>,
+
+    /// Show at most N rows
+    #[arg(short = 'n', long)]
+    pub limit: Option<usize>,
+}
+
+/// `--sort` value: a column name with an optional `:asc` / `:desc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortSpec {
+    pub column: String,
+    /// `None` uses the column's natural direction.
+    pub descending: Option<bool>,
+}
+
+impl FromStr for SortSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (column, direction) = match s.split_once(':') {
+            Some((column, direction)) => (column, Some(direction)),
+            None => (s, None),
+        };
+        let column = column.trim().to_ascii_lowercase();
+        if column.is_empty() {
+            return Err("missing sort column".to_string());
+        }
+        let descending = match direction.map(|d| d.trim().to_ascii_lowercase()) {
+            None => None,
+            Some(d) if d == "asc" => Some(false),
+            Some(d) if d == "desc" => Some(true),
+            Some(d) => {
+                return Err(format!(
+                    "unknown sort direction '{d}' (expected asc or desc)"
+                ))
+            }
+        };
+        Ok(Self { column, descending })
+    }
+}
+
+/// One table cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Empty,
+    Text(String),
+    Int(i64),
+    /// Value and decimal places to print.
+    Float(f64, usize),
+}
+
+impl Cell {
+    pub fn text(value: impl Into<String>) -> Self {
+        Self::Text(value.into())
+    }
+
+    /// `Empty` for `None` or an empty string.
+    pub fn opt_text(value: Option<&str>) -> Self {
+        match value {
+            Some(v) if !v.is_empty() => Self::text(v),
+            _ => Self::Empty,
+        }
+    }
+
+    pub fn opt_int(value: Option<i64>) -> Self {
+        value.map_or(Self::Empty, Self::Int)
+    }
+
+    pub fn opt_float(value: Option<f64>, decimals: usize) -> Self {
+        value.map_or(Self::Empty, |v| Self::Float(v, decimals))
+    }
+
+    /// CSV field, quoted when it contains a comma, quote or line break.
+    fn to_csv(&self) -> String {
+        let value = match self {
+            Self::Empty => return String::new(),
+            Self::Float(v, _) => v.to_string(),
+            other => other.to_string(),
+        };
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value
+        }
+    }
+
+    fn number(&self) -> Option<f64> {
+        match *self {
+            Self::Int(v) => Some(v as f64),
+            Self::Float(v, _) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Ascending order; empty cells are handled by the caller.
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self.number(), other.number()) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => self
+                .to_string()
+                .to_lowercase()
+                .cmp(&other.to_string().to_lowercase()),
+        }
+    }
+}
+
+/// Machine-readable value: `null` when empty, numbers in full precision.
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Empty => serializer.serialize_none(),
+            Self::Text(v) => serializer.serialize_str(v),
+            Self::Int(v) => serializer.serialize_i64(*v),
+            Self::Float(v, _) => serializer.serialize_f64(*v),
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("-"),
+            Self::Text(v) => f.write_str(v),
+            Self::Int(v) => write!(f, "{v}"),
+            Self::Float(v, decimals) => write!(f, "{v:.decimals$}"),
+        }
+    }
+}
+
+/// A column a listing can show.
+struct Column<'a, T> {
+    name: &'static str,
+    numeric: bool,
+    get: Box<dyn Fn(&T) -> Cell + 'a>,
+}
+
+/// A listing: its rows, the columns it offers and the ones shown by default.
+pub struct Table<'a, T> {
+    rows: Vec<T>,
+    columns: Vec<Column<'a, T>>,
+    default_columns: &'static [&'static str],
+}
+
+impl<'a, T> Table<'a, T> {
+    /// Table over `rows`, showing `default_columns` unless `--columns` is given.
+    pub fn new(rows: Vec<T>, default_columns: &'static [&'static str]) -> Self {
+        Self {
+            rows,
+            columns: Vec::new(),
+            default_columns,
+        }
+    }
+
+    /// Add a text column (left-aligned, sorted A-Z by default).
+    pub fn text(mut self, name: &'static str, get: impl Fn(&T) -> Cell + 'a) -> Self {
+        self.columns.push(Column {
+            name,
+            numeric: false,
+            get: Box::new(get),
+        });
+        self
+    }
+
+    /// Add a numeric column (right-aligned, sorted largest first by default).
+    pub fn number(mut self, name: &'static str, get: impl Fn(&T) -> Cell + 'a) -> Self {
+        self.columns.push(Column {
+            name,
+            numeric: true,
+            get: Box::new(get),
+        });
+        self
+    }
+}
+
+impl<T> Table<'_, T> {
+    fn column(&self, name: &str) -> anyhow::Result<usize> {
+        let name = name.trim().to_ascii_lowercase();
+        self.columns
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or_else(|| {
+                let available: Vec<&str> = self.columns.iter().map(|c| c.name).collect();
+                anyhow::anyhow!(
+                    "unknown column '{name}' (available: {})",
+                    available.join(", ")
+                )
+            })
+    }
+
+    /// Sort, limit and print the table in `format`. Empty cells are null in
+    /// JSON and empty in CSV.
+    ///
+    /// Without `--columns`, the default columns are shown, plus the sort
+    /// column if it isn't one of them. Rows with an empty sort cell come
+    /// last in either direction; ties keep the listing's own order.
+    pub fn write(
+        self,
+        out: &mut impl Write,
+        args: &TableArgs,
+        format: OutputFormat,
+    ) -> anyhow::Result<()> {
+        let (shown, rows) = self.select(args)?;
+        let names: Vec<&str> = shown.iter().map(|&i| self.columns[i].name).collect();
+        match format {
+            OutputFormat::Text => {
+                let numeric: Vec<bool> = shown.iter().map(|&i| self.columns[i].numeric).collect();
+                write_aligned(out, &names, &numeric, &rows)
+            }
+            OutputFormat::Json => {
+                let records: Vec<_> = rows
+                    .iter()
+                    .map(|row| Record { names: &names, row })
+                    .collect();
+                serde_json::to_writer_pretty(&mut *out, &records)?;
+                writeln!(out)?;
+                Ok(())
+            }
+            OutputFormat::Jsonl => {
+                for row in &rows {
+                    serde_json::to_writer(&mut *out, &Record { names: &names, row })?;
+                    writeln!(out)?;
+                }
+                Ok(())
+            }
+            OutputFormat::Csv => {
+                writeln!(out, "{}", names.join(","))?;
+                for row in &rows {
+                    let fields: Vec<String> = row.iter().map(Cell::to_csv).collect();
+                    writeln!(out, "{}", fields.join(","))?;
+                }
+                Ok(())
+            }
+            #[cfg(feature = "msgpack")]
+            OutputFormat::Msgpack => unreachable!("not among the table formats"),
+        }
+    }
+
+    /// Indices of the shown columns, and the sorted, limited rows with the
+    /// cells of those columns only.
+    fn select(&self, args: &TableArgs) -> anyhow::Result<(Vec<usize>, Vec<Vec<Cell>>)> {
+        let mut shown: Vec<usize> = if args.columns.is_empty() {
+            self.default_columns
+                .iter()
+                .map(|name| self.column(name))
+                .collect::<anyhow::Result<_>>()?
+        } else {
+            args.columns
+                .iter()
+                .filter(|name| !name.trim().is_empty())
+                .map(|name| self.column(name))
+                .collect::<anyhow::Result<_>>()?
+        };
+
+        let mut rows: Vec<Vec<Cell>> = self
+            .rows
+            .iter()
+            .map(|row| self.columns.iter().map(|c| (c.get)(row)).collect())
+            .collect();
+
+        if let Some(sort) = &args.sort {
+            let index = self.column(&sort.column)?;
+            if args.columns.is_empty() && !shown.contains(&index) {
+                shown.push(index);
+            }
+            let descending = sort.descending.unwrap_or(self.columns[index].numeric);
+            rows.sort_by(|a, b| match (&a[index], &b[index]) {
+                (Cell::Empty, Cell::Empty) => Ordering::Equal,
+                (Cell::Empty, _) => Ordering::Greater,
+                (_, Cell::Empty) => Ordering::Less,
+                (x, y) if descending => y.compare(x),
+                (x, y) => x.compare(y),
+            });
+        }
+        if let Some(limit) = args.limit {
+            rows.truncate(limit);
+        }
+
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                shown
+                    .iter()
+                    .map(|&i| std::mem::replace(&mut row[i], Cell::Empty))
+                    .collect()
+            })
+            .collect();
+        Ok((shown, rows))
+    }
+
+    /// [`Self::write`] to stdout; a closed pipe (e.g. `| head`) is not an error.
+    pub fn print(self, args: &TableArgs, format: OutputFormat) -> anyhow::Result<()> {
+        to_stdout(|out| self.write(out, args, format))
+    }
+
+    /// Print the first row with every column, for lookups of a single
+    /// entity: a JSON object, or a JSON line or CSV header and line (aligned
+    /// text in text format, though lookups usually print their own layout).
+    pub fn print_record(self, format: OutputFormat) -> anyhow::Result<()> {
+        let args = TableArgs {
+            columns: self.columns.iter().map(|c| c.name.to_string()).collect(),
+            limit: Some(1),
+            ..TableArgs::default()
+        };
+        if format != OutputFormat::Json {
+            return self.print(&args, format);
+        }
+        let (shown, rows) = self.select(&args)?;
+        let names: Vec<&str> = shown.iter().map(|&i| self.columns[i].name).collect();
+        to_stdout(|out| {
+            if let Some(row) = rows.first() {
+                serde_json::to_writer_pretty(&mut *out, &Record { names: &names, row })?;
+                writeln!(out)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Run `write` against a buffered stdout; a closed pipe is not an error.
+fn to_stdout(
+    write: impl FnOnce(&mut io::BufWriter<io::StdoutLock<'static>>) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let result = write(&mut out).and_then(|()| out.flush().map_err(Into::into));
+    match result {
+        Err(e)
+            if e.downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+/// One row as a JSON object, keyed by column name in column order.
+struct Record<'a> {
+    names: &'a [&'a str],
+    row: &'a [Cell],
+}
+
+impl Serialize for Record<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.names.len()))?;
+        for (name, cell) in self.names.iter().zip(self.row) {
+            map.serialize_entry(name, cell)?;
+        }
+        map.end()
+    }
+}
+
+/// Aligned text: text columns padded on the right, numeric ones on the left.
+fn write_aligned(
+    out: &mut impl Write,
+    names: &[&str],
+    numeric: &[bool],
+    rows: &[Vec<Cell>],
+) -> anyhow::Result<()> {
+    let header: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(Cell::to_string).collect())
+        .collect();
+    let widths: Vec<usize> = (0..names.len())
+        .map(|col| {
+            std::iter::once(&header)
+                .chain(&body)
+                .map(|line| line[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for line in std::iter::once(&header).chain(&body) {
+        let mut text = String::new();
+        for (col, value) in line.iter().enumerate() {
+            if col > 0 {
+                text.push_str("  ");
+            }
+            let pad = widths[col] - value.chars().count();
+            if numeric[col] {
+                text.extend(std::iter::repeat_n(' ', pad));
+                text.push_str(value);
+            } else {
+                text.push_str(value);
+                text.extend(std::iter::repeat_n(' ', pad));
+            }
+        }
+        writeln!(out, "{}", text.trim_end())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::process::{Command, Output};
+
+/// Run geodb-cli on Liechtenstein only with `args`.
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_geodb-cli"))
+        .args(["--filter", "LI"])
+        .args(args)
+        .output()
+        .expect("run geodb-cli")
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn one_format_flag_before_or_after_the_command() {
+    let after = stdout(&["cities", "vaduz", "--format", "json"]);
+    let before = stdout(&["--format", "json", "cities", "vaduz"]);
+    assert_eq!(after, before);
+    let rows: serde_json::Value = serde_json::from_str(&after).unwrap();
+    assert_eq!(rows[0]["name"], "Vaduz");
+
+    let lines = stdout(&["states", "LI", "--format", "jsonl", "--sort", "name"]);
+    assert_eq!(lines.lines().count(), 11);
+    for line in lines.lines() {
+        let _: serde_json::Value = serde_json::from_str(line).expect("one object per line");
+    }
+
+    let csv = stdout(&["stats", "--format", "csv"]);
+    assert!(
+        csv.starts_with("countries,states,cities,dataset_version\n1,"),
+        "{csv}"
+    );
+    // `table` is the old name of `text`
+    assert_eq!(
+        stdout(&["country", "LI", "--format", "table"]),
+        stdout(&["country", "LI"])
+    );
+}
+
+#[test]
+fn formats_a_command_cannot_write_are_rejected() {
+    for (args, message) in [
+        (
+            &["search", "vaduz", "--format", "csv"][..],
+            "`search` can't write --format csv (supported: text, json, jsonl",
+        ),
+        (
+            &["stats", "--format", "jsonl"],
+            "`stats` can't write --format jsonl (supported: text, json, csv)",
+        ),
+        (
+            &["paths", "--format", "json"],
+            "`paths` can't write --format json (supported: text)",
+        ),
+        (
+            &["export", "--format", "text"],
+            "`export` can't write --format text (supported: jsonl, csv)",
+        ),
+    ] {
+        let output = run(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{args:?}: {stderr}");
+        assert!(output.stdout.is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Hits of `geodb search berg` with `args`, as JSON objects.
+fn search(args: &[&str]) -> Vec<serde_json::Value> {
+    let output = Command::new(env!("CARGO_BIN_EXE_geodb-cli"))
+        .args(["search", "berg", "--format", "jsonl"])
+        .args(args)
+        .output()
+        .expect("run geodb-cli");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("one JSON array")
+}
+
+fn config(name: &str, text: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("geodb-cli-{name}-{}.toml", std::process::id()));
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn config_limit_and_offset_apply_unless_overridden() {
+    let empty = config("empty", "");
+    let paged = config("paged", "[search]\nlimit = 3\noffset = 2\n");
+    let (empty_arg, paged_arg) = (empty.to_str().unwrap(), paged.to_str().unwrap());
+
+    let first = search(&["--config", empty_arg]);
+    assert_eq!(first.len(), 10);
+    assert_eq!(search(&["--config", paged_arg]), first[2..5]);
+    assert_eq!(
+        search(&["--config", paged_arg, "--limit", "5"]),
+        first[2..7]
+    );
+    assert_eq!(
+        search(&["--config", paged_arg, "--offset", "0"]),
+        first[..3]
+    );
+
+    std::fs::remove_file(&empty).ok();
+    std::fs::remove_file(&paged).ok();
+}
@@ -0,0 +1,19 @@
+//! Binary read path: arbitrary bytes must either fail to decode or yield a
+//! database that can be queried and re-encoded.
+#![no_main]
+
+use geodb_core::{GeoDb, LoadOptions, StandardBackend};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let options = LoadOptions::default().with_max_bytes(1 << 20);
+    let Ok((db, _)) = GeoDb::<StandardBackend>::from_bytes_with(data, &options) else {
+        return;
+    };
+    let _ = db.stats();
+    let _ = db.validate();
+    let _ = db.smart_search("a");
+
+    let bytes = db.to_bytes().expect("re-encode");
+    GeoDb::<StandardBackend>::from_bytes_with(&bytes, &options).expect("round trip");
+});
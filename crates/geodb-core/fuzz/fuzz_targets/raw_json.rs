@@ -0,0 +1,25 @@
+//! Source JSON path: any records the raw structs accept must build into a
+//! database or fail with an error, never panic.
+#![no_main]
+
+use geodb_core::model::CountriesRaw;
+use geodb_core::{try_build_geodb, GeoDb, LoadLimits, LoadOptions, StandardBackend};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = serde_json::from_slice::<CountriesRaw>(data) else {
+        return;
+    };
+    let Ok(db) = try_build_geodb::<StandardBackend>(raw) else {
+        return;
+    };
+    let _ = db.validate();
+    let _ = db.smart_search("a");
+
+    // the JSON path isn't limited, so neither is the round trip
+    let options = LoadOptions::default()
+        .with_max_bytes(u64::MAX)
+        .with_limits(LoadLimits::unlimited());
+    let bytes = db.to_bytes().expect("encode");
+    GeoDb::<StandardBackend>::from_bytes_with(&bytes, &options).expect("round trip");
+});
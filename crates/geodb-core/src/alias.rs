@@ -1,16 +1,16 @@
 // src/alias.rs
-use crate::error::Result;
 use crate::model::{GeoBackend, GeoDb};
+use crate::provenance::MetaField;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+#[cfg(feature = "json")]
+use {crate::error::Result, std::fs, std::path::Path};
 
 /// One canonical city entry with aliases + regions (from JSON).
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CityMeta {
     pub iso2: String,  // "DE"
-    pub state: String, // "Nordrhein-Westfalen"
+    pub state: String, // state name as in the dataset, e.g. "North Rhine-Westphalia"
     pub city: String,  // canonical city name, e.g. "Münster"
     #[serde(default)]
     pub aliases: Vec<String>,
@@ -23,14 +23,24 @@ pub struct CityMetaFile {
     pub cities: Vec<CityMeta>,
 }
 
+/// Source name of indexes built with [`CityMetaIndex::from_entries`].
+const DEFAULT_META_SOURCE: &str = "city meta";
+
 /// In-memory index for fast lookups by alias and by canonical triple.
+///
+/// Indexes from several sidecars can be combined with
+/// [`CityMetaIndex::merge`]; each entry remembers the sidecar it came from
+/// (see [`CityMetaIndex::source`]), which [`GeoDb::apply_city_meta`] records
+/// on the cities and the alias audit reports.
 #[derive(Debug, Default)]
 pub struct CityMetaIndex {
     pub entries: Vec<CityMeta>,
+    /// source name per entry, parallel to `entries`
+    sources: Vec<String>,
     /// alias (lowercased) → index into `entries`
     alias_index: HashMap<String, usize>,
-    /// (iso2.lower, state.lower, city.lower) → index
-    canonical_index: HashMap<(String, String, String), usize>,
+    /// (iso2.lower, state.lower, city.lower) → indices, in order
+    canonical_index: HashMap<(String, String, String), Vec<usize>>,
 }
 
 impl CityMetaIndex {
@@ -39,41 +49,75 @@ impl CityMetaIndex {
     /// Expected format:
     /// {
     ///   "cities": [
-    ///     { "iso2": "DE", "state": "Bayern", "city": "München",
-    ///       "aliases": ["Munich", "Muenchen"],
+    ///     { "iso2": "DE", "state": "Bavaria", "city": "Munich",
+    ///       "aliases": ["München", "Muenchen"],
     ///       "regions": ["Oberbayern"]
     ///     },
     ///     ...
     ///   ]
     /// }
+    ///
+    /// The file name becomes the source of all entries.
+    #[cfg(feature = "json")]
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
         let bytes = fs::read(path)?;
         let file: CityMetaFile = serde_json::from_slice(&bytes)?;
+        Ok(Self::from_entries(file.cities).with_source(crate::provenance::source_name(path)))
+    }
 
+    /// Build an index from meta entries, with `"city meta"` as their
+    /// source (see [`CityMetaIndex::with_source`]).
+    pub fn from_entries(entries: Vec<CityMeta>) -> Self {
         let mut index = CityMetaIndex {
-            entries: file.cities,
+            sources: vec![DEFAULT_META_SOURCE.to_string(); entries.len()],
+            entries,
             alias_index: HashMap::new(),
             canonical_index: HashMap::new(),
         };
+        index.reindex();
+        index
+    }
+
+    /// Set the source name of all entries, e.g. `"geonames"`.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        let source = source.into();
+        self.sources.iter_mut().for_each(|s| s.clone_from(&source));
+        self
+    }
+
+    /// Append the entries of `other`, keeping their sources. Where both
+    /// list an alias, lookups by alias resolve to `other`'s entry.
+    pub fn merge(&mut self, other: CityMetaIndex) {
+        self.entries.extend(other.entries);
+        self.sources.extend(other.sources);
+        self.reindex();
+    }
 
-        for (i, entry) in index.entries.iter().enumerate() {
+    /// Source name of `entries[entry]`.
+    pub fn source(&self, entry: usize) -> &str {
+        &self.sources[entry]
+    }
+
+    fn reindex(&mut self) {
+        self.alias_index.clear();
+        self.canonical_index.clear();
+        for (i, entry) in self.entries.iter().enumerate() {
             let key = (
                 entry.iso2.to_ascii_lowercase(),
                 entry.state.to_ascii_lowercase(),
                 entry.city.to_ascii_lowercase(),
             );
-            index.canonical_index.insert(key, i);
+            self.canonical_index.entry(key).or_default().push(i);
 
             // index all aliases
             for alias in &entry.aliases {
-                index.alias_index.insert(alias.to_ascii_lowercase(), i);
+                self.alias_index.insert(alias.to_ascii_lowercase(), i);
             }
 
             // also index canonical name itself as an alias
-            index.alias_index.insert(entry.city.to_ascii_lowercase(), i);
+            self.alias_index.insert(entry.city.to_ascii_lowercase(), i);
         }
-
-        Ok(index)
     }
 
     /// Find meta entry by alias; optional iso2/state hints for disambiguation.
@@ -103,20 +147,26 @@ impl CityMetaIndex {
         Some(meta)
     }
 
-    /// Lookup by canonical triple (iso2, state, city).
+    /// Lookup by canonical triple (iso2, state, city); the last entry if
+    /// several sidecars list the city.
     pub fn find_canonical(&self, iso2: &str, state: &str, city: &str) -> Option<&CityMeta> {
+        let idx = self.canonical_entries(iso2, state, city).last()?;
+        Some(&self.entries[*idx])
+    }
+
+    /// Indices of all entries for the canonical triple, in order.
+    fn canonical_entries(&self, iso2: &str, state: &str, city: &str) -> &[usize] {
         let key = (
             iso2.to_ascii_lowercase(),
             state.to_ascii_lowercase(),
             city.to_ascii_lowercase(),
         );
-        let idx = self.canonical_index.get(&key)?;
-        Some(&self.entries[*idx])
+        self.canonical_index.get(&key).map_or(&[], Vec::as_slice)
     }
 }
 
 impl<B: GeoBackend> GeoDb<B> {
-    /// Resolve an alias (e.g. "Munich") into (country_iso2, state_name, city_name)
+    /// Resolve an alias (e.g. "Muenchen") into (country_iso2, state_name, city_name)
     /// using the given CityMetaIndex.
     pub fn resolve_city_alias_with_index<'a>(
         &'a self,
@@ -145,14 +195,247 @@ impl<B: GeoBackend> GeoDb<B> {
 
         None
     }
+
+    /// Copy aliases and regions from `index` onto the matching cities, so they
+    /// are available via `City::aliases` / `City::regions` and in city views.
+    ///
+    /// The values replace those previously applied from the same sources
+    /// (and untracked ones); values from other sidecars, e.g. an overrides
+    /// file or another meta file applied before, are kept. Each value's
+    /// source is recorded in [`City::provenance`](crate::City::provenance).
+    ///
+    /// Returns the number of cities updated. Entries without a matching city
+    /// are ignored.
+    pub fn apply_city_meta(&mut self, index: &CityMetaIndex) -> usize {
+        let mut applied = 0;
+        for country in &mut self.countries {
+            for state in &mut country.states {
+                for city in &mut state.cities {
+                    let entries = index.canonical_entries(
+                        country.iso2.as_ref(),
+                        state.name.as_ref(),
+                        city.name.as_ref(),
+                    );
+                    if entries.is_empty() {
+                        continue;
+                    }
+
+                    // keep what other sidecars contributed
+                    let replaced = |source: Option<&str>| {
+                        source.is_none_or(|s| index.sources.iter().any(|own| own == s))
+                    };
+                    let aliases = std::mem::take(&mut city.aliases);
+                    city.aliases = aliases
+                        .into_iter()
+                        .filter(|a| !replaced(city.source_of(MetaField::Alias, Some(a.as_ref()))))
+                        .collect();
+                    let regions = std::mem::take(&mut city.regions);
+                    city.regions = regions
+                        .into_iter()
+                        .filter(|r| !replaced(city.source_of(MetaField::Region, Some(r.as_ref()))))
+                        .collect();
+                    city.provenance.retain(|p| {
+                        !matches!(p.field, MetaField::Alias | MetaField::Region)
+                            || !replaced(Some(&p.source))
+                    });
+
+                    for &i in entries {
+                        let (meta, source) = (&index.entries[i], index.source(i));
+                        for alias in &meta.aliases {
+                            let lower = alias.to_lowercase();
+                            if !city
+                                .aliases
+                                .iter()
+                                .any(|a| a.as_ref().to_lowercase() == lower)
+                            {
+                                city.aliases.push(B::str_from(alias));
+                                city.record_source(MetaField::Alias, Some(alias), source);
+                            }
+                        }
+                        for region in &meta.regions {
+                            let lower = region.to_lowercase();
+                            if !city
+                                .regions
+                                .iter()
+                                .any(|r| r.as_ref().to_lowercase() == lower)
+                            {
+                                city.regions.push(B::str_from(region));
+                                city.record_source(MetaField::Region, Some(region), source);
+                            }
+                        }
+                    }
+                    applied += 1;
+                }
+            }
+        }
+        applied
+    }
 }
 // near the bottom of src/alias.rs
 
 impl CityMetaIndex {
-    /// Load `city_meta.json` from the crate's default `data/` directory.
+    /// Load `city_meta.json` from the crate's default `data/` directory
+    /// (source `city_meta.json`).
+    #[cfg(feature = "json")]
     pub fn load_default() -> Result<Self> {
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
         let path: std::path::PathBuf = [manifest_dir, "data", "city_meta.json"].iter().collect();
         Self::load_from_path(path)
     }
 }
+
+/// Kind of problem found by [`CityMetaIndex::audit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AliasConflictKind {
+    /// The same alias is listed for different cities of one country; lookups
+    /// silently resolve to whichever entry was indexed last.
+    SharedAlias,
+    /// The alias equals the canonical name of a different city in the same
+    /// country, so searching for that city's real name finds this one.
+    ShadowsCanonicalName,
+}
+
+/// A city reference in an [`AliasConflict`]: (iso2, state, city).
+pub type CityRef = (String, String, String);
+
+/// One alias problem with the cities involved and a suggested fix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AliasConflict {
+    pub kind: AliasConflictKind,
+    /// The alias as written in the meta file.
+    pub alias: String,
+    /// The meta entries listing the alias.
+    pub owners: Vec<CityRef>,
+    /// Sidecar listing the alias for each owner, parallel to `owners`.
+    pub sources: Vec<String>,
+    /// Other cities carrying the alias as their canonical name
+    /// (only for [`AliasConflictKind::ShadowsCanonicalName`]).
+    pub shadowed: Vec<CityRef>,
+    pub suggestion: String,
+}
+
+fn city_ref(meta: &CityMeta) -> CityRef {
+    (meta.iso2.clone(), meta.state.clone(), meta.city.clone())
+}
+
+fn city_ref_eq(a: &CityRef, b: &CityRef) -> bool {
+    a.1.eq_ignore_ascii_case(&b.1) && a.2.eq_ignore_ascii_case(&b.2)
+}
+
+impl CityMetaIndex {
+    /// Check the meta entries for ambiguous aliases.
+    ///
+    /// Only looks at the meta file itself; use
+    /// [`GeoDb::audit_city_aliases`] to also check against dataset names.
+    pub fn audit(&self) -> Vec<AliasConflict> {
+        self.audit_with(|_, _| Vec::new())
+    }
+
+    /// Shared audit logic; `canonical_matches(iso2, alias)` returns dataset
+    /// cities of `iso2` named `alias`.
+    fn audit_with(
+        &self,
+        canonical_matches: impl Fn(&str, &str) -> Vec<CityRef>,
+    ) -> Vec<AliasConflict> {
+        // (iso2, alias) → alias as first written + owning entries, in file order
+        let mut by_alias: Vec<(String, Vec<usize>)> = Vec::new();
+        let mut slot: HashMap<(String, String), usize> = HashMap::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            for alias in &entry.aliases {
+                let key = (entry.iso2.to_ascii_lowercase(), alias.to_ascii_lowercase());
+                let pos = *slot.entry(key).or_insert_with(|| {
+                    by_alias.push((alias.clone(), Vec::new()));
+                    by_alias.len() - 1
+                });
+                // the same city may be listed by several merged sidecars
+                let owners = &mut by_alias[pos].1;
+                if !owners
+                    .iter()
+                    .any(|&o| city_ref_eq(&city_ref(&self.entries[o]), &city_ref(entry)))
+                {
+                    owners.push(i);
+                }
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        for (alias, owners) in by_alias {
+            let first = &self.entries[owners[0]];
+            let owner_refs: Vec<CityRef> =
+                owners.iter().map(|&i| city_ref(&self.entries[i])).collect();
+            let sources: Vec<String> = owners.iter().map(|&i| self.sources[i].clone()).collect();
+
+            if owners.len() > 1 {
+                conflicts.push(AliasConflict {
+                    kind: AliasConflictKind::SharedAlias,
+                    suggestion: format!(
+                        "keep \"{alias}\" on one city only, or qualify it (e.g. \"{alias} ({})\")",
+                        first.state
+                    ),
+                    alias: alias.clone(),
+                    owners: owner_refs.clone(),
+                    sources: sources.clone(),
+                    shadowed: Vec::new(),
+                });
+            }
+
+            // Canonical names of other meta entries and of dataset cities
+            let mut shadowed: Vec<CityRef> = Vec::new();
+            let meta_matches = self
+                .entries
+                .iter()
+                .filter(|e| {
+                    e.iso2.eq_ignore_ascii_case(&first.iso2) && e.city.eq_ignore_ascii_case(&alias)
+                })
+                .map(city_ref);
+            for r in meta_matches.chain(canonical_matches(&first.iso2, &alias)) {
+                if !shadowed.iter().any(|s| city_ref_eq(s, &r)) {
+                    shadowed.push(r);
+                }
+            }
+            shadowed.retain(|s| !owner_refs.iter().any(|o| city_ref_eq(o, s)));
+
+            if !shadowed.is_empty() {
+                conflicts.push(AliasConflict {
+                    kind: AliasConflictKind::ShadowsCanonicalName,
+                    suggestion: format!(
+                        "drop alias \"{alias}\" or resolve it with an iso2/state hint; \"{}\" is a real city name",
+                        shadowed[0].2
+                    ),
+                    alias,
+                    owners: owner_refs,
+                    sources,
+                    shadowed,
+                });
+            }
+        }
+
+        conflicts
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Audit `index` like [`CityMetaIndex::audit`], additionally reporting
+    /// aliases that equal the name of another city in the dataset.
+    pub fn audit_city_aliases(&self, index: &CityMetaIndex) -> Vec<AliasConflict> {
+        index.audit_with(|iso2, alias| {
+            let Some(country) = self.find_country_by_iso2(iso2) else {
+                return Vec::new();
+            };
+            country
+                .states()
+                .iter()
+                .flat_map(|s| s.cities().iter().map(move |c| (s, c)))
+                .filter(|(_, c)| c.name().eq_ignore_ascii_case(alias))
+                .map(|(s, c)| {
+                    (
+                        country.iso2().to_string(),
+                        s.name().to_string(),
+                        c.name().to_string(),
+                    )
+                })
+                .collect()
+        })
+    }
+}
@@ -3,11 +3,38 @@
 //! This module exposes thin, serialization-friendly wrappers around the core
 //! model types so that consumers like WASM and CLI can reuse a single place
 //! that defines how a country/state/city is rendered to JSON.
-use crate::model::{City, Country, GeoBackend, State};
-use serde::{ser::SerializeStruct, Serialize, Serializer};
+//!
+//! The field set is versioned by [`VIEW_VERSION`] (also reported by
+//! [`crate::GeoDb::version_info`]). Version 2 gives states and cities their
+//! coordinates and the codes and ids of their parents:
+//!
+//! - country: `kind`, `id`, `name`, `emoji`, `iso2`, `iso3`, codes, currency,
+//!   population, area, region, coordinates, `primary_timezone`,
+//!   `translations`
+//! - state: `kind`, `id`, `name`, `native_name`, `country`, `country_code`,
+//!   `emoji`, `state_code`, `full_code`, `latitude`, `longitude`,
+//!   `translations`
+//! - city: `kind`, `id`, `name`, `country`, `country_code`, `state`,
+//!   `state_id`, `state_code`, `emoji`, `latitude`, `longitude`, `timezone`,
+//!   plus `aliases` and `regions` with [`ViewOptions::city_meta`]
+//! - district (feature `districts`): `kind`, `name`, `code`, `country`,
+//!   `country_code`, `state`, `state_code`, `city_count`
+//!
+//! Ids are the stable [`crate::CountryId`] / [`crate::StateId`] /
+//! [`crate::CityId`] strings; the dataset's numeric ids and city
+//! populations aren't kept in the model. Missing values are `null`, so every
+//! view of a kind has the same keys. [`ViewOptions::translations`] leaves
+//! out the translation maps, the bulk of a country or state view.
+#[cfg(feature = "districts")]
+use crate::model::District;
+use crate::model::{City, Country, GeoBackend, SmartHit, SmartItem, State};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 use std::ops::Not;
 
+/// Version of the view field set; see the [module docs](self).
+pub const VIEW_VERSION: u32 = 2;
+
 /// JSON-serializable view for a Country.
 ///
 /// Keeps the JSON shape centralized so WASM/CLI bindings can be thin and
@@ -15,22 +42,49 @@ use std::ops::Not;
 #[derive(Debug, Clone, Copy)]
 pub struct CountryView<'a, B: GeoBackend>(pub &'a Country<B>);
 
+impl<'a, B: GeoBackend> CountryView<'a, B> {
+    /// Render this country with the given [`ViewOptions`].
+    pub fn with_options(self, options: ViewOptions) -> CountryViewWith<'a, B> {
+        CountryViewWith {
+            view: self,
+            options,
+        }
+    }
+}
+
 impl<'a, B: GeoBackend> Serialize for CountryView<'a, B> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let c = self.0;
-        let mut s = serializer.serialize_struct("Country", 23)?;
+        CountryView(self.0)
+            .with_options(ViewOptions::default())
+            .serialize(serializer)
+    }
+}
+
+/// A [`CountryView`] rendered with explicit [`ViewOptions`].
+#[derive(Debug, Clone, Copy)]
+pub struct CountryViewWith<'a, B: GeoBackend> {
+    pub view: CountryView<'a, B>,
+    pub options: ViewOptions,
+}
+
+impl<'a, B: GeoBackend> Serialize for CountryViewWith<'a, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let c = self.view.0;
+        let len = if self.options.translations { 27 } else { 26 };
+        let mut s = serializer.serialize_struct("Country", len)?;
         s.serialize_field("kind", "country")?;
+        s.serialize_field("id", &c.id())?;
         s.serialize_field("name", c.name())?;
-        s.serialize_field("emoji", &c.emoji.as_ref().map(|v| B::str_to_string(v)))?;
+        s.serialize_field("emoji", &c.emoji())?;
         s.serialize_field("iso2", c.iso2())?;
         s.serialize_field("iso3", &c.iso3.as_ref().map(|v| B::str_to_string(v)))?;
-        s.serialize_field(
-            "numeric_code",
-            &c.numeric_code.as_ref().map(|v| B::str_to_string(v)),
-        )?;
+        s.serialize_field("numeric_code", &c.numeric_code())?;
         s.serialize_field(
             "phonecode",
             &c.phone_code().is_empty().not().then(|| c.phone_code()),
@@ -40,40 +94,30 @@ impl<'a, B: GeoBackend> Serialize for CountryView<'a, B> {
             "currency",
             &(!c.currency().is_empty()).then(|| c.currency()),
         )?;
-        s.serialize_field(
-            "currency_name",
-            &c.currency_name.as_ref().map(|v| B::str_to_string(v)),
-        )?;
-        s.serialize_field(
-            "currency_symbol",
-            &c.currency_symbol.as_ref().map(|v| B::str_to_string(v)),
-        )?;
-        s.serialize_field("tld", &c.tld.as_ref().map(|v| B::str_to_string(v)))?;
-        s.serialize_field(
-            "native_name",
-            &c.native_name.as_ref().map(|v| B::str_to_string(v)),
-        )?;
+        s.serialize_field("currency_name", &c.currency_name())?;
+        s.serialize_field("currency_symbol", &c.currency_symbol())?;
+        s.serialize_field("tld", &c.tld())?;
+        s.serialize_field("native_name", &c.native_name())?;
         s.serialize_field("population", &c.population())?;
-        s.serialize_field("gdp", &c.gdp)?;
+        s.serialize_field("gdp", &c.gdp())?;
+        s.serialize_field("area", &c.area())?;
+        s.serialize_field("density", &c.density())?;
         s.serialize_field("region", &(!c.region().is_empty()).then(|| c.region()))?;
-        s.serialize_field("region_id", &c.region_id)?;
-        s.serialize_field(
-            "subregion",
-            &c.subregion.as_ref().map(|v| B::str_to_string(v)),
-        )?;
-        s.serialize_field("subregion_id", &c.subregion_id)?;
-        s.serialize_field(
-            "nationality",
-            &c.nationality.as_ref().map(|v| B::str_to_string(v)),
-        )?;
-        s.serialize_field("latitude", &c.latitude.map(B::float_to_f64))?;
-        s.serialize_field("longitude", &c.longitude.map(B::float_to_f64))?;
-        let translations: HashMap<String, String> = c
-            .translations
-            .iter()
-            .map(|(k, v)| (k.clone(), B::str_to_string(v)))
-            .collect();
-        s.serialize_field("translations", &translations)?;
+        s.serialize_field("region_id", &c.region_id())?;
+        s.serialize_field("subregion", &c.subregion())?;
+        s.serialize_field("subregion_id", &c.subregion_id())?;
+        s.serialize_field("nationality", &c.nationality())?;
+        s.serialize_field("latitude", &c.latitude())?;
+        s.serialize_field("longitude", &c.longitude())?;
+        s.serialize_field("primary_timezone", &c.primary_timezone())?;
+        if self.options.translations {
+            let translations: HashMap<String, String> = c
+                .translations
+                .iter()
+                .map(|(k, v)| (k.clone(), B::str_to_string(v)))
+                .collect();
+            s.serialize_field("translations", &translations)?;
+        }
         s.end()
     }
 }
@@ -87,17 +131,52 @@ pub struct StateView<'a, B: GeoBackend> {
     pub state: &'a State<B>,
 }
 
+impl<'a, B: GeoBackend> StateView<'a, B> {
+    /// Render this state with the given [`ViewOptions`].
+    pub fn with_options(self, options: ViewOptions) -> StateViewWith<'a, B> {
+        StateViewWith {
+            view: self,
+            options,
+        }
+    }
+}
+
 impl<'a, B: GeoBackend> Serialize for StateView<'a, B> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let country = self.country;
-        let s = self.state;
-        let mut ser = serializer.serialize_struct("State", 6)?;
+        StateView {
+            country: self.country,
+            state: self.state,
+        }
+        .with_options(ViewOptions::default())
+        .serialize(serializer)
+    }
+}
+
+/// A [`StateView`] rendered with explicit [`ViewOptions`].
+#[derive(Debug, Clone, Copy)]
+pub struct StateViewWith<'a, B: GeoBackend> {
+    pub view: StateView<'a, B>,
+    pub options: ViewOptions,
+}
+
+impl<'a, B: GeoBackend> Serialize for StateViewWith<'a, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let country = self.view.country;
+        let s = self.view.state;
+        let len = if self.options.translations { 12 } else { 11 };
+        let mut ser = serializer.serialize_struct("State", len)?;
         ser.serialize_field("kind", "state")?;
+        ser.serialize_field("id", &country.state_id(s))?;
         ser.serialize_field("name", s.name())?;
+        ser.serialize_field("native_name", &s.native_name())?;
         ser.serialize_field("country", country.name())?;
+        ser.serialize_field("country_code", country.iso2())?;
         ser.serialize_field(
             "emoji",
             &country.emoji.as_ref().map(|e| B::str_to_string(e)),
@@ -110,6 +189,11 @@ impl<'a, B: GeoBackend> Serialize for StateView<'a, B> {
             "full_code",
             &s.full_code.as_ref().map(|v| B::str_to_string(v)),
         )?;
+        ser.serialize_field("latitude", &s.latitude.map(B::float_to_f64))?;
+        ser.serialize_field("longitude", &s.longitude.map(B::float_to_f64))?;
+        if self.options.translations {
+            ser.serialize_field("translations", &s.translations)?;
+        }
         ser.end()
     }
 }
@@ -124,23 +208,217 @@ pub struct CityView<'a, B: GeoBackend> {
     pub city: &'a City<B>,
 }
 
+impl<'a, B: GeoBackend> CityView<'a, B> {
+    /// Render this city with the given [`ViewOptions`].
+    pub fn with_options(self, options: ViewOptions) -> CityViewWith<'a, B> {
+        CityViewWith {
+            view: self,
+            options,
+        }
+    }
+}
+
 impl<'a, B: GeoBackend> Serialize for CityView<'a, B> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let country = self.country;
-        let state = self.state;
-        let city = self.city;
-        let mut ser = serializer.serialize_struct("City", 5)?;
+        CityView {
+            country: self.country,
+            state: self.state,
+            city: self.city,
+        }
+        .with_options(ViewOptions::default())
+        .serialize(serializer)
+    }
+}
+
+/// JSON-serializable view for a District with its parent State and Country
+/// (feature `districts`).
+#[cfg(feature = "districts")]
+#[derive(Debug, Clone, Copy)]
+pub struct DistrictView<'a, B: GeoBackend> {
+    pub country: &'a Country<B>,
+    pub state: &'a State<B>,
+    pub district: &'a District<B>,
+}
+
+#[cfg(feature = "districts")]
+impl<'a, B: GeoBackend> Serialize for DistrictView<'a, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser = serializer.serialize_struct("District", 8)?;
+        ser.serialize_field("kind", "district")?;
+        ser.serialize_field("name", self.district.name())?;
+        ser.serialize_field(
+            "code",
+            &self.district.code.as_ref().map(|v| B::str_to_string(v)),
+        )?;
+        ser.serialize_field("country", self.country.name())?;
+        ser.serialize_field("country_code", self.country.iso2())?;
+        ser.serialize_field("state", self.state.name())?;
+        ser.serialize_field(
+            "state_code",
+            &self.state.state_code.as_ref().map(|v| B::str_to_string(v)),
+        )?;
+        ser.serialize_field("city_count", &self.district.city_count())?;
+        ser.end()
+    }
+}
+
+/// Optional fields of the views.
+///
+/// The default output is the shape of [`CountryView`], [`StateView`] and
+/// [`CityView`]: translations, no city aliases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewOptions {
+    /// Include `aliases` and `regions` (see [`crate::GeoDb::apply_city_meta`]).
+    pub city_meta: bool,
+    /// Include the `translations` of countries and states.
+    pub translations: bool,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        Self {
+            city_meta: false,
+            translations: true,
+        }
+    }
+}
+
+impl ViewOptions {
+    /// Include city `aliases` and `regions`, or don't.
+    pub fn with_city_meta(mut self, yes: bool) -> Self {
+        self.city_meta = yes;
+        self
+    }
+
+    /// Include country and state `translations`, or don't.
+    pub fn with_translations(mut self, yes: bool) -> Self {
+        self.translations = yes;
+        self
+    }
+}
+
+/// A [`CityView`] rendered with explicit [`ViewOptions`].
+#[derive(Debug, Clone, Copy)]
+pub struct CityViewWith<'a, B: GeoBackend> {
+    pub view: CityView<'a, B>,
+    pub options: ViewOptions,
+}
+
+impl<'a, B: GeoBackend> Serialize for CityViewWith<'a, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let country = self.view.country;
+        let state = self.view.state;
+        let city = self.view.city;
+        let len = if self.options.city_meta { 14 } else { 12 };
+        let mut ser = serializer.serialize_struct("City", len)?;
         ser.serialize_field("kind", "city")?;
+        ser.serialize_field("id", &country.city_id(state, city))?;
         ser.serialize_field("name", city.name())?;
         ser.serialize_field("country", country.name())?;
+        ser.serialize_field("country_code", country.iso2())?;
         ser.serialize_field("state", state.name())?;
+        ser.serialize_field("state_id", &country.state_id(state))?;
+        ser.serialize_field(
+            "state_code",
+            &state.state_code.as_ref().map(|v| B::str_to_string(v)),
+        )?;
         ser.serialize_field(
             "emoji",
             &country.emoji.as_ref().map(|e| B::str_to_string(e)),
         )?;
+        ser.serialize_field("latitude", &city.latitude.map(B::float_to_f64))?;
+        ser.serialize_field("longitude", &city.longitude.map(B::float_to_f64))?;
+        ser.serialize_field(
+            "timezone",
+            &city.timezone.as_ref().map(|v| B::str_to_string(v)),
+        )?;
+        if self.options.city_meta {
+            ser.serialize_field("aliases", &city.aliases().collect::<Vec<_>>())?;
+            ser.serialize_field("regions", &city.regions().collect::<Vec<_>>())?;
+        }
         ser.end()
     }
 }
+
+/// JSON-serializable view of any [`SmartItem`]: the matching
+/// [`CountryViewWith`], [`StateViewWith`] or [`CityViewWith`] (or
+/// `DistrictView`, feature `districts`), told apart by their `kind` field.
+///
+/// Built with [`SmartItem::to_view`] / [`SmartHit::to_view`], so every
+/// binding renders search hits the same way.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged, bound = "")]
+pub enum ItemView<'a, B: GeoBackend> {
+    Country(CountryViewWith<'a, B>),
+    State(StateViewWith<'a, B>),
+    City(CityViewWith<'a, B>),
+    #[cfg(feature = "districts")]
+    District(DistrictView<'a, B>),
+}
+
+impl<'a, B: GeoBackend> SmartItem<'a, B> {
+    /// The JSON view of this entity.
+    pub fn to_view(&self) -> ItemView<'a, B> {
+        self.to_view_with(ViewOptions::default())
+    }
+
+    /// The JSON view of this entity, with explicit [`ViewOptions`].
+    pub fn to_view_with(&self, options: ViewOptions) -> ItemView<'a, B> {
+        match *self {
+            SmartItem::Country(c) => ItemView::Country(CountryView(c).with_options(options)),
+            SmartItem::State { country, state } => {
+                ItemView::State(StateView { country, state }.with_options(options))
+            }
+            SmartItem::City {
+                country,
+                state,
+                city,
+            } => ItemView::City(
+                CityView {
+                    country,
+                    state,
+                    city,
+                }
+                .with_options(options),
+            ),
+            #[cfg(feature = "districts")]
+            SmartItem::District {
+                country,
+                state,
+                district,
+            } => ItemView::District(DistrictView {
+                country,
+                state,
+                district,
+            }),
+        }
+    }
+}
+
+impl<'a, B: GeoBackend> SmartHit<'a, B> {
+    /// The JSON view of the matched entity (the score is not included).
+    pub fn to_view(&self) -> ItemView<'a, B> {
+        self.item.to_view()
+    }
+
+    /// [`Self::to_view`] with explicit [`ViewOptions`].
+    pub fn to_view_with(&self, options: ViewOptions) -> ItemView<'a, B> {
+        self.item.to_view_with(options)
+    }
+
+    /// The matched entity rendered as a [`serde_json::Value`].
+    #[cfg(feature = "json")]
+    pub fn to_json_value(&self) -> crate::error::Result<serde_json::Value> {
+        Ok(serde_json::to_value(self.to_view())?)
+    }
+}
@@ -0,0 +1,361 @@
+// src/area.rs
+//! Country surface areas and derived statistics.
+//!
+//! The upstream dataset has no area, so a table compiled into the crate
+//! fills [`Country::area`] for every entry, including builds without `json`
+//! (e.g. WASM). Values are total areas (land plus inland water) in km²,
+//! rounded, after UN and CIA World Factbook figures. Territories that have
+//! their own entry (e.g. "GF", "RE", "SJ") are not included in the area of
+//! the country administering them.
+use crate::model::{Country, GeoBackend, GeoDb};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// ISO2 → total area in km², sorted by ISO2.
+const COUNTRY_AREA_KM2: &[(&str, f64)] = &[
+    ("AD", 468.0),
+    ("AE", 83600.0),
+    ("AF", 652230.0),
+    ("AG", 442.6),
+    ("AI", 91.0),
+    ("AL", 28748.0),
+    ("AM", 29743.0),
+    ("AO", 1246700.0),
+    ("AQ", 14200000.0),
+    ("AR", 2780400.0),
+    ("AS", 199.0),
+    ("AT", 83871.0),
+    ("AU", 7741220.0),
+    ("AW", 180.0),
+    ("AX", 1580.0),
+    ("AZ", 86600.0),
+    ("BA", 51197.0),
+    ("BB", 430.0),
+    ("BD", 148460.0),
+    ("BE", 30528.0),
+    ("BF", 274200.0),
+    ("BG", 110879.0),
+    ("BH", 760.0),
+    ("BI", 27830.0),
+    ("BJ", 112622.0),
+    ("BL", 25.0),
+    ("BM", 54.0),
+    ("BN", 5765.0),
+    ("BO", 1098581.0),
+    ("BQ", 328.0),
+    ("BR", 8515767.0),
+    ("BS", 13880.0),
+    ("BT", 38394.0),
+    ("BV", 49.0),
+    ("BW", 581730.0),
+    ("BY", 207600.0),
+    ("BZ", 22966.0),
+    ("CA", 9984670.0),
+    ("CC", 14.0),
+    ("CD", 2344858.0),
+    ("CF", 622984.0),
+    ("CG", 342000.0),
+    ("CH", 41285.0),
+    ("CI", 322463.0),
+    ("CK", 236.0),
+    ("CL", 756102.0),
+    ("CM", 475442.0),
+    ("CN", 9596960.0),
+    ("CO", 1141748.0),
+    ("CR", 51100.0),
+    ("CU", 109884.0),
+    ("CV", 4033.0),
+    ("CW", 444.0),
+    ("CX", 135.0),
+    ("CY", 9251.0),
+    ("CZ", 78871.0),
+    ("DE", 357588.0),
+    ("DJ", 23200.0),
+    ("DK", 42933.0),
+    ("DM", 751.0),
+    ("DO", 48671.0),
+    ("DZ", 2381741.0),
+    ("EC", 283561.0),
+    ("EE", 45339.0),
+    ("EG", 1001450.0),
+    ("EH", 266000.0),
+    ("ER", 117600.0),
+    ("ES", 505990.0),
+    ("ET", 1104300.0),
+    ("FI", 338455.0),
+    ("FJ", 18274.0),
+    ("FK", 12173.0),
+    ("FM", 702.0),
+    ("FO", 1393.0),
+    ("FR", 551695.0),
+    ("GA", 267668.0),
+    ("GB", 242495.0),
+    ("GD", 344.0),
+    ("GE", 69700.0),
+    ("GF", 83534.0),
+    ("GG", 78.0),
+    ("GH", 238533.0),
+    ("GI", 6.8),
+    ("GL", 2166086.0),
+    ("GM", 10689.0),
+    ("GN", 245857.0),
+    ("GP", 1628.0),
+    ("GQ", 28051.0),
+    ("GR", 131957.0),
+    ("GS", 3903.0),
+    ("GT", 108889.0),
+    ("GU", 544.0),
+    ("GW", 36125.0),
+    ("GY", 214969.0),
+    ("HK", 1106.0),
+    ("HM", 412.0),
+    ("HN", 112492.0),
+    ("HR", 56594.0),
+    ("HT", 27750.0),
+    ("HU", 93028.0),
+    ("ID", 1904569.0),
+    ("IE", 70273.0),
+    ("IL", 22072.0),
+    ("IM", 572.0),
+    ("IN", 3287263.0),
+    ("IO", 60.0),
+    ("IQ", 438317.0),
+    ("IR", 1648195.0),
+    ("IS", 103000.0),
+    ("IT", 301340.0),
+    ("JE", 116.0),
+    ("JM", 10991.0),
+    ("JO", 89342.0),
+    ("JP", 377975.0),
+    ("KE", 580367.0),
+    ("KG", 199951.0),
+    ("KH", 181035.0),
+    ("KI", 811.0),
+    ("KM", 1862.0),
+    ("KN", 261.0),
+    ("KP", 120538.0),
+    ("KR", 100210.0),
+    ("KW", 17818.0),
+    ("KY", 264.0),
+    ("KZ", 2724900.0),
+    ("LA", 236800.0),
+    ("LB", 10452.0),
+    ("LC", 617.0),
+    ("LI", 160.0),
+    ("LK", 65610.0),
+    ("LR", 111369.0),
+    ("LS", 30355.0),
+    ("LT", 65300.0),
+    ("LU", 2586.0),
+    ("LV", 64589.0),
+    ("LY", 1759540.0),
+    ("MA", 446550.0),
+    ("MC", 2.02),
+    ("MD", 33846.0),
+    ("ME", 13812.0),
+    ("MF", 53.0),
+    ("MG", 587041.0),
+    ("MH", 181.0),
+    ("MK", 25713.0),
+    ("ML", 1240192.0),
+    ("MM", 676578.0),
+    ("MN", 1564116.0),
+    ("MO", 33.0),
+    ("MP", 464.0),
+    ("MQ", 1128.0),
+    ("MR", 1030700.0),
+    ("MS", 102.0),
+    ("MT", 316.0),
+    ("MU", 2040.0),
+    ("MV", 298.0),
+    ("MW", 118484.0),
+    ("MX", 1964375.0),
+    ("MY", 330803.0),
+    ("MZ", 801590.0),
+    ("NA", 825615.0),
+    ("NC", 18575.0),
+    ("NE", 1267000.0),
+    ("NF", 36.0),
+    ("NG", 923768.0),
+    ("NI", 130373.0),
+    ("NL", 41850.0),
+    ("NO", 323802.0),
+    ("NP", 147181.0),
+    ("NR", 21.0),
+    ("NU", 260.0),
+    ("NZ", 268021.0),
+    ("OM", 309500.0),
+    ("PA", 75417.0),
+    ("PE", 1285216.0),
+    ("PF", 4167.0),
+    ("PG", 462840.0),
+    ("PH", 300000.0),
+    ("PK", 796095.0),
+    ("PL", 312696.0),
+    ("PM", 242.0),
+    ("PN", 47.0),
+    ("PR", 9104.0),
+    ("PS", 6020.0),
+    ("PT", 92212.0),
+    ("PW", 459.0),
+    ("PY", 406752.0),
+    ("QA", 11586.0),
+    ("RE", 2512.0),
+    ("RO", 238397.0),
+    ("RS", 77474.0),
+    ("RU", 17098246.0),
+    ("RW", 26338.0),
+    ("SA", 2149690.0),
+    ("SB", 28896.0),
+    ("SC", 455.0),
+    ("SD", 1886068.0),
+    ("SE", 450295.0),
+    ("SG", 728.0),
+    ("SH", 394.0),
+    ("SI", 20273.0),
+    ("SJ", 61399.0),
+    ("SK", 49035.0),
+    ("SL", 71740.0),
+    ("SM", 61.0),
+    ("SN", 196722.0),
+    ("SO", 637657.0),
+    ("SR", 163820.0),
+    ("SS", 619745.0),
+    ("ST", 964.0),
+    ("SV", 21041.0),
+    ("SX", 34.0),
+    ("SY", 185180.0),
+    ("SZ", 17364.0),
+    ("TC", 948.0),
+    ("TD", 1284000.0),
+    ("TF", 7747.0),
+    ("TG", 56785.0),
+    ("TH", 513120.0),
+    ("TJ", 143100.0),
+    ("TK", 12.0),
+    ("TL", 14874.0),
+    ("TM", 488100.0),
+    ("TN", 163610.0),
+    ("TO", 747.0),
+    ("TR", 783562.0),
+    ("TT", 5130.0),
+    ("TV", 26.0),
+    ("TW", 36193.0),
+    ("TZ", 947303.0),
+    ("UA", 603550.0),
+    ("UG", 241550.0),
+    ("UM", 34.0),
+    ("US", 9833520.0),
+    ("UY", 176215.0),
+    ("UZ", 448978.0),
+    ("VA", 0.49),
+    ("VC", 389.0),
+    ("VE", 916445.0),
+    ("VG", 151.0),
+    ("VI", 347.0),
+    ("VN", 331212.0),
+    ("VU", 12189.0),
+    ("WF", 142.0),
+    ("WS", 2842.0),
+    ("XK", 10887.0),
+    ("YE", 527968.0),
+    ("YT", 374.0),
+    ("ZA", 1221037.0),
+    ("ZM", 752612.0),
+    ("ZW", 390757.0),
+];
+
+/// Total area of the country `iso2` in km² (case-insensitive).
+pub fn country_area_km2(iso2: &str) -> Option<f64> {
+    let iso2 = iso2.trim().to_ascii_uppercase();
+    COUNTRY_AREA_KM2
+        .binary_search_by(|(code, _)| code.cmp(&iso2.as_str()))
+        .ok()
+        .map(|i| COUNTRY_AREA_KM2[i].1)
+}
+
+impl<B: GeoBackend> Country<B> {
+    /// Total area in km², see the [module docs](crate::area).
+    pub fn area(&self) -> Option<f64> {
+        country_area_km2(self.iso2())
+    }
+
+    /// Inhabitants per km², if both population and area are known.
+    pub fn density(&self) -> Option<f64> {
+        let population = self.population()? as f64;
+        let area = self.area().filter(|a| *a > 0.0)?;
+        Some(population / area)
+    }
+}
+
+/// Sort key for [`GeoDb::countries_sorted`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CountrySort {
+    /// Alphabetical by name.
+    #[default]
+    Name,
+    /// Largest population first.
+    Population,
+    /// Largest area first.
+    Area,
+    /// Most densely populated first.
+    Density,
+}
+
+impl FromStr for CountrySort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "name" => Ok(Self::Name),
+            "population" | "pop" => Ok(Self::Population),
+            "area" => Ok(Self::Area),
+            "density" => Ok(Self::Density),
+            other => Err(format!(
+                "unknown sort key '{other}' (expected name, population, area or density)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CountrySort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Name => "name",
+            Self::Population => "population",
+            Self::Area => "area",
+            Self::Density => "density",
+        })
+    }
+}
+
+/// Descending order for known values; unknown values go last.
+fn desc(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// All countries ordered by `sort`. Numeric keys sort descending, with
+    /// countries lacking the value at the end; ties keep database order.
+    pub fn countries_sorted(&self, sort: CountrySort) -> Vec<&Country<B>> {
+        let mut countries: Vec<&Country<B>> = self.countries.iter().collect();
+        match sort {
+            CountrySort::Name => countries.sort_by(|a, b| a.name().cmp(b.name())),
+            CountrySort::Population => countries.sort_by(|a, b| {
+                desc(
+                    a.population().map(|p| p as f64),
+                    b.population().map(|p| p as f64),
+                )
+            }),
+            CountrySort::Area => countries.sort_by(|a, b| desc(a.area(), b.area())),
+            CountrySort::Density => countries.sort_by(|a, b| desc(a.density(), b.density())),
+        }
+        countries
+    }
+}
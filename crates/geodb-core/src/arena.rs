@@ -0,0 +1,281 @@
+// src/arena.rs
+//! Arena-allocated strings for the binary read path.
+//!
+//! With the standard backend every name, code and translation of a decoded
+//! database is its own `String`: a few hundred thousand small heap
+//! allocations per load, interleaved with the `Vec`s of the hierarchy.
+//! [`ArenaBackend`] stores its strings as [`ArenaStr`] instead. While
+//! [`GeoDb::from_bytes_with`](crate::GeoDb::from_bytes_with) decodes, their
+//! bytes are bump-allocated into large shared chunks
+//! ([`LoadOptions::arena_chunk_size`](crate::LoadOptions::arena_chunk_size)),
+//! so a load makes a few hundred allocations for all strings together, and
+//! an `ArenaStr` is 16 bytes instead of a `String`'s 24.
+//!
+//! ```no_run
+//! use geodb_core::{ArenaBackend, GeoDb, LoadOptions};
+//!
+//! let bytes = std::fs::read("geodb.bin")?;
+//! let (db, metrics) = GeoDb::<ArenaBackend>::from_bytes_with(&bytes, &LoadOptions::default())?;
+//! println!("{} bytes of strings in the arena", metrics.arena_bytes);
+//! let berlin = db.smart_search("berlin");
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! The binary format is the same for both backends. A chunk is freed when
+//! the last string pointing into it is dropped; strings created outside a
+//! load (e.g. by overrides) get a chunk of their own.
+use crate::model::GeoBackend;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+use std::cell::RefCell;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Default size of an arena chunk, see
+/// [`LoadOptions::arena_chunk_size`](crate::LoadOptions::arena_chunk_size).
+pub const DEFAULT_ARENA_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Backend with arena-allocated strings and `f64` coordinates; see the
+/// [module docs](self).
+#[derive(Clone, Serialize, serde::Deserialize)]
+pub struct ArenaBackend;
+
+impl GeoBackend for ArenaBackend {
+    type Str = ArenaStr;
+    type Float = f64;
+
+    #[inline]
+    fn str_from(s: &str) -> Self::Str {
+        ArenaStr::new(s)
+    }
+
+    #[inline]
+    fn float_from(f: f64) -> Self::Float {
+        f
+    }
+
+    fn float_to_f64(v: Self::Float) -> f64 {
+        v
+    }
+}
+
+/// One allocation holding the bytes of many strings. Bytes below the
+/// writer's fill mark are written once and then only read.
+struct Chunk {
+    ptr: *mut u8,
+    cap: usize,
+}
+
+// SAFETY: the chunk owns its allocation; shared access only reads ranges
+// that are no longer written (see `Writer::alloc`).
+unsafe impl Send for Chunk {}
+unsafe impl Sync for Chunk {}
+
+impl Chunk {
+    fn with_capacity(cap: usize) -> Self {
+        let mut bytes = ManuallyDrop::new(Vec::<u8>::with_capacity(cap));
+        Self {
+            ptr: bytes.as_mut_ptr(),
+            cap: bytes.capacity(),
+        }
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `cap` come from the Vec in `with_capacity`;
+        // length 0, as u8 needs no drop.
+        unsafe { drop(Vec::from_raw_parts(self.ptr, 0, self.cap)) }
+    }
+}
+
+/// A string in an arena chunk.
+#[derive(Clone)]
+pub struct ArenaStr {
+    chunk: Arc<Chunk>,
+    start: u32,
+    len: u32,
+}
+
+impl ArenaStr {
+    /// `s` in the arena of the load in progress on this thread, or in a
+    /// chunk of its own outside a load.
+    pub fn new(s: &str) -> Self {
+        ARENA.with(|arena| match arena.borrow_mut().as_mut() {
+            Some(writer) => writer.alloc(s),
+            None => {
+                let chunk = Arc::new(Chunk::with_capacity(s.len()));
+                write(&chunk, 0, s);
+                Self::at(chunk, 0, s.len())
+            }
+        })
+    }
+
+    fn at(chunk: Arc<Chunk>, start: usize, len: usize) -> Self {
+        let start = u32::try_from(start).expect("arena chunk over 4 GiB");
+        let len = u32::try_from(len).expect("arena string over 4 GiB");
+        Self { chunk, start, len }
+    }
+
+    /// The string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: `start..start + len` lies within the chunk, was written
+        // from a `&str` before this value was created and is never written
+        // again.
+        unsafe {
+            let bytes = std::slice::from_raw_parts(
+                self.chunk.ptr.add(self.start as usize),
+                self.len as usize,
+            );
+            std::str::from_utf8_unchecked(bytes)
+        }
+    }
+}
+
+/// Copy `s` to `at` in `chunk`, which has room for it there.
+fn write(chunk: &Chunk, at: usize, s: &str) {
+    assert!(at + s.len() <= chunk.cap);
+    // SAFETY: in bounds (checked above) and not handed out yet, so nothing
+    // reads these bytes while they are written.
+    unsafe { std::ptr::copy_nonoverlapping(s.as_ptr(), chunk.ptr.add(at), s.len()) }
+}
+
+/// Fills chunks during one load.
+struct Writer {
+    chunk_size: usize,
+    current: Option<(Arc<Chunk>, usize)>,
+    allocated: usize,
+}
+
+impl Writer {
+    fn alloc(&mut self, s: &str) -> ArenaStr {
+        if s.len() > self.chunk_size / 4 {
+            // large strings would waste the rest of a chunk
+            let chunk = Arc::new(Chunk::with_capacity(s.len()));
+            self.allocated += chunk.cap;
+            write(&chunk, 0, s);
+            return ArenaStr::at(chunk, 0, s.len());
+        }
+        let full = match &self.current {
+            Some((chunk, used)) => used + s.len() > chunk.cap,
+            None => true,
+        };
+        if full {
+            let chunk = Chunk::with_capacity(self.chunk_size);
+            self.allocated += chunk.cap;
+            self.current = Some((Arc::new(chunk), 0));
+        }
+        let (chunk, used) = self.current.as_mut().expect("current chunk");
+        let start = *used;
+        write(chunk, start, s);
+        *used += s.len();
+        ArenaStr::at(Arc::clone(chunk), start, s.len())
+    }
+}
+
+thread_local! {
+    static ARENA: RefCell<Option<Writer>> = const { RefCell::new(None) };
+}
+
+/// Run `decode` with [`ArenaStr`]s allocated in chunks of `chunk_size`
+/// bytes; also returns the bytes allocated for chunks.
+pub(crate) fn with_arena<T>(chunk_size: usize, decode: impl FnOnce() -> T) -> (T, usize) {
+    struct Reset(Option<Writer>);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            ARENA.with(|a| *a.borrow_mut() = self.0.take());
+        }
+    }
+
+    let writer = Writer {
+        chunk_size: chunk_size.max(1),
+        current: None,
+        allocated: 0,
+    };
+    let reset = Reset(ARENA.with(|a| a.borrow_mut().replace(writer)));
+    let value = decode();
+    let allocated = ARENA.with(|a| a.borrow().as_ref().map_or(0, |w| w.allocated));
+    drop(reset);
+    (value, allocated)
+}
+
+impl Deref for ArenaStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ArenaStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for ArenaStr {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl PartialEq for ArenaStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for ArenaStr {}
+
+impl Hash for ArenaStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl fmt::Debug for ArenaStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArenaStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ArenaStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ArenaStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StrVisitor;
+
+        impl Visitor<'_> for StrVisitor {
+            type Value = ArenaStr;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ArenaStr, E> {
+                Ok(ArenaStr::new(v))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ArenaStr, E> {
+                std::str::from_utf8(v)
+                    .map(ArenaStr::new)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
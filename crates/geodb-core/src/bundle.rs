@@ -0,0 +1,377 @@
+// src/bundle.rs
+//! Versioned release bundles of prebuilt databases (feature `bundle`).
+//!
+//! [`GeoDb::write_release_bundle`] standardizes how prebuilt data is
+//! published. Each release is one directory:
+//!
+//! ```text
+//! <root>/<version>/
+//!   geodb-<version>.bin   the artifact (a sealed envelope when signed)
+//!   SHA256SUMS            checksums, `sha256sum -c` compatible
+//!   provenance.json       how the artifact was built (Provenance)
+//!   index.json            what loaders read (BundleIndex)
+//! ```
+//!
+//! Remote loaders fetch `index.json`, pick an artifact and check the
+//! download against its `sha256` (e.g. via
+//! `DownloadOptions::expected_sha256` with the `fetch` feature).
+//!
+//! ```no_run
+//! use geodb_core::bundle::{BundleIndex, BundleOptions};
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let index = db.write_release_bundle("dist", &BundleOptions::new("2024.06.01"))?;
+//! println!("wrote {}", index.artifacts[0].file);
+//!
+//! // Consumer side: check the files against the index
+//! let index = BundleIndex::load_from_path("dist/2024.06.01/index.json")?;
+//! index.verify("dist/2024.06.01")?;
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::error::{GeoError, Result};
+use crate::hex::to_hex;
+use crate::model::civil_date;
+use crate::model::{GeoBackend, GeoDb};
+use crate::stats::unix_now;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bundle layout version written to [`BundleIndex::format`].
+pub const BUNDLE_FORMAT: u32 = 1;
+/// File name of the index in a bundle directory.
+pub const INDEX_FILE: &str = "index.json";
+/// File name of the provenance manifest in a bundle directory.
+pub const PROVENANCE_FILE: &str = "provenance.json";
+/// File name of the checksum list in a bundle directory.
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// How an artifact's bytes are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactEncoding {
+    /// Plain [`GeoDb::to_bytes`] output, read with [`GeoDb::from_bytes`].
+    Bincode,
+    /// A `crate::signed` envelope around the bincode payload.
+    Sealed,
+}
+
+/// One artifact listed in a [`BundleIndex`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleArtifact {
+    /// File name, relative to the bundle directory.
+    pub file: String,
+    pub encoding: ArtifactEncoding,
+    /// `true` if the envelope carries an ed25519 signature.
+    pub signed: bool,
+    /// Size in bytes.
+    pub size: u64,
+    /// SHA-256 of the file (hex).
+    pub sha256: String,
+    /// ISO2 codes the database was filtered to; empty for all countries.
+    pub filter: Vec<String>,
+    pub countries: usize,
+    pub states: usize,
+    pub cities: usize,
+}
+
+/// How a bundle was built; written to `provenance.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Crate and version that wrote the bundle, e.g. `geodb-core 0.1.3`.
+    pub generator: String,
+    /// Build time in seconds since the Unix epoch.
+    pub created_at: u64,
+    /// Upstream dataset the data derives from.
+    pub source_url: String,
+    /// File name of the source dataset, if known.
+    pub source_file: Option<String>,
+    /// SHA-256 of the source dataset (hex), if known.
+    pub source_sha256: Option<String>,
+    /// ISO2 filter applied while building.
+    pub filter: Vec<String>,
+    /// Built with the `districts` layout.
+    pub districts: bool,
+    /// Coordinates were removed (see [`BundleOptions::strip_coords`]).
+    #[serde(default)]
+    pub coords_stripped: bool,
+}
+
+/// Machine-readable description of a bundle; written to `index.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleIndex {
+    /// Layout version ([`BUNDLE_FORMAT`]).
+    pub format: u32,
+    pub name: String,
+    pub version: String,
+    /// Build time in seconds since the Unix epoch.
+    pub created_at: u64,
+    pub artifacts: Vec<BundleArtifact>,
+    /// File names of the provenance manifest and checksum list.
+    pub provenance: String,
+    pub checksums: String,
+}
+
+impl BundleIndex {
+    /// Read an `index.json`.
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let index: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if index.format != BUNDLE_FORMAT {
+            return Err(GeoError::InvalidData(format!(
+                "unsupported bundle format {}",
+                index.format
+            )));
+        }
+        Ok(index)
+    }
+
+    /// Check every artifact in `dir` against its listed size and SHA-256.
+    pub fn verify(&self, dir: impl AsRef<Path>) -> Result<()> {
+        for artifact in &self.artifacts {
+            let bytes = fs::read(dir.as_ref().join(&artifact.file))?;
+            let actual = sha256_hex(&bytes);
+            if bytes.len() as u64 != artifact.size || actual != artifact.sha256 {
+                return Err(GeoError::ChecksumMismatch {
+                    expected: artifact.sha256.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings for [`GeoDb::write_release_bundle`].
+#[derive(Debug, Clone)]
+pub struct BundleOptions {
+    /// Bundle name, used as the artifact file prefix (default `geodb`).
+    pub name: String,
+    /// Release version and directory name, e.g. `2024.06.01`.
+    pub version: String,
+    /// Source dataset, hashed into the provenance manifest.
+    pub source: Option<PathBuf>,
+    /// ISO2 filter the database was loaded with, recorded in the index.
+    pub filter: Vec<String>,
+    /// Replace an existing bundle directory of the same version.
+    pub overwrite: bool,
+    /// Publish the database without coordinates (see
+    /// [`GeoDb::without_coordinates`]).
+    pub strip_coords: bool,
+    /// Seal and sign the artifact (see [`crate::signed`]).
+    #[cfg(feature = "signed")]
+    pub signing_key: Option<crate::signed::SigningKey>,
+}
+
+impl BundleOptions {
+    /// Options for release `version`.
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            name: "geodb".to_string(),
+            version: version.into(),
+            source: None,
+            filter: Vec::new(),
+            overwrite: false,
+            strip_coords: false,
+            #[cfg(feature = "signed")]
+            signing_key: None,
+        }
+    }
+
+    /// Version named after today's UTC date (`YYYY.MM.DD`).
+    pub fn dated() -> Self {
+        Self::new(date_version(unix_now()))
+    }
+
+    /// Use `name` as the bundle name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Record `path` as the source dataset.
+    pub fn with_source(mut self, path: impl Into<PathBuf>) -> Self {
+        self.source = Some(path.into());
+        self
+    }
+
+    /// Record the ISO2 filter the database was loaded with.
+    pub fn with_filter<I, S>(mut self, iso2: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.filter = iso2
+            .into_iter()
+            .map(|c| c.as_ref().trim().to_ascii_uppercase())
+            .filter(|c| !c.is_empty())
+            .collect();
+        self
+    }
+
+    /// Replace an existing bundle of the same version.
+    pub fn with_overwrite(mut self, yes: bool) -> Self {
+        self.overwrite = yes;
+        self
+    }
+
+    /// Strip all coordinates from the published artifact.
+    pub fn with_strip_coords(mut self, yes: bool) -> Self {
+        self.strip_coords = yes;
+        self
+    }
+
+    /// Seal the artifact and sign it with `key`.
+    #[cfg(feature = "signed")]
+    pub fn with_signing_key(mut self, key: crate::signed::SigningKey) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
+
+/// `YYYY.MM.DD` for a Unix timestamp (UTC).
+fn date_version(secs: u64) -> String {
+    let (year, month, day) = civil_date(secs);
+    format!("{year:04}.{month:02}.{day:02}")
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let mut json = serde_json::to_vec_pretty(value)?;
+    json.push(b'\n');
+    fs::write(path, json)?;
+    Ok(())
+}
+
+impl<B> GeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    /// Write this database as release bundle `options.version` below `root`;
+    /// see the [module docs](self) for the layout.
+    ///
+    /// Files are written to a temporary sibling directory that is renamed
+    /// into place at the end, so a failed build never leaves a partial
+    /// bundle behind. An existing bundle of the same version is an error
+    /// unless [`BundleOptions::overwrite`] is set.
+    pub fn write_release_bundle(
+        &self,
+        root: impl AsRef<Path>,
+        options: &BundleOptions,
+    ) -> Result<BundleIndex> {
+        let version = options.version.trim();
+        if version.is_empty() || version.contains(['/', '\\']) || version.starts_with('.') {
+            return Err(GeoError::InvalidData(format!(
+                "invalid bundle version '{version}'"
+            )));
+        }
+        let root = root.as_ref();
+        let target = root.join(version);
+        if target.exists() && !options.overwrite {
+            return Err(GeoError::InvalidData(format!(
+                "bundle {} already exists (set overwrite to replace it)",
+                target.display()
+            )));
+        }
+
+        let created_at = unix_now();
+        let stripped;
+        let db = if options.strip_coords {
+            stripped = self.without_coordinates();
+            &stripped
+        } else {
+            self
+        };
+        let payload = db.to_bytes()?;
+        #[cfg(feature = "signed")]
+        let (bytes, encoding, signed) = match &options.signing_key {
+            Some(key) => (
+                crate::signed::seal(&payload, Some(key)),
+                ArtifactEncoding::Sealed,
+                true,
+            ),
+            None => (payload, ArtifactEncoding::Bincode, false),
+        };
+        #[cfg(not(feature = "signed"))]
+        let (bytes, encoding, signed) = (payload, ArtifactEncoding::Bincode, false);
+
+        let stats = db.stats();
+        let artifact = BundleArtifact {
+            file: format!("{}-{version}.bin", options.name),
+            encoding,
+            signed,
+            size: bytes.len() as u64,
+            sha256: sha256_hex(&bytes),
+            filter: options.filter.clone(),
+            countries: stats.countries,
+            states: stats.states,
+            cities: stats.cities,
+        };
+        let provenance = Provenance {
+            generator: format!("geodb-core {}", env!("CARGO_PKG_VERSION")),
+            created_at,
+            source_url: crate::loader::DATA_REPO_URL.to_string(),
+            source_file: options
+                .source
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned()),
+            source_sha256: match &options.source {
+                Some(path) => Some(sha256_hex(&fs::read(path)?)),
+                None => None,
+            },
+            filter: options.filter.clone(),
+            districts: cfg!(feature = "districts"),
+            coords_stripped: options.strip_coords,
+        };
+        let index = BundleIndex {
+            format: BUNDLE_FORMAT,
+            name: options.name.clone(),
+            version: version.to_string(),
+            created_at,
+            artifacts: vec![artifact],
+            provenance: PROVENANCE_FILE.to_string(),
+            checksums: CHECKSUMS_FILE.to_string(),
+        };
+
+        fs::create_dir_all(root)?;
+        let staging = root.join(format!(".{version}.partial"));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir(&staging)?;
+        let written = (|| -> Result<()> {
+            fs::write(staging.join(&index.artifacts[0].file), &bytes)?;
+            write_json(&staging.join(PROVENANCE_FILE), &provenance)?;
+            write_json(&staging.join(INDEX_FILE), &index)?;
+
+            // the index and manifest are covered too, so `sha256sum -c` checks everything
+            let mut sums = String::new();
+            for file in [
+                index.artifacts[0].file.as_str(),
+                PROVENANCE_FILE,
+                INDEX_FILE,
+            ] {
+                let digest = sha256_hex(&fs::read(staging.join(file))?);
+                sums.push_str(&format!("{digest}  {file}\n"));
+            }
+            fs::write(staging.join(CHECKSUMS_FILE), sums)?;
+
+            if target.exists() {
+                fs::remove_dir_all(&target)?;
+            }
+            fs::rename(&staging, &target)?;
+            Ok(())
+        })();
+        if written.is_err() {
+            let _ = fs::remove_dir_all(&staging);
+        }
+        written?;
+        Ok(index)
+    }
+}
@@ -1 +1,213 @@
+// src/cache.rs
+//! Caller-managed in-process cache of loaded databases.
+//!
+//! Loading is never cached implicitly: every `GeoDb::load*` call reads the
+//! (binary-cached) dataset again, and the caller owns the result. Code that
+//! loads repeatedly keeps databases in a [`GeoDbCache`] instead, which it
+//! creates, sizes and drops itself:
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, GeoDbCache, StandardBackend};
+//!
+//! // Keep at most two databases; the least recently used one is evicted.
+//! let cache = GeoDbCache::<StandardBackend>::new().with_max_entries(2);
+//!
+//! let all = cache.load_default()?;
+//! let dach = cache.load_filtered(&["DE", "AT", "CH"])?;
+//! let custom = cache.get_or_try_insert_with("us-only", || {
+//!     GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"])
+//! })?;
+//! assert!(std::sync::Arc::ptr_eq(&all, &cache.load_default()?));
+//!
+//! cache.remove("us-only");
+//! drop(custom); // memory is released once the last handle is gone
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Entries are handed out as [`Arc`]s, so evicting or removing an entry only
+//! drops the cache's handle; databases still in use stay alive until their
+//! last handle is dropped.
+use crate::error::Result;
+use crate::model::{DefaultBackend, GeoBackend, GeoDb};
+use std::sync::{Arc, Mutex, MutexGuard};
 
+struct Entry<B: GeoBackend> {
+    name: String,
+    db: Arc<GeoDb<B>>,
+    last_used: u64,
+}
+
+struct Inner<B: GeoBackend> {
+    entries: Vec<Entry<B>>,
+    clock: u64,
+}
+
+/// Named, optionally bounded cache of loaded [`GeoDb`]s.
+///
+/// Thread-safe; share it by reference or in an `Arc`. Loads through
+/// [`Self::get_or_try_insert_with`] run while the cache is locked, so
+/// concurrent callers asking for the same name load it only once.
+pub struct GeoDbCache<B: GeoBackend = DefaultBackend> {
+    inner: Mutex<Inner<B>>,
+    max_entries: Option<usize>,
+}
+
+impl<B: GeoBackend> Default for GeoDbCache<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: GeoBackend> std::fmt::Debug for GeoDbCache<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeoDbCache")
+            .field("names", &self.names())
+            .field("max_entries", &self.max_entries)
+            .finish()
+    }
+}
+
+impl<B: GeoBackend> GeoDbCache<B> {
+    /// Empty, unbounded cache.
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: Vec::new(),
+                clock: 0,
+            }),
+            max_entries: None,
+        }
+    }
+
+    /// Keep at most `max` databases, evicting the least recently used one.
+    /// A limit of 0 disables caching (every lookup loads).
+    pub fn with_max_entries(mut self, max: usize) -> Self {
+        self.max_entries = Some(max);
+        self
+    }
+
+    /// The database cached under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Arc<GeoDb<B>>> {
+        let mut inner = self.lock();
+        inner.touch(name)
+    }
+
+    /// The database cached under `name`, loading it with `load` on a miss.
+    ///
+    /// Load errors are returned and nothing is cached.
+    pub fn get_or_try_insert_with<F>(&self, name: &str, load: F) -> Result<Arc<GeoDb<B>>>
+    where
+        F: FnOnce() -> Result<GeoDb<B>>,
+    {
+        let mut inner = self.lock();
+        if let Some(db) = inner.touch(name) {
+            return Ok(db);
+        }
+        let db = Arc::new(load()?);
+        self.store(&mut inner, name, Arc::clone(&db));
+        Ok(db)
+    }
+
+    /// Cache `db` under `name`, replacing any previous entry.
+    pub fn insert(&self, name: &str, db: GeoDb<B>) -> Arc<GeoDb<B>> {
+        let db = Arc::new(db);
+        let mut inner = self.lock();
+        inner.entries.retain(|e| e.name != name);
+        self.store(&mut inner, name, Arc::clone(&db));
+        db
+    }
+
+    /// Drop the entry `name`, returning the cache's handle to it.
+    pub fn remove(&self, name: &str) -> Option<Arc<GeoDb<B>>> {
+        let mut inner = self.lock();
+        let pos = inner.entries.iter().position(|e| e.name == name)?;
+        Some(inner.entries.remove(pos).db)
+    }
+
+    /// Drop all entries.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Names of the cached entries, most recently used first.
+    pub fn names(&self) -> Vec<String> {
+        let inner = self.lock();
+        let mut entries: Vec<&Entry<B>> = inner.entries.iter().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.last_used));
+        entries.into_iter().map(|e| e.name.clone()).collect()
+    }
+
+    /// Number of cached databases.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner<B>> {
+        // a panicking loader can't leave the entry list half-updated
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn store(&self, inner: &mut Inner<B>, name: &str, db: Arc<GeoDb<B>>) {
+        if self.max_entries == Some(0) {
+            return;
+        }
+        if let Some(max) = self.max_entries {
+            while inner.entries.len() >= max {
+                let oldest = inner
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, e)| e.last_used)
+                    .map(|(i, _)| i)
+                    .expect("non-empty");
+                inner.entries.remove(oldest);
+            }
+        }
+        inner.clock += 1;
+        let last_used = inner.clock;
+        inner.entries.push(Entry {
+            name: name.to_string(),
+            db,
+            last_used,
+        });
+    }
+}
+
+impl<B: GeoBackend> Inner<B> {
+    fn touch(&mut self, name: &str) -> Option<Arc<GeoDb<B>>> {
+        self.clock += 1;
+        let clock = self.clock;
+        let entry = self.entries.iter_mut().find(|e| e.name == name)?;
+        entry.last_used = clock;
+        Some(Arc::clone(&entry.db))
+    }
+}
+
+#[cfg(feature = "json")]
+impl GeoDbCache<DefaultBackend> {
+    /// Name of the entry used by [`Self::load_default`].
+    pub const DEFAULT_NAME: &'static str = "default";
+
+    /// The bundled, unfiltered database ([`GeoDb::load`]), cached under
+    /// [`Self::DEFAULT_NAME`].
+    pub fn load_default(&self) -> Result<Arc<GeoDb<DefaultBackend>>> {
+        self.get_or_try_insert_with(Self::DEFAULT_NAME, GeoDb::load)
+    }
+
+    /// The bundled database filtered to `iso2` ([`GeoDb::load_filtered_by_iso2`]),
+    /// cached under the sorted, uppercased codes (e.g. `"AT_CH_DE"`).
+    pub fn load_filtered(&self, iso2: &[&str]) -> Result<Arc<GeoDb<DefaultBackend>>> {
+        let mut codes: Vec<String> = iso2.iter().map(|c| c.trim().to_ascii_uppercase()).collect();
+        codes.sort();
+        codes.dedup();
+        self.get_or_try_insert_with(&codes.join("_"), || {
+            let codes: Vec<&str> = codes.iter().map(String::as_str).collect();
+            GeoDb::load_filtered_by_iso2(&codes)
+        })
+    }
+}
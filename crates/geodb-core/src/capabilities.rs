@@ -0,0 +1,85 @@
+// src/capabilities.rs
+//! Which optional data a loaded database carries.
+//!
+//! Coordinates can be stripped at build time, aliases and translations are
+//! sidecar tables applied after loading, and districts depend on a feature.
+//! [`GeoDb::capabilities`] reports what is actually there, so consumers can
+//! hide a "near me" button or an alias hint up front instead of probing
+//! individual entities for `None`.
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! if !db.capabilities().coordinates {
+//!     println!("distance search disabled: no coordinates in this build");
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Postal codes, airports and boundaries are not part of the data model, so
+//! there are no flags for them.
+use crate::model::{GeoBackend, GeoDb};
+use serde::{Deserialize, Serialize};
+
+/// Optional data present in a [`GeoDb`]; every flag is `true` if at least
+/// one entity has the data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// City latitudes and longitudes (nearest city, bounding boxes,
+    /// distance ranking); `false` for `--strip-coords` builds.
+    pub coordinates: bool,
+    /// IANA timezones of cities.
+    pub city_timezones: bool,
+    /// City aliases, from [`GeoDb::apply_city_meta`] or a GeoNames import.
+    pub city_aliases: bool,
+    /// Informal city regions (e.g. "Münsterland"), applied like aliases.
+    pub city_regions: bool,
+    /// Country populations (and with them densities).
+    pub country_populations: bool,
+    /// Country names in other languages, from the dataset.
+    pub country_translations: bool,
+    /// State names in other languages, from
+    /// [`GeoDb::apply_state_translations`].
+    pub state_translations: bool,
+    /// Region and subregion names in other languages, from
+    /// [`GeoDb::apply_region_translations`].
+    pub region_translations: bool,
+    /// Measurement system, date format and first weekday, from
+    /// [`GeoDb::apply_country_locale`].
+    pub country_locale: bool,
+    /// Districts below states (feature `districts`).
+    pub districts: bool,
+    /// A changelog for [`GeoDb::changes_since`].
+    pub changelog: bool,
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Which optional data this database carries; see [`Capabilities`].
+    ///
+    /// Scans the entities until each flag is settled, so it is cheap but
+    /// not free; call it once after loading and applying sidecar tables.
+    pub fn capabilities(&self) -> Capabilities {
+        let cities = || self.iter_cities().map(|(city, _, _)| city);
+        let states = || self.countries.iter().flat_map(|c| &c.states);
+        Capabilities {
+            coordinates: cities().any(|c| c.latitude.is_some() && c.longitude.is_some()),
+            city_timezones: cities().any(|c| c.timezone.is_some()),
+            city_aliases: cities().any(|c| !c.aliases.is_empty()),
+            city_regions: cities().any(|c| !c.regions.is_empty()),
+            country_populations: self.countries.iter().any(|c| c.population.is_some()),
+            country_translations: self.countries.iter().any(|c| !c.translations.is_empty()),
+            state_translations: states().any(|s| !s.translations.is_empty()),
+            region_translations: self
+                .countries
+                .iter()
+                .any(|c| !c.region_translations.is_empty() || !c.subregion_translations.is_empty()),
+            country_locale: self.countries.iter().any(|c| c.locale.is_some()),
+            #[cfg(feature = "districts")]
+            districts: states().any(|s| !s.districts.is_empty()),
+            #[cfg(not(feature = "districts"))]
+            districts: false,
+            changelog: self.changelog.is_some(),
+        }
+    }
+}
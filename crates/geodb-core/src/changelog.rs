@@ -0,0 +1,425 @@
+// src/changelog.rs
+//! Per-entity changes across dataset versions.
+//!
+//! [`GeoDb::diff`] compares two loaded databases and lists the countries,
+//! states and cities that were added, removed or renamed. Each dataset
+//! refresh appends one [`ChangeSet`] to a [`Changelog`] (a JSON sidecar
+//! file, e.g. written by `geodb diff --changelog`). With the changelog
+//! attached, [`GeoDb::changes_since`] tells downstream caches and search
+//! indexes what to update instead of reimporting everything:
+//!
+//! ```no_run
+//! use geodb_core::changelog::Changelog;
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let mut db = GeoDb::<StandardBackend>::load()?;
+//! db.set_changelog(Changelog::load_from_path("changelog.json")?);
+//!
+//! match db.changes_since("2024.05.01") {
+//!     Some(changes) => {
+//!         for change in changes {
+//!             println!("{change}");
+//!         }
+//!     }
+//!     None => println!("version unknown to the changelog; reimport"),
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Countries are matched by ISO2 and states by state code (by name where a
+//! state has none). Cities are matched by name within their state; a city
+//! that disappeared while a new one appeared less than
+//! [`RENAME_MAX_KM`] away counts as renamed.
+use crate::error::{GeoError, Result};
+use crate::model::{City, Country, GeoBackend, GeoDb, State};
+use crate::spatial::haversine_km;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+#[cfg(feature = "json")]
+use {std::fs, std::path::Path};
+
+/// Distance below which a removed and an added city of the same state are
+/// taken to be one renamed city.
+pub const RENAME_MAX_KM: f64 = 1.0;
+
+/// Which level of the hierarchy changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeEntity {
+    Country,
+    State,
+    City,
+}
+
+/// What happened to the entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// Same entity, new name; the old one is in
+    /// [`EntityChange::previous_name`].
+    Renamed,
+}
+
+/// One added, removed or renamed country, state or city.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityChange {
+    pub entity: ChangeEntity,
+    pub kind: ChangeKind,
+    /// Country the entity belongs to (or is).
+    pub iso2: String,
+    /// State name for states and cities (the last known one for removals).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// State code for states and cities, if the state has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_code: Option<String>,
+    /// Current name, or the last known one for removals.
+    pub name: String,
+    /// Name before a rename.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_name: Option<String>,
+}
+
+impl fmt::Display for EntityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {:?}: {}", self.entity, self.kind, self.iso2)?;
+        if let (ChangeEntity::City, Some(state)) = (self.entity, &self.state) {
+            write!(f, "/{state}")?;
+        }
+        match &self.previous_name {
+            Some(previous) => write!(f, "/{previous} -> {}", self.name),
+            None => write!(f, "/{}", self.name),
+        }
+    }
+}
+
+/// Changes from dataset version `previous` to `version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub version: String,
+    pub previous: String,
+    pub changes: Vec<EntityChange>,
+}
+
+/// Consecutive [`ChangeSet`]s, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Changelog {
+    pub sets: Vec<ChangeSet>,
+}
+
+impl Changelog {
+    /// Empty changelog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a changelog from a JSON file.
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Write the changelog as pretty-printed JSON.
+    #[cfg(feature = "json")]
+    pub fn save_to_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut json = serde_json::to_vec_pretty(self)?;
+        json.push(b'\n');
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Version the newest change set leads to.
+    pub fn latest_version(&self) -> Option<&str> {
+        self.sets.last().map(|s| s.version.as_str())
+    }
+
+    /// Append `set`; it must continue from [`Self::latest_version`].
+    pub fn push(&mut self, set: ChangeSet) -> Result<()> {
+        if let Some(latest) = self.latest_version() {
+            if set.previous != latest {
+                return Err(GeoError::InvalidData(format!(
+                    "change set {} starts at {}, but the changelog ends at {latest}",
+                    set.version, set.previous
+                )));
+            }
+        }
+        if self.sets.iter().any(|s| s.version == set.version) {
+            return Err(GeoError::InvalidData(format!(
+                "changelog already has version {}",
+                set.version
+            )));
+        }
+        self.sets.push(set);
+        Ok(())
+    }
+
+    /// All changes after `version`, oldest first.
+    ///
+    /// `None` if the changelog doesn't know `version` (too old, or from
+    /// another dataset); the caller then has to reimport everything. The
+    /// latest version yields an empty list.
+    pub fn changes_since(&self, version: &str) -> Option<Vec<&EntityChange>> {
+        let start = if self.sets.first().is_some_and(|s| s.previous == version) {
+            0
+        } else {
+            self.sets.iter().position(|s| s.version == version)? + 1
+        };
+        Some(self.sets[start..].iter().flat_map(|s| &s.changes).collect())
+    }
+}
+
+/// Matching key of a state within its country.
+fn state_key<B: GeoBackend>(state: &State<B>) -> &str {
+    match state.state_code() {
+        "" => state.name(),
+        code => code,
+    }
+}
+
+fn coords<B: GeoBackend>(city: &City<B>) -> Option<(f64, f64)> {
+    Some((
+        B::float_to_f64(city.latitude?),
+        B::float_to_f64(city.longitude?),
+    ))
+}
+
+fn change<B: GeoBackend>(
+    entity: ChangeEntity,
+    kind: ChangeKind,
+    country: &Country<B>,
+    state: Option<&State<B>>,
+    name: &str,
+) -> EntityChange {
+    EntityChange {
+        entity,
+        kind,
+        iso2: country.iso2().to_string(),
+        state: state.map(|s| s.name().to_string()),
+        state_code: state
+            .map(|s| s.state_code())
+            .filter(|c| !c.is_empty())
+            .map(str::to_string),
+        name: name.to_string(),
+        previous_name: None,
+    }
+}
+
+fn renamed<B: GeoBackend>(
+    entity: ChangeEntity,
+    country: &Country<B>,
+    state: Option<&State<B>>,
+    name: &str,
+    previous: &str,
+) -> EntityChange {
+    EntityChange {
+        previous_name: Some(previous.to_string()),
+        ..change(entity, ChangeKind::Renamed, country, state, name)
+    }
+}
+
+/// A state appearing or disappearing whole, with all its cities.
+fn whole_state<B: GeoBackend>(
+    out: &mut Vec<EntityChange>,
+    kind: ChangeKind,
+    country: &Country<B>,
+    state: &State<B>,
+) {
+    out.push(change(
+        ChangeEntity::State,
+        kind,
+        country,
+        Some(state),
+        state.name(),
+    ));
+    for city in state.cities() {
+        out.push(change(
+            ChangeEntity::City,
+            kind,
+            country,
+            Some(state),
+            city.name(),
+        ));
+    }
+}
+
+fn whole_country<B: GeoBackend>(
+    out: &mut Vec<EntityChange>,
+    kind: ChangeKind,
+    country: &Country<B>,
+) {
+    out.push(change(
+        ChangeEntity::Country,
+        kind,
+        country,
+        None,
+        country.name(),
+    ));
+    for state in country.states() {
+        whole_state(out, kind, country, state);
+    }
+}
+
+fn diff_cities<B: GeoBackend>(
+    out: &mut Vec<EntityChange>,
+    country: &Country<B>,
+    old: &State<B>,
+    new: &State<B>,
+) {
+    // pair equal names first (names may repeat within a state)
+    let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, city) in old.cities().iter().enumerate().rev() {
+        by_name.entry(city.name()).or_default().push(i);
+    }
+    let mut old_matched = vec![false; old.cities().len()];
+    let mut added: Vec<&City<B>> = Vec::new();
+    for city in new.cities() {
+        match by_name.get_mut(city.name()).and_then(Vec::pop) {
+            Some(i) => old_matched[i] = true,
+            None => added.push(city),
+        }
+    }
+    let mut removed: Vec<&City<B>> = old
+        .cities()
+        .iter()
+        .zip(&old_matched)
+        .filter(|(_, &matched)| !matched)
+        .map(|(city, _)| city)
+        .collect();
+
+    // a removed and an added city at the same spot: renamed
+    let mut renames = Vec::new();
+    added.retain(|city| {
+        let Some((lat, lng)) = coords(city) else {
+            return true;
+        };
+        let nearest = removed
+            .iter()
+            .enumerate()
+            .filter_map(|(i, old)| {
+                let (olat, olng) = coords(old)?;
+                Some((i, haversine_km(lat, lng, olat, olng)))
+            })
+            .filter(|&(_, km)| km < RENAME_MAX_KM)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match nearest {
+            Some((i, _)) => {
+                let old = removed.remove(i);
+                renames.push(renamed(
+                    ChangeEntity::City,
+                    country,
+                    Some(new),
+                    city.name(),
+                    old.name(),
+                ));
+                false
+            }
+            None => true,
+        }
+    });
+
+    for city in added {
+        out.push(change(
+            ChangeEntity::City,
+            ChangeKind::Added,
+            country,
+            Some(new),
+            city.name(),
+        ));
+    }
+    for city in removed {
+        out.push(change(
+            ChangeEntity::City,
+            ChangeKind::Removed,
+            country,
+            Some(old),
+            city.name(),
+        ));
+    }
+    out.extend(renames);
+}
+
+fn diff_states<B: GeoBackend>(out: &mut Vec<EntityChange>, old: &Country<B>, new: &Country<B>) {
+    let old_states: HashMap<&str, &State<B>> =
+        old.states().iter().map(|s| (state_key(s), s)).collect();
+    let new_keys: HashMap<&str, ()> = new.states().iter().map(|s| (state_key(s), ())).collect();
+    for state in new.states() {
+        match old_states.get(state_key(state)) {
+            None => whole_state(out, ChangeKind::Added, new, state),
+            Some(previous) => {
+                if previous.name() != state.name() {
+                    out.push(renamed(
+                        ChangeEntity::State,
+                        new,
+                        Some(state),
+                        state.name(),
+                        previous.name(),
+                    ));
+                }
+                diff_cities(out, new, previous, state);
+            }
+        }
+    }
+    for state in old.states() {
+        if !new_keys.contains_key(state_key(state)) {
+            whole_state(out, ChangeKind::Removed, old, state);
+        }
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Changes from `self` (the older dataset) to `newer`.
+    ///
+    /// New entities come in `newer`'s order, followed by removals. Added or
+    /// removed countries and states list all their states and cities too,
+    /// so consumers can apply the changes without looking anything up.
+    pub fn diff(&self, newer: &GeoDb<B>) -> Vec<EntityChange> {
+        let mut out = Vec::new();
+        for country in &newer.countries {
+            match self.find_country_by_iso2(country.iso2()) {
+                None => whole_country(&mut out, ChangeKind::Added, country),
+                Some(previous) => {
+                    if previous.name() != country.name() {
+                        out.push(renamed(
+                            ChangeEntity::Country,
+                            country,
+                            None,
+                            country.name(),
+                            previous.name(),
+                        ));
+                    }
+                    diff_states(&mut out, previous, country);
+                }
+            }
+        }
+        for country in &self.countries {
+            if newer.find_country_by_iso2(country.iso2()).is_none() {
+                whole_country(&mut out, ChangeKind::Removed, country);
+            }
+        }
+        out
+    }
+
+    /// Attach a changelog for [`Self::changes_since`]; its latest version
+    /// should be the one this database was built from.
+    pub fn set_changelog(&mut self, changelog: Changelog) {
+        self.changelog = Some(Arc::new(changelog));
+    }
+
+    /// The attached changelog, if any.
+    pub fn changelog(&self) -> Option<&Changelog> {
+        self.changelog.as_deref()
+    }
+
+    /// Changes since dataset `version`, oldest first; see
+    /// [`Changelog::changes_since`].
+    ///
+    /// `None` without an attached changelog or if it doesn't know `version`.
+    pub fn changes_since(&self, version: &str) -> Option<Vec<&EntityChange>> {
+        self.changelog.as_ref()?.changes_since(version)
+    }
+}
@@ -0,0 +1,190 @@
+// src/codegen.rs
+//! Rust source for a database subset, compiled into an application.
+//!
+//! For targets that can't afford a deserializer or a data file (embedded
+//! firmware, tiny WASM modules), [`GeoDb::write_rust_source`] writes the
+//! countries (and optionally their states and cities) as `static` arrays
+//! of plain structs. The generated file has no dependencies, not even on
+//! this crate:
+//!
+//! ```no_run
+//! use geodb_core::{ExportLevel, GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH"])?;
+//! let file = std::fs::File::create("src/geodata.rs")?;
+//! db.write_rust_source(file, ExportLevel::States)?;
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! The application then declares `mod geodata;` (or `include!`s the file
+//! from `OUT_DIR`) and uses `geodata::COUNTRIES` and
+//! `geodata::country_by_iso2("CH")`. Keep the subset small: every city is
+//! a struct literal the compiler has to process.
+use crate::error::Result;
+use crate::export::ExportLevel;
+use crate::model::{DbStats, GeoBackend, GeoDb};
+use std::io::{BufWriter, Write};
+
+/// A string literal, escaped for Rust source.
+fn string(s: &str) -> String {
+    format!("{s:?}")
+}
+
+fn opt_string(s: Option<&str>) -> String {
+    match s {
+        Some(s) if !s.is_empty() => format!("Some({})", string(s)),
+        _ => "None".to_string(),
+    }
+}
+
+/// A float literal; `{:?}` always keeps a decimal point or exponent.
+fn opt_float(v: Option<f64>) -> String {
+    match v {
+        Some(v) if v.is_finite() => format!("Some({v:?})"),
+        _ => "None".to_string(),
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Write the database down to `level` as a Rust module with `static`
+    /// arrays (see the [module docs](crate::codegen)). Returns what was
+    /// written; levels below `level` count as 0.
+    pub fn write_rust_source<W: Write>(&self, writer: W, level: ExportLevel) -> Result<DbStats> {
+        let mut out = BufWriter::new(writer);
+        let with_states = level != ExportLevel::Countries;
+        let with_cities = level == ExportLevel::Cities;
+        let float = |v: Option<B::Float>| opt_float(v.map(B::float_to_f64));
+        let mut stats = DbStats {
+            countries: 0,
+            states: 0,
+            cities: 0,
+        };
+
+        writeln!(
+            out,
+            "// Generated by geodb-core {} ({level}); do not edit.",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        writeln!(
+            out,
+            "// Data: countries-states-cities-database by dr5hn, CC-BY-4.0."
+        )?;
+        writeln!(out)?;
+        writeln!(out, "#[allow(dead_code)]")?;
+        writeln!(out, "#[derive(Debug)]")?;
+        writeln!(out, "pub struct Country {{")?;
+        writeln!(out, "    pub iso2: &'static str,")?;
+        writeln!(out, "    pub iso3: &'static str,")?;
+        writeln!(out, "    pub name: &'static str,")?;
+        writeln!(out, "    pub capital: Option<&'static str>,")?;
+        writeln!(out, "    pub phone_code: &'static str,")?;
+        writeln!(out, "    pub currency: &'static str,")?;
+        writeln!(out, "    pub region: &'static str,")?;
+        writeln!(out, "    pub timezone: Option<&'static str>,")?;
+        writeln!(out, "    pub latitude: Option<f64>,")?;
+        writeln!(out, "    pub longitude: Option<f64>,")?;
+        if with_states {
+            writeln!(out, "    pub states: &'static [State],")?;
+        }
+        writeln!(out, "}}")?;
+        if with_states {
+            writeln!(out)?;
+            writeln!(out, "#[allow(dead_code)]")?;
+            writeln!(out, "#[derive(Debug)]")?;
+            writeln!(out, "pub struct State {{")?;
+            writeln!(out, "    pub name: &'static str,")?;
+            writeln!(out, "    pub code: Option<&'static str>,")?;
+            writeln!(out, "    pub latitude: Option<f64>,")?;
+            writeln!(out, "    pub longitude: Option<f64>,")?;
+            if with_cities {
+                writeln!(out, "    pub cities: &'static [City],")?;
+            }
+            writeln!(out, "}}")?;
+        }
+        if with_cities {
+            writeln!(out)?;
+            writeln!(out, "#[allow(dead_code)]")?;
+            writeln!(out, "#[derive(Debug)]")?;
+            writeln!(out, "pub struct City {{")?;
+            writeln!(out, "    pub name: &'static str,")?;
+            writeln!(out, "    pub latitude: Option<f64>,")?;
+            writeln!(out, "    pub longitude: Option<f64>,")?;
+            writeln!(out, "    pub timezone: Option<&'static str>,")?;
+            writeln!(out, "}}")?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "pub static COUNTRIES: &[Country] = &[")?;
+        for country in &self.countries {
+            stats.countries += 1;
+            writeln!(out, "    Country {{")?;
+            writeln!(out, "        iso2: {},", string(country.iso2()))?;
+            writeln!(out, "        iso3: {},", string(country.iso3()))?;
+            writeln!(out, "        name: {},", string(country.name()))?;
+            writeln!(out, "        capital: {},", opt_string(country.capital()))?;
+            writeln!(out, "        phone_code: {},", string(country.phone_code()))?;
+            writeln!(out, "        currency: {},", string(country.currency()))?;
+            writeln!(out, "        region: {},", string(country.region()))?;
+            writeln!(
+                out,
+                "        timezone: {},",
+                opt_string(country.primary_timezone())
+            )?;
+            writeln!(out, "        latitude: {},", float(country.latitude))?;
+            writeln!(out, "        longitude: {},", float(country.longitude))?;
+            if with_states {
+                writeln!(out, "        states: &[")?;
+                for state in &country.states {
+                    stats.states += 1;
+                    writeln!(out, "            State {{")?;
+                    writeln!(out, "                name: {},", string(state.name()))?;
+                    writeln!(
+                        out,
+                        "                code: {},",
+                        opt_string(Some(state.state_code()))
+                    )?;
+                    writeln!(out, "                latitude: {},", float(state.latitude))?;
+                    writeln!(
+                        out,
+                        "                longitude: {},",
+                        float(state.longitude)
+                    )?;
+                    if with_cities {
+                        writeln!(out, "                cities: &[")?;
+                        for city in &state.cities {
+                            stats.cities += 1;
+                            writeln!(
+                                out,
+                                "                    City {{ name: {}, latitude: {}, longitude: {}, timezone: {} }},",
+                                string(city.name()),
+                                float(city.latitude),
+                                float(city.longitude),
+                                opt_string(city.timezone.as_ref().map(|t| t.as_ref()))
+                            )?;
+                        }
+                        writeln!(out, "                ],")?;
+                    }
+                    writeln!(out, "            }},")?;
+                }
+                writeln!(out, "        ],")?;
+            }
+            writeln!(out, "    }},")?;
+        }
+        writeln!(out, "];")?;
+
+        writeln!(out)?;
+        writeln!(out, "/// Country by ISO2 code, case-insensitive.")?;
+        writeln!(out, "#[allow(dead_code)]")?;
+        writeln!(
+            out,
+            "pub fn country_by_iso2(iso2: &str) -> Option<&'static Country> {{"
+        )?;
+        writeln!(
+            out,
+            "    COUNTRIES.iter().find(|c| c.iso2.eq_ignore_ascii_case(iso2))"
+        )?;
+        writeln!(out, "}}")?;
+        out.flush()?;
+        Ok(stats)
+    }
+}
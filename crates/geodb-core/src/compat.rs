@@ -0,0 +1,328 @@
+// src/compat.rs
+//! Geocoder-compatible response envelopes.
+//!
+//! Renders [`SmartHit`]s in the JSON shapes of common geocoding services so
+//! existing clients can switch to a self-hosted geodb with minimal changes:
+//!
+//! - [`ResponseFormat::Native`]: the regular [`crate::api`] views
+//! - [`ResponseFormat::Nominatim`]: a list of Nominatim-like places
+//! - [`ResponseFormat::Google`]: a Google Geocoding-like `{ results, status }`
+//!
+//! The format is meant to be selectable per request (e.g. `?format=nominatim`),
+//! hence the [`FromStr`] implementation.
+//!
+//! Only the fields geodb can fill are emitted; ids, bounding boxes and
+//! other service-specific fields are omitted.
+use crate::api::{ItemView, ViewOptions};
+use crate::model::{GeoBackend, SmartHit, SmartItem};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Output format for search responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// geodb's own view objects.
+    #[default]
+    Native,
+    /// Nominatim `/search?format=json`-like list.
+    Nominatim,
+    /// Google Geocoding API-like envelope.
+    Google,
+}
+
+impl FromStr for ResponseFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "native" | "geodb" => Ok(Self::Native),
+            "nominatim" | "osm" => Ok(Self::Nominatim),
+            "google" => Ok(Self::Google),
+            other => Err(format!(
+                "unknown response format '{other}' (expected native, nominatim or google)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ResponseFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Native => "native",
+            Self::Nominatim => "nominatim",
+            Self::Google => "google",
+        })
+    }
+}
+
+/// A response rendered in one of the [`ResponseFormat`]s.
+///
+/// Serializes directly to the target JSON shape.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum CompatResponse<'a, B: GeoBackend> {
+    Native(Vec<NativeItem<'a, B>>),
+    Nominatim(Vec<NominatimPlace>),
+    Google(GoogleResponse),
+}
+
+/// One entry of a native response.
+pub type NativeItem<'a, B> = ItemView<'a, B>;
+
+/// Nominatim-like place.
+#[derive(Debug, Clone, Serialize)]
+pub struct NominatimPlace {
+    pub display_name: String,
+    /// Latitude as string, like Nominatim.
+    pub lat: Option<String>,
+    /// Longitude as string, like Nominatim.
+    pub lon: Option<String>,
+    pub class: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub addresstype: &'static str,
+    /// Relevance in `0.0..=1.0`, derived from the smart-search score.
+    pub importance: f64,
+    pub address: NominatimAddress,
+}
+
+/// Address block of a [`NominatimPlace`].
+#[derive(Debug, Clone, Serialize)]
+pub struct NominatimAddress {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    pub country: String,
+    /// Lowercase ISO2 code, like Nominatim.
+    pub country_code: String,
+}
+
+/// Google Geocoding-like envelope.
+#[derive(Debug, Clone, Serialize)]
+pub struct GoogleResponse {
+    pub results: Vec<GoogleResult>,
+    /// `"OK"` or `"ZERO_RESULTS"`.
+    pub status: &'static str,
+}
+
+/// One Google Geocoding-like result.
+#[derive(Debug, Clone, Serialize)]
+pub struct GoogleResult {
+    pub formatted_address: String,
+    pub address_components: Vec<GoogleAddressComponent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<GoogleGeometry>,
+    pub types: Vec<&'static str>,
+}
+
+/// Address component of a [`GoogleResult`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GoogleAddressComponent {
+    pub long_name: String,
+    pub short_name: String,
+    pub types: Vec<&'static str>,
+}
+
+/// Geometry of a [`GoogleResult`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GoogleGeometry {
+    pub location: GoogleLatLng,
+    pub location_type: &'static str,
+}
+
+/// Google `{ lat, lng }` pair.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct GoogleLatLng {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// Flattened parts of a hit shared by all compat formats.
+struct HitParts {
+    kind: &'static str,
+    city: Option<String>,
+    district: Option<String>,
+    state: Option<(String, String)>, // (name, code)
+    country: (String, String),       // (name, iso2)
+    position: Option<(f64, f64)>,
+}
+
+impl HitParts {
+    fn new<B: GeoBackend>(item: &SmartItem<'_, B>) -> Self {
+        let position = item.coordinates();
+        match *item {
+            SmartItem::Country(c) => Self {
+                kind: "country",
+                city: None,
+                district: None,
+                state: None,
+                country: (c.name().to_string(), c.iso2().to_string()),
+                position,
+            },
+            SmartItem::State { country, state } => Self {
+                kind: "state",
+                city: None,
+                district: None,
+                state: Some((state.name().to_string(), state.state_code().to_string())),
+                country: (country.name().to_string(), country.iso2().to_string()),
+                position,
+            },
+            SmartItem::City {
+                country,
+                state,
+                city,
+            } => Self {
+                kind: "city",
+                city: Some(city.name().to_string()),
+                district: None,
+                // cities listed directly on the country have no real state
+                state: (!state.is_synthetic())
+                    .then(|| (state.name().to_string(), state.state_code().to_string())),
+                country: (country.name().to_string(), country.iso2().to_string()),
+                position,
+            },
+            #[cfg(feature = "districts")]
+            SmartItem::District {
+                country,
+                state,
+                district,
+            } => Self {
+                kind: "district",
+                city: None,
+                district: Some(district.name().to_string()),
+                state: Some((state.name().to_string(), state.state_code().to_string())),
+                country: (country.name().to_string(), country.iso2().to_string()),
+                position,
+            },
+        }
+    }
+
+    fn display_name(&self) -> String {
+        let mut parts: Vec<&str> = Vec::with_capacity(3);
+        if let Some(city) = &self.city {
+            parts.push(city);
+        }
+        if let Some(district) = &self.district {
+            parts.push(district);
+        }
+        if let Some((state, _)) = &self.state {
+            parts.push(state);
+        }
+        parts.push(&self.country.0);
+        parts.join(", ")
+    }
+}
+
+/// Render one hit as a Nominatim-like place.
+pub fn to_nominatim<B: GeoBackend>(hit: &SmartHit<'_, B>) -> NominatimPlace {
+    let parts = HitParts::new(&hit.item);
+    let (class, kind) = if parts.kind == "city" {
+        ("place", "city")
+    } else {
+        ("boundary", "administrative")
+    };
+    NominatimPlace {
+        display_name: parts.display_name(),
+        lat: parts.position.map(|(lat, _)| lat.to_string()),
+        lon: parts.position.map(|(_, lng)| lng.to_string()),
+        class,
+        kind,
+        addresstype: parts.kind,
+        importance: (f64::from(hit.score) / 100.0).clamp(0.0, 1.0),
+        address: NominatimAddress {
+            city: parts.city.clone(),
+            state: parts.state.as_ref().map(|(name, _)| name.clone()),
+            country: parts.country.0.clone(),
+            country_code: parts.country.1.to_ascii_lowercase(),
+        },
+    }
+}
+
+/// Render one hit as a Google Geocoding-like result.
+pub fn to_google<B: GeoBackend>(hit: &SmartHit<'_, B>) -> GoogleResult {
+    let parts = HitParts::new(&hit.item);
+
+    let mut components = Vec::with_capacity(3);
+    if let Some(city) = &parts.city {
+        components.push(GoogleAddressComponent {
+            long_name: city.clone(),
+            short_name: city.clone(),
+            types: vec!["locality", "political"],
+        });
+    }
+    if let Some(district) = &parts.district {
+        components.push(GoogleAddressComponent {
+            long_name: district.clone(),
+            short_name: district.clone(),
+            types: vec!["administrative_area_level_2", "political"],
+        });
+    }
+    if let Some((name, code)) = &parts.state {
+        components.push(GoogleAddressComponent {
+            long_name: name.clone(),
+            short_name: if code.is_empty() {
+                name.clone()
+            } else {
+                code.clone()
+            },
+            types: vec!["administrative_area_level_1", "political"],
+        });
+    }
+    components.push(GoogleAddressComponent {
+        long_name: parts.country.0.clone(),
+        short_name: parts.country.1.clone(),
+        types: vec!["country", "political"],
+    });
+
+    let types = match parts.kind {
+        "city" => vec!["locality", "political"],
+        "state" => vec!["administrative_area_level_1", "political"],
+        "district" => vec!["administrative_area_level_2", "political"],
+        _ => vec!["country", "political"],
+    };
+
+    GoogleResult {
+        formatted_address: parts.display_name(),
+        address_components: components,
+        geometry: parts.position.map(|(lat, lng)| GoogleGeometry {
+            location: GoogleLatLng { lat, lng },
+            location_type: "APPROXIMATE",
+        }),
+        types,
+    }
+}
+
+/// Render a list of hits in the requested format.
+pub fn render_hits<'a, B: GeoBackend>(
+    hits: &[SmartHit<'a, B>],
+    format: ResponseFormat,
+) -> CompatResponse<'a, B> {
+    render_hits_with(hits, format, ViewOptions::default())
+}
+
+/// Like [`render_hits`], with [`ViewOptions`] for the native city views.
+pub fn render_hits_with<'a, B: GeoBackend>(
+    hits: &[SmartHit<'a, B>],
+    format: ResponseFormat,
+    view: ViewOptions,
+) -> CompatResponse<'a, B> {
+    match format {
+        ResponseFormat::Native => {
+            CompatResponse::Native(hits.iter().map(|hit| hit.to_view_with(view)).collect())
+        }
+        ResponseFormat::Nominatim => {
+            CompatResponse::Nominatim(hits.iter().map(to_nominatim).collect())
+        }
+        ResponseFormat::Google => {
+            let results: Vec<_> = hits.iter().map(to_google).collect();
+            let status = if results.is_empty() {
+                "ZERO_RESULTS"
+            } else {
+                "OK"
+            };
+            CompatResponse::Google(GoogleResponse { results, status })
+        }
+    }
+}
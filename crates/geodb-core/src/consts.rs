@@ -0,0 +1,774 @@
+// @generated by `cargo run --example gen_country_consts`; do not edit.
+//! Country constants, one per country in the bundled dataset.
+//!
+//! ```
+//! use geodb_core::consts;
+//!
+//! assert_eq!(consts::DE.as_str(), "DE");
+//! ```
+use crate::country_id::CountryId;
+
+/// Andorra
+pub const AD: CountryId = CountryId::from_ascii(*b"AD");
+
+/// United Arab Emirates
+pub const AE: CountryId = CountryId::from_ascii(*b"AE");
+
+/// Afghanistan
+pub const AF: CountryId = CountryId::from_ascii(*b"AF");
+
+/// Antigua and Barbuda
+pub const AG: CountryId = CountryId::from_ascii(*b"AG");
+
+/// Anguilla
+pub const AI: CountryId = CountryId::from_ascii(*b"AI");
+
+/// Albania
+pub const AL: CountryId = CountryId::from_ascii(*b"AL");
+
+/// Armenia
+pub const AM: CountryId = CountryId::from_ascii(*b"AM");
+
+/// Angola
+pub const AO: CountryId = CountryId::from_ascii(*b"AO");
+
+/// Antarctica
+pub const AQ: CountryId = CountryId::from_ascii(*b"AQ");
+
+/// Argentina
+pub const AR: CountryId = CountryId::from_ascii(*b"AR");
+
+/// American Samoa
+pub const AS: CountryId = CountryId::from_ascii(*b"AS");
+
+/// Austria
+pub const AT: CountryId = CountryId::from_ascii(*b"AT");
+
+/// Australia
+pub const AU: CountryId = CountryId::from_ascii(*b"AU");
+
+/// Aruba
+pub const AW: CountryId = CountryId::from_ascii(*b"AW");
+
+/// Aland Islands
+pub const AX: CountryId = CountryId::from_ascii(*b"AX");
+
+/// Azerbaijan
+pub const AZ: CountryId = CountryId::from_ascii(*b"AZ");
+
+/// Bosnia and Herzegovina
+pub const BA: CountryId = CountryId::from_ascii(*b"BA");
+
+/// Barbados
+pub const BB: CountryId = CountryId::from_ascii(*b"BB");
+
+/// Bangladesh
+pub const BD: CountryId = CountryId::from_ascii(*b"BD");
+
+/// Belgium
+pub const BE: CountryId = CountryId::from_ascii(*b"BE");
+
+/// Burkina Faso
+pub const BF: CountryId = CountryId::from_ascii(*b"BF");
+
+/// Bulgaria
+pub const BG: CountryId = CountryId::from_ascii(*b"BG");
+
+/// Bahrain
+pub const BH: CountryId = CountryId::from_ascii(*b"BH");
+
+/// Burundi
+pub const BI: CountryId = CountryId::from_ascii(*b"BI");
+
+/// Benin
+pub const BJ: CountryId = CountryId::from_ascii(*b"BJ");
+
+/// Saint-Barthelemy
+pub const BL: CountryId = CountryId::from_ascii(*b"BL");
+
+/// Bermuda
+pub const BM: CountryId = CountryId::from_ascii(*b"BM");
+
+/// Brunei
+pub const BN: CountryId = CountryId::from_ascii(*b"BN");
+
+/// Bolivia
+pub const BO: CountryId = CountryId::from_ascii(*b"BO");
+
+/// Bonaire, Sint Eustatius and Saba
+pub const BQ: CountryId = CountryId::from_ascii(*b"BQ");
+
+/// Brazil
+pub const BR: CountryId = CountryId::from_ascii(*b"BR");
+
+/// The Bahamas
+pub const BS: CountryId = CountryId::from_ascii(*b"BS");
+
+/// Bhutan
+pub const BT: CountryId = CountryId::from_ascii(*b"BT");
+
+/// Bouvet Island
+pub const BV: CountryId = CountryId::from_ascii(*b"BV");
+
+/// Botswana
+pub const BW: CountryId = CountryId::from_ascii(*b"BW");
+
+/// Belarus
+pub const BY: CountryId = CountryId::from_ascii(*b"BY");
+
+/// Belize
+pub const BZ: CountryId = CountryId::from_ascii(*b"BZ");
+
+/// Canada
+pub const CA: CountryId = CountryId::from_ascii(*b"CA");
+
+/// Cocos (Keeling) Islands
+pub const CC: CountryId = CountryId::from_ascii(*b"CC");
+
+/// Democratic Republic of the Congo
+pub const CD: CountryId = CountryId::from_ascii(*b"CD");
+
+/// Central African Republic
+pub const CF: CountryId = CountryId::from_ascii(*b"CF");
+
+/// Congo
+pub const CG: CountryId = CountryId::from_ascii(*b"CG");
+
+/// Switzerland
+pub const CH: CountryId = CountryId::from_ascii(*b"CH");
+
+/// Ivory Coast
+pub const CI: CountryId = CountryId::from_ascii(*b"CI");
+
+/// Cook Islands
+pub const CK: CountryId = CountryId::from_ascii(*b"CK");
+
+/// Chile
+pub const CL: CountryId = CountryId::from_ascii(*b"CL");
+
+/// Cameroon
+pub const CM: CountryId = CountryId::from_ascii(*b"CM");
+
+/// China
+pub const CN: CountryId = CountryId::from_ascii(*b"CN");
+
+/// Colombia
+pub const CO: CountryId = CountryId::from_ascii(*b"CO");
+
+/// Costa Rica
+pub const CR: CountryId = CountryId::from_ascii(*b"CR");
+
+/// Cuba
+pub const CU: CountryId = CountryId::from_ascii(*b"CU");
+
+/// Cape Verde
+pub const CV: CountryId = CountryId::from_ascii(*b"CV");
+
+/// Curaçao
+pub const CW: CountryId = CountryId::from_ascii(*b"CW");
+
+/// Christmas Island
+pub const CX: CountryId = CountryId::from_ascii(*b"CX");
+
+/// Cyprus
+pub const CY: CountryId = CountryId::from_ascii(*b"CY");
+
+/// Czech Republic
+pub const CZ: CountryId = CountryId::from_ascii(*b"CZ");
+
+/// Germany
+pub const DE: CountryId = CountryId::from_ascii(*b"DE");
+
+/// Djibouti
+pub const DJ: CountryId = CountryId::from_ascii(*b"DJ");
+
+/// Denmark
+pub const DK: CountryId = CountryId::from_ascii(*b"DK");
+
+/// Dominica
+pub const DM: CountryId = CountryId::from_ascii(*b"DM");
+
+/// Dominican Republic
+pub const DO: CountryId = CountryId::from_ascii(*b"DO");
+
+/// Algeria
+pub const DZ: CountryId = CountryId::from_ascii(*b"DZ");
+
+/// Ecuador
+pub const EC: CountryId = CountryId::from_ascii(*b"EC");
+
+/// Estonia
+pub const EE: CountryId = CountryId::from_ascii(*b"EE");
+
+/// Egypt
+pub const EG: CountryId = CountryId::from_ascii(*b"EG");
+
+/// Western Sahara
+pub const EH: CountryId = CountryId::from_ascii(*b"EH");
+
+/// Eritrea
+pub const ER: CountryId = CountryId::from_ascii(*b"ER");
+
+/// Spain
+pub const ES: CountryId = CountryId::from_ascii(*b"ES");
+
+/// Ethiopia
+pub const ET: CountryId = CountryId::from_ascii(*b"ET");
+
+/// Finland
+pub const FI: CountryId = CountryId::from_ascii(*b"FI");
+
+/// Fiji Islands
+pub const FJ: CountryId = CountryId::from_ascii(*b"FJ");
+
+/// Falkland Islands
+pub const FK: CountryId = CountryId::from_ascii(*b"FK");
+
+/// Micronesia
+pub const FM: CountryId = CountryId::from_ascii(*b"FM");
+
+/// Faroe Islands
+pub const FO: CountryId = CountryId::from_ascii(*b"FO");
+
+/// France
+pub const FR: CountryId = CountryId::from_ascii(*b"FR");
+
+/// Gabon
+pub const GA: CountryId = CountryId::from_ascii(*b"GA");
+
+/// United Kingdom
+pub const GB: CountryId = CountryId::from_ascii(*b"GB");
+
+/// Grenada
+pub const GD: CountryId = CountryId::from_ascii(*b"GD");
+
+/// Georgia
+pub const GE: CountryId = CountryId::from_ascii(*b"GE");
+
+/// French Guiana
+pub const GF: CountryId = CountryId::from_ascii(*b"GF");
+
+/// Guernsey
+pub const GG: CountryId = CountryId::from_ascii(*b"GG");
+
+/// Ghana
+pub const GH: CountryId = CountryId::from_ascii(*b"GH");
+
+/// Gibraltar
+pub const GI: CountryId = CountryId::from_ascii(*b"GI");
+
+/// Greenland
+pub const GL: CountryId = CountryId::from_ascii(*b"GL");
+
+/// The Gambia
+pub const GM: CountryId = CountryId::from_ascii(*b"GM");
+
+/// Guinea
+pub const GN: CountryId = CountryId::from_ascii(*b"GN");
+
+/// Guadeloupe
+pub const GP: CountryId = CountryId::from_ascii(*b"GP");
+
+/// Equatorial Guinea
+pub const GQ: CountryId = CountryId::from_ascii(*b"GQ");
+
+/// Greece
+pub const GR: CountryId = CountryId::from_ascii(*b"GR");
+
+/// South Georgia
+pub const GS: CountryId = CountryId::from_ascii(*b"GS");
+
+/// Guatemala
+pub const GT: CountryId = CountryId::from_ascii(*b"GT");
+
+/// Guam
+pub const GU: CountryId = CountryId::from_ascii(*b"GU");
+
+/// Guinea-Bissau
+pub const GW: CountryId = CountryId::from_ascii(*b"GW");
+
+/// Guyana
+pub const GY: CountryId = CountryId::from_ascii(*b"GY");
+
+/// Hong Kong S.A.R.
+pub const HK: CountryId = CountryId::from_ascii(*b"HK");
+
+/// Heard Island and McDonald Islands
+pub const HM: CountryId = CountryId::from_ascii(*b"HM");
+
+/// Honduras
+pub const HN: CountryId = CountryId::from_ascii(*b"HN");
+
+/// Croatia
+pub const HR: CountryId = CountryId::from_ascii(*b"HR");
+
+/// Haiti
+pub const HT: CountryId = CountryId::from_ascii(*b"HT");
+
+/// Hungary
+pub const HU: CountryId = CountryId::from_ascii(*b"HU");
+
+/// Indonesia
+pub const ID: CountryId = CountryId::from_ascii(*b"ID");
+
+/// Ireland
+pub const IE: CountryId = CountryId::from_ascii(*b"IE");
+
+/// Israel
+pub const IL: CountryId = CountryId::from_ascii(*b"IL");
+
+/// Man (Isle of)
+pub const IM: CountryId = CountryId::from_ascii(*b"IM");
+
+/// India
+pub const IN: CountryId = CountryId::from_ascii(*b"IN");
+
+/// British Indian Ocean Territory
+pub const IO: CountryId = CountryId::from_ascii(*b"IO");
+
+/// Iraq
+pub const IQ: CountryId = CountryId::from_ascii(*b"IQ");
+
+/// Iran
+pub const IR: CountryId = CountryId::from_ascii(*b"IR");
+
+/// Iceland
+pub const IS: CountryId = CountryId::from_ascii(*b"IS");
+
+/// Italy
+pub const IT: CountryId = CountryId::from_ascii(*b"IT");
+
+/// Jersey
+pub const JE: CountryId = CountryId::from_ascii(*b"JE");
+
+/// Jamaica
+pub const JM: CountryId = CountryId::from_ascii(*b"JM");
+
+/// Jordan
+pub const JO: CountryId = CountryId::from_ascii(*b"JO");
+
+/// Japan
+pub const JP: CountryId = CountryId::from_ascii(*b"JP");
+
+/// Kenya
+pub const KE: CountryId = CountryId::from_ascii(*b"KE");
+
+/// Kyrgyzstan
+pub const KG: CountryId = CountryId::from_ascii(*b"KG");
+
+/// Cambodia
+pub const KH: CountryId = CountryId::from_ascii(*b"KH");
+
+/// Kiribati
+pub const KI: CountryId = CountryId::from_ascii(*b"KI");
+
+/// Comoros
+pub const KM: CountryId = CountryId::from_ascii(*b"KM");
+
+/// Saint Kitts and Nevis
+pub const KN: CountryId = CountryId::from_ascii(*b"KN");
+
+/// North Korea
+pub const KP: CountryId = CountryId::from_ascii(*b"KP");
+
+/// South Korea
+pub const KR: CountryId = CountryId::from_ascii(*b"KR");
+
+/// Kuwait
+pub const KW: CountryId = CountryId::from_ascii(*b"KW");
+
+/// Cayman Islands
+pub const KY: CountryId = CountryId::from_ascii(*b"KY");
+
+/// Kazakhstan
+pub const KZ: CountryId = CountryId::from_ascii(*b"KZ");
+
+/// Laos
+pub const LA: CountryId = CountryId::from_ascii(*b"LA");
+
+/// Lebanon
+pub const LB: CountryId = CountryId::from_ascii(*b"LB");
+
+/// Saint Lucia
+pub const LC: CountryId = CountryId::from_ascii(*b"LC");
+
+/// Liechtenstein
+pub const LI: CountryId = CountryId::from_ascii(*b"LI");
+
+/// Sri Lanka
+pub const LK: CountryId = CountryId::from_ascii(*b"LK");
+
+/// Liberia
+pub const LR: CountryId = CountryId::from_ascii(*b"LR");
+
+/// Lesotho
+pub const LS: CountryId = CountryId::from_ascii(*b"LS");
+
+/// Lithuania
+pub const LT: CountryId = CountryId::from_ascii(*b"LT");
+
+/// Luxembourg
+pub const LU: CountryId = CountryId::from_ascii(*b"LU");
+
+/// Latvia
+pub const LV: CountryId = CountryId::from_ascii(*b"LV");
+
+/// Libya
+pub const LY: CountryId = CountryId::from_ascii(*b"LY");
+
+/// Morocco
+pub const MA: CountryId = CountryId::from_ascii(*b"MA");
+
+/// Monaco
+pub const MC: CountryId = CountryId::from_ascii(*b"MC");
+
+/// Moldova
+pub const MD: CountryId = CountryId::from_ascii(*b"MD");
+
+/// Montenegro
+pub const ME: CountryId = CountryId::from_ascii(*b"ME");
+
+/// Saint-Martin (French part)
+pub const MF: CountryId = CountryId::from_ascii(*b"MF");
+
+/// Madagascar
+pub const MG: CountryId = CountryId::from_ascii(*b"MG");
+
+/// Marshall Islands
+pub const MH: CountryId = CountryId::from_ascii(*b"MH");
+
+/// North Macedonia
+pub const MK: CountryId = CountryId::from_ascii(*b"MK");
+
+/// Mali
+pub const ML: CountryId = CountryId::from_ascii(*b"ML");
+
+/// Myanmar
+pub const MM: CountryId = CountryId::from_ascii(*b"MM");
+
+/// Mongolia
+pub const MN: CountryId = CountryId::from_ascii(*b"MN");
+
+/// Macau S.A.R.
+pub const MO: CountryId = CountryId::from_ascii(*b"MO");
+
+/// Northern Mariana Islands
+pub const MP: CountryId = CountryId::from_ascii(*b"MP");
+
+/// Martinique
+pub const MQ: CountryId = CountryId::from_ascii(*b"MQ");
+
+/// Mauritania
+pub const MR: CountryId = CountryId::from_ascii(*b"MR");
+
+/// Montserrat
+pub const MS: CountryId = CountryId::from_ascii(*b"MS");
+
+/// Malta
+pub const MT: CountryId = CountryId::from_ascii(*b"MT");
+
+/// Mauritius
+pub const MU: CountryId = CountryId::from_ascii(*b"MU");
+
+/// Maldives
+pub const MV: CountryId = CountryId::from_ascii(*b"MV");
+
+/// Malawi
+pub const MW: CountryId = CountryId::from_ascii(*b"MW");
+
+/// Mexico
+pub const MX: CountryId = CountryId::from_ascii(*b"MX");
+
+/// Malaysia
+pub const MY: CountryId = CountryId::from_ascii(*b"MY");
+
+/// Mozambique
+pub const MZ: CountryId = CountryId::from_ascii(*b"MZ");
+
+/// Namibia
+pub const NA: CountryId = CountryId::from_ascii(*b"NA");
+
+/// New Caledonia
+pub const NC: CountryId = CountryId::from_ascii(*b"NC");
+
+/// Niger
+pub const NE: CountryId = CountryId::from_ascii(*b"NE");
+
+/// Norfolk Island
+pub const NF: CountryId = CountryId::from_ascii(*b"NF");
+
+/// Nigeria
+pub const NG: CountryId = CountryId::from_ascii(*b"NG");
+
+/// Nicaragua
+pub const NI: CountryId = CountryId::from_ascii(*b"NI");
+
+/// Netherlands
+pub const NL: CountryId = CountryId::from_ascii(*b"NL");
+
+/// Norway
+pub const NO: CountryId = CountryId::from_ascii(*b"NO");
+
+/// Nepal
+pub const NP: CountryId = CountryId::from_ascii(*b"NP");
+
+/// Nauru
+pub const NR: CountryId = CountryId::from_ascii(*b"NR");
+
+/// Niue
+pub const NU: CountryId = CountryId::from_ascii(*b"NU");
+
+/// New Zealand
+pub const NZ: CountryId = CountryId::from_ascii(*b"NZ");
+
+/// Oman
+pub const OM: CountryId = CountryId::from_ascii(*b"OM");
+
+/// Panama
+pub const PA: CountryId = CountryId::from_ascii(*b"PA");
+
+/// Peru
+pub const PE: CountryId = CountryId::from_ascii(*b"PE");
+
+/// French Polynesia
+pub const PF: CountryId = CountryId::from_ascii(*b"PF");
+
+/// Papua New Guinea
+pub const PG: CountryId = CountryId::from_ascii(*b"PG");
+
+/// Philippines
+pub const PH: CountryId = CountryId::from_ascii(*b"PH");
+
+/// Pakistan
+pub const PK: CountryId = CountryId::from_ascii(*b"PK");
+
+/// Poland
+pub const PL: CountryId = CountryId::from_ascii(*b"PL");
+
+/// Saint Pierre and Miquelon
+pub const PM: CountryId = CountryId::from_ascii(*b"PM");
+
+/// Pitcairn Island
+pub const PN: CountryId = CountryId::from_ascii(*b"PN");
+
+/// Puerto Rico
+pub const PR: CountryId = CountryId::from_ascii(*b"PR");
+
+/// Palestinian Territory Occupied
+pub const PS: CountryId = CountryId::from_ascii(*b"PS");
+
+/// Portugal
+pub const PT: CountryId = CountryId::from_ascii(*b"PT");
+
+/// Palau
+pub const PW: CountryId = CountryId::from_ascii(*b"PW");
+
+/// Paraguay
+pub const PY: CountryId = CountryId::from_ascii(*b"PY");
+
+/// Qatar
+pub const QA: CountryId = CountryId::from_ascii(*b"QA");
+
+/// Reunion
+pub const RE: CountryId = CountryId::from_ascii(*b"RE");
+
+/// Romania
+pub const RO: CountryId = CountryId::from_ascii(*b"RO");
+
+/// Serbia
+pub const RS: CountryId = CountryId::from_ascii(*b"RS");
+
+/// Russia
+pub const RU: CountryId = CountryId::from_ascii(*b"RU");
+
+/// Rwanda
+pub const RW: CountryId = CountryId::from_ascii(*b"RW");
+
+/// Saudi Arabia
+pub const SA: CountryId = CountryId::from_ascii(*b"SA");
+
+/// Solomon Islands
+pub const SB: CountryId = CountryId::from_ascii(*b"SB");
+
+/// Seychelles
+pub const SC: CountryId = CountryId::from_ascii(*b"SC");
+
+/// Sudan
+pub const SD: CountryId = CountryId::from_ascii(*b"SD");
+
+/// Sweden
+pub const SE: CountryId = CountryId::from_ascii(*b"SE");
+
+/// Singapore
+pub const SG: CountryId = CountryId::from_ascii(*b"SG");
+
+/// Saint Helena
+pub const SH: CountryId = CountryId::from_ascii(*b"SH");
+
+/// Slovenia
+pub const SI: CountryId = CountryId::from_ascii(*b"SI");
+
+/// Svalbard and Jan Mayen Islands
+pub const SJ: CountryId = CountryId::from_ascii(*b"SJ");
+
+/// Slovakia
+pub const SK: CountryId = CountryId::from_ascii(*b"SK");
+
+/// Sierra Leone
+pub const SL: CountryId = CountryId::from_ascii(*b"SL");
+
+/// San Marino
+pub const SM: CountryId = CountryId::from_ascii(*b"SM");
+
+/// Senegal
+pub const SN: CountryId = CountryId::from_ascii(*b"SN");
+
+/// Somalia
+pub const SO: CountryId = CountryId::from_ascii(*b"SO");
+
+/// Suriname
+pub const SR: CountryId = CountryId::from_ascii(*b"SR");
+
+/// South Sudan
+pub const SS: CountryId = CountryId::from_ascii(*b"SS");
+
+/// Sao Tome and Principe
+pub const ST: CountryId = CountryId::from_ascii(*b"ST");
+
+/// El Salvador
+pub const SV: CountryId = CountryId::from_ascii(*b"SV");
+
+/// Sint Maarten (Dutch part)
+pub const SX: CountryId = CountryId::from_ascii(*b"SX");
+
+/// Syria
+pub const SY: CountryId = CountryId::from_ascii(*b"SY");
+
+/// Eswatini
+pub const SZ: CountryId = CountryId::from_ascii(*b"SZ");
+
+/// Turks and Caicos Islands
+pub const TC: CountryId = CountryId::from_ascii(*b"TC");
+
+/// Chad
+pub const TD: CountryId = CountryId::from_ascii(*b"TD");
+
+/// French Southern Territories
+pub const TF: CountryId = CountryId::from_ascii(*b"TF");
+
+/// Togo
+pub const TG: CountryId = CountryId::from_ascii(*b"TG");
+
+/// Thailand
+pub const TH: CountryId = CountryId::from_ascii(*b"TH");
+
+/// Tajikistan
+pub const TJ: CountryId = CountryId::from_ascii(*b"TJ");
+
+/// Tokelau
+pub const TK: CountryId = CountryId::from_ascii(*b"TK");
+
+/// Timor-Leste
+pub const TL: CountryId = CountryId::from_ascii(*b"TL");
+
+/// Turkmenistan
+pub const TM: CountryId = CountryId::from_ascii(*b"TM");
+
+/// Tunisia
+pub const TN: CountryId = CountryId::from_ascii(*b"TN");
+
+/// Tonga
+pub const TO: CountryId = CountryId::from_ascii(*b"TO");
+
+/// Turkey
+pub const TR: CountryId = CountryId::from_ascii(*b"TR");
+
+/// Trinidad and Tobago
+pub const TT: CountryId = CountryId::from_ascii(*b"TT");
+
+/// Tuvalu
+pub const TV: CountryId = CountryId::from_ascii(*b"TV");
+
+/// Taiwan
+pub const TW: CountryId = CountryId::from_ascii(*b"TW");
+
+/// Tanzania
+pub const TZ: CountryId = CountryId::from_ascii(*b"TZ");
+
+/// Ukraine
+pub const UA: CountryId = CountryId::from_ascii(*b"UA");
+
+/// Uganda
+pub const UG: CountryId = CountryId::from_ascii(*b"UG");
+
+/// United States Minor Outlying Islands
+pub const UM: CountryId = CountryId::from_ascii(*b"UM");
+
+/// United States
+pub const US: CountryId = CountryId::from_ascii(*b"US");
+
+/// Uruguay
+pub const UY: CountryId = CountryId::from_ascii(*b"UY");
+
+/// Uzbekistan
+pub const UZ: CountryId = CountryId::from_ascii(*b"UZ");
+
+/// Vatican City State (Holy See)
+pub const VA: CountryId = CountryId::from_ascii(*b"VA");
+
+/// Saint Vincent and the Grenadines
+pub const VC: CountryId = CountryId::from_ascii(*b"VC");
+
+/// Venezuela
+pub const VE: CountryId = CountryId::from_ascii(*b"VE");
+
+/// Virgin Islands (British)
+pub const VG: CountryId = CountryId::from_ascii(*b"VG");
+
+/// Virgin Islands (US)
+pub const VI: CountryId = CountryId::from_ascii(*b"VI");
+
+/// Vietnam
+pub const VN: CountryId = CountryId::from_ascii(*b"VN");
+
+/// Vanuatu
+pub const VU: CountryId = CountryId::from_ascii(*b"VU");
+
+/// Wallis and Futuna Islands
+pub const WF: CountryId = CountryId::from_ascii(*b"WF");
+
+/// Samoa
+pub const WS: CountryId = CountryId::from_ascii(*b"WS");
+
+/// Kosovo
+pub const XK: CountryId = CountryId::from_ascii(*b"XK");
+
+/// Yemen
+pub const YE: CountryId = CountryId::from_ascii(*b"YE");
+
+/// Mayotte
+pub const YT: CountryId = CountryId::from_ascii(*b"YT");
+
+/// South Africa
+pub const ZA: CountryId = CountryId::from_ascii(*b"ZA");
+
+/// Zambia
+pub const ZM: CountryId = CountryId::from_ascii(*b"ZM");
+
+/// Zimbabwe
+pub const ZW: CountryId = CountryId::from_ascii(*b"ZW");
+
+/// All constants above, sorted by code.
+pub const ALL: &[CountryId] = &[
+    AD, AE, AF, AG, AI, AL, AM, AO, AQ, AR, AS, AT, AU, AW, AX, AZ, BA, BB, BD, BE, BF, BG, BH, BI,
+    BJ, BL, BM, BN, BO, BQ, BR, BS, BT, BV, BW, BY, BZ, CA, CC, CD, CF, CG, CH, CI, CK, CL, CM, CN,
+    CO, CR, CU, CV, CW, CX, CY, CZ, DE, DJ, DK, DM, DO, DZ, EC, EE, EG, EH, ER, ES, ET, FI, FJ, FK,
+    FM, FO, FR, GA, GB, GD, GE, GF, GG, GH, GI, GL, GM, GN, GP, GQ, GR, GS, GT, GU, GW, GY, HK, HM,
+    HN, HR, HT, HU, ID, IE, IL, IM, IN, IO, IQ, IR, IS, IT, JE, JM, JO, JP, KE, KG, KH, KI, KM, KN,
+    KP, KR, KW, KY, KZ, LA, LB, LC, LI, LK, LR, LS, LT, LU, LV, LY, MA, MC, MD, ME, MF, MG, MH, MK,
+    ML, MM, MN, MO, MP, MQ, MR, MS, MT, MU, MV, MW, MX, MY, MZ, NA, NC, NE, NF, NG, NI, NL, NO, NP,
+    NR, NU, NZ, OM, PA, PE, PF, PG, PH, PK, PL, PM, PN, PR, PS, PT, PW, PY, QA, RE, RO, RS, RU, RW,
+    SA, SB, SC, SD, SE, SG, SH, SI, SJ, SK, SL, SM, SN, SO, SR, SS, ST, SV, SX, SY, SZ, TC, TD, TF,
+    TG, TH, TJ, TK, TL, TM, TN, TO, TR, TT, TV, TW, TZ, UA, UG, UM, US, UY, UZ, VA, VC, VE, VG, VI,
+    VN, VU, WF, WS, XK, YE, YT, ZA, ZM, ZW,
+];
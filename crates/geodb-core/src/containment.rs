@@ -0,0 +1,252 @@
+// src/containment.rs
+//! Administrative containment: which country and state a city belongs to,
+//! what lies below a country, and whether a city is inside a named area.
+//!
+//! ```no_run
+//! use geodb_core::{consts, AdminLevel, GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let (city, _, _) = db.find_cities_by_substring("Heidelberg")[0];
+//! let id = db.city_id(city).expect("city of this database");
+//!
+//! let path = db.ancestors_of(id).expect("valid id");
+//! println!("{path}"); // Germany → Baden-Württemberg → Heidelberg
+//! assert!(db.is_within(id, "Europe"));
+//!
+//! let states = db.descendants_of(consts::DE, AdminLevel::State);
+//! println!("{} states", states.len());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+pub use crate::country_id::CityId;
+use crate::country_id::{CountryId, StateId};
+use crate::exact::fold_name;
+use crate::model::{City, Country, GeoBackend, GeoDb, State};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Level of the administrative hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminLevel {
+    Country,
+    State,
+    City,
+}
+
+/// A path down the hierarchy: country → state → city.
+///
+/// `state` and `city` are set according to [`Self::level`].
+#[derive(Debug)]
+pub struct AdminPath<'a, B: GeoBackend> {
+    pub country: &'a Country<B>,
+    pub state: Option<&'a State<B>>,
+    pub city: Option<&'a City<B>>,
+}
+
+impl<B: GeoBackend> Clone for AdminPath<'_, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B: GeoBackend> Copy for AdminPath<'_, B> {}
+
+impl<'a, B: GeoBackend> AdminPath<'a, B> {
+    /// Level of the last element of the path.
+    pub fn level(&self) -> AdminLevel {
+        match (self.state, self.city) {
+            (_, Some(_)) => AdminLevel::City,
+            (Some(_), None) => AdminLevel::State,
+            (None, None) => AdminLevel::Country,
+        }
+    }
+
+    /// Names along the path, country first.
+    pub fn names(&self) -> Vec<&'a str> {
+        let mut names = vec![self.country.name()];
+        names.extend(self.state.map(|s| s.name()));
+        names.extend(self.city.map(|c| c.name()));
+        names
+    }
+
+    /// `true` if the path lies inside `area`: the name of one of its
+    /// ancestors, the country's ISO2/ISO3 code, state code, region
+    /// ("Europe") or subregion ("Western Europe"). Compared
+    /// case-insensitively; the last element itself doesn't count.
+    pub fn is_within(&self, area: &str) -> bool {
+        let area = fold_name(area);
+        if area.is_empty() {
+            return false;
+        }
+        let is = |name: &str| fold_name(name) == area;
+
+        // `area` isn't empty, so missing codes ("") never match
+        let c = self.country;
+        let country_is =
+            self.level() != AdminLevel::Country && (is(c.name()) || is(c.iso2()) || is(c.iso3()));
+        let state_is = self.city.is_some()
+            && self
+                .state
+                .is_some_and(|s| is(s.name()) || is(s.state_code()));
+        country_is || state_is || is(c.region()) || c.subregion().is_some_and(is)
+    }
+}
+
+impl<B: GeoBackend> fmt::Display for AdminPath<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.names().join(" → "))
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Id of `state`, which must be borrowed from this database.
+    pub fn state_id(&self, state: &State<B>) -> Option<StateId> {
+        self.countries.iter().find_map(|c| c.state_id(state))
+    }
+
+    /// Id of `city`, which must be borrowed from this database.
+    pub fn city_id(&self, city: &City<B>) -> Option<CityId> {
+        self.countries.iter().find_map(|country| {
+            country
+                .states
+                .iter()
+                .find_map(|state| country.city_id(state, city))
+        })
+    }
+
+    /// The state with this id.
+    pub fn state_by_id(&self, id: StateId) -> Option<&State<B>> {
+        self.country(id.country())?.states.get(id.index() as usize)
+    }
+
+    /// The city with this id.
+    pub fn city_by_id(&self, id: CityId) -> Option<&City<B>> {
+        self.state_by_id(id.state())?
+            .cities
+            .get(id.index() as usize)
+    }
+
+    /// Path from the country down to the city with this id, or `None` for
+    /// an id outside this database.
+    pub fn ancestors_of(&self, id: CityId) -> Option<AdminPath<'_, B>> {
+        let country = self.country(id.country())?;
+        let state = country.states.get(id.state().index() as usize)?;
+        let city = state.cities.get(id.index() as usize)?;
+        Some(AdminPath {
+            country,
+            state: Some(state),
+            city: Some(city),
+        })
+    }
+
+    /// Everything at `level` inside `country`, in dataset order: the
+    /// country itself, its states, or all cities of its states. Empty for
+    /// an unknown country.
+    pub fn descendants_of(&self, country: CountryId, level: AdminLevel) -> Vec<AdminPath<'_, B>> {
+        let Some(country) = self.country(country) else {
+            return Vec::new();
+        };
+        let path = |state, city| AdminPath {
+            country,
+            state,
+            city,
+        };
+        match level {
+            AdminLevel::Country => vec![path(None, None)],
+            AdminLevel::State => country
+                .states()
+                .iter()
+                .map(|s| path(Some(s), None))
+                .collect(),
+            AdminLevel::City => country
+                .states()
+                .iter()
+                .flat_map(|s| s.cities().iter().map(move |c| path(Some(s), Some(c))))
+                .collect(),
+        }
+    }
+
+    /// `true` if the city with this id lies inside `area`; see
+    /// [`AdminPath::is_within`].
+    pub fn is_within(&self, id: CityId, area: &str) -> bool {
+        self.ancestors_of(id).is_some_and(|p| p.is_within(area))
+    }
+}
+
+/// A place written as "city, country" or "city, state, country", e.g.
+/// `"Geneva, CH"` or `"Springfield, IL, US"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceQuery {
+    pub city: String,
+    /// State code ("IL"), full code ("US-IL") or name.
+    pub state: Option<String>,
+    /// ISO2 or ISO3 code, or the country's name or native name.
+    pub country: String,
+}
+
+impl PlaceQuery {
+    /// Split `input` at its commas; `None` unless it has two or three
+    /// non-empty parts.
+    pub fn parse(input: &str) -> Option<Self> {
+        let parts: Vec<&str> = input.split(',').map(str::trim).collect();
+        if parts.iter().any(|p| p.is_empty()) {
+            return None;
+        }
+        let (city, state, country) = match parts[..] {
+            [city, country] => (city, None, country),
+            [city, state, country] => (city, Some(state), country),
+            _ => return None,
+        };
+        Some(Self {
+            city: city.to_string(),
+            state: state.map(str::to_string),
+            country: country.to_string(),
+        })
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// A country by ISO2 or ISO3 code (see [`Self::find_country_by_code`]),
+    /// falling back to its name or native name, compared case-insensitively.
+    pub fn find_country_by_code_or_name(&self, country: &str) -> Option<&Country<B>> {
+        self.find_country_by_code(country).or_else(|| {
+            let name = fold_name(country);
+            self.countries.iter().find(|c| {
+                fold_name(c.name()) == name || c.native_name().is_some_and(|n| fold_name(n) == name)
+            })
+        })
+    }
+
+    /// Every city matching `place`, by name or alias (case-insensitive), in
+    /// dataset order: names repeat across states, so "Springfield, US" has
+    /// several answers and "Springfield, IL, US" one.
+    ///
+    /// Empty if the country, state or city is unknown.
+    pub fn resolve_place(&self, place: &PlaceQuery) -> Vec<AdminPath<'_, B>> {
+        let Some(country) = self.find_country_by_code_or_name(&place.country) else {
+            return Vec::new();
+        };
+        let states = match &place.state {
+            Some(state) => match self.find_state(country.iso2(), state) {
+                Some(state) => std::slice::from_ref(state),
+                None => return Vec::new(),
+            },
+            None => country.states(),
+        };
+        let city = fold_name(&place.city);
+        states
+            .iter()
+            .flat_map(|state| state.cities().iter().map(move |c| (state, c)))
+            .filter(|(_, c)| {
+                std::iter::once(c.name())
+                    .chain(c.aliases())
+                    .any(|n| fold_name(n) == city)
+            })
+            .map(|(state, c)| AdminPath {
+                country,
+                state: Some(state),
+                city: Some(c),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,247 @@
+// src/country_id.rs
+//! Typed entity identifiers.
+//!
+//! [`CountryId`] wraps an ISO2 code. The generated [`crate::consts`] module
+//! has one constant per country in the bundled dataset, so application code
+//! can write `db.country(consts::DE)` and get a compile error for typos
+//! instead of a `None` at runtime.
+//!
+//! [`StateId`] and [`CityId`] add the entity's position within its country
+//! (`"DE/3"`, `"DE/3/17"` as strings) and are part of the JSON views. They
+//! are cheap handles to hold across calls, e.g. in Python or JS, instead of
+//! searching by name again. Positions count within a country, so ids stay
+//! valid in databases loaded with a different ISO2 filter, but not across
+//! dataset versions or in artifacts pruned with
+//! [`GeoDb::pruned_for_queries`].
+//!
+//! ```no_run
+//! use geodb_core::{CityId, GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let (city, state, country) = db.find_cities_by_substring("berlin")[0];
+//! let id = country.city_id(state, city).expect("city of this country");
+//!
+//! // later, e.g. after the id made a round trip through JS
+//! let id: CityId = id.to_string().parse().expect("valid id");
+//! assert!(db.city_by_id(id).is_some_and(|c| std::ptr::eq(c, city)));
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::model::{City, Country, GeoBackend, GeoDb, State};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// ISO 3166-1 alpha-2 country code (always two uppercase ASCII letters).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CountryId([u8; 2]);
+
+impl CountryId {
+    /// Construct from two uppercase ASCII letters; used by [`crate::consts`].
+    pub(crate) const fn from_ascii(code: [u8; 2]) -> Self {
+        Self(code)
+    }
+
+    /// Parse an ISO2 code (case-insensitive, surrounding whitespace ignored).
+    pub fn new(code: &str) -> Option<Self> {
+        match code.trim().as_bytes() {
+            &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                Some(Self([a.to_ascii_uppercase(), b.to_ascii_uppercase()]))
+            }
+            _ => None,
+        }
+    }
+
+    /// The ISO2 code, e.g. "DE".
+    pub fn as_str(&self) -> &str {
+        // Always two ASCII letters
+        std::str::from_utf8(&self.0).unwrap_or("")
+    }
+}
+
+impl fmt::Display for CountryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CountryId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s).ok_or_else(|| format!("invalid ISO2 country code '{s}'"))
+    }
+}
+
+impl TryFrom<String> for CountryId {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<CountryId> for String {
+    fn from(id: CountryId) -> Self {
+        id.as_str().to_string()
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Country by typed id, e.g. `db.country(consts::DE)`.
+    pub fn country(&self, id: CountryId) -> Option<&Country<B>> {
+        self.find_country_by_iso2(id.as_str())
+    }
+}
+
+impl<B: GeoBackend> Country<B> {
+    /// Typed id of this country, if its ISO2 code is well-formed.
+    pub fn id(&self) -> Option<CountryId> {
+        CountryId::new(self.iso2())
+    }
+}
+
+/// A state: its country and position in [`Country::states`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct StateId {
+    country: CountryId,
+    state: u32,
+}
+
+impl StateId {
+    pub fn new(country: CountryId, state: u32) -> Self {
+        Self { country, state }
+    }
+
+    pub fn country(&self) -> CountryId {
+        self.country
+    }
+
+    /// Position in [`Country::states`].
+    pub fn index(&self) -> u32 {
+        self.state
+    }
+}
+
+/// A city: its state and position in [`State::cities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CityId {
+    state: StateId,
+    city: u32,
+}
+
+impl CityId {
+    pub fn new(state: StateId, city: u32) -> Self {
+        Self { state, city }
+    }
+
+    pub fn state(&self) -> StateId {
+        self.state
+    }
+
+    pub fn country(&self) -> CountryId {
+        self.state.country
+    }
+
+    /// Position in [`State::cities`].
+    pub fn index(&self) -> u32 {
+        self.city
+    }
+}
+
+impl fmt::Display for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.country, self.state)
+    }
+}
+
+impl fmt::Display for CityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.state, self.city)
+    }
+}
+
+/// Parse the `/`-separated parts of an id: an ISO2 code and `N` positions.
+fn parse_parts<const N: usize>(s: &str) -> Option<(CountryId, [u32; N])> {
+    let mut parts = s.trim().split('/');
+    let country = CountryId::new(parts.next()?)?;
+    let mut positions = [0; N];
+    for position in &mut positions {
+        *position = parts.next()?.parse().ok()?;
+    }
+    parts.next().is_none().then_some((country, positions))
+}
+
+impl FromStr for StateId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_parts(s)
+            .map(|(country, [state])| Self::new(country, state))
+            .ok_or_else(|| format!("invalid state id '{s}' (expected e.g. \"DE/3\")"))
+    }
+}
+
+impl FromStr for CityId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_parts(s)
+            .map(|(country, [state, city])| Self::new(StateId::new(country, state), city))
+            .ok_or_else(|| format!("invalid city id '{s}' (expected e.g. \"DE/3/17\")"))
+    }
+}
+
+impl TryFrom<String> for StateId {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for CityId {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<StateId> for String {
+    fn from(id: StateId) -> Self {
+        id.to_string()
+    }
+}
+
+impl From<CityId> for String {
+    fn from(id: CityId) -> Self {
+        id.to_string()
+    }
+}
+
+/// Position of `item` in `items`, if it is one of them.
+fn position<T>(items: &[T], item: &T) -> Option<u32> {
+    let range = items.as_ptr_range();
+    let ptr: *const T = item;
+    range
+        .contains(&ptr)
+        .then(|| ((ptr as usize - range.start as usize) / std::mem::size_of::<T>()) as u32)
+}
+
+impl<B: GeoBackend> Country<B> {
+    /// Typed id of `state`, if it is one of this country's states.
+    pub fn state_id(&self, state: &State<B>) -> Option<StateId> {
+        Some(StateId::new(self.id()?, position(&self.states, state)?))
+    }
+
+    /// Typed id of `city` in `state`, if both belong to this country.
+    pub fn city_id(&self, state: &State<B>, city: &City<B>) -> Option<CityId> {
+        Some(CityId::new(
+            self.state_id(state)?,
+            position(&state.cities, city)?,
+        ))
+    }
+}
@@ -0,0 +1,312 @@
+// src/coverage.rs
+//! Per-country coverage of the dataset, checked against a benchmark.
+//!
+//! An upstream release occasionally loses data: a country's cities vanish
+//! in a botched re-import, or coordinates go missing for a whole state.
+//! [`GeoDb::country_coverage`] counts each country's states and cities and
+//! how many cities have coordinates and a timezone;
+//! [`GeoDb::audit_coverage`] compares those counts with a
+//! [`CoverageBenchmark`] of expected minimums, so a CI gate can reject a
+//! dataset update before artifacts are baked:
+//!
+//! ```no_run
+//! use geodb_core::{CoverageBenchmark, GeoDb, StandardBackend};
+//!
+//! let previous = GeoDb::<StandardBackend>::load_from_path("old.json.gz", None)?;
+//! // allow each count to drop by 5%
+//! let benchmark = CoverageBenchmark::from_db(&previous, 0.05);
+//!
+//! let db = GeoDb::<StandardBackend>::load_from_path("new.json.gz", None)?;
+//! let report = db.audit_coverage(&benchmark);
+//! for issue in &report.issues {
+//!     eprintln!("{issue}");
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::model::{GeoBackend, GeoDb};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Counts of one country, as returned by [`GeoDb::country_coverage`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CountryCoverage {
+    pub iso2: String,
+    pub name: String,
+    /// States, not counting the one holding cities listed directly on the
+    /// country.
+    pub states: usize,
+    pub cities: usize,
+    /// Cities with both a latitude and a longitude.
+    pub cities_with_coords: usize,
+    /// Cities with a (non-empty) timezone.
+    pub cities_with_timezone: usize,
+}
+
+impl CountryCoverage {
+    /// Share of the cities with coordinates, 1.0 for a country without
+    /// cities.
+    pub fn coords_share(&self) -> f64 {
+        share(self.cities_with_coords, self.cities)
+    }
+
+    /// Share of the cities with a timezone, 1.0 for a country without
+    /// cities.
+    pub fn timezone_share(&self) -> f64 {
+        share(self.cities_with_timezone, self.cities)
+    }
+}
+
+fn share(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Expected minimums of one country; unset fields aren't checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CountryBenchmark {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_states: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_cities: Option<usize>,
+    /// Minimum share (0.0 to 1.0) of cities with coordinates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_coords_share: Option<f64>,
+    /// Minimum share (0.0 to 1.0) of cities with a timezone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_timezone_share: Option<f64>,
+}
+
+/// Expected minimums per country (ISO2), e.g. from a `coverage.json`:
+///
+/// ```json
+/// { "countries": { "DE": { "min_states": 16, "min_cities": 10000 } } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoverageBenchmark {
+    pub countries: BTreeMap<String, CountryBenchmark>,
+}
+
+impl CoverageBenchmark {
+    /// Benchmark from the current counts of `db`, each lowered by
+    /// `tolerance` (0.05 lets a count drop by 5% before it is flagged).
+    pub fn from_db<B: GeoBackend>(db: &GeoDb<B>, tolerance: f64) -> Self {
+        let keep = (1.0 - tolerance).clamp(0.0, 1.0);
+        let floor = |count: usize| (count as f64 * keep).floor() as usize;
+        // three decimals keep the file readable
+        let floor_share = |share: f64| (share * keep * 1000.0).floor() / 1000.0;
+        let countries = db
+            .country_coverage()
+            .into_iter()
+            .map(|c| {
+                let benchmark = CountryBenchmark {
+                    min_states: Some(floor(c.states)),
+                    min_cities: Some(floor(c.cities)),
+                    min_coords_share: Some(floor_share(c.coords_share())),
+                    min_timezone_share: Some(floor_share(c.timezone_share())),
+                };
+                (c.iso2, benchmark)
+            })
+            .collect();
+        Self { countries }
+    }
+
+    /// Load a benchmark from a JSON file; country codes are upper-cased.
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let mut benchmark: Self = serde_json::from_slice(&bytes)?;
+        benchmark.countries = benchmark
+            .countries
+            .into_iter()
+            .map(|(k, v)| (k.to_ascii_uppercase(), v))
+            .collect();
+        Ok(benchmark)
+    }
+
+    /// Keep only the countries in `iso2` (case-insensitive), e.g. to check
+    /// a database loaded with a filter.
+    pub fn retain_iso2(&mut self, iso2: &[&str]) {
+        self.countries
+            .retain(|code, _| iso2.iter().any(|c| c.eq_ignore_ascii_case(code)));
+    }
+}
+
+/// What fell short of a [`CountryBenchmark`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageIssueKind {
+    /// The benchmark lists a country the database doesn't have.
+    MissingCountry,
+    TooFewStates,
+    TooFewCities,
+    /// Fewer cities than expected have coordinates.
+    MissingCoords,
+    /// Fewer cities than expected have a timezone.
+    MissingTimezones,
+}
+
+/// One country below its benchmark.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageIssue {
+    pub kind: CoverageIssueKind,
+    pub iso2: String,
+    /// The benchmark's minimum (a count, or a share for coords and
+    /// timezones; 1 for a missing country).
+    pub expected: f64,
+    /// The database's value (0 for a missing country).
+    pub actual: f64,
+}
+
+impl fmt::Display for CoverageIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (expected, actual) = (self.expected, self.actual);
+        match self.kind {
+            CoverageIssueKind::MissingCountry => {
+                write!(f, "{}: missing from the database", self.iso2)
+            }
+            CoverageIssueKind::TooFewStates => {
+                write!(
+                    f,
+                    "{}: {actual} states, expected at least {expected}",
+                    self.iso2
+                )
+            }
+            CoverageIssueKind::TooFewCities => {
+                write!(
+                    f,
+                    "{}: {actual} cities, expected at least {expected}",
+                    self.iso2
+                )
+            }
+            CoverageIssueKind::MissingCoords => write!(
+                f,
+                "{}: {:.1}% of cities have coordinates, expected at least {:.1}%",
+                self.iso2,
+                actual * 100.0,
+                expected * 100.0
+            ),
+            CoverageIssueKind::MissingTimezones => write!(
+                f,
+                "{}: {:.1}% of cities have a timezone, expected at least {:.1}%",
+                self.iso2,
+                actual * 100.0,
+                expected * 100.0
+            ),
+        }
+    }
+}
+
+/// Result of [`GeoDb::audit_coverage`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Counts of every country in the database, in database order.
+    pub countries: Vec<CountryCoverage>,
+    /// Countries below the benchmark, ordered by country code and kind.
+    pub issues: Vec<CoverageIssue>,
+}
+
+impl CoverageReport {
+    /// `true` if nothing fell short of the benchmark.
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// State and city counts of each country, in database order.
+    pub fn country_coverage(&self) -> Vec<CountryCoverage> {
+        self.countries
+            .iter()
+            .map(|country| {
+                let mut coverage = CountryCoverage {
+                    iso2: country.iso2().to_string(),
+                    name: country.name().to_string(),
+                    states: 0,
+                    cities: 0,
+                    cities_with_coords: 0,
+                    cities_with_timezone: 0,
+                };
+                for state in country.states() {
+                    if !state.is_synthetic() {
+                        coverage.states += 1;
+                    }
+                    for city in state.cities() {
+                        coverage.cities += 1;
+                        if city.latitude.is_some() && city.longitude.is_some() {
+                            coverage.cities_with_coords += 1;
+                        }
+                        if city
+                            .timezone
+                            .as_ref()
+                            .is_some_and(|t| !t.as_ref().is_empty())
+                        {
+                            coverage.cities_with_timezone += 1;
+                        }
+                    }
+                }
+                coverage
+            })
+            .collect()
+    }
+
+    /// Compare [`Self::country_coverage`] with `benchmark`.
+    ///
+    /// Countries the benchmark doesn't list are counted but not checked.
+    pub fn audit_coverage(&self, benchmark: &CoverageBenchmark) -> CoverageReport {
+        let countries = self.country_coverage();
+        let mut issues = Vec::new();
+        for (iso2, expected) in &benchmark.countries {
+            let issue = |kind, expected: f64, actual: f64| CoverageIssue {
+                kind,
+                iso2: iso2.clone(),
+                expected,
+                actual,
+            };
+            let Some(actual) = countries.iter().find(|c| c.iso2.eq_ignore_ascii_case(iso2)) else {
+                issues.push(issue(CoverageIssueKind::MissingCountry, 1.0, 0.0));
+                continue;
+            };
+            let counts = [
+                (
+                    CoverageIssueKind::TooFewStates,
+                    expected.min_states,
+                    actual.states,
+                ),
+                (
+                    CoverageIssueKind::TooFewCities,
+                    expected.min_cities,
+                    actual.cities,
+                ),
+            ];
+            for (kind, min, count) in counts {
+                if let Some(min) = min.filter(|&min| count < min) {
+                    issues.push(issue(kind, min as f64, count as f64));
+                }
+            }
+            let shares = [
+                (
+                    CoverageIssueKind::MissingCoords,
+                    expected.min_coords_share,
+                    actual.coords_share(),
+                ),
+                (
+                    CoverageIssueKind::MissingTimezones,
+                    expected.min_timezone_share,
+                    actual.timezone_share(),
+                ),
+            ];
+            for (kind, min, share) in shares {
+                if let Some(min) = min.filter(|&min| share < min) {
+                    issues.push(issue(kind, min, share));
+                }
+            }
+        }
+        CoverageReport { countries, issues }
+    }
+}
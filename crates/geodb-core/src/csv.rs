@@ -0,0 +1,499 @@
+// src/csv.rs
+//! CSV export and import, for corrections kept in spreadsheets.
+//!
+//! [`GeoDb::export_csv_dir`] writes three files linked by keys:
+//!
+//! - `countries.csv`, keyed by `iso2`;
+//! - `states.csv`, keyed by `id` (`DE/3`), with the `country` it belongs to;
+//! - `cities.csv`, with the `state` id it belongs to (and its own `id` and
+//!   `country` for reference).
+//!
+//! [`GeoDb::load_csv_dir`] reads them back through the same build step as
+//! the upstream JSON, so an edited export becomes a database again:
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"])?;
+//! db.export_csv_dir("corrections")?;
+//! // ... fix city names or coordinates in a spreadsheet ...
+//! let fixed = GeoDb::<StandardBackend>::load_csv_dir("corrections")?;
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Rows are read in file order; a new state only needs an `id` no other
+//! state uses (e.g. `DE/new`), and ids are renumbered on the next export.
+//! Columns are found by their header, so they may be reordered, and
+//! unknown columns are ignored. A country's `timezones` and `translations`
+//! are JSON cells. City aliases and regions (from the city meta file) and
+//! districts are not exported.
+use crate::error::{GeoError, Result};
+use crate::model::{
+    try_build_geodb, CityRaw, CountriesRaw, CountryRaw, CountryTimezoneRaw, DbStats, GeoBackend,
+    GeoDb, StateRaw, SYNTHETIC_STATE_NAME,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// File name of the countries in a CSV directory.
+pub const COUNTRIES_CSV: &str = "countries.csv";
+/// File name of the states in a CSV directory.
+pub const STATES_CSV: &str = "states.csv";
+/// File name of the cities in a CSV directory.
+pub const CITIES_CSV: &str = "cities.csv";
+
+const COUNTRY_COLUMNS: &[&str] = &[
+    "iso2",
+    "iso3",
+    "name",
+    "native",
+    "numeric_code",
+    "phonecode",
+    "capital",
+    "currency",
+    "currency_name",
+    "currency_symbol",
+    "tld",
+    "population",
+    "gdp",
+    "region",
+    "region_id",
+    "subregion",
+    "subregion_id",
+    "nationality",
+    "latitude",
+    "longitude",
+    "emoji",
+    "emoji_u",
+    "timezones",
+    "translations",
+];
+const STATE_COLUMNS: &[&str] = &[
+    "id",
+    "country",
+    "name",
+    "native",
+    "code",
+    "full_code",
+    "latitude",
+    "longitude",
+];
+const CITY_COLUMNS: &[&str] = &[
+    "id",
+    "state",
+    "country",
+    "name",
+    "latitude",
+    "longitude",
+    "timezone",
+];
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Write `countries.csv`, `states.csv` and `cities.csv` into `dir`
+    /// (created if missing). Returns the number of rows of each.
+    pub fn export_csv_dir<P: AsRef<Path>>(&self, dir: P) -> Result<DbStats> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let create = |name: &str| fs::File::create(dir.join(name));
+        self.export_csv(
+            create(COUNTRIES_CSV)?,
+            create(STATES_CSV)?,
+            create(CITIES_CSV)?,
+        )
+    }
+
+    /// [`Self::export_csv_dir`] into three writers.
+    pub fn export_csv(
+        &self,
+        countries: impl Write,
+        states: impl Write,
+        cities: impl Write,
+    ) -> Result<DbStats> {
+        let mut countries = CsvWriter::new(countries, COUNTRY_COLUMNS)?;
+        let mut states = CsvWriter::new(states, STATE_COLUMNS)?;
+        let mut cities = CsvWriter::new(cities, CITY_COLUMNS)?;
+        let text = |v: &Option<B::Str>| v.as_ref().map_or("", |v| v.as_ref()).to_string();
+        let number =
+            |v: Option<B::Float>| v.map_or(String::new(), |v| B::float_to_f64(v).to_string());
+        let integer = |v: Option<i64>| v.map_or(String::new(), |v| v.to_string());
+        let mut stats = DbStats {
+            countries: 0,
+            states: 0,
+            cities: 0,
+        };
+
+        for country in &self.countries {
+            let timezones: Vec<CountryTimezoneRaw> = country
+                .timezones
+                .iter()
+                .map(|tz| CountryTimezoneRaw {
+                    zone_name: tz.zone_name.as_ref().map(B::str_to_string),
+                    gmt_offset: tz.gmt_offset,
+                    gmt_offset_name: tz.gmt_offset_name.as_ref().map(B::str_to_string),
+                    abbreviation: tz.abbreviation.as_ref().map(B::str_to_string),
+                    tz_name: tz.tz_name.as_ref().map(B::str_to_string),
+                })
+                .collect();
+            let translations: BTreeMap<&str, &str> = country
+                .translations
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_ref()))
+                .collect();
+            countries.row(&[
+                country.iso2().to_string(),
+                text(&country.iso3),
+                country.name().to_string(),
+                text(&country.native_name),
+                text(&country.numeric_code),
+                text(&country.phonecode),
+                text(&country.capital),
+                text(&country.currency),
+                text(&country.currency_name),
+                text(&country.currency_symbol),
+                text(&country.tld),
+                integer(country.population),
+                integer(country.gdp),
+                text(&country.region),
+                integer(country.region_id),
+                text(&country.subregion),
+                integer(country.subregion_id),
+                text(&country.nationality),
+                number(country.latitude),
+                number(country.longitude),
+                text(&country.emoji),
+                text(&country.emoji_u),
+                json_cell(&timezones)?,
+                json_cell(&translations)?,
+            ])?;
+            stats.countries += 1;
+
+            for (si, state) in country.states.iter().enumerate() {
+                let state_id = format!("{}/{si}", country.iso2());
+                states.row(&[
+                    state_id.clone(),
+                    country.iso2().to_string(),
+                    state.name().to_string(),
+                    text(&state.native_name),
+                    text(&state.state_code),
+                    text(&state.full_code),
+                    number(state.latitude),
+                    number(state.longitude),
+                ])?;
+                stats.states += 1;
+
+                for (ci, city) in state.cities.iter().enumerate() {
+                    cities.row(&[
+                        format!("{state_id}/{ci}"),
+                        state_id.clone(),
+                        country.iso2().to_string(),
+                        city.name().to_string(),
+                        number(city.latitude),
+                        number(city.longitude),
+                        text(&city.timezone),
+                    ])?;
+                    stats.cities += 1;
+                }
+            }
+        }
+        countries.finish()?;
+        states.finish()?;
+        cities.finish()?;
+        Ok(stats)
+    }
+
+    /// Build a database from the `countries.csv`, `states.csv` and
+    /// `cities.csv` in `dir` (see the [module docs](crate::csv)).
+    ///
+    /// Fails with [`GeoError::InvalidData`] naming the file and line of a
+    /// row without a required column (`iso2` and `name` of countries,
+    /// `id`, `country` and `name` of states, `state` and `name` of cities)
+    /// or whose key points nowhere.
+    pub fn load_csv_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let read = |name: &str| -> Result<String> {
+            fs::read_to_string(dir.join(name))
+                .map_err(|e| GeoError::NotFound(format!("{}: {e}", dir.join(name).display())))
+        };
+        let raw = countries_from_csv(
+            &read(COUNTRIES_CSV)?,
+            &read(STATES_CSV)?,
+            &read(CITIES_CSV)?,
+        )?;
+        try_build_geodb(raw)
+    }
+}
+
+/// Source-format countries from the contents of the three CSV files.
+pub fn countries_from_csv(countries: &str, states: &str, cities: &str) -> Result<CountriesRaw> {
+    let mut out: CountriesRaw = Vec::new();
+    let mut country_index: HashMap<String, usize> = HashMap::new();
+    for row in CsvTable::parse(COUNTRIES_CSV, countries)?.rows() {
+        let iso2 = row.required("iso2")?.to_string();
+        let timezones = row.json("timezones")?.unwrap_or_default();
+        let translations = row.json("translations")?.unwrap_or_default();
+        if country_index
+            .insert(iso2.to_ascii_uppercase(), out.len())
+            .is_some()
+        {
+            return Err(row.error(&format!("duplicate country {iso2}")));
+        }
+        out.push(CountryRaw {
+            id: None,
+            name: row.required("name")?.to_string(),
+            iso3: row.text("iso3"),
+            iso2,
+            numeric_code: row.text("numeric_code"),
+            phonecode: row.text("phonecode"),
+            capital: row.text("capital"),
+            currency: row.text("currency"),
+            currency_name: row.text("currency_name"),
+            currency_symbol: row.text("currency_symbol"),
+            tld: row.text("tld"),
+            native: row.text("native"),
+            population: row.integer("population")?,
+            gdp: row.integer("gdp")?,
+            region: row.text("region"),
+            region_id: row.integer("region_id")?,
+            subregion: row.text("subregion"),
+            subregion_id: row.integer("subregion_id")?,
+            nationality: row.text("nationality"),
+            timezones,
+            translations,
+            latitude: row.text("latitude"),
+            longitude: row.text("longitude"),
+            emoji: row.text("emoji"),
+            emoji_u: row.text("emoji_u"),
+            states: Vec::new(),
+            cities: Vec::new(),
+        });
+    }
+
+    // state id -> (country, position in its states, or None for the
+    // synthetic state holding the country's own cities)
+    let mut state_index: HashMap<String, (usize, Option<usize>)> = HashMap::new();
+    for row in CsvTable::parse(STATES_CSV, states)?.rows() {
+        let id = row.required("id")?;
+        let iso2 = row.required("country")?;
+        let Some(&ci) = country_index.get(&iso2.to_ascii_uppercase()) else {
+            return Err(row.error(&format!("unknown country {iso2}")));
+        };
+        let name = row.required("name")?;
+        let code = row.text("code");
+        let position = if name == SYNTHETIC_STATE_NAME && code.is_none() {
+            None
+        } else {
+            let states = &mut out[ci].states;
+            states.push(StateRaw {
+                id: None,
+                name: name.to_string(),
+                iso2: code,
+                iso3166_2: row.text("full_code"),
+                native: row.text("native"),
+                latitude: row.text("latitude"),
+                longitude: row.text("longitude"),
+                r#type: None,
+                timezone: None,
+                cities: Vec::new(),
+                #[cfg(feature = "districts")]
+                districts: Vec::new(),
+            });
+            Some(states.len() - 1)
+        };
+        if state_index.insert(id.to_string(), (ci, position)).is_some() {
+            return Err(row.error(&format!("duplicate state id {id}")));
+        }
+    }
+
+    for row in CsvTable::parse(CITIES_CSV, cities)?.rows() {
+        let state = row.required("state")?;
+        let Some(&(ci, position)) = state_index.get(state) else {
+            return Err(row.error(&format!("unknown state {state}")));
+        };
+        let city = CityRaw {
+            id: None,
+            name: row.required("name")?.to_string(),
+            latitude: row.text("latitude"),
+            longitude: row.text("longitude"),
+            timezone: row.text("timezone"),
+        };
+        match position {
+            Some(si) => out[ci].states[si].cities.push(city),
+            None => out[ci].cities.push(city),
+        }
+    }
+    Ok(out)
+}
+
+fn json_cell<T: Serialize>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(value)?)
+}
+
+/// Writes records, quoting fields that contain a comma, quote or line
+/// break.
+struct CsvWriter<W: Write> {
+    out: BufWriter<W>,
+}
+
+impl<W: Write> CsvWriter<W> {
+    fn new(writer: W, header: &[&str]) -> Result<Self> {
+        let mut out = BufWriter::new(writer);
+        writeln!(out, "{}", header.join(","))?;
+        Ok(Self { out })
+    }
+
+    fn row(&mut self, fields: &[String]) -> Result<()> {
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                self.out.write_all(b",")?;
+            }
+            if field.contains([',', '"', '\n', '\r']) {
+                write!(self.out, "\"{}\"", field.replace('"', "\"\""))?;
+            } else {
+                self.out.write_all(field.as_bytes())?;
+            }
+        }
+        self.out.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// A parsed CSV file: its header and records, with the line each record
+/// starts on.
+struct CsvTable {
+    file: &'static str,
+    columns: HashMap<String, usize>,
+    records: Vec<(usize, Vec<String>)>,
+}
+
+impl CsvTable {
+    fn parse(file: &'static str, text: &str) -> Result<Self> {
+        let mut records = parse_records(text.strip_prefix('\u{feff}').unwrap_or(text))
+            .map_err(|line| GeoError::InvalidData(format!("{file} line {line}: unclosed quote")))?
+            .into_iter();
+        let (_, header) = records
+            .next()
+            .ok_or_else(|| GeoError::InvalidData(format!("{file}: missing header")))?;
+        let columns = header
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.trim().to_ascii_lowercase(), i))
+            .collect();
+        Ok(Self {
+            file,
+            columns,
+            // blank lines (e.g. a trailing one from a spreadsheet) are skipped
+            records: records
+                .filter(|(_, fields)| fields.iter().any(|f| !f.is_empty()))
+                .collect(),
+        })
+    }
+
+    fn rows(&self) -> impl Iterator<Item = Row<'_>> {
+        self.records.iter().map(move |(line, fields)| Row {
+            table: self,
+            line: *line,
+            fields,
+        })
+    }
+}
+
+struct Row<'t> {
+    table: &'t CsvTable,
+    line: usize,
+    fields: &'t [String],
+}
+
+impl<'t> Row<'t> {
+    fn error(&self, message: &str) -> GeoError {
+        GeoError::InvalidData(format!("{} line {}: {message}", self.table.file, self.line))
+    }
+
+    /// The trimmed cell of `column`; `None` if empty or missing.
+    fn get(&self, column: &str) -> Option<&'t str> {
+        let i = *self.table.columns.get(column)?;
+        let value = self.fields.get(i)?.trim();
+        (!value.is_empty()).then_some(value)
+    }
+
+    fn required(&self, column: &str) -> Result<&'t str> {
+        self.get(column)
+            .ok_or_else(|| self.error(&format!("missing {column}")))
+    }
+
+    fn text(&self, column: &str) -> Option<String> {
+        self.get(column).map(str::to_string)
+    }
+
+    fn integer(&self, column: &str) -> Result<Option<i64>> {
+        self.get(column)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| self.error(&format!("{column} is not an integer: {v}")))
+            })
+            .transpose()
+    }
+
+    fn json<T: serde::de::DeserializeOwned>(&self, column: &str) -> Result<Option<T>> {
+        self.get(column)
+            .map(|v| serde_json::from_str(v).map_err(|e| self.error(&format!("{column}: {e}"))))
+            .transpose()
+    }
+}
+
+/// Records of `text` (RFC 4180: fields in double quotes may hold commas,
+/// doubled quotes and line breaks), each with the line it starts on. Fails
+/// with the starting line of an unclosed quoted field.
+fn parse_records(text: &str) -> std::result::Result<Vec<(usize, Vec<String>)>, usize> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut start) = (1, 1);
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+    let mut quote_line = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => {
+                quoted = true;
+                quote_line = line;
+            }
+            '\n' if quoted => {
+                field.push(c);
+                line += 1;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut fields)));
+                line += 1;
+                start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(quote_line);
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((start, fields));
+    }
+    Ok(records)
+}
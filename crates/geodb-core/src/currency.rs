@@ -0,0 +1,122 @@
+// src/currency.rs
+//! Currencies and the countries using them.
+//!
+//! The dataset stores the currency on each country; [`Currency`] gathers
+//! code, name and symbol once per code, with the countries that use it.
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! for country in db.find_countries_by_currency("eur") {
+//!     println!("{} ({})", country.name(), country.iso2());
+//! }
+//! // "Swiss franc", "CFA franc BEAC", ...
+//! let francs = db.find_countries_by_currency_name("franc");
+//! if let Some(chf) = db.currency("CHF") {
+//!     println!("{} {:?}: {:?}", chf.code, chf.symbol, chf.countries);
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::model::{Country, GeoBackend, GeoDb};
+use crate::search::{fold_key, SearchLanguage};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A currency code with its name, symbol and the countries using it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Currency<'a> {
+    /// ISO 4217 code as in the dataset, e.g. `EUR`.
+    pub code: &'a str,
+    /// Name from the first country that has one, e.g. `Euro`.
+    pub name: Option<&'a str>,
+    /// Symbol from the first country that has one, e.g. `€`.
+    pub symbol: Option<&'a str>,
+    /// ISO2 codes of the countries using it, in database order.
+    pub countries: Vec<&'a str>,
+}
+
+impl<'a> Currency<'a> {
+    fn new<B: GeoBackend>(country: &'a Country<B>) -> Self {
+        Self {
+            code: country.currency(),
+            name: None,
+            symbol: None,
+            countries: Vec::new(),
+        }
+    }
+
+    fn add<B: GeoBackend>(&mut self, country: &'a Country<B>) {
+        self.name = self.name.or(country.currency_name());
+        self.symbol = self.symbol.or(country.currency_symbol());
+        self.countries.push(country.iso2());
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Countries whose currency is `code` (case-insensitive, e.g. "eur"),
+    /// in database order.
+    pub fn find_countries_by_currency(&self, code: &str) -> Vec<&Country<B>> {
+        let code = code.trim();
+        if code.is_empty() {
+            return Vec::new();
+        }
+        self.countries
+            .iter()
+            .filter(|c| c.currency().eq_ignore_ascii_case(code))
+            .collect()
+    }
+
+    /// Countries whose currency name contains `name`, in database order.
+    ///
+    /// Case and Latin accents are ignored, so "franc" finds the Swiss franc
+    /// and the CFA francs, and "zloty" the złoty.
+    pub fn find_countries_by_currency_name(&self, name: &str) -> Vec<&Country<B>> {
+        let name = fold_key(name, SearchLanguage::Ascii);
+        if name.is_empty() {
+            return Vec::new();
+        }
+        self.countries
+            .iter()
+            .filter(|c| {
+                c.currency_name()
+                    .is_some_and(|n| fold_key(n, SearchLanguage::Ascii).contains(&name))
+            })
+            .collect()
+    }
+
+    /// Countries whose currency symbol is `symbol` (e.g. "€", "$"), in
+    /// database order.
+    pub fn find_countries_by_currency_symbol(&self, symbol: &str) -> Vec<&Country<B>> {
+        let symbol = symbol.trim();
+        if symbol.is_empty() {
+            return Vec::new();
+        }
+        self.countries
+            .iter()
+            .filter(|c| c.currency_symbol() == Some(symbol))
+            .collect()
+    }
+
+    /// The currency with `code` (case-insensitive), if any country uses it.
+    pub fn currency(&self, code: &str) -> Option<Currency<'_>> {
+        let countries = self.find_countries_by_currency(code);
+        let mut currency = Currency::new(*countries.first()?);
+        for country in countries {
+            currency.add(country);
+        }
+        Some(currency)
+    }
+
+    /// All currencies used by at least one country, sorted by code.
+    pub fn currencies(&self) -> Vec<Currency<'_>> {
+        let mut by_code: BTreeMap<&str, Currency<'_>> = BTreeMap::new();
+        for country in self.countries.iter().filter(|c| !c.currency().is_empty()) {
+            by_code
+                .entry(country.currency())
+                .or_insert_with(|| Currency::new(country))
+                .add(country);
+        }
+        by_code.into_values().collect()
+    }
+}
@@ -0,0 +1,56 @@
+// src/distinct.rs
+//! Distinct values with counts, e.g. for filter dropdowns.
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! for tz in db.distinct_timezones() {
+//!     println!("{} ({} cities)", tz.value, tz.count);
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::model::{GeoBackend, GeoDb};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A distinct value and the number of entities that have it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ValueCount<'a> {
+    pub value: &'a str,
+    pub count: usize,
+}
+
+/// Count `values`, skipping empty ones; sorted by value.
+fn count<'a>(values: impl Iterator<Item = &'a str>) -> Vec<ValueCount<'a>> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for value in values.filter(|v| !v.is_empty()) {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect()
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// IANA timezones of the cities, with the number of cities in each.
+    pub fn distinct_timezones(&self) -> Vec<ValueCount<'_>> {
+        count(
+            self.iter_cities()
+                .filter_map(|(city, _, _)| city.timezone.as_ref().map(|z| z.as_ref())),
+        )
+    }
+
+    /// Currency codes of the countries, with the number of countries using
+    /// each.
+    pub fn distinct_currencies(&self) -> Vec<ValueCount<'_>> {
+        count(self.countries.iter().map(|c| c.currency()))
+    }
+
+    /// Regions (continents) of the countries, with the number of countries
+    /// in each.
+    pub fn distinct_regions(&self) -> Vec<ValueCount<'_>> {
+        count(self.countries.iter().map(|c| c.region()))
+    }
+}
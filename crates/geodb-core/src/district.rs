@@ -0,0 +1,102 @@
+// src/district.rs
+//! District (third administrative level) helpers, feature `districts`.
+use crate::error::{GeoError, Result};
+use crate::model::{City, Country, District, GeoBackend, GeoDb, State};
+
+/// Narrow a city index of state `state` (country `iso2`) to the `u32`
+/// stored in [`District`] ranges.
+///
+/// Fails with an actionable message instead of truncating, which would make
+/// districts silently point at the wrong cities.
+pub fn district_city_index(index: usize, state: &str, iso2: &str) -> Result<u32> {
+    u32::try_from(index).map_err(|_| {
+        GeoError::InvalidData(format!(
+            "state '{state}' ({iso2}) has {index} cities, more than district \
+             ranges can address ({}); load fewer countries (e.g. \
+             load_filtered_by_iso2) or widen District::city_start/city_end",
+            u32::MAX
+        ))
+    })
+}
+
+impl<B: GeoBackend> District<B> {
+    /// District display name.
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// District code if provided by the dataset, or empty string otherwise.
+    pub fn code(&self) -> &str {
+        self.code.as_ref().map(|s| s.as_ref()).unwrap_or("")
+    }
+
+    /// Number of cities in this district.
+    pub fn city_count(&self) -> usize {
+        (self.city_end - self.city_start) as usize
+    }
+}
+
+impl<B: GeoBackend> State<B> {
+    /// Read-only slice of districts below this state.
+    pub fn districts(&self) -> &[District<B>] {
+        &self.districts
+    }
+
+    /// Cities belonging to the given district of this state.
+    ///
+    /// Returns an empty slice if the district does not belong to this state.
+    pub fn cities_for_district(&self, district: &District<B>) -> &[City<B>] {
+        self.cities
+            .get(district.city_start as usize..district.city_end as usize)
+            .unwrap_or(&[])
+    }
+
+    /// District containing the city at `city_index` in [`State::cities`], if any.
+    pub fn district_of_city(&self, city_index: usize) -> Option<&District<B>> {
+        let i = u32::try_from(city_index).ok()?;
+        self.districts
+            .iter()
+            .find(|d| d.city_start <= i && i < d.city_end)
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Find all districts whose name contains the given ASCII substring
+    /// (case-insensitive). Returns triplets of (district, state, country).
+    pub fn find_districts_by_substring(
+        &self,
+        substr: &str,
+    ) -> Vec<(&District<B>, &State<B>, &Country<B>)> {
+        let q = substr.to_ascii_lowercase();
+        let mut out = Vec::new();
+        for c in &self.countries {
+            for s in &c.states {
+                for d in &s.districts {
+                    if d.name().to_ascii_lowercase().contains(&q) {
+                        out.push((d, s, c));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Cities of the district named `district` (case-insensitive) in the
+    /// given country (ISO2) and state (name or state code).
+    pub fn cities_for_district(&self, iso2: &str, state: &str, district: &str) -> &[City<B>] {
+        self.find_country_by_iso2(iso2)
+            .and_then(|c| {
+                c.states().iter().find(|s| {
+                    s.name().eq_ignore_ascii_case(state)
+                        || s.state_code().eq_ignore_ascii_case(state)
+                })
+            })
+            .and_then(|s| {
+                s.districts()
+                    .iter()
+                    .find(|d| d.name().eq_ignore_ascii_case(district))
+                    .map(|d| s.cities_for_district(d))
+            })
+            .unwrap_or(&[])
+    }
+}
@@ -0,0 +1,319 @@
+// src/dyn_search.rs
+//! Object-safe search facade.
+//!
+//! [`GeoDb`] is generic over its [`GeoBackend`] and hands out borrowed
+//! references, so it can't be stored as a trait object. [`DynGeoSearch`]
+//! wraps the common queries behind an object-safe trait returning owned DTOs,
+//! so applications can inject the database as `Box<dyn DynGeoSearch>` or
+//! `Arc<dyn DynGeoSearch>` (e.g. in plugin architectures or test doubles).
+use crate::country_id::{CityId, CountryId, StateId};
+use crate::model::{City, Country, DbStats, GeoBackend, GeoDb, SmartHit, SmartItem, State};
+use crate::phone::PhoneCodeSearch;
+use serde::{Deserialize, Serialize};
+
+/// Owned country record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CountryDto {
+    #[serde(default)]
+    pub id: Option<CountryId>,
+    pub name: String,
+    pub iso2: String,
+    pub iso3: Option<String>,
+    pub phone_code: Option<String>,
+    pub currency: Option<String>,
+    pub capital: Option<String>,
+    pub region: Option<String>,
+    pub population: Option<i64>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub emoji: Option<String>,
+}
+
+/// Owned state/region record including its country.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateDto {
+    /// Handle for [`DynGeoSearch::state_by_id`].
+    #[serde(default)]
+    pub id: Option<StateId>,
+    pub name: String,
+    pub state_code: Option<String>,
+    pub full_code: Option<String>,
+    pub country: String,
+    pub country_iso2: String,
+}
+
+/// Owned city record including its state and country.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CityDto {
+    /// Handle for [`DynGeoSearch::city_by_id`].
+    #[serde(default)]
+    pub id: Option<CityId>,
+    pub name: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub timezone: Option<String>,
+    pub state: String,
+    pub country: String,
+    pub country_iso2: String,
+}
+
+/// Owned district record including its state and country (feature
+/// `districts`).
+#[cfg(feature = "districts")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistrictDto {
+    pub name: String,
+    pub code: Option<String>,
+    pub state: String,
+    pub country: String,
+    pub country_iso2: String,
+}
+
+/// Owned entity of a smart-search hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum EntityDto {
+    Country(CountryDto),
+    State(StateDto),
+    City(CityDto),
+    #[cfg(feature = "districts")]
+    District(DistrictDto),
+}
+
+/// Owned smart-search hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HitDto {
+    pub score: i32,
+    /// Alias the city matched by, if not by its name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub item: EntityDto,
+}
+
+fn opt_string<B: GeoBackend>(v: &Option<B::Str>) -> Option<String> {
+    v.as_ref().map(B::str_to_string)
+}
+
+impl<B: GeoBackend> From<&Country<B>> for CountryDto {
+    fn from(c: &Country<B>) -> Self {
+        Self {
+            id: c.id(),
+            name: c.name().to_string(),
+            iso2: c.iso2().to_string(),
+            iso3: opt_string::<B>(&c.iso3),
+            phone_code: opt_string::<B>(&c.phonecode),
+            currency: opt_string::<B>(&c.currency),
+            capital: opt_string::<B>(&c.capital),
+            region: opt_string::<B>(&c.region),
+            population: c.population,
+            latitude: c.latitude.map(B::float_to_f64),
+            longitude: c.longitude.map(B::float_to_f64),
+            emoji: opt_string::<B>(&c.emoji),
+        }
+    }
+}
+
+impl StateDto {
+    /// Build from a state and its parent country.
+    pub fn new<B: GeoBackend>(state: &State<B>, country: &Country<B>) -> Self {
+        Self {
+            id: country.state_id(state),
+            name: state.name().to_string(),
+            state_code: opt_string::<B>(&state.state_code),
+            full_code: opt_string::<B>(&state.full_code),
+            country: country.name().to_string(),
+            country_iso2: country.iso2().to_string(),
+        }
+    }
+}
+
+impl CityDto {
+    /// Build from a city and its parent state and country.
+    pub fn new<B: GeoBackend>(city: &City<B>, state: &State<B>, country: &Country<B>) -> Self {
+        Self {
+            id: country.city_id(state, city),
+            name: city.name().to_string(),
+            latitude: city.latitude.map(B::float_to_f64),
+            longitude: city.longitude.map(B::float_to_f64),
+            timezone: opt_string::<B>(&city.timezone),
+            state: state.name().to_string(),
+            country: country.name().to_string(),
+            country_iso2: country.iso2().to_string(),
+        }
+    }
+}
+
+impl<B: GeoBackend> From<&SmartHit<'_, B>> for HitDto {
+    fn from(hit: &SmartHit<'_, B>) -> Self {
+        Self {
+            score: hit.score,
+            alias: hit.alias.map(str::to_string),
+            item: match hit.item {
+                SmartItem::Country(c) => EntityDto::Country(c.into()),
+                SmartItem::State { country, state } => {
+                    EntityDto::State(StateDto::new(state, country))
+                }
+                SmartItem::City {
+                    country,
+                    state,
+                    city,
+                } => EntityDto::City(CityDto::new(city, state, country)),
+                #[cfg(feature = "districts")]
+                SmartItem::District {
+                    country,
+                    state,
+                    district,
+                } => EntityDto::District(DistrictDto {
+                    name: district.name().to_string(),
+                    code: opt_string::<B>(&district.code),
+                    state: state.name().to_string(),
+                    country: country.name().to_string(),
+                    country_iso2: country.iso2().to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// Object-safe, backend-independent search interface.
+///
+/// Implemented for every [`GeoDb<B>`]; all results are owned so the trait
+/// can be used as `dyn DynGeoSearch`.
+pub trait DynGeoSearch: Send + Sync {
+    /// Aggregate statistics.
+    fn stats(&self) -> DbStats;
+    /// All countries.
+    fn countries(&self) -> Vec<CountryDto>;
+    /// Country by ISO2 or ISO3 code (case-insensitive).
+    fn country_by_code(&self, code: &str) -> Option<CountryDto>;
+    /// Country by typed id.
+    fn country_by_id(&self, id: CountryId) -> Option<CountryDto>;
+    /// State by the id of an earlier result.
+    fn state_by_id(&self, id: StateId) -> Option<StateDto>;
+    /// City by the id of an earlier result.
+    fn city_by_id(&self, id: CityId) -> Option<CityDto>;
+    /// States of the country with the given ISO2 code.
+    fn states_of(&self, iso2: &str) -> Vec<StateDto>;
+    /// Countries whose phone code starts with `prefix`.
+    fn countries_by_phone_code(&self, prefix: &str) -> Vec<CountryDto>;
+    /// Countries using the currency `code` (case-insensitive, e.g. "EUR").
+    fn countries_by_currency(&self, code: &str) -> Vec<CountryDto>;
+    /// States whose name contains `substr` (case-insensitive).
+    fn states_by_substring(&self, substr: &str) -> Vec<StateDto>;
+    /// Cities whose name contains `substr` (case-insensitive).
+    fn cities_by_substring(&self, substr: &str) -> Vec<CityDto>;
+    /// Ranked search across countries, states, cities and phone codes.
+    fn smart_search(&self, query: &str) -> Vec<HitDto>;
+    /// At most `limit` countries, states and cities whose name starts with
+    /// `prefix`, biggest countries first; see [`GeoDb::autocomplete`].
+    fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<HitDto>;
+    /// City closest to `(lat, lng)` within `radius_km`.
+    fn nearest_city(&self, lat: f64, lng: f64, radius_km: f64) -> Option<CityDto>;
+    /// Cities inside a bounding box; `min_lng > max_lng` crosses the
+    /// antimeridian.
+    fn cities_in_bbox(
+        &self,
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+    ) -> Vec<CityDto>;
+}
+
+impl<B: GeoBackend> DynGeoSearch for GeoDb<B> {
+    fn stats(&self) -> DbStats {
+        GeoDb::stats(self)
+    }
+
+    fn countries(&self) -> Vec<CountryDto> {
+        GeoDb::countries(self)
+            .iter()
+            .map(CountryDto::from)
+            .collect()
+    }
+
+    fn country_by_code(&self, code: &str) -> Option<CountryDto> {
+        self.find_country_by_code(code).map(CountryDto::from)
+    }
+
+    fn country_by_id(&self, id: CountryId) -> Option<CountryDto> {
+        self.country(id).map(CountryDto::from)
+    }
+
+    fn state_by_id(&self, id: StateId) -> Option<StateDto> {
+        let country = self.country(id.country())?;
+        let state = GeoDb::state_by_id(self, id)?;
+        Some(StateDto::new(state, country))
+    }
+
+    fn city_by_id(&self, id: CityId) -> Option<CityDto> {
+        let path = self.ancestors_of(id)?;
+        Some(CityDto::new(path.city?, path.state?, path.country))
+    }
+
+    fn states_of(&self, iso2: &str) -> Vec<StateDto> {
+        self.find_country_by_iso2(iso2)
+            .map(|c| c.states().iter().map(|s| StateDto::new(s, c)).collect())
+            .unwrap_or_default()
+    }
+
+    fn countries_by_phone_code(&self, prefix: &str) -> Vec<CountryDto> {
+        self.find_countries_by_phone_code(prefix)
+            .into_iter()
+            .map(CountryDto::from)
+            .collect()
+    }
+
+    fn countries_by_currency(&self, code: &str) -> Vec<CountryDto> {
+        self.find_countries_by_currency(code)
+            .into_iter()
+            .map(CountryDto::from)
+            .collect()
+    }
+
+    fn states_by_substring(&self, substr: &str) -> Vec<StateDto> {
+        self.find_states_by_substring(substr)
+            .into_iter()
+            .map(|(s, c)| StateDto::new(s, c))
+            .collect()
+    }
+
+    fn cities_by_substring(&self, substr: &str) -> Vec<CityDto> {
+        self.find_cities_by_substring(substr)
+            .into_iter()
+            .map(|(city, s, c)| CityDto::new(city, s, c))
+            .collect()
+    }
+
+    fn smart_search(&self, query: &str) -> Vec<HitDto> {
+        GeoDb::smart_search(self, query)
+            .iter()
+            .map(HitDto::from)
+            .collect()
+    }
+
+    fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<HitDto> {
+        GeoDb::autocomplete(self, prefix, limit)
+            .iter()
+            .map(HitDto::from)
+            .collect()
+    }
+
+    fn nearest_city(&self, lat: f64, lng: f64, radius_km: f64) -> Option<CityDto> {
+        self.find_nearest_city(lat, lng, radius_km)
+            .map(|near| CityDto::new(near.city.0, near.city.1, near.city.2))
+    }
+
+    fn cities_in_bbox(
+        &self,
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+    ) -> Vec<CityDto> {
+        GeoDb::cities_in_bbox(self, min_lat, min_lng, max_lat, max_lng)
+            .into_iter()
+            .map(|(city, s, c)| CityDto::new(city, s, c))
+            .collect()
+    }
+}
@@ -6,12 +6,18 @@ pub enum GeoError {
     #[error("IO error while reading data: {0}")]
     Io(#[from] io::Error),
 
+    #[cfg(feature = "json")]
     #[error("Gzip decompression error: {0}")]
     Gzip(#[from] flate2::DecompressError),
 
+    #[cfg(feature = "json")]
     #[error("JSON parse error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[cfg(feature = "json")]
+    #[error("Invalid entry in the source data: {0}")]
+    InvalidEntity(Box<crate::parse::InvalidEntity>),
+
     #[error("Bincode error: {0}")]
     Bincode(#[from] bincode::Error),
 
@@ -20,6 +26,20 @@ pub enum GeoError {
 
     #[error("Invalid data: {0}")]
     InvalidData(String),
+
+    #[cfg(feature = "fetch")]
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack encoding error: {0}")]
+    MsgPack(#[from] rmp_serde::encode::Error),
+
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Signature verification failed: {0}")]
+    Signature(String),
 }
 
 pub type Result<T> = std::result::Result<T, GeoError>;
@@ -0,0 +1,131 @@
+// src/exact.rs
+//! Exact-match lookups in O(1).
+//!
+//! The regular finders scan every city and match substrings. Validation
+//! workloads ("is Berlin, DE a real city?") only need exact names, so
+//! [`GeoDb::exact_lookup`] builds hash maps keyed by folded
+//! `(iso2, name)` once and answers each check with a single hash lookup.
+//! Names are folded with [`SearchLanguage::Ascii`] (see [`fold_key`]), so
+//! case, accents and repeated whitespace don't matter: "KÖLN", "Koln" and
+//! "köln" are the same city.
+//!
+//! The lookup borrows the database, so the maps can't go stale while it is
+//! in use; build it once after loading (and after `apply_city_meta`, whose
+//! aliases are indexed too) and reuse it for all checks.
+use crate::model::{City, Country, GeoBackend, GeoDb, State};
+#[cfg(doc)]
+use crate::search::fold_key;
+use crate::search::{fold_words, SearchLanguage};
+use std::collections::HashMap;
+
+/// Comparison form of a whole name for exact matching.
+pub(crate) fn fold_name(name: &str) -> String {
+    fold_words(name, SearchLanguage::Ascii)
+}
+
+/// Position of a city: (country, state, city) indices.
+type CityPos = (usize, usize, usize);
+
+/// Precomputed exact-match maps over a [`GeoDb`].
+///
+/// Built with [`GeoDb::exact_lookup`].
+#[derive(Debug)]
+pub struct ExactLookup<'a, B: GeoBackend> {
+    db: &'a GeoDb<B>,
+    /// ISO2 (uppercase) → country index
+    countries: HashMap<String, usize>,
+    /// (ISO2 uppercase, folded city name or alias) → positions
+    cities: HashMap<(String, String), Vec<CityPos>>,
+}
+
+impl<'a, B: GeoBackend> ExactLookup<'a, B> {
+    /// `true` if the country `iso2` has a city named `name` (or with that alias).
+    ///
+    /// Both arguments are folded, see the [module docs](self).
+    pub fn city_exists(&self, iso2: &str, name: &str) -> bool {
+        self.cities.contains_key(&Self::city_key(iso2, name))
+    }
+
+    /// All cities in `iso2` named `name` (names repeat across states, e.g.
+    /// "Frankfurt"), in database order.
+    pub fn find_cities(
+        &self,
+        iso2: &str,
+        name: &str,
+    ) -> Vec<(&'a City<B>, &'a State<B>, &'a Country<B>)> {
+        let Some(positions) = self.cities.get(&Self::city_key(iso2, name)) else {
+            return Vec::new();
+        };
+        positions
+            .iter()
+            .map(|&(ci, si, ti)| {
+                let country = &self.db.countries[ci];
+                let state = &country.states[si];
+                (&state.cities[ti], state, country)
+            })
+            .collect()
+    }
+
+    /// Country by ISO2 code (case-insensitive).
+    pub fn country(&self, iso2: &str) -> Option<&'a Country<B>> {
+        let idx = self.countries.get(&iso2.trim().to_ascii_uppercase())?;
+        Some(&self.db.countries[*idx])
+    }
+
+    /// Number of distinct `(iso2, name)` keys.
+    pub fn len(&self) -> usize {
+        self.cities.len()
+    }
+
+    /// `true` if no city is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.cities.is_empty()
+    }
+
+    fn city_key(iso2: &str, name: &str) -> (String, String) {
+        (iso2.trim().to_ascii_uppercase(), fold_name(name))
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Build O(1) exact-match maps for this database.
+    ///
+    /// Indexes every city by its name and by the aliases applied with
+    /// [`GeoDb::apply_city_meta`].
+    ///
+    /// ```no_run
+    /// use geodb_core::{GeoDb, StandardBackend};
+    ///
+    /// let db = GeoDb::<StandardBackend>::load()?;
+    /// let lookup = db.exact_lookup();
+    /// assert!(lookup.city_exists("DE", "berlin"));
+    /// assert!(!lookup.city_exists("FR", "Berlin"));
+    /// # Ok::<(), geodb_core::GeoError>(())
+    /// ```
+    pub fn exact_lookup(&self) -> ExactLookup<'_, B> {
+        let mut countries = HashMap::with_capacity(self.countries.len());
+        let mut cities: HashMap<(String, String), Vec<CityPos>> = HashMap::new();
+
+        for (ci, country) in self.countries.iter().enumerate() {
+            let iso2 = country.iso2().to_ascii_uppercase();
+            countries.insert(iso2.clone(), ci);
+            for (si, state) in country.states.iter().enumerate() {
+                for (ti, city) in state.cities.iter().enumerate() {
+                    for name in std::iter::once(city.name()).chain(city.aliases()) {
+                        let slot = cities.entry((iso2.clone(), fold_name(name))).or_default();
+                        // an alias may equal the name after folding
+                        if slot.last() != Some(&(ci, si, ti)) {
+                            slot.push((ci, si, ti));
+                        }
+                    }
+                }
+            }
+        }
+
+        ExactLookup {
+            db: self,
+            countries,
+            cities,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// src/export.rs
+//! Streaming NDJSON export.
+//!
+//! [`GeoDb::export_ndjson`] writes one JSON object per line (the owned
+//! records of [`crate::dyn_search`]) straight to a writer, so a full city
+//! dump can be piped into data pipelines without building it in memory:
+//!
+//! ```no_run
+//! use geodb_core::{ExportLevel, GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let written = db.export_ndjson(std::io::stdout().lock(), ExportLevel::Cities)?;
+//! eprintln!("{written} cities");
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::dyn_search::{CityDto, CountryDto, StateDto};
+use crate::error::Result;
+use crate::model::{GeoBackend, GeoDb};
+use serde::Serialize;
+use std::fmt;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+
+/// Which entities [`GeoDb::export_ndjson`] writes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportLevel {
+    /// One [`CountryDto`] per line.
+    Countries,
+    /// One [`StateDto`] per line.
+    States,
+    /// One [`CityDto`] per line.
+    #[default]
+    Cities,
+}
+
+impl FromStr for ExportLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "countries" | "country" => Ok(Self::Countries),
+            "states" | "state" => Ok(Self::States),
+            "cities" | "city" => Ok(Self::Cities),
+            other => Err(format!(
+                "unknown export level '{other}' (expected countries, states or cities)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExportLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Countries => "countries",
+            Self::States => "states",
+            Self::Cities => "cities",
+        })
+    }
+}
+
+fn write_line<W: Write, T: Serialize>(out: &mut W, record: &T) -> Result<()> {
+    // keep write failures (e.g. a closed pipe) as `GeoError::Io`
+    serde_json::to_writer(&mut *out, record).map_err(std::io::Error::from)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Write every entity of `level` as one JSON object per line, in
+    /// database order. Returns the number of lines written.
+    ///
+    /// Output is buffered internally and flushed before returning; records
+    /// are built one at a time, so memory use doesn't grow with the dataset.
+    pub fn export_ndjson<W: Write>(&self, writer: W, level: ExportLevel) -> Result<usize> {
+        let mut out = BufWriter::new(writer);
+        let mut written = 0;
+        for country in &self.countries {
+            match level {
+                ExportLevel::Countries => {
+                    write_line(&mut out, &CountryDto::from(country))?;
+                    written += 1;
+                }
+                ExportLevel::States => {
+                    for state in &country.states {
+                        write_line(&mut out, &StateDto::new(state, country))?;
+                        written += 1;
+                    }
+                }
+                ExportLevel::Cities => {
+                    for state in &country.states {
+                        for city in &state.cities {
+                            write_line(&mut out, &CityDto::new(city, state, country))?;
+                            written += 1;
+                        }
+                    }
+                }
+            }
+        }
+        out.flush()?;
+        Ok(written)
+    }
+}
@@ -0,0 +1,320 @@
+// src/fetch.rs
+//! HTTP download helper for dataset refreshes (feature `fetch`).
+//!
+//! A small wrapper around a blocking `reqwest` client that downloads a file
+//! to disk with:
+//!
+//! - resume support (an interrupted download continues from `<dest>.part`)
+//! - progress callbacks
+//! - optional SHA-256 checksum validation
+//! - optional signed-artifact verification (with the `signed` feature)
+//! - optional HTTP(S) proxy
+//!
+//! The file is only moved to its final destination after the download
+//! completed and the checksum (if any) matched, so a failed refresh never
+//! replaces a good dataset.
+//!
+//! ```no_run
+//! use geodb_core::fetch::{DownloadOptions, Downloader};
+//!
+//! let downloader = Downloader::new(DownloadOptions::default())?;
+//! let report = downloader.download_to(
+//!     "https://example.org/countries+states+cities.json.gz",
+//!     "data/countries+states+cities.json.gz",
+//!     |p| eprintln!("{} / {:?} bytes", p.downloaded, p.total),
+//! )?;
+//! println!("sha256: {}", report.sha256);
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! [`Downloader::refresh_dataset`] replaces the upstream dataset itself
+//! ([`DATASET_URL`]) and rebuilds its binary cache.
+use crate::error::{GeoError, Result};
+use crate::hex::to_hex;
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+#[cfg(feature = "json")]
+use {
+    crate::loader::{cache_path_for, read_source, remove_cache_files},
+    crate::model::{try_build_geodb, DefaultBackend, GeoDb},
+    crate::parse::parse_countries,
+};
+
+/// Direct download of the upstream dataset whose repository page is
+/// [`crate::GeoDb::get_3rd_party_data_url`].
+pub const DATASET_URL: &str = "https://raw.githubusercontent.com/dr5hn/countries-states-cities-database/master/json/countries%2Bstates%2Bcities.json.gz";
+
+/// Options for [`Downloader`].
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// Proxy URL (e.g. `http://proxy:3128`). When `None`, the usual
+    /// `HTTP_PROXY` / `HTTPS_PROXY` environment variables are honored.
+    pub proxy: Option<String>,
+    /// Expected SHA-256 of the complete file, as lowercase or uppercase hex.
+    pub expected_sha256: Option<String>,
+    /// Continue a previous partial download if `<dest>.part` exists.
+    pub resume: bool,
+    /// Overall request timeout. `None` disables the timeout.
+    pub timeout: Option<Duration>,
+    /// Verify the download as a signed artifact (see [`crate::signed`]).
+    /// The file is kept as downloaded, envelope included.
+    #[cfg(feature = "signed")]
+    pub verify: Option<crate::signed::VerifyOptions>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            expected_sha256: None,
+            resume: true,
+            timeout: Some(Duration::from_secs(300)),
+            #[cfg(feature = "signed")]
+            verify: None,
+        }
+    }
+}
+
+/// Progress information passed to the download callback.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    /// Bytes on disk so far (including resumed bytes).
+    pub downloaded: u64,
+    /// Total size if the server reported it.
+    pub total: Option<u64>,
+}
+
+/// Summary of a finished download.
+#[derive(Debug, Clone)]
+pub struct DownloadReport {
+    /// Final location of the file.
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub bytes: u64,
+    /// Bytes that were reused from a previous partial download.
+    pub resumed_from: u64,
+    /// SHA-256 of the file (lowercase hex).
+    pub sha256: String,
+}
+
+/// Blocking HTTP downloader with resume and checksum support.
+#[derive(Debug, Clone)]
+pub struct Downloader {
+    client: Client,
+    options: DownloadOptions,
+}
+
+impl Downloader {
+    /// Build a downloader from options.
+    pub fn new(options: DownloadOptions) -> Result<Self> {
+        let mut builder = Client::builder()
+            .user_agent(concat!("geodb-core/", env!("CARGO_PKG_VERSION")))
+            .timeout(options.timeout);
+        if let Some(proxy) = &options.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        Ok(Self {
+            client: builder.build()?,
+            options,
+        })
+    }
+
+    /// The options this downloader was created with.
+    pub fn options(&self) -> &DownloadOptions {
+        &self.options
+    }
+
+    /// Download `url` to `dest`, calling `progress` as data arrives.
+    ///
+    /// Data is written to `<dest>.part` first and renamed once complete and
+    /// verified. On checksum mismatch (or a failed artifact verification)
+    /// the partial file is removed so the next attempt starts from scratch.
+    pub fn download_to(
+        &self,
+        url: &str,
+        dest: impl AsRef<Path>,
+        mut progress: impl FnMut(Progress),
+    ) -> Result<DownloadReport> {
+        let dest = dest.as_ref().to_path_buf();
+        let part = part_path(&dest);
+
+        let mut offset = if self.options.resume {
+            fs::metadata(&part).map(|m| m.len()).unwrap_or(0)
+        } else {
+            0
+        };
+
+        let mut request = self.client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={offset}-"));
+        }
+        let mut response = request.send()?;
+
+        let status = response.status();
+        if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+            // The partial file is stale (or already complete); start over.
+            response = self.client.get(url).send()?.error_for_status()?;
+            offset = 0;
+        } else {
+            response = response.error_for_status()?;
+        }
+
+        // Servers that ignore the range header answer 200 with the full body.
+        let resuming = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        if !resuming {
+            offset = 0;
+        }
+
+        let total = if resuming {
+            response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.rsplit('/').next())
+                .and_then(|v| v.parse::<u64>().ok())
+        } else {
+            response.content_length()
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resuming)
+            .truncate(!resuming)
+            .open(&part)?;
+
+        let mut downloaded = offset;
+        progress(Progress { downloaded, total });
+
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = response.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            file.write_all(&buf[..n])?;
+            downloaded += n as u64;
+            progress(Progress { downloaded, total });
+        }
+        file.flush()?;
+        drop(file);
+
+        let sha256 = sha256_file(&part)?;
+        if let Some(expected) = &self.options.expected_sha256 {
+            if !expected.trim().eq_ignore_ascii_case(&sha256) {
+                let _ = fs::remove_file(&part);
+                return Err(GeoError::ChecksumMismatch {
+                    expected: expected.trim().to_ascii_lowercase(),
+                    actual: sha256,
+                });
+            }
+        }
+
+        #[cfg(feature = "signed")]
+        if let Some(verify) = &self.options.verify {
+            let bytes = fs::read(&part)?;
+            if let Err(e) = crate::signed::open(&bytes, verify) {
+                let _ = fs::remove_file(&part);
+                return Err(e);
+            }
+        }
+
+        fs::rename(&part, &dest)?;
+
+        Ok(DownloadReport {
+            path: dest,
+            bytes: downloaded,
+            resumed_from: offset,
+            sha256,
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+impl Downloader {
+    /// Download the dataset from `url` (usually [`DATASET_URL`]) and make
+    /// it the dataset at `json_path`, returning the database built from it
+    /// for `iso2_filter`.
+    ///
+    /// The download is checked (checksum, if set), parsed and encoded
+    /// before it replaces the previous file; errors up to that point leave
+    /// the previous dataset and its caches as they were. Afterwards the
+    /// unfiltered cache and the one for `iso2_filter` are rewritten and the
+    /// other filtered caches removed (see [`remove_cache_files`]); an error
+    /// there is returned with the new dataset already in place, and loads
+    /// may then still find a stale filtered cache.
+    pub fn refresh_dataset(
+        &self,
+        url: &str,
+        json_path: impl AsRef<Path>,
+        iso2_filter: Option<&[&str]>,
+        progress: impl FnMut(Progress),
+    ) -> Result<(GeoDb<DefaultBackend>, DownloadReport)> {
+        let json_path = json_path.as_ref();
+        let staged = with_suffix(json_path, ".new");
+
+        let mut report = self.download_to(url, &staged, progress)?;
+        let built = read_source(&staged)
+            .and_then(|json| parse_countries(&json, None, false))
+            .and_then(|parsed| try_build_geodb(parsed.countries))
+            .and_then(|all| {
+                // the unfiltered cache is rewritten too, it is compiled in elsewhere
+                let mut caches = vec![(cache_path_for(json_path, None), all.to_bytes()?)];
+                let db = match iso2_filter {
+                    Some(codes) if !codes.is_empty() => {
+                        let db = all.filtered_by_iso2(codes);
+                        caches.push((cache_path_for(json_path, iso2_filter), db.to_bytes()?));
+                        db
+                    }
+                    _ => all,
+                };
+                Ok((db, caches))
+            });
+        let (db, caches) = match built {
+            Ok(built) => built,
+            Err(e) => {
+                let _ = fs::remove_file(&staged);
+                return Err(e);
+            }
+        };
+
+        fs::rename(&staged, json_path)?;
+        remove_cache_files(json_path)?;
+        for (path, bytes) in caches {
+            // replaced in one step, so a reader never sees half a cache
+            let tmp = with_suffix(&path, ".tmp");
+            fs::write(&tmp, bytes)?;
+            fs::rename(&tmp, &path)?;
+        }
+        report.path = json_path.to_path_buf();
+        Ok((db, report))
+    }
+}
+
+/// `path` with `suffix` appended to its file name.
+#[cfg(feature = "json")]
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// SHA-256 of a file as lowercase hex.
+pub fn sha256_file(path: impl AsRef<Path>) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut file = File::open(path)?;
+    io::copy(&mut file, &mut hasher)?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
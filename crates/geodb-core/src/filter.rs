@@ -1 +1,159 @@
+// src/filter.rs
+//! In-memory subsetting of a loaded database.
+//!
+//! The loaders filter by ISO2 while building; these helpers derive smaller
+//! databases from one that is already in memory, e.g. deterministic samples
+//! for examples, doc tests and demos, artifacts pruned to the cities a
+//! query log actually asked for, or artifacts without coordinates.
+use crate::model::{City, GeoBackend, GeoDb, SmartItem};
+use crate::search::SearchOptions;
+use std::collections::HashSet;
 
+impl<B: GeoBackend> GeoDb<B> {
+    /// Copy of the database restricted to the given ISO2 codes (case-insensitive).
+    ///
+    /// In-memory counterpart of `GeoDb::load_filtered_by_iso2`; country order
+    /// is preserved.
+    pub fn filtered_by_iso2(&self, iso2: &[&str]) -> Self {
+        GeoDb {
+            countries: self
+                .countries
+                .iter()
+                .filter(|c| iso2.iter().any(|code| c.iso2().eq_ignore_ascii_case(code)))
+                .cloned()
+                .collect(),
+            load_warnings: Vec::new(),
+            changelog: None,
+            spatial: Default::default(),
+        }
+    }
+
+    /// Deterministic sample of `n_countries` countries.
+    ///
+    /// The same `seed` always selects the same countries for the same input
+    /// database, on every platform. Countries keep their original order and
+    /// are copied whole (all states and cities), so the result is a valid
+    /// database in its own right. Asking for more countries than available
+    /// returns a full copy.
+    pub fn sample(&self, n_countries: usize, seed: u64) -> Self {
+        GeoDb {
+            countries: sample_indices(self.countries.len(), n_countries, seed)
+                .into_iter()
+                .map(|i| self.countries[i].clone())
+                .collect(),
+            load_warnings: Vec::new(),
+            changelog: None,
+            spatial: Default::default(),
+        }
+    }
+
+    /// Copy keeping only the cities that `queries` would have returned.
+    ///
+    /// Every query is run through [`GeoDb::smart_search_with`]; a city is kept
+    /// if it is among the first `max_hits` hits of any query. All countries and
+    /// states are kept (with their other fields), so country, state and phone
+    /// lookups behave as before and only the city lists shrink. Meant for
+    /// kiosks and embedded devices that serve a known set of queries:
+    /// replaying the same log against the pruned database yields the same
+    /// city hits.
+    pub fn pruned_for_queries<'q, I>(
+        &self,
+        queries: I,
+        options: &SearchOptions,
+        max_hits: usize,
+    ) -> Self
+    where
+        I: IntoIterator<Item = &'q str>,
+    {
+        let mut keep: HashSet<*const City<B>> = HashSet::new();
+        for query in queries {
+            for hit in self.smart_search_with(query, options).iter().take(max_hits) {
+                if let SmartItem::City { city, .. } = hit.item {
+                    keep.insert(city as *const _);
+                }
+            }
+        }
+
+        let mut pruned = self.clone();
+        for (country, source) in pruned.countries.iter_mut().zip(&self.countries) {
+            for (state, source) in country.states.iter_mut().zip(&source.states) {
+                let kept = |c: &&City<B>| keep.contains(&(*c as *const _));
+                state.cities = source.cities.iter().filter(kept).cloned().collect();
+
+                // district ranges must follow the compacted city list
+                #[cfg(feature = "districts")]
+                for district in &mut state.districts {
+                    let (start, end) = (district.city_start as usize, district.city_end as usize);
+                    let before = source.cities[..start].iter().filter(kept).count() as u32;
+                    let inside = source.cities[start..end].iter().filter(kept).count() as u32;
+                    district.city_start = before;
+                    district.city_end = before + inside;
+                }
+            }
+        }
+        pruned
+    }
+
+    /// Copy without any coordinates, for deployments that must not ship
+    /// precise locations.
+    ///
+    /// Latitude and longitude become `None` on every country, state and
+    /// city; names, codes and timezones are kept, so name, code and phone
+    /// searches behave as before. Coordinate queries (nearest city,
+    /// bounding boxes, `must_have_coords`) find nothing.
+    pub fn without_coordinates(&self) -> Self {
+        let mut stripped = self.clone();
+        for country in &mut stripped.countries {
+            (country.latitude, country.longitude) = (None, None);
+            for state in &mut country.states {
+                (state.latitude, state.longitude) = (None, None);
+                for city in &mut state.cities {
+                    (city.latitude, city.longitude) = (None, None);
+                }
+            }
+        }
+        stripped
+    }
+}
+
+/// Deterministic choice of `n` out of `total` indices, in ascending order.
+///
+/// The sampling primitive behind [`GeoDb::sample`]: the same `seed` picks the
+/// same indices on every platform. Asking for more than `total` returns all
+/// of them.
+pub fn sample_indices(total: usize, n: usize, seed: u64) -> Vec<usize> {
+    let n = n.min(total);
+
+    // Partial Fisher-Yates over the indices
+    let mut rng = SplitMix64(seed);
+    let mut indices: Vec<usize> = (0..total).collect();
+    for i in 0..n {
+        let j = i + (rng.next() % (total - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    indices.truncate(n);
+    indices.sort_unstable();
+    indices
+}
+
+/// Seed for an independent sample derived from `seed` and a position, so
+/// nested samples (e.g. the cities of each state) don't repeat each other.
+#[cfg(feature = "json")]
+pub(crate) fn derive_seed(seed: u64, parts: &[usize]) -> u64 {
+    parts.iter().fold(seed, |acc, &part| {
+        SplitMix64(acc ^ (part as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)).next()
+    })
+}
+
+/// Small, portable PRNG so samples don't depend on an external crate.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
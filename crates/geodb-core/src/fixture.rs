@@ -0,0 +1,124 @@
+// src/fixture.rs
+//! Miniature source datasets for test fixtures.
+//!
+//! Other projects that consume the Countries+States+Cities format want a
+//! small, stable input for their test suites. [`sample_source_dataset`]
+//! reads the source `.json.gz` and keeps a deterministic sample of it:
+//!
+//! ```no_run
+//! use geodb_core::fixture::{sample_source_dataset, FixtureOptions};
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let path = GeoDb::<StandardBackend>::default_data_dir()
+//!     .join(GeoDb::<StandardBackend>::default_dataset_filename());
+//! let options = FixtureOptions {
+//!     countries: 3,
+//!     cities_per_state: 5,
+//!     seed: 42,
+//! };
+//! let fixture = sample_source_dataset(path, None, &options)?;
+//! std::fs::write("fixture.json", serde_json::to_vec_pretty(&fixture)?)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The records are copied untyped, so every field of the source format
+//! survives, including the ones `GeoDb` doesn't use. Countries are chosen
+//! like [`GeoDb::sample`](crate::GeoDb::sample) with the same seed; all their
+//! states are kept, each with at most `cities_per_state` cities (as are
+//! cities listed on the country itself).
+use crate::error::{GeoError, Result};
+use crate::filter::{derive_seed, sample_indices};
+use crate::loader::load_raw_countries;
+use serde_json::Value;
+use std::path::Path;
+
+/// Size and seed of a fixture sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixtureOptions {
+    /// Countries to keep.
+    pub countries: usize,
+    /// Cities to keep per state (states with fewer keep all of them).
+    pub cities_per_state: usize,
+    /// The same seed always yields the same fixture for the same source.
+    pub seed: u64,
+}
+
+impl Default for FixtureOptions {
+    fn default() -> Self {
+        Self {
+            countries: 3,
+            cities_per_state: 5,
+            seed: 0,
+        }
+    }
+}
+
+/// Read the source dataset at `path` (`.json.gz`), restrict it to
+/// `iso2_filter` if given and sample it; see the [module docs](self).
+pub fn sample_source_dataset(
+    path: impl AsRef<Path>,
+    iso2_filter: Option<&[&str]>,
+    options: &FixtureOptions,
+) -> Result<Value> {
+    let mut countries: Vec<Value> = load_raw_countries(path.as_ref())?;
+    if let Some(filter) = iso2_filter {
+        countries.retain(|c| {
+            c.get("iso2")
+                .and_then(Value::as_str)
+                .is_some_and(|iso2| filter.iter().any(|f| f.eq_ignore_ascii_case(iso2)))
+        });
+    }
+    Ok(Value::Array(sample_source_countries(countries, options)?))
+}
+
+/// Sample already parsed source country records.
+///
+/// Fails if a country or state isn't a JSON object.
+pub fn sample_source_countries(
+    countries: Vec<Value>,
+    options: &FixtureOptions,
+) -> Result<Vec<Value>> {
+    let chosen = sample_indices(countries.len(), options.countries, options.seed);
+    let mut out = Vec::with_capacity(chosen.len());
+    for (ci, mut country) in countries.into_iter().enumerate() {
+        if chosen.binary_search(&ci).is_err() {
+            continue;
+        }
+        let record = country
+            .as_object_mut()
+            .ok_or_else(|| invalid("country", ci))?;
+        // cities listed on the country itself are sampled like one more state
+        if let Some(Value::Array(cities)) = record.get_mut("cities") {
+            sample_cities(
+                cities,
+                options.cities_per_state,
+                derive_seed(options.seed, &[ci]),
+            );
+        }
+        if let Some(Value::Array(states)) = record.get_mut("states") {
+            for (si, state) in states.iter_mut().enumerate() {
+                let state = state.as_object_mut().ok_or_else(|| invalid("state", si))?;
+                if let Some(Value::Array(cities)) = state.get_mut("cities") {
+                    let seed = derive_seed(options.seed, &[ci, si]);
+                    sample_cities(cities, options.cities_per_state, seed);
+                }
+            }
+        }
+        out.push(country);
+    }
+    Ok(out)
+}
+
+/// Keep a seeded sample of `n` cities, in their original order.
+fn sample_cities(cities: &mut Vec<Value>, n: usize, seed: u64) {
+    let keep = sample_indices(cities.len(), n, seed);
+    let mut index = 0;
+    cities.retain(|_| {
+        index += 1;
+        keep.binary_search(&(index - 1)).is_ok()
+    });
+}
+
+fn invalid(what: &str, index: usize) -> GeoError {
+    GeoError::InvalidData(format!("source {what} #{index} is not a JSON object"))
+}
@@ -0,0 +1,116 @@
+// src/geo_interop.rs
+//! GeoRust interop (feature `geo-interop`).
+//!
+//! Entities convert to [`geo_types::Point`]s, with x = longitude and
+//! y = latitude as everywhere in GeoRust, and [`GeoDb::city_rtree`]
+//! bulk-loads an [`rstar::RTree`] of [`CityPoint`]s that keep their
+//! city/state/country references:
+//!
+//! ```no_run
+//! use geodb_core::geo_interop::{geo_types::Point, rstar::AABB};
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"])?;
+//! let tree = db.city_rtree();
+//!
+//! let berlin = Point::new(13.405, 52.52);
+//! for hit in tree.nearest_neighbor_iter(&berlin).take(3) {
+//!     println!("{} ({})", hit.city.name(), hit.state.name());
+//! }
+//!
+//! let ruhr = AABB::from_corners(Point::new(6.6, 51.3), Point::new(7.8, 51.7));
+//! println!("{} cities in the Ruhr box", tree.locate_in_envelope(&ruhr).count());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! The tree works in plain degrees, like most GeoRust pipelines: envelope
+//! queries are exact, but nearest-neighbour order treats a degree of
+//! longitude like a degree of latitude. Re-rank candidates with
+//! [`crate::haversine_km`] where true distances matter.
+pub use geo_types;
+pub use rstar;
+
+use crate::model::{City, Country, GeoBackend, GeoDb, SmartItem, State};
+use geo_types::Point;
+use rstar::{PointDistance, RTree, RTreeObject, AABB};
+
+fn point<B: GeoBackend>(lat: Option<B::Float>, lng: Option<B::Float>) -> Option<Point<f64>> {
+    Some(Point::new(B::float_to_f64(lng?), B::float_to_f64(lat?)))
+}
+
+impl<B: GeoBackend> Country<B> {
+    /// Country centroid as a point (x = lng, y = lat), if known.
+    pub fn point(&self) -> Option<Point<f64>> {
+        point::<B>(self.latitude, self.longitude)
+    }
+}
+
+impl<B: GeoBackend> State<B> {
+    /// State centroid as a point (x = lng, y = lat), if known.
+    pub fn point(&self) -> Option<Point<f64>> {
+        point::<B>(self.latitude, self.longitude)
+    }
+}
+
+impl<B: GeoBackend> City<B> {
+    /// City position as a point (x = lng, y = lat), if known.
+    pub fn point(&self) -> Option<Point<f64>> {
+        point::<B>(self.latitude, self.longitude)
+    }
+}
+
+impl<B: GeoBackend> SmartItem<'_, B> {
+    /// Position of the matched entity as a point (x = lng, y = lat), if known.
+    pub fn point(&self) -> Option<Point<f64>> {
+        let (lat, lng) = self.coordinates()?;
+        Some(Point::new(lng, lat))
+    }
+}
+
+/// A city with its position, as stored in [`GeoDb::city_rtree`].
+#[derive(Debug, Clone, Copy)]
+pub struct CityPoint<'a, B: GeoBackend> {
+    pub city: &'a City<B>,
+    pub state: &'a State<B>,
+    pub country: &'a Country<B>,
+    /// x = longitude, y = latitude.
+    pub point: Point<f64>,
+}
+
+impl<B: GeoBackend> RTreeObject for CityPoint<'_, B> {
+    type Envelope = AABB<Point<f64>>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point(self.point)
+    }
+}
+
+impl<B: GeoBackend> PointDistance for CityPoint<'_, B> {
+    /// Squared planar distance in degrees.
+    fn distance_2(&self, point: &Point<f64>) -> f64 {
+        let (dx, dy) = (self.point.x() - point.x(), self.point.y() - point.y());
+        dx * dx + dy * dy
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// All cities with coordinates as [`CityPoint`]s, in database order.
+    pub fn city_points(&self) -> impl Iterator<Item = CityPoint<'_, B>> {
+        self.iter_cities().filter_map(|(city, state, country)| {
+            Some(CityPoint {
+                city,
+                state,
+                country,
+                point: city.point()?,
+            })
+        })
+    }
+
+    /// R-tree of all cities with coordinates; see the [module docs](self).
+    ///
+    /// Bulk-loaded, so building it for the full dataset takes well under a
+    /// second. The tree borrows the database.
+    pub fn city_rtree(&self) -> RTree<CityPoint<'_, B>> {
+        RTree::bulk_load(self.city_points().collect())
+    }
+}
@@ -0,0 +1,331 @@
+// src/geonames.rs
+//! Bulk city aliases from GeoNames dumps (feature `geonames`).
+//!
+//! Hand-curating `city_meta.json` doesn't scale beyond a few hundred cities.
+//! GeoNames publishes alternate names for millions of places; this importer
+//! turns them into [`CityMeta`] entries for the cities of a loaded database.
+//!
+//! Two files from <https://download.geonames.org/export/dump/> are needed,
+//! both provided by the caller (nothing is downloaded):
+//!
+//! - a places dump (`cities500.txt`, `cities15000.txt`, `DE.txt`, …), used to
+//!   map GeoNames ids onto dataset cities by country, name and position;
+//! - `alternateNamesV2.txt` (or the older `alternateNames.txt`), streamed
+//!   line by line and filtered by [`GeonamesImportOptions`].
+//!
+//! ```no_run
+//! use geodb_core::geonames::GeonamesImportOptions;
+//! use geodb_core::{GeoDb, StandardBackend};
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"])?;
+//! let options = GeonamesImportOptions::default().with_languages(["de", "en"]);
+//! let import = db.import_geonames_aliases(
+//!     BufReader::new(File::open("DE.txt")?),
+//!     BufReader::new(File::open("alternateNamesV2.txt")?),
+//!     &options,
+//! )?;
+//! println!("{} aliases for {} cities", import.aliases, import.entries.len());
+//! db.apply_city_meta(&import.into_index());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::alias::{CityMeta, CityMetaIndex};
+use crate::error::{GeoError, Result};
+use crate::model::{GeoBackend, GeoDb};
+use crate::search::{fold_words, SearchLanguage};
+use crate::spatial::haversine_km;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufRead;
+
+/// GeoNames "language" codes that aren't names (postal codes, airport codes,
+/// Wikipedia links, ...). Always skipped.
+const NON_NAME_CODES: &[&str] = &[
+    "abbr", "faac", "iata", "icao", "link", "post", "tcid", "unlc", "wkdt",
+];
+
+/// Which alternate names [`GeoDb::import_geonames_aliases`] keeps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeonamesImportOptions {
+    /// Language codes to keep (e.g. `["de", "fr"]`); empty keeps all.
+    /// Names without a language are kept only if `""` is listed.
+    pub languages: Vec<String>,
+    /// Keep only names flagged as preferred for their language.
+    pub preferred_only: bool,
+    /// Keep short names ("Frankfurt" for "Frankfurt am Main").
+    pub include_short: bool,
+    /// Keep colloquial names ("Big Apple").
+    pub include_colloquial: bool,
+    /// Keep historic names ("Königsberg").
+    pub include_historic: bool,
+    /// Maximum distance between a GeoNames place and a dataset city of the
+    /// same name for them to count as the same city.
+    pub max_distance_km: f64,
+    /// Cap on aliases per city, in file order.
+    pub max_aliases: Option<usize>,
+}
+
+impl Default for GeonamesImportOptions {
+    fn default() -> Self {
+        Self {
+            languages: Vec::new(),
+            preferred_only: false,
+            include_short: true,
+            include_colloquial: false,
+            include_historic: false,
+            max_distance_km: 30.0,
+            max_aliases: None,
+        }
+    }
+}
+
+impl GeonamesImportOptions {
+    /// Keep only names in these languages.
+    pub fn with_languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Keep only preferred names.
+    pub fn with_preferred_only(mut self, yes: bool) -> Self {
+        self.preferred_only = yes;
+        self
+    }
+
+    /// Cap the number of aliases per city.
+    pub fn with_max_aliases(mut self, max: usize) -> Self {
+        self.max_aliases = Some(max);
+        self
+    }
+
+    fn keeps(&self, name: &AlternateName<'_>) -> bool {
+        if NON_NAME_CODES.contains(&name.language) {
+            return false;
+        }
+        if !self.languages.is_empty()
+            && !self
+                .languages
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(name.language))
+        {
+            return false;
+        }
+        (!self.preferred_only || name.preferred)
+            && (self.include_short || !name.short)
+            && (self.include_colloquial || !name.colloquial)
+            && (self.include_historic || !name.historic)
+    }
+}
+
+/// Result of [`GeoDb::import_geonames_aliases`].
+#[derive(Debug, Clone, Default)]
+pub struct GeonamesImport {
+    /// One entry per city that received aliases, sorted by (iso2, state, city).
+    pub entries: Vec<CityMeta>,
+    /// GeoNames places mapped onto a dataset city.
+    pub matched_places: usize,
+    /// GeoNames places without a dataset city (other country, too far away,
+    /// different name).
+    pub unmatched_places: usize,
+    /// Aliases added across all entries.
+    pub aliases: usize,
+}
+
+impl GeonamesImport {
+    /// Index the imported entries, e.g. for [`GeoDb::apply_city_meta`],
+    /// with `"geonames"` as their source.
+    pub fn into_index(self) -> CityMetaIndex {
+        CityMetaIndex::from_entries(self.entries).with_source("geonames")
+    }
+
+    /// Merge the imported aliases into existing meta entries (e.g. a
+    /// hand-curated `city_meta.json`). Curated aliases and regions stay
+    /// first; cities without an entry get a new one.
+    pub fn merge_into(&self, base: &mut Vec<CityMeta>) {
+        for entry in &self.entries {
+            let existing = base.iter_mut().find(|b| {
+                b.iso2.eq_ignore_ascii_case(&entry.iso2)
+                    && b.state.eq_ignore_ascii_case(&entry.state)
+                    && b.city.eq_ignore_ascii_case(&entry.city)
+            });
+            match existing {
+                Some(b) => {
+                    for alias in &entry.aliases {
+                        if !b.aliases.iter().any(|a| alias_key(a) == alias_key(alias)) {
+                            b.aliases.push(alias.clone());
+                        }
+                    }
+                }
+                None => base.push(entry.clone()),
+            }
+        }
+    }
+}
+
+/// One parsed line of `alternateNames(V2).txt`.
+struct AlternateName<'a> {
+    geoname_id: u64,
+    language: &'a str,
+    name: &'a str,
+    preferred: bool,
+    short: bool,
+    colloquial: bool,
+    historic: bool,
+}
+
+impl<'a> AlternateName<'a> {
+    fn parse(line: &'a str, line_no: usize) -> Result<Self> {
+        let cols: Vec<&str> = line.split('\t').collect();
+        if cols.len() < 4 {
+            return Err(malformed("alternate names", line_no));
+        }
+        let flag = |i: usize| cols.get(i).is_some_and(|v| *v == "1");
+        Ok(Self {
+            geoname_id: cols[1]
+                .parse()
+                .map_err(|_| malformed("alternate names", line_no))?,
+            language: cols[2],
+            name: cols[3],
+            preferred: flag(4),
+            short: flag(5),
+            colloquial: flag(6),
+            historic: flag(7),
+        })
+    }
+}
+
+/// Aliases that differ only in (ASCII) case or spacing are duplicates;
+/// spellings with and without accents are kept, since the default search
+/// tells them apart.
+fn alias_key(name: &str) -> String {
+    fold_words(name, SearchLanguage::Simple)
+}
+
+fn malformed(file: &str, line_no: usize) -> GeoError {
+    GeoError::InvalidData(format!("malformed GeoNames {file} line {line_no}"))
+}
+
+/// (iso2, state, city) of a dataset city.
+type CityKey = (String, String, String);
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Build city aliases from GeoNames dumps; see the [module docs](self).
+    ///
+    /// `places` is a GeoNames places dump; only populated places (feature
+    /// class `P`) are used. Each is matched to the nearest dataset city in
+    /// the same country whose name equals the place's name, ASCII name or
+    /// one of its listed alternate names, within
+    /// [`GeonamesImportOptions::max_distance_km`]. `alternate_names` is then
+    /// streamed and filtered; names equal to the city's own name are skipped.
+    pub fn import_geonames_aliases<P, A>(
+        &self,
+        places: P,
+        alternate_names: A,
+        options: &GeonamesImportOptions,
+    ) -> Result<GeonamesImport>
+    where
+        P: BufRead,
+        A: BufRead,
+    {
+        let lookup = self.exact_lookup();
+        let mut import = GeonamesImport::default();
+        let mut by_id: HashMap<u64, CityKey> = HashMap::new();
+
+        for (i, line) in places.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let cols: Vec<&str> = line.split('\t').collect();
+            if cols.len() < 9 {
+                return Err(malformed("places", i + 1));
+            }
+            if cols[6] != "P" {
+                continue;
+            }
+            let id: u64 = cols[0].parse().map_err(|_| malformed("places", i + 1))?;
+            let (Ok(lat), Ok(lng)) = (cols[4].parse::<f64>(), cols[5].parse::<f64>()) else {
+                return Err(malformed("places", i + 1));
+            };
+            let iso2 = cols[8];
+
+            let names = [cols[1], cols[2]]
+                .into_iter()
+                .chain(cols[3].split(',').filter(|n| !n.is_empty()));
+            let mut best: Option<(f64, CityKey)> = None;
+            for name in names {
+                for (city, state, country) in lookup.find_cities(iso2, name) {
+                    let (Some(clat), Some(clng)) = (city.latitude, city.longitude) else {
+                        continue;
+                    };
+                    let km = haversine_km(lat, lng, B::float_to_f64(clat), B::float_to_f64(clng));
+                    if km <= options.max_distance_km && best.as_ref().is_none_or(|(d, _)| km < *d) {
+                        let key = (
+                            country.iso2().to_string(),
+                            state.name().to_string(),
+                            city.name().to_string(),
+                        );
+                        best = Some((km, key));
+                    }
+                }
+                // the primary names are the most reliable; stop at the first hit
+                if best.is_some() {
+                    break;
+                }
+            }
+
+            match best {
+                Some((_, key)) => {
+                    by_id.insert(id, key);
+                    import.matched_places += 1;
+                }
+                None => import.unmatched_places += 1,
+            }
+        }
+
+        let mut aliases: BTreeMap<CityKey, Vec<String>> = BTreeMap::new();
+        for (i, line) in alternate_names.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let alt = AlternateName::parse(&line, i + 1)?;
+            let Some(key) = by_id.get(&alt.geoname_id) else {
+                continue;
+            };
+            if !options.keeps(&alt) {
+                continue;
+            }
+            let folded = alias_key(alt.name);
+            if folded.is_empty() || folded == alias_key(&key.2) {
+                continue;
+            }
+            let list = aliases.entry(key.clone()).or_default();
+            if options.max_aliases.is_some_and(|max| list.len() >= max)
+                || list.iter().any(|a| alias_key(a) == folded)
+            {
+                continue;
+            }
+            list.push(alt.name.trim().to_string());
+        }
+
+        import.aliases = aliases.values().map(Vec::len).sum();
+        import.entries = aliases
+            .into_iter()
+            .map(|((iso2, state, city), aliases)| CityMeta {
+                iso2,
+                state,
+                city,
+                aliases,
+                regions: Vec::new(),
+            })
+            .collect();
+        Ok(import)
+    }
+}
@@ -0,0 +1,8 @@
+// src/hex.rs
+//! Lowercase hex for the SHA-256 digests and keys of the `bundle`, `fetch`
+//! and `signed` modules.
+
+/// `bytes` as lowercase hex, two digits per byte.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,102 @@
+// src/indexed.rs
+//! Binary artifacts with a country index up front.
+//!
+//! The plain binary format (see [`GeoDb::to_bytes`]) is the bincode
+//! encoding of the countries one after another, with no way to find a
+//! country without decoding all before it. The indexed layout puts a table
+//! of ISO2 codes and byte lengths in front of that same payload:
+//!
+//! ```text
+//! "GEODBIDX" | u64 table length | bincode table | plain binary database
+//! ```
+//!
+//! so a reader can jump straight to a country (see [`crate::mmap`],
+//! feature `mmap`), while [`GeoDb::from_bytes`] and
+//! [`crate::inspect::inspect_bytes`] skip the table and read the payload
+//! as before. Write one with [`GeoDb::to_indexed_bytes`] or
+//! `geodb build --indexed`.
+use crate::error::{GeoError, Result};
+use crate::model::{GeoBackend, GeoDb};
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Leading bytes of an indexed artifact.
+pub const INDEX_MAGIC: &[u8; 8] = b"GEODBIDX";
+
+/// One country in the table of an indexed artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub iso2: String,
+    /// Bytes of the country in the payload, everything inside it included.
+    pub len: u64,
+}
+
+/// Whether `bytes` start like an indexed artifact.
+pub fn is_indexed(bytes: &[u8]) -> bool {
+    bytes.starts_with(INDEX_MAGIC)
+}
+
+/// Table and plain payload of an indexed artifact; `None` for anything
+/// else. Fails if the table doesn't decode or doesn't cover the payload.
+pub fn split_index(bytes: &[u8]) -> Result<Option<(Vec<IndexEntry>, &[u8])>> {
+    let Some(rest) = bytes.strip_prefix(INDEX_MAGIC.as_slice()) else {
+        return Ok(None);
+    };
+    let invalid = |msg: &str| GeoError::InvalidData(format!("indexed artifact: {msg}"));
+    let (len, rest) = rest
+        .split_first_chunk::<8>()
+        .ok_or_else(|| invalid("truncated table length"))?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).unwrap_or(usize::MAX);
+    if len > rest.len() {
+        return Err(invalid("table is longer than the artifact"));
+    }
+    let (table, payload) = rest.split_at(len);
+    let entries: Vec<IndexEntry> = crate::loader::bincode_options(len as u64)
+        .deserialize(table)
+        .map_err(|e| invalid(&e.to_string()))?;
+
+    // the payload is the plain format: country count, then the countries
+    let count = payload
+        .first_chunk::<8>()
+        .map(|n| u64::from_le_bytes(*n))
+        .ok_or_else(|| invalid("truncated payload"))?;
+    let covered = entries
+        .iter()
+        .try_fold(8u64, |sum, e| sum.checked_add(e.len))
+        .ok_or_else(|| invalid("table overflows"))?;
+    if count != entries.len() as u64 || covered > payload.len() as u64 {
+        return Err(invalid("table doesn't match the payload"));
+    }
+    Ok(Some((entries, payload)))
+}
+
+impl<B> GeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    /// Serialize into the indexed layout; see the [module docs](self).
+    /// Readable by [`Self::from_bytes`] like the output of
+    /// [`Self::to_bytes`].
+    pub fn to_indexed_bytes(&self) -> Result<Vec<u8>> {
+        let payload = self.to_bytes()?;
+        let entries = self
+            .countries
+            .iter()
+            .map(|c| {
+                Ok(IndexEntry {
+                    iso2: c.iso2().to_string(),
+                    len: bincode::serialized_size(c)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let table = bincode::serialize(&entries)?;
+
+        let mut out = Vec::with_capacity(INDEX_MAGIC.len() + 8 + table.len() + payload.len());
+        out.extend_from_slice(INDEX_MAGIC);
+        out.extend_from_slice(&(table.len() as u64).to_le_bytes());
+        out.extend_from_slice(&table);
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+}
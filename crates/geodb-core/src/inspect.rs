@@ -0,0 +1,474 @@
+// src/inspect.rs
+//! Size breakdown of a binary database artifact.
+//!
+//! [`inspect_bytes`] walks a `*.bin` blob one country at a time, so only a
+//! single country is in memory at once, and reports how many bytes each
+//! level takes. Use it to find out why an artifact grew after a data update
+//! (`geodb inspect <file>` in the CLI):
+//!
+//! ```no_run
+//! use geodb_core::inspect::inspect_bytes;
+//! use geodb_core::StandardBackend;
+//!
+//! let bytes = std::fs::read("geodb.bin")?;
+//! let report = inspect_bytes::<StandardBackend>(&bytes)?;
+//! println!("{} bytes of cities", report.sections.cities);
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Sealed artifacts (feature `signed`) are checked for integrity and their
+//! payload is inspected; gzip-compressed blobs (feature `json`) are
+//! decompressed first.
+//!
+//! The binary format has no header and no string table: it is the bincode
+//! encoding of the countries, with each state and its cities nested inside
+//! its country and strings stored inline. [`SectionSizes::strings`]
+//! therefore overlaps the other sections.
+use crate::error::{GeoError, Result};
+use crate::limits::{with_limits, LoadLimits};
+use crate::model::{Country, GeoBackend};
+use serde::de::DeserializeOwned;
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Compression around the bincode payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    None,
+    Gzip,
+}
+
+/// Header of a sealed artifact (see [`crate::signed`]).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct EnvelopeInfo {
+    /// Envelope format version.
+    pub version: u8,
+    /// Whether an ed25519 signature is present (not verified here).
+    pub signed: bool,
+    /// SHA-256 of the payload (hex); checked against the payload.
+    pub sha256: String,
+}
+
+/// Bytes per level of the payload.
+///
+/// `countries`, `states`, `cities` and `trailing` add up to the payload
+/// size; `strings` counts the string contents within them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SectionSizes {
+    /// Country records without their states, plus the list lengths.
+    pub countries: u64,
+    /// State records without their cities (districts included).
+    pub states: u64,
+    /// City records.
+    pub cities: u64,
+    /// UTF-8 bytes of all strings (names, codes, ...) without their length
+    /// prefixes.
+    pub strings: u64,
+    /// Bytes after the last country; a valid artifact has none.
+    pub trailing: u64,
+}
+
+/// Bytes taken by one country, everything inside it included.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct CountrySize {
+    pub iso2: String,
+    pub name: String,
+    pub bytes: u64,
+    /// Bytes of its states, their cities included.
+    pub states: u64,
+    /// Bytes of its cities.
+    pub cities: u64,
+}
+
+/// What [`inspect_bytes`] found.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ArtifactReport {
+    /// Size of the inspected input.
+    pub size: u64,
+    pub compression: Compression,
+    /// Envelope header, for sealed artifacts.
+    pub envelope: Option<EnvelopeInfo>,
+    /// Size of the bincode payload (after unwrapping, decompressing and
+    /// skipping the table of an indexed artifact).
+    pub payload_size: u64,
+    pub countries: usize,
+    pub states: usize,
+    pub cities: usize,
+    pub sections: SectionSizes,
+    /// One entry per country, in database order.
+    pub per_country: Vec<CountrySize>,
+}
+
+/// Break down the binary database `bytes`; see the [module docs](self).
+///
+/// Fails like [`GeoDb::from_bytes`](crate::GeoDb::from_bytes) if the
+/// payload doesn't decode (with the default [`LoadLimits`]), and with
+/// [`GeoError::ChecksumMismatch`] for a corrupted sealed artifact.
+pub fn inspect_bytes<B>(bytes: &[u8]) -> Result<ArtifactReport>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    let (compression, decompressed) = decompress(bytes)?;
+    let data = decompressed.as_deref().unwrap_or(bytes);
+    let (envelope, payload) = unwrap_envelope(data)?;
+    let payload = match crate::indexed::split_index(payload)? {
+        Some((_, payload)) => payload,
+        None => payload,
+    };
+
+    let mut report = ArtifactReport {
+        size: bytes.len() as u64,
+        compression,
+        envelope,
+        payload_size: payload.len() as u64,
+        countries: 0,
+        states: 0,
+        cities: 0,
+        sections: SectionSizes::default(),
+        per_country: Vec::new(),
+    };
+    with_limits(&LoadLimits::default(), || walk::<B>(payload, &mut report))?;
+    Ok(report)
+}
+
+fn walk<B>(payload: &[u8], report: &mut ArtifactReport) -> Result<()>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    use bincode::Options;
+
+    let (len, mut rest) = payload
+        .split_first_chunk::<8>()
+        .ok_or_else(|| GeoError::InvalidData("binary database is empty".into()))?;
+    let count = u64::from_le_bytes(*len);
+    if count > LoadLimits::default().max_countries as u64 {
+        return Err(GeoError::InvalidData(format!(
+            "binary database claims {count} countries"
+        )));
+    }
+    report.sections.countries = 8;
+
+    let options = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(payload.len() as u64);
+    for _ in 0..count {
+        let before = rest.len();
+        let country: Country<B> = options.deserialize_from(&mut rest)?;
+        let bytes = (before - rest.len()) as u64;
+
+        let mut states = 0;
+        let mut cities = 0;
+        for state in &country.states {
+            states += bincode::serialized_size(state)?;
+            for city in &state.cities {
+                cities += bincode::serialized_size(city)?;
+            }
+            report.cities += state.cities.len();
+        }
+        report.countries += 1;
+        report.states += country.states.len();
+        report.sections.countries += bytes - states;
+        report.sections.states += states - cities;
+        report.sections.cities += cities;
+        report.sections.strings += string_bytes(&country);
+        report.per_country.push(CountrySize {
+            iso2: country.iso2().to_string(),
+            name: country.name().to_string(),
+            bytes,
+            states,
+            cities,
+        });
+    }
+    report.sections.trailing = rest.len() as u64;
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+fn decompress(bytes: &[u8]) -> Result<(Compression, Option<Vec<u8>>)> {
+    use std::io::Read;
+
+    if !bytes.starts_with(&[0x1f, 0x8b]) {
+        return Ok((Compression::None, None));
+    }
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(bytes).read_to_end(&mut out)?;
+    if matches!(
+        out.iter().find(|b| !b.is_ascii_whitespace()),
+        Some(b'[' | b'{')
+    ) {
+        return Err(GeoError::InvalidData(
+            "gzip-compressed JSON: a source dataset, not a binary database".into(),
+        ));
+    }
+    Ok((Compression::Gzip, Some(out)))
+}
+
+#[cfg(not(feature = "json"))]
+fn decompress(bytes: &[u8]) -> Result<(Compression, Option<Vec<u8>>)> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        return Err(GeoError::InvalidData(
+            "gzip-compressed input needs the `json` feature".into(),
+        ));
+    }
+    Ok((Compression::None, None))
+}
+
+#[cfg(feature = "signed")]
+fn unwrap_envelope(bytes: &[u8]) -> Result<(Option<EnvelopeInfo>, &[u8])> {
+    use crate::signed::{self, VerifyOptions, MAGIC};
+
+    if !signed::is_sealed(bytes) {
+        return Ok((None, bytes));
+    }
+    let payload = signed::open(bytes, &VerifyOptions::default())?;
+    let header = &bytes[MAGIC.len()..];
+    let info = EnvelopeInfo {
+        version: header[0],
+        signed: header[1] & 1 != 0,
+        sha256: crate::hex::to_hex(&header[2..34]),
+    };
+    Ok((Some(info), payload))
+}
+
+#[cfg(not(feature = "signed"))]
+fn unwrap_envelope(bytes: &[u8]) -> Result<(Option<EnvelopeInfo>, &[u8])> {
+    if bytes.starts_with(b"GEODBSIG") {
+        return Err(GeoError::InvalidData(
+            "sealed artifact needs the `signed` feature".into(),
+        ));
+    }
+    Ok((None, bytes))
+}
+
+/// UTF-8 bytes of all strings in `value`.
+fn string_bytes(value: &impl Serialize) -> u64 {
+    let mut counter = StringBytes(0);
+    // the counter itself never fails
+    let _ = value.serialize(&mut counter);
+    counter.0
+}
+
+/// Serializer that only sums up string lengths.
+struct StringBytes(u64);
+
+type Counted = std::result::Result<(), Never>;
+
+#[derive(Debug)]
+struct Never;
+
+impl fmt::Display for Never {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("string counter error")
+    }
+}
+
+impl std::error::Error for Never {}
+
+impl ser::Error for Never {
+    fn custom<T: fmt::Display>(_: T) -> Self {
+        Never
+    }
+}
+
+impl ser::Serializer for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, _: bool) -> Counted {
+        Ok(())
+    }
+    fn serialize_i8(self, _: i8) -> Counted {
+        Ok(())
+    }
+    fn serialize_i16(self, _: i16) -> Counted {
+        Ok(())
+    }
+    fn serialize_i32(self, _: i32) -> Counted {
+        Ok(())
+    }
+    fn serialize_i64(self, _: i64) -> Counted {
+        Ok(())
+    }
+    fn serialize_u8(self, _: u8) -> Counted {
+        Ok(())
+    }
+    fn serialize_u16(self, _: u16) -> Counted {
+        Ok(())
+    }
+    fn serialize_u32(self, _: u32) -> Counted {
+        Ok(())
+    }
+    fn serialize_u64(self, _: u64) -> Counted {
+        Ok(())
+    }
+    fn serialize_f32(self, _: f32) -> Counted {
+        Ok(())
+    }
+    fn serialize_f64(self, _: f64) -> Counted {
+        Ok(())
+    }
+    fn serialize_char(self, c: char) -> Counted {
+        self.0 += c.len_utf8() as u64;
+        Ok(())
+    }
+    fn serialize_str(self, v: &str) -> Counted {
+        self.0 += v.len() as u64;
+        Ok(())
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Counted {
+        Ok(())
+    }
+    fn serialize_none(self) -> Counted {
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Counted {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Counted {
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Counted {
+        Ok(())
+    }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Counted {
+        Ok(())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Counted {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Counted {
+        value.serialize(self)
+    }
+    fn serialize_seq(self, _: Option<usize>) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+    fn serialize_tuple(self, _: usize) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+    fn serialize_map(self, _: Option<usize>) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+    fn serialize_struct(self, _: &'static str, _: usize) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> std::result::Result<Self, Never> {
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Counted {
+        key.serialize(&mut **self)
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _: &'static str, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut StringBytes {
+    type Ok = ();
+    type Error = Never;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _: &'static str, value: &T) -> Counted {
+        value.serialize(&mut **self)
+    }
+    fn end(self) -> Counted {
+        Ok(())
+    }
+}
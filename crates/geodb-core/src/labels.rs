@@ -0,0 +1,174 @@
+// src/labels.rs
+//! Multilingual city labels (feature `multilingual-cities`).
+//!
+//! The upstream dataset carries one name per city. This module merges
+//! language variants from a prepared sidecar (e.g. exported from Wikidata
+//! labels) so "Vienna", "Wien" and "Vienne" all resolve to the same city.
+//!
+//! Labels live next to the database instead of inside it, so the binary
+//! layout is unchanged; pass the index to [`GeoDb::find_cities_by_label`]
+//! or to smart search via [`crate::SearchOptions::with_city_labels`].
+use crate::model::{City, Country, GeoBackend, GeoDb, State};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "json")]
+use {crate::error::Result, std::fs, std::path::Path};
+
+/// Language variants of one canonical city.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CityLabels {
+    pub iso2: String,  // "AT"
+    pub state: String, // "Vienna"
+    pub city: String,  // canonical city name as in the dataset
+    /// Wikidata item id, e.g. "Q1741".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wikidata: Option<String>,
+    /// Language code → label, e.g. `{"de": "Wien", "fr": "Vienne"}`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CityLabelsFile {
+    pub cities: Vec<CityLabels>,
+}
+
+/// In-memory index for lookups by label and by canonical triple.
+#[derive(Debug, Default)]
+pub struct CityLabelIndex {
+    pub entries: Vec<CityLabels>,
+    /// label (lowercased) → indices into `entries`; labels can be ambiguous
+    label_index: HashMap<String, Vec<usize>>,
+    /// (iso2.lower, state.lower, city.lower) → index
+    canonical_index: HashMap<(String, String, String), usize>,
+}
+
+impl CityLabelIndex {
+    /// Build an index from label entries.
+    pub fn from_entries(entries: Vec<CityLabels>) -> Self {
+        let mut index = CityLabelIndex {
+            entries,
+            label_index: HashMap::new(),
+            canonical_index: HashMap::new(),
+        };
+
+        for (i, entry) in index.entries.iter().enumerate() {
+            let key = (
+                entry.iso2.to_ascii_lowercase(),
+                entry.state.to_ascii_lowercase(),
+                entry.city.to_ascii_lowercase(),
+            );
+            index.canonical_index.insert(key, i);
+
+            // the canonical name counts as a label too
+            let names = std::iter::once(&entry.city).chain(entry.labels.values());
+            for name in names {
+                let slot = index
+                    .label_index
+                    .entry(name.to_ascii_lowercase())
+                    .or_default();
+                if !slot.contains(&i) {
+                    slot.push(i);
+                }
+            }
+        }
+
+        index
+    }
+
+    /// Load labels from a JSON file.
+    ///
+    /// Expected format:
+    /// {
+    ///   "cities": [
+    ///     { "iso2": "AT", "state": "Vienna", "city": "Vienna",
+    ///       "wikidata": "Q1741",
+    ///       "labels": { "de": "Wien", "fr": "Vienne", "it": "Vienna" }
+    ///     },
+    ///     ...
+    ///   ]
+    /// }
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        let file: CityLabelsFile = serde_json::from_slice(&bytes)?;
+        Ok(Self::from_entries(file.cities))
+    }
+
+    /// Load `city_labels.json` from the crate's default `data/` directory.
+    #[cfg(feature = "json")]
+    pub fn load_default() -> Result<Self> {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let path: std::path::PathBuf = [manifest_dir, "data", "city_labels.json"].iter().collect();
+        Self::load_from_path(path)
+    }
+
+    /// Number of cities with labels.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` if the index holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries having `label` (case-insensitive) in any language.
+    pub fn find_by_label(&self, label: &str) -> Vec<&CityLabels> {
+        self.label_index
+            .get(&label.trim().to_ascii_lowercase())
+            .map(|ids| ids.iter().map(|&i| &self.entries[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Lookup by canonical triple (iso2, state, city).
+    pub fn find_canonical(&self, iso2: &str, state: &str, city: &str) -> Option<&CityLabels> {
+        let key = (
+            iso2.to_ascii_lowercase(),
+            state.to_ascii_lowercase(),
+            city.to_ascii_lowercase(),
+        );
+        let idx = self.canonical_index.get(&key)?;
+        Some(&self.entries[*idx])
+    }
+
+    /// Label of a canonical city in `lang`, if present.
+    pub fn label(&self, iso2: &str, state: &str, city: &str, lang: &str) -> Option<&str> {
+        self.find_canonical(iso2, state, city)?
+            .labels
+            .get(lang)
+            .map(String::as_str)
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Resolve a label entry to the city it describes.
+    pub fn resolve_city_labels(
+        &self,
+        entry: &CityLabels,
+    ) -> Option<(&City<B>, &State<B>, &Country<B>)> {
+        let country = self.find_country_by_iso2(&entry.iso2)?;
+        let state = country
+            .states()
+            .iter()
+            .find(|s| s.name().eq_ignore_ascii_case(&entry.state))?;
+        let city = state
+            .cities()
+            .iter()
+            .find(|c| c.name().eq_ignore_ascii_case(&entry.city))?;
+        Some((city, state, country))
+    }
+
+    /// Cities whose name or any language label equals `label` (case-insensitive).
+    pub fn find_cities_by_label<'a>(
+        &'a self,
+        label: &str,
+        index: &CityLabelIndex,
+    ) -> Vec<(&'a City<B>, &'a State<B>, &'a Country<B>)> {
+        index
+            .find_by_label(label)
+            .into_iter()
+            .filter_map(|entry| self.resolve_city_labels(entry))
+            .collect()
+    }
+}
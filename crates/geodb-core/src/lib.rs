@@ -48,28 +48,210 @@
 //! - `crates/geodb-core/data/countries+states+cities.json.gz`
 //!
 //! At runtime, `GeoDb::<DefaultBackend>::load()` reads that file and builds a
-//! binary cache alongside it. If you replace or update the dataset, ensure the
+//! binary cache alongside it. Nothing is cached in memory behind the caller's
+//! back; use a [`GeoDbCache`] to share loaded databases. If you replace or update the dataset, ensure the
 //! JSON structure matches the upstream file format. You can retrieve the
 //! canonical URL we rely on via `GeoDb::<DefaultBackend>::get_3rd_party_data_url()`.
 //! Please keep the upstream CC‑BY‑4.0 attribution when distributing data.
+//!
+//! Sharing one database between processes
+//! ---------------------------------------
+//!
+//! A loaded [`GeoDb`] is an ordinary heap structure (`String`s and `Vec`s)
+//! that every process decodes into its own memory. Three ways to avoid one
+//! full copy per worker:
+//!
+//! - Load in the parent before forking (e.g. `gunicorn --preload` with the
+//!   Python bindings). The pages are shared copy-on-write, but only until a
+//!   process writes to them: allocator bookkeeping, reference counts (the
+//!   attached changelog is an `Arc`; Python objects wrapping the database
+//!   are refcounted) and applied sidecars gradually un-share them.
+//! - Map a binary artifact with [`GeoDb::load_mmap`] (feature `mmap`): the
+//!   file stays in the page cache, shared by all processes, and each
+//!   process decodes only the countries it asks for.
+//! - Put the zero-copy layout in shared memory with the `shared` module
+//!   (feature `shared`, Unix): the parent writes it once, workers map it
+//!   read-only through a small handle and read names, codes, coordinates
+//!   and timezones in place, without any per-process copy.
+//!
+//! Cargo features
+//! --------------
+//!
+//! - `json` (default): build the database from the upstream `.json.gz`
+//!   dataset. Pulls in `serde_json` and `flate2`. Without it, only the binary
+//!   read path ([`GeoDb::from_bytes`] / [`GeoDb::load_binary`]) is available,
+//!   which is what the WASM bindings use.
+//! - `districts`: optional third administrative level below states
+//!   (`State::districts`, `GeoDb::cities_for_district`). Read from a
+//!   `districts` array on each state in the source JSON.
+//! - `fetch`: HTTP download helper for refreshing the dataset (`fetch` module).
+//! - `embed-data`: compile the prebuilt database into the binary and load
+//!   it with `GeoDb::load_embedded()`, no data directory needed.
+//! - `signed`: SHA-256 checked and ed25519 signed database artifacts
+//!   (`signed` module). Together with `fetch`, downloads can be verified
+//!   before they replace the previous file.
+//! - `msgpack`: MessagePack encoding of views and result sets (`msgpack` module).
+//! - `multilingual-cities`: city labels in other languages from a sidecar
+//!   file (`labels` module), searchable via `SearchOptions::with_city_labels`.
+//! - `geonames`: import city aliases in bulk from GeoNames dumps
+//!   (`geonames` module), as an alternative to curating `city_meta.json`.
+//! - `geo-interop`: `geo-types` points for countries, states and cities and
+//!   an `rstar` R-tree of cities (`geo_interop` module).
+//! - `bundle`: versioned release directories with the binary artifact,
+//!   checksums, a provenance manifest and a JSON index (`bundle` module).
+//! - `mmap`: memory-mapped binary artifacts whose countries are decoded on
+//!   first access (`mmap` module), for servers that only touch a few.
+//! - `zerocopy`: a serde-free layout read in place (`zerocopy` module),
+//!   used by the WASM bindings to attach cities without bincode.
+//! - `shared`: the zero-copy layout in a shared-memory file, mapped
+//!   read-only by pre-fork workers through a handle (`shared` module, Unix
+//!   only; implies `mmap` and `zerocopy`).
 pub mod alias;
 pub mod api;
+pub mod area;
+pub mod arena;
+#[cfg(feature = "bundle")]
+pub mod bundle;
 pub mod cache;
+pub mod capabilities;
+pub mod changelog;
+#[cfg(feature = "json")]
+pub mod codegen;
+pub mod compat;
+pub mod consts;
+pub mod containment;
+pub mod country_id;
+pub mod coverage;
+#[cfg(feature = "json")]
+pub mod csv;
+pub mod currency;
+pub mod distinct;
+#[cfg(feature = "districts")]
+pub mod district;
+pub mod dyn_search;
 pub mod error;
+pub mod exact;
+#[cfg(feature = "json")]
+pub mod export;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 pub mod filter;
+#[cfg(feature = "json")]
+pub mod fixture;
+#[cfg(feature = "geo-interop")]
+pub mod geo_interop;
+#[cfg(feature = "geonames")]
+pub mod geonames;
+#[cfg(any(feature = "bundle", feature = "fetch", feature = "signed"))]
+mod hex;
+pub mod indexed;
+pub mod inspect;
+#[cfg(feature = "multilingual-cities")]
+pub mod labels;
+pub mod limits;
 pub mod loader;
+pub mod locale;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 pub mod model;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+pub mod nearest;
+pub mod order;
+pub mod overrides;
+#[cfg(feature = "json")]
+pub mod parse;
 pub mod phone;
 pub mod prelude;
+pub mod provenance;
+pub mod query_cache;
 pub mod region;
+pub mod search;
+pub mod search_index;
+#[cfg(all(feature = "shared", unix))]
+pub mod shared;
+#[cfg(feature = "signed")]
+pub mod signed;
+pub mod spatial;
+pub mod stable;
+pub mod staged;
+#[cfg(feature = "json")]
+pub mod stats;
+pub mod timezone_audit;
+pub mod translations;
+pub mod validate;
+pub mod version;
+#[cfg(feature = "zerocopy")]
+pub mod zerocopy;
 
 // Re-exports for convenience
-pub use crate::alias::{CityMeta, CityMetaIndex};
-pub use crate::api::{CityView, CountryView, StateView};
+pub use crate::alias::{AliasConflict, AliasConflictKind, CityMeta, CityMetaIndex};
+#[cfg(feature = "districts")]
+pub use crate::api::DistrictView;
+pub use crate::api::{
+    CityView, CityViewWith, CountryView, CountryViewWith, ItemView, StateView, StateViewWith,
+    ViewOptions, VIEW_VERSION,
+};
+pub use crate::area::CountrySort;
+pub use crate::arena::{ArenaBackend, ArenaStr};
+pub use crate::cache::GeoDbCache;
+pub use crate::capabilities::Capabilities;
+pub use crate::changelog::{ChangeEntity, ChangeKind, ChangeSet, Changelog, EntityChange};
+pub use crate::compat::{render_hits, render_hits_with, ResponseFormat};
+pub use crate::containment::{AdminLevel, AdminPath, PlaceQuery};
+pub use crate::country_id::{CityId, CountryId, StateId};
+pub use crate::coverage::{
+    CountryBenchmark, CountryCoverage, CoverageBenchmark, CoverageIssue, CoverageIssueKind,
+    CoverageReport,
+};
+pub use crate::currency::Currency;
+pub use crate::distinct::ValueCount;
+#[cfg(feature = "districts")]
+pub use crate::dyn_search::DistrictDto;
+pub use crate::dyn_search::{CityDto, CountryDto, DynGeoSearch, EntityDto, HitDto, StateDto};
 pub use crate::error::{GeoDbError, GeoError, Result};
+pub use crate::exact::ExactLookup;
+#[cfg(feature = "json")]
+pub use crate::export::ExportLevel;
+pub use crate::filter::sample_indices;
+#[cfg(feature = "multilingual-cities")]
+pub use crate::labels::{CityLabelIndex, CityLabels};
+pub use crate::limits::LoadLimits;
+#[cfg(feature = "json")]
+pub use crate::loader::{CacheStatus, LoadSource};
+pub use crate::loader::{LoadMetrics, LoadOptions};
+pub use crate::locale::{CountryLocale, CountryLocaleTable, MeasurementSystem, Weekday};
+#[cfg(feature = "mmap")]
+pub use crate::mmap::MmapGeoDb;
+#[cfg(feature = "districts")]
+pub use crate::model::District;
 pub use crate::model::{
-    build_geodb, City, Country, CountryTimezone, DefaultBackend, DefaultGeoDb, GeoBackend, GeoDb,
-    SmartHit, SmartItem, StandardBackend, State,
+    build_geodb, try_build_geodb, City, Country, CountryTimezone, DbStats, DefaultBackend,
+    DefaultGeoDb, GeoBackend, GeoDb, SmartHit, SmartItem, StandardBackend, State, TERRITORY_ISO2,
+};
+pub use crate::nearest::{CityIndex, NearestCity};
+pub use crate::order::CountryOrder;
+pub use crate::overrides::{CityOverride, OverrideAction, Overrides, SkippedOverride};
+#[cfg(feature = "json")]
+pub use crate::parse::InvalidEntity;
+pub use crate::phone::{normalize_phone_code, DialInfo, PhoneCodeSearch};
+pub use crate::provenance::{FieldSource, MetaField};
+pub use crate::query_cache::{CachedGeoDb, QueryCacheStats};
+pub use crate::search::{
+    edit_distance, equals_folded, fold_key, CityFilter, ExpansionTable, LevelScores, NameMatch,
+    ScoringProfile, SearchLanguage, SearchOptions, SearchSessionHints, TieredHits,
+};
+pub use crate::search_index::SearchIndex;
+pub use crate::spatial::{
+    cluster_cities, haversine_km, vincenty_km, CityCluster, CityRef, ClusterMethod, ClusterOptions,
+    DistanceOptions, DistanceUnit, EarthModel, NearHit,
+};
+#[cfg(feature = "json")]
+pub use crate::stats::{DatasetSource, DatasetStats};
+pub use crate::timezone_audit::{TimezoneIssue, TimezoneIssueKind};
+pub use crate::translations::{
+    RegionTranslationTable, RegionTranslations, StateTranslationTable, StateTranslations,
 };
-pub use crate::phone::PhoneCodeSearch;
+pub use crate::validate::LoadWarning;
+pub use crate::version::VersionInfo;
 // pub use crate::region::*;
@@ -0,0 +1,299 @@
+// src/limits.rs
+//! Entity and string limits for decoding untrusted binary databases.
+//!
+//! [`LoadOptions::max_bytes`](crate::LoadOptions::max_bytes) bounds the input,
+//! but a few input bytes can still claim a large structure: an empty city
+//! takes about a dozen bytes in the binary format and over a hundred in
+//! memory. [`LoadLimits`] caps the number of countries, states and cities
+//! and the length of every string, and is enforced while decoding: each
+//! collection's length is checked before its elements are read, so a hostile
+//! artifact (a remote WASM load, a user-provided path) fails early instead
+//! of exhausting memory.
+//!
+//! The limits are carried in a thread-local for the duration of
+//! [`GeoDb::from_bytes_with`](crate::GeoDb::from_bytes_with); other
+//! deserializers of the model types are not affected.
+use crate::model::{City, Country, GeoBackend, State};
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Upper bounds for a decoded database; see the [module docs](self).
+///
+/// The defaults leave room for several times the bundled dataset (~250
+/// countries, ~5,000 states, ~150,000 cities).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadLimits {
+    /// Countries in the database.
+    pub max_countries: usize,
+    /// States across all countries.
+    pub max_states: usize,
+    /// Cities across all states.
+    pub max_cities: usize,
+    /// Bytes in any single string (names, codes, translations, ...).
+    pub max_string_len: usize,
+}
+
+impl Default for LoadLimits {
+    fn default() -> Self {
+        Self {
+            max_countries: 1_000,
+            max_states: 100_000,
+            max_cities: 2_000_000,
+            max_string_len: 4_096,
+        }
+    }
+}
+
+impl LoadLimits {
+    /// No limits beyond [`LoadOptions::max_bytes`](crate::LoadOptions::max_bytes).
+    pub fn unlimited() -> Self {
+        Self {
+            max_countries: usize::MAX,
+            max_states: usize::MAX,
+            max_cities: usize::MAX,
+            max_string_len: usize::MAX,
+        }
+    }
+
+    /// Limits with a custom city budget.
+    pub fn with_max_cities(mut self, max: usize) -> Self {
+        self.max_cities = max;
+        self
+    }
+
+    /// Limits with a custom maximum string length.
+    pub fn with_max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = max;
+        self
+    }
+}
+
+/// Remaining budget of the decode in progress on this thread.
+#[derive(Clone, Copy)]
+struct Budget {
+    countries: usize,
+    states: usize,
+    cities: usize,
+    max_string_len: usize,
+}
+
+thread_local! {
+    static BUDGET: Cell<Option<Budget>> = const { Cell::new(None) };
+}
+
+/// Run `decode` with `limits` enforced on the model collections it reads.
+pub(crate) fn with_limits<T>(limits: &LoadLimits, decode: impl FnOnce() -> T) -> T {
+    struct Reset(Option<Budget>);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            BUDGET.with(|b| b.set(self.0));
+        }
+    }
+
+    let budget = Budget {
+        countries: limits.max_countries,
+        states: limits.max_states,
+        cities: limits.max_cities,
+        max_string_len: limits.max_string_len,
+    };
+    let _reset = Reset(BUDGET.with(|b| b.replace(Some(budget))));
+    decode()
+}
+
+/// A limited entity type.
+trait Limited {
+    const NAME: &'static str;
+    fn budget(budget: &mut Budget) -> &mut usize;
+    fn longest_string(&self) -> usize;
+}
+
+fn longest<'a>(strings: impl IntoIterator<Item = &'a str>) -> usize {
+    strings.into_iter().map(str::len).max().unwrap_or(0)
+}
+
+impl<B: GeoBackend> Limited for Country<B> {
+    const NAME: &'static str = "countries";
+
+    fn budget(budget: &mut Budget) -> &mut usize {
+        &mut budget.countries
+    }
+
+    fn longest_string(&self) -> usize {
+        let fields = [
+            Some(&self.name),
+            Some(&self.iso2),
+            self.iso3.as_ref(),
+            self.numeric_code.as_ref(),
+            self.phonecode.as_ref(),
+            self.capital.as_ref(),
+            self.currency.as_ref(),
+            self.currency_name.as_ref(),
+            self.currency_symbol.as_ref(),
+            self.tld.as_ref(),
+            self.native_name.as_ref(),
+            self.region.as_ref(),
+            self.subregion.as_ref(),
+            self.nationality.as_ref(),
+            self.emoji.as_ref(),
+            self.emoji_u.as_ref(),
+            self.primary_timezone.as_ref(),
+        ];
+        let timezones = self.timezones.iter().flat_map(|tz| {
+            [
+                tz.zone_name.as_ref(),
+                tz.gmt_offset_name.as_ref(),
+                tz.abbreviation.as_ref(),
+                tz.tz_name.as_ref(),
+            ]
+        });
+        let translations = self
+            .translations
+            .iter()
+            .flat_map(|(lang, name)| [lang.as_str(), name.as_ref()]);
+        longest(
+            fields
+                .into_iter()
+                .chain(timezones)
+                .flatten()
+                .map(|s| s.as_ref())
+                .chain(translations),
+        )
+    }
+}
+
+impl<B: GeoBackend> Limited for State<B> {
+    const NAME: &'static str = "states";
+
+    fn budget(budget: &mut Budget) -> &mut usize {
+        &mut budget.states
+    }
+
+    fn longest_string(&self) -> usize {
+        #[allow(unused_mut)]
+        let mut fields = vec![
+            Some(&self.name),
+            self.native_name.as_ref(),
+            self.state_code.as_ref(),
+            self.full_code.as_ref(),
+        ];
+        #[cfg(feature = "districts")]
+        for district in &self.districts {
+            fields.extend([Some(&district.name), district.code.as_ref()]);
+        }
+        longest(fields.into_iter().flatten().map(|s| s.as_ref()))
+    }
+}
+
+impl<B: GeoBackend> Limited for City<B> {
+    const NAME: &'static str = "cities";
+
+    fn budget(budget: &mut Budget) -> &mut usize {
+        &mut budget.cities
+    }
+
+    fn longest_string(&self) -> usize {
+        longest(
+            [Some(&self.name), self.timezone.as_ref()]
+                .into_iter()
+                .flatten()
+                .map(|s| s.as_ref()),
+        )
+    }
+}
+
+/// Take `n` entities of type `T` from the budget, if one is active.
+fn charge<T: Limited, E: Error>(n: usize) -> Result<(), E> {
+    BUDGET.with(|cell| {
+        let Some(mut budget) = cell.get() else {
+            return Ok(());
+        };
+        let left = T::budget(&mut budget);
+        if n > *left {
+            return Err(E::custom(format_args!(
+                "binary database exceeds the limit for {}",
+                T::NAME
+            )));
+        }
+        *left -= n;
+        cell.set(Some(budget));
+        Ok(())
+    })
+}
+
+fn check_strings<T: Limited, E: Error>(item: &T) -> Result<(), E> {
+    let max = BUDGET.with(|cell| cell.get().map(|b| b.max_string_len));
+    match max {
+        Some(max) if item.longest_string() > max => Err(E::custom(format_args!(
+            "binary database has a string longer than {max} bytes in {}",
+            T::NAME
+        ))),
+        _ => Ok(()),
+    }
+}
+
+struct LimitedSeq<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for LimitedSeq<T>
+where
+    T: Limited + Deserialize<'de>,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of {}", T::NAME)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // bincode announces the length up front: reject before reading
+        let announced = seq.size_hint();
+        if let Some(n) = announced {
+            charge::<T, A::Error>(n)?;
+        }
+        let mut out = Vec::with_capacity(announced.unwrap_or(0).min(4096));
+        while let Some(item) = seq.next_element::<T>()? {
+            if announced.is_none() {
+                charge::<T, A::Error>(1)?;
+            }
+            check_strings::<T, A::Error>(&item)?;
+            out.push(item);
+        }
+        Ok(out)
+    }
+}
+
+fn limited_seq<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Limited + Deserialize<'de>,
+{
+    deserializer.deserialize_seq(LimitedSeq(PhantomData))
+}
+
+pub(crate) fn countries<'de, D, B>(deserializer: D) -> Result<Vec<Country<B>>, D::Error>
+where
+    D: Deserializer<'de>,
+    B: GeoBackend,
+    Country<B>: Deserialize<'de>,
+{
+    limited_seq(deserializer)
+}
+
+pub(crate) fn states<'de, D, B>(deserializer: D) -> Result<Vec<State<B>>, D::Error>
+where
+    D: Deserializer<'de>,
+    B: GeoBackend,
+    State<B>: Deserialize<'de>,
+{
+    limited_seq(deserializer)
+}
+
+pub(crate) fn cities<'de, D, B>(deserializer: D) -> Result<Vec<City<B>>, D::Error>
+where
+    D: Deserializer<'de>,
+    B: GeoBackend,
+    City<B>: Deserialize<'de>,
+{
+    limited_seq(deserializer)
+}
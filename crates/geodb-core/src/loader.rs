@@ -1,13 +1,212 @@
+use crate::arena::{with_arena, DEFAULT_ARENA_CHUNK_SIZE};
 use crate::error::{GeoError, Result};
-use crate::model::{build_geodb, CountriesRaw, DefaultBackend, GeoDb};
-use flate2::read::GzDecoder;
-use once_cell::sync::OnceCell;
-use std::fs::File;
-use std::io::BufReader;
+use crate::limits::{with_limits, LoadLimits};
+use crate::model::{DefaultBackend, GeoBackend, GeoDb};
+use crate::order::CountryOrder;
+use bincode::Options;
+use serde::{de::DeserializeOwned, Serialize};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+#[cfg(feature = "json")]
+use {
+    crate::model::try_build_geodb,
+    crate::parse::{parse_countries, InvalidEntity},
+    flate2::read::GzDecoder,
+    std::fs::File,
+    std::io::{BufReader, Read},
+    std::time::SystemTime,
+};
 
-// In-process cache for default unfiltered load()
-static GEO_DB_CACHE: OnceCell<GeoDb<DefaultBackend>> = OnceCell::new();
+/// Prebuilt full database compiled into the binary (feature `embed-data`).
+///
+/// The bundled `ALL.bin` uses the default layout, so this is not available
+/// together with `districts`.
+#[cfg(all(feature = "embed-data", not(feature = "districts")))]
+static EMBEDDED_DB: &[u8] = include_bytes!("../data/countries+states+cities.json.gz.ALL.bin");
+
+/// Default upper bound for a serialized database, see [`LoadOptions::max_bytes`].
+pub const DEFAULT_MAX_BINARY_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Options for the binary read path ([`GeoDb::from_bytes_with`]).
+#[derive(Debug, Clone, Copy)]
+pub struct LoadOptions {
+    /// Maximum number of bytes bincode may decode. Protects against
+    /// corrupted or hostile artifacts claiming huge allocations.
+    pub max_bytes: u64,
+    /// Run [`GeoDb::validate`] after loading and keep the result for
+    /// [`GeoDb::load_warnings`]. Off by default.
+    pub validate: bool,
+    /// Entity and string limits enforced while decoding.
+    pub limits: LoadLimits,
+    /// Order of the countries after loading; the artifact's own order by
+    /// default.
+    pub country_order: CountryOrder,
+    /// Size of the chunks [`ArenaBackend`](crate::ArenaBackend) strings are
+    /// bump-allocated from (see [`crate::arena`]); other backends ignore it.
+    pub arena_chunk_size: usize,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_BINARY_SIZE,
+            validate: false,
+            limits: LoadLimits::default(),
+            country_order: CountryOrder::Dataset,
+            arena_chunk_size: DEFAULT_ARENA_CHUNK_SIZE,
+        }
+    }
+}
+
+impl LoadOptions {
+    /// Options with a custom size limit.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Options with custom entity and string limits.
+    pub fn with_limits(mut self, limits: LoadLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Options with validation switched on or off.
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Options sorting the countries by `order` after loading.
+    pub fn with_country_order(mut self, order: CountryOrder) -> Self {
+        self.country_order = order;
+        self
+    }
+
+    /// Options with a custom arena chunk size.
+    pub fn with_arena_chunk_size(mut self, bytes: usize) -> Self {
+        self.arena_chunk_size = bytes;
+        self
+    }
+}
+
+/// Metrics collected while loading a binary database.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadMetrics {
+    /// Size of the binary input.
+    pub bytes_read: u64,
+    /// Entities materialized in memory.
+    pub countries: usize,
+    pub states: usize,
+    pub cities: usize,
+    /// Time spent reading the file (zero for in-memory input).
+    pub read_time: Duration,
+    /// Time spent deserializing.
+    pub deserialize_time: Duration,
+    /// Bytes of the arena chunks allocated for the strings (only with
+    /// [`ArenaBackend`](crate::ArenaBackend), zero otherwise).
+    pub arena_bytes: usize,
+}
+
+impl LoadMetrics {
+    /// Total load time across all stages.
+    pub fn total_time(&self) -> Duration {
+        self.read_time + self.deserialize_time
+    }
+}
+
+pub(crate) fn bincode_options(max_bytes: u64) -> impl Options {
+    // Same encoding as `bincode::serialize`/`deserialize`, plus a size limit.
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(max_bytes)
+}
+
+impl<B> GeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    /// Deserialize a database from its binary (bincode) representation.
+    ///
+    /// This is the pure read path: it needs neither `serde_json` nor `flate2`
+    /// and is available without the `json` feature. Use it with the prebuilt
+    /// `*.bin` cache files or bytes embedded via `include_bytes!`; indexed
+    /// artifacts ([`Self::to_indexed_bytes`]) are read the same way.
+    ///
+    /// Uses [`LoadOptions::default`]; see [`Self::from_bytes_with`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with(bytes, &LoadOptions::default()).map(|(db, _)| db)
+    }
+
+    /// Deserialize with explicit [`LoadOptions`], returning load metrics.
+    pub fn from_bytes_with(bytes: &[u8], options: &LoadOptions) -> Result<(Self, LoadMetrics)> {
+        // bincode only counts allocations against the limit, so also reject
+        // oversized input up front.
+        if bytes.len() as u64 > options.max_bytes {
+            return Err(GeoError::InvalidData(format!(
+                "binary database is {} bytes, limit is {}",
+                bytes.len(),
+                options.max_bytes
+            )));
+        }
+        let start = Instant::now();
+        // the table of an indexed artifact is only needed for random access
+        let payload = match crate::indexed::split_index(bytes)? {
+            Some((_, payload)) => payload,
+            None => bytes,
+        };
+        let (db, arena_bytes) = with_arena(options.arena_chunk_size, || {
+            with_limits(&options.limits, || {
+                bincode_options(options.max_bytes).deserialize::<Self>(payload)
+            })
+        });
+        let mut db = db?;
+        db.sort_countries(options.country_order);
+        if options.validate {
+            db.load_warnings = db.validate();
+        }
+        let stats = db.stats();
+        let metrics = LoadMetrics {
+            bytes_read: bytes.len() as u64,
+            countries: stats.countries,
+            states: stats.states,
+            cities: stats.cities,
+            read_time: Duration::ZERO,
+            deserialize_time: start.elapsed(),
+            arena_bytes,
+        };
+        Ok((db, metrics))
+    }
+
+    /// Serialize the database into the binary format read by [`Self::from_bytes`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    /// Load a database from a binary cache file (see [`Self::from_bytes`]).
+    pub fn load_binary(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load_binary_with(path, &LoadOptions::default()).map(|(db, _)| db)
+    }
+
+    /// Load a binary cache file with explicit [`LoadOptions`], returning load metrics.
+    pub fn load_binary_with(
+        path: impl AsRef<Path>,
+        options: &LoadOptions,
+    ) -> Result<(Self, LoadMetrics)> {
+        let start = Instant::now();
+        let bytes = std::fs::read(path)?;
+        let read_time = start.elapsed();
+        let (db, metrics) = Self::from_bytes_with(&bytes, options)?;
+        Ok((
+            db,
+            LoadMetrics {
+                read_time,
+                ..metrics
+            },
+        ))
+    }
+}
 /// Upstream dataset URL used by this crate.
 ///
 /// This crate relies on the Countries+States+Cities dataset maintained at:
@@ -20,7 +219,7 @@ static GEO_DB_CACHE: OnceCell<GeoDb<DefaultBackend>> = OnceCell::new();
 ///
 /// Licensing: the upstream dataset is licensed under CC-BY-4.0. Please ensure
 /// proper attribution when using the data.
-const DATA_REPO_URL: &str = "https://github.com/dr5hn/countries-states-cities-database/blob/master/json/countries%2Bstates%2Bcities.json.gz";
+pub(crate) const DATA_REPO_URL: &str = "https://github.com/dr5hn/countries-states-cities-database/blob/master/json/countries%2Bstates%2Bcities.json.gz";
 
 impl GeoDb<DefaultBackend> {
     /// Default directory where the bundled dataset is stored: `<crate>/data`.
@@ -40,21 +239,19 @@ impl GeoDb<DefaultBackend> {
         "countries+states+cities.json.gz"
     }
 
+    #[cfg(feature = "json")]
     /// Load the default database (unfiltered) from the bundled dataset.
     ///
-    /// Uses an in-process cache to avoid re-parsing on subsequent calls within
-    /// the same process. Also creates/uses an on-disk binary cache file next to
-    /// the JSON dataset for faster future startups.
+    /// Creates/uses an on-disk binary cache file next to the JSON dataset for
+    /// faster startups. Each call returns a fresh database; to share one
+    /// instance across call sites, keep it in a [`crate::GeoDbCache`].
     pub fn load() -> Result<Self> {
-        GEO_DB_CACHE
-            .get_or_try_init(|| {
-                let dir = Self::default_data_dir();
-                let file = Self::default_dataset_filename();
-                Self::load_from_path(dir.join(file), None)
-            })
-            .cloned()
+        let dir = Self::default_data_dir();
+        let file = Self::default_dataset_filename();
+        Self::load_from_path(dir.join(file), None)
     }
 
+    #[cfg(feature = "json")]
     /// Load from a custom on-disk dataset path.
     ///
     /// Parameters:
@@ -73,6 +270,37 @@ impl GeoDb<DefaultBackend> {
         load_generic(json_path, iso2_filter)
     }
 
+    #[cfg(feature = "json")]
+    /// Like [`Self::load_from_path`], but leaves malformed countries,
+    /// states and cities out instead of failing, and returns them.
+    ///
+    /// The binary cache is neither read nor written, so the report is
+    /// always complete and a partial database never ends up in the cache.
+    pub fn load_from_path_skipping_invalid(
+        json_path: impl AsRef<Path>,
+        iso2_filter: Option<&[&str]>,
+    ) -> Result<(Self, Vec<InvalidEntity>)> {
+        let json = read_source(json_path.as_ref())?;
+        let parsed = parse_countries(&json, iso2_filter, true)?;
+        Ok((try_build_geodb(parsed.countries)?, parsed.skipped))
+    }
+
+    /// Load the database compiled into the binary (feature `embed-data`).
+    ///
+    /// Needs no data directory at runtime, so a server or CLI can ship as a
+    /// single static binary. Deserialized on every call, like [`Self::load`];
+    /// keep the result (or a [`crate::GeoDbCache`]) to avoid repeated work.
+    #[cfg(all(feature = "embed-data", not(feature = "districts")))]
+    pub fn load_embedded() -> Result<Self> {
+        Self::from_bytes(EMBEDDED_DB)
+    }
+
+    /// Raw bytes of the embedded database (feature `embed-data`).
+    #[cfg(all(feature = "embed-data", not(feature = "districts")))]
+    pub fn embedded_bytes() -> &'static [u8] {
+        EMBEDDED_DB
+    }
+
     /// Returns the canonical upstream URL to the dataset this crate relies on.
     ///
     /// Developers embedding or packaging this crate should acknowledge the
@@ -83,6 +311,7 @@ impl GeoDb<DefaultBackend> {
         DATA_REPO_URL
     }
 
+    #[cfg(feature = "json")]
     /// Load a filtered database using the bundled dataset.
     ///
     /// Only countries whose ISO2 code is contained in `iso2` are loaded. An
@@ -95,66 +324,203 @@ impl GeoDb<DefaultBackend> {
     }
 }
 
-/// Core logic: dataset + filter → DB
-fn load_generic(json_path: PathBuf, iso2_filter: Option<&[&str]>) -> Result<GeoDb<DefaultBackend>> {
-    //
-    // Derive cache filename from dataset file name
-    //
-    let dataset_filename = json_path.file_name().unwrap().to_string_lossy().to_string(); // e.g. "countries+states+cities.json.gz"
+/// Which path [`GeoDb::load_from_path`] takes for a dataset.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadSource {
+    /// The binary cache next to the dataset is read.
+    BinaryCache,
+    /// The `.json.gz` dataset is parsed (and a cache written).
+    Source,
+}
+
+/// Resolved dataset and cache locations, for diagnostics.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct CacheStatus {
+    pub dataset_path: PathBuf,
+    /// Dataset size in bytes, `None` if the file is missing.
+    pub dataset_size: Option<u64>,
+    pub dataset_modified: Option<SystemTime>,
+    /// FNV-1a 64 fingerprint of the dataset file (hex), `None` if missing.
+    pub dataset_fingerprint: Option<String>,
+    pub cache_path: PathBuf,
+    pub cache_exists: bool,
+    /// The cache is at least as new as the dataset.
+    pub cache_fresh: bool,
+    /// The path a load would take right now.
+    pub source: LoadSource,
+}
+
+/// Binary cache file used for `json_path` and the given filter.
+///
+/// The name is derived from the dataset file name and the filter, e.g.
+/// `countries+states+cities.json.gz.DE_FR.bin`, and lives next to the dataset.
+#[cfg(feature = "json")]
+pub fn cache_path_for(json_path: &Path, iso2_filter: Option<&[&str]>) -> PathBuf {
+    let dataset_filename = json_path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default(); // e.g. "countries+states+cities.json.gz"
 
     let suffix = match iso2_filter {
         None => "ALL".to_string(),
         Some([]) => "ALL".to_string(),
         Some(list) => list.join("_"),
     };
+    // District-enabled databases have a different binary layout.
+    #[cfg(feature = "districts")]
+    let suffix = format!("{suffix}.districts");
 
     let cache_filename = format!("{dataset_filename}.{suffix}.bin");
 
     // cache file is next to the JSON file
-    let bin_path = json_path.parent().unwrap().join(cache_filename);
+    json_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(cache_filename)
+}
+
+/// Remove the binary caches of `json_path` for filtered loads (e.g.
+/// `….DE_FR.bin`), e.g. after the dataset was replaced: a cache is used
+/// regardless of its age, and loads write a missing one again. The
+/// unfiltered `….ALL.bin` caches are kept, since `embed-data` and the WASM
+/// build compile them in; rewrite them instead. Returns how many files
+/// were removed.
+#[cfg(feature = "json")]
+pub fn remove_cache_files(json_path: &Path) -> Result<usize> {
+    let prefix = match json_path.file_name() {
+        Some(name) => format!("{}.", name.to_string_lossy()),
+        None => return Ok(0),
+    };
+    let unfiltered = format!("{prefix}ALL.");
+    let dir = match json_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(&prefix) && name.ends_with(".bin") && !name.starts_with(&unfiltered) {
+            std::fs::remove_file(entry.path())?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(feature = "json")]
+impl GeoDb<DefaultBackend> {
+    /// Describe the dataset/cache files a load of `json_path` would use.
+    ///
+    /// Mirrors the logic of [`Self::load_from_path`]: the binary cache is
+    /// used whenever it exists and decodes, regardless of age. To tell
+    /// whether it decodes, an existing cache is read and deserialized once
+    /// (and the dataset read for its fingerprint), so this costs about as
+    /// much as a load from the cache; don't call it on a hot path.
+    pub fn cache_status(json_path: impl AsRef<Path>, iso2_filter: Option<&[&str]>) -> CacheStatus {
+        let dataset_path = json_path.as_ref().to_path_buf();
+        let cache_path = cache_path_for(&dataset_path, iso2_filter);
+
+        let dataset_meta = std::fs::metadata(&dataset_path).ok();
+        let dataset_modified = dataset_meta.as_ref().and_then(|m| m.modified().ok());
+        let cache_modified = std::fs::metadata(&cache_path)
+            .ok()
+            .and_then(|m| m.modified().ok());
+
+        let cache_exists = cache_path.is_file();
+        let cache_fresh = match (cache_modified, dataset_modified) {
+            (Some(cache), Some(dataset)) => cache >= dataset,
+            // Without a dataset the cache is all there is.
+            (Some(_), None) => true,
+            _ => false,
+        };
+        let source = if cache_exists && Self::load_binary(&cache_path).is_ok() {
+            LoadSource::BinaryCache
+        } else {
+            LoadSource::Source
+        };
+
+        CacheStatus {
+            dataset_size: dataset_meta.map(|m| m.len()),
+            dataset_modified,
+            dataset_fingerprint: file_fingerprint(&dataset_path).ok(),
+            dataset_path,
+            cache_path,
+            cache_exists,
+            cache_fresh,
+            source,
+        }
+    }
+}
+
+/// FNV-1a 64 over the file contents, as hex. Cheap and stable across builds;
+/// not a cryptographic checksum.
+#[cfg(feature = "json")]
+pub(crate) fn file_fingerprint(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path)?;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    Ok(format!("{hash:016x}"))
+}
+
+/// Core logic: dataset + filter → DB
+#[cfg(feature = "json")]
+fn load_generic(json_path: PathBuf, iso2_filter: Option<&[&str]>) -> Result<GeoDb<DefaultBackend>> {
+    let bin_path = cache_path_for(&json_path, iso2_filter);
 
     //
     // 1) Try binary cache
     //
-    if let Ok(bytes) = std::fs::read(&bin_path) {
-        if let Ok(db) = bincode::deserialize::<GeoDb<DefaultBackend>>(&bytes) {
-            return Ok(db);
-        }
+    if let Ok(db) = GeoDb::<DefaultBackend>::load_binary(&bin_path) {
+        return Ok(db);
     }
 
     //
-    // 2) Load JSON .gz
+    // 2) Load JSON .gz, keeping the filtered countries
     //
-    let raw = load_raw_countries(&json_path)?;
+    let json = read_source(&json_path)?;
+    let filtered = parse_countries(&json, iso2_filter, false)?.countries;
 
-    // 3) Apply filter
-    let mut filtered = raw;
-    if let Some(filter) = iso2_filter {
-        filtered.retain(|c| filter.contains(&c.iso2.as_str()));
-    }
-
-    // 4) Build DB
-    let db = build_geodb(filtered);
+    // 3) Build DB
+    let db = try_build_geodb(filtered)?;
 
     //
-    // 5) Save new cache
+    // 4) Save new cache
     //
-    if let Ok(bin) = bincode::serialize(&db) {
+    if let Ok(bin) = db.to_bytes() {
         let _ = std::fs::write(&bin_path, bin);
     }
 
     Ok(db)
 }
 
-/// load `countries+states+cities.json.gz`
-fn load_raw_countries(json_path: &Path) -> Result<CountriesRaw> {
+/// Open `countries+states+cities.json.gz` for decompressed reading.
+#[cfg(feature = "json")]
+fn open_source(json_path: &Path) -> Result<BufReader<GzDecoder<File>>> {
     let file = File::open(json_path).map_err(|_| {
         GeoError::NotFound(format!(
             "Dataset not found at path: {}",
             json_path.display()
         ))
     })?;
-    let gz = GzDecoder::new(file);
-    let reader = BufReader::new(gz);
-    Ok(serde_json::from_reader(reader)?)
+    Ok(BufReader::new(GzDecoder::new(file)))
+}
+
+/// The decompressed JSON of `countries+states+cities.json.gz`.
+#[cfg(feature = "json")]
+pub(crate) fn read_source(json_path: &Path) -> Result<String> {
+    let mut json = String::new();
+    open_source(json_path)?.read_to_string(&mut json)?;
+    Ok(json)
+}
+
+/// load `countries+states+cities.json.gz` untyped
+#[cfg(feature = "json")]
+pub(crate) fn load_raw_countries<T: DeserializeOwned>(json_path: &Path) -> Result<T> {
+    Ok(serde_json::from_reader(open_source(json_path)?)?)
 }
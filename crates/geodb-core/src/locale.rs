@@ -0,0 +1,151 @@
+// src/locale.rs
+//! Country-level localization metadata.
+//!
+//! Measurement system, short date format and first day of the week per
+//! country, loaded from a sidecar file (`data/country_locale.json`) and
+//! merged onto [`Country`] with [`GeoDb::apply_country_locale`].
+//!
+//! The sidecar stores a default plus per-country overrides, so only the
+//! countries that differ need an entry.
+use crate::model::{Country, GeoBackend, GeoDb};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+#[cfg(feature = "json")]
+use {crate::error::Result, std::fs, std::path::Path};
+
+/// Measurement system in everyday use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MeasurementSystem {
+    Metric,
+    /// US customary / imperial units.
+    Imperial,
+}
+
+/// Day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Localization metadata of one country.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountryLocale {
+    pub measurement_system: MeasurementSystem,
+    /// Short numeric date pattern, e.g. "DD.MM.YYYY" or "MM/DD/YYYY".
+    pub date_format: String,
+    pub first_day_of_week: Weekday,
+}
+
+/// Per-country override; unset fields fall back to the table default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountryLocaleOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_system: Option<MeasurementSystem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_day_of_week: Option<Weekday>,
+}
+
+/// Default locale plus overrides keyed by ISO2.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountryLocaleTable {
+    pub default: CountryLocale,
+    #[serde(default)]
+    pub countries: HashMap<String, CountryLocaleOverride>,
+}
+
+impl CountryLocaleTable {
+    /// Load a table from a JSON file.
+    ///
+    /// Expected format:
+    /// {
+    ///   "default": { "measurement_system": "metric", "date_format": "DD/MM/YYYY",
+    ///                "first_day_of_week": "monday" },
+    ///   "countries": {
+    ///     "US": { "measurement_system": "imperial", "date_format": "MM/DD/YYYY",
+    ///             "first_day_of_week": "sunday" },
+    ///     ...
+    ///   }
+    /// }
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        let mut table: Self = serde_json::from_slice(&bytes)?;
+        table.countries = table
+            .countries
+            .into_iter()
+            .map(|(k, v)| (k.to_ascii_uppercase(), v))
+            .collect();
+        Ok(table)
+    }
+
+    /// Load `country_locale.json` from the crate's default `data/` directory.
+    #[cfg(feature = "json")]
+    pub fn load_default() -> Result<Self> {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let path: std::path::PathBuf = [manifest_dir, "data", "country_locale.json"]
+            .iter()
+            .collect();
+        Self::load_from_path(path)
+    }
+
+    /// Resolved locale for an ISO2 code (default merged with its override).
+    pub fn get(&self, iso2: &str) -> CountryLocale {
+        let mut locale = self.default.clone();
+        if let Some(o) = self.countries.get(&iso2.to_ascii_uppercase()) {
+            if let Some(m) = o.measurement_system {
+                locale.measurement_system = m;
+            }
+            if let Some(f) = &o.date_format {
+                locale.date_format = f.clone();
+            }
+            if let Some(d) = o.first_day_of_week {
+                locale.first_day_of_week = d;
+            }
+        }
+        locale
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Attach locale metadata from `table` to every country.
+    ///
+    /// Returns the number of countries updated.
+    pub fn apply_country_locale(&mut self, table: &CountryLocaleTable) -> usize {
+        for country in &mut self.countries {
+            country.locale = Some(table.get(country.iso2.as_ref()));
+        }
+        self.countries.len()
+    }
+}
+
+impl<B: GeoBackend> Country<B> {
+    /// Locale metadata, if applied (see [`GeoDb::apply_country_locale`]).
+    pub fn locale(&self) -> Option<&CountryLocale> {
+        self.locale.as_ref()
+    }
+
+    /// Measurement system in everyday use, if locale metadata was applied.
+    pub fn measurement_system(&self) -> Option<MeasurementSystem> {
+        self.locale.as_ref().map(|l| l.measurement_system)
+    }
+
+    /// Short date pattern (e.g. "DD.MM.YYYY"), if locale metadata was applied.
+    pub fn date_format(&self) -> Option<&str> {
+        self.locale.as_ref().map(|l| l.date_format.as_str())
+    }
+
+    /// First day of the week, if locale metadata was applied.
+    pub fn first_day_of_week(&self) -> Option<Weekday> {
+        self.locale.as_ref().map(|l| l.first_day_of_week)
+    }
+}
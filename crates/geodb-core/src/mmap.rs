@@ -0,0 +1,187 @@
+// src/mmap.rs
+//! Memory-mapped binary artifacts, decoded per country on demand (feature
+//! `mmap`).
+//!
+//! [`GeoDb::load_binary`] decodes every country, state and city into heap
+//! vectors up front. A server that only answers for a few countries pays
+//! start-up time and memory for all ~150,000 cities. [`GeoDb::load_mmap`]
+//! maps the artifact instead and decodes a country the first time it is
+//! asked for; the rest stays in the page cache, shared between processes.
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let mapped = GeoDb::<StandardBackend>::load_mmap("geodb.bin")?;
+//! if let Some(de) = mapped.country("DE")? {
+//!     println!("{} states", de.state_count());
+//! }
+//! // the full search API over the countries this process serves
+//! let dach = mapped.filtered_by_iso2(&["DE", "AT", "CH"])?;
+//! let hits = dach.smart_search("zürich");
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Indexed artifacts ([`GeoDb::to_indexed_bytes`], `geodb build
+//! --indexed`) open instantly. Plain `*.bin` artifacts work too, but opening
+//! them decodes each country once to find where it ends (only the offsets
+//! are kept). Compressed and sealed artifacts can't be mapped; load them
+//! with [`GeoDb::from_bytes`].
+//!
+//! The file must not be modified or truncated while it is mapped. Replace
+//! artifacts by writing a new file and renaming it over the old one, as
+//! release bundles do.
+use crate::error::{GeoError, Result};
+use crate::indexed::split_index;
+use crate::limits::{with_limits, LoadLimits};
+use crate::loader::bincode_options;
+use crate::model::{Country, GeoBackend, GeoDb};
+use bincode::Options;
+use memmap2::Mmap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// A mapped binary artifact; see the [module docs](self).
+pub struct MmapGeoDb<B: GeoBackend> {
+    map: Mmap,
+    iso2: Vec<String>,
+    /// Byte range of each country in `map`.
+    ranges: Vec<Range<usize>>,
+    /// Countries decoded so far.
+    countries: Vec<OnceLock<Country<B>>>,
+}
+
+impl<B> GeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    /// Map the binary artifact at `path` without decoding its countries;
+    /// see [`crate::mmap`].
+    pub fn load_mmap(path: impl AsRef<Path>) -> Result<MmapGeoDb<B>> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only; the module docs require that the
+        // file isn't modified while mapped, and decoding validates the bytes.
+        let map = unsafe { Mmap::map(&file)? };
+        MmapGeoDb::new(map)
+    }
+}
+
+impl<B> MmapGeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    fn new(map: Mmap) -> Result<Self> {
+        if map.starts_with(&[0x1f, 0x8b]) || map.starts_with(b"GEODBSIG") {
+            return Err(GeoError::InvalidData(
+                "compressed or sealed artifacts can't be memory-mapped; use GeoDb::from_bytes"
+                    .into(),
+            ));
+        }
+        let (iso2, ranges) = match split_index(&map)? {
+            Some((entries, payload)) => {
+                let mut start = map.len() - payload.len() + 8;
+                let mut ranges = Vec::with_capacity(entries.len());
+                for entry in &entries {
+                    let end = start + entry.len as usize;
+                    ranges.push(start..end);
+                    start = end;
+                }
+                (entries.into_iter().map(|e| e.iso2).collect(), ranges)
+            }
+            None => scan::<B>(&map)?,
+        };
+        let countries = iso2.iter().map(|_| OnceLock::new()).collect();
+        Ok(Self {
+            map,
+            iso2,
+            ranges,
+            countries,
+        })
+    }
+
+    /// Number of countries in the artifact.
+    pub fn country_count(&self) -> usize {
+        self.iso2.len()
+    }
+
+    /// ISO2 codes of all countries, in artifact order, without decoding.
+    pub fn iso2_codes(&self) -> impl Iterator<Item = &str> {
+        self.iso2.iter().map(String::as_str)
+    }
+
+    /// Number of countries decoded so far.
+    pub fn decoded_count(&self) -> usize {
+        self.countries.iter().filter(|c| c.get().is_some()).count()
+    }
+
+    /// The country with ISO2 code `iso2` (case-insensitive), decoded on
+    /// first access; `Ok(None)` if the artifact has no such country.
+    pub fn country(&self, iso2: &str) -> Result<Option<&Country<B>>> {
+        match self.iso2.iter().position(|c| c.eq_ignore_ascii_case(iso2)) {
+            Some(i) => self.decode(i).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// A [`GeoDb`] with copies of the given countries (case-insensitive), in
+    /// artifact order; codes not in the artifact are ignored, like
+    /// [`GeoDb::filtered_by_iso2`].
+    pub fn filtered_by_iso2(&self, iso2: &[&str]) -> Result<GeoDb<B>> {
+        let mut countries = Vec::new();
+        for (i, code) in self.iso2.iter().enumerate() {
+            if iso2.iter().any(|c| c.eq_ignore_ascii_case(code)) {
+                countries.push(self.decode(i)?.clone());
+            }
+        }
+        Ok(GeoDb {
+            countries,
+            load_warnings: Vec::new(),
+            changelog: None,
+            spatial: Default::default(),
+        })
+    }
+
+    fn decode(&self, i: usize) -> Result<&Country<B>> {
+        if let Some(country) = self.countries[i].get() {
+            return Ok(country);
+        }
+        let bytes = &self.map[self.ranges[i].clone()];
+        let country: Country<B> = with_limits(&LoadLimits::default(), || {
+            bincode_options(bytes.len() as u64).deserialize(bytes)
+        })?;
+        // another thread may have decoded it meanwhile; both copies are equal
+        Ok(self.countries[i].get_or_init(|| country))
+    }
+}
+
+/// ISO2 codes and byte ranges of the countries in a plain artifact.
+fn scan<B>(bytes: &[u8]) -> Result<(Vec<String>, Vec<Range<usize>>)>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    let (count, mut rest) = bytes
+        .split_first_chunk::<8>()
+        .ok_or_else(|| GeoError::InvalidData("binary database is empty".into()))?;
+    let count = u64::from_le_bytes(*count);
+    if count > LoadLimits::default().max_countries as u64 {
+        return Err(GeoError::InvalidData(format!(
+            "binary database claims {count} countries"
+        )));
+    }
+    let mut iso2 = Vec::with_capacity(count as usize);
+    let mut ranges = Vec::with_capacity(count as usize);
+    with_limits(&LoadLimits::default(), || -> Result<()> {
+        for _ in 0..count {
+            let start = bytes.len() - rest.len();
+            let country: Country<B> =
+                bincode_options(bytes.len() as u64).deserialize_from(&mut rest)?;
+            iso2.push(country.iso2().to_string());
+            ranges.push(start..bytes.len() - rest.len());
+        }
+        Ok(())
+    })?;
+    Ok((iso2, ranges))
+}
@@ -1,6 +1,15 @@
-use crate::phone::PhoneCodeSearch;
+use crate::changelog::Changelog;
+#[cfg(feature = "districts")]
+use crate::district::district_city_index;
+use crate::error::Result;
+use crate::locale::CountryLocale;
+use crate::nearest::SpatialCache;
+use crate::provenance::FieldSource;
+use crate::search::{CityFilter, SearchOptions};
+use crate::validate::LoadWarning;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 /// Raw city structure as it comes from JSON.
 #[derive(Debug, Deserialize)]
@@ -54,6 +63,25 @@ pub struct StateRaw {
     pub timezone: Option<String>,
     #[serde(default)]
     pub cities: Vec<CityRaw>,
+    /// Optional third administrative level (counties, departments, ...).
+    #[cfg(feature = "districts")]
+    #[serde(default)]
+    pub districts: Vec<DistrictRaw>,
+}
+
+/// Raw district structure from JSON (feature `districts`).
+///
+/// Districts sit below a state and carry their own cities:
+/// { "name": "Kreis Coesfeld", "code": "COE", "cities": [ ... ] }
+#[cfg(feature = "districts")]
+#[derive(Debug, Deserialize)]
+pub struct DistrictRaw {
+    pub id: Option<i64>,
+    pub name: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub cities: Vec<CityRaw>,
 }
 
 /// Raw country structure from JSON.
@@ -110,8 +138,21 @@ pub struct CountryRaw {
     pub emoji_u: Option<String>,
     #[serde(default)]
     pub states: Vec<StateRaw>,
+    /// Cities listed on the country itself, as in the upstream
+    /// countries+cities export and for city-states without subdivisions;
+    /// they end up in a synthetic state (see [`SYNTHETIC_STATE_NAME`]).
+    #[serde(default)]
+    pub cities: Vec<CityRaw>,
 }
 
+/// Name of the state that holds the cities a source lists directly on a
+/// country (see [`State::is_synthetic`]).
+///
+/// The database always nests cities in states, so these cities get a
+/// state of their own without code or coordinates, placed after the real
+/// states. Views show its name as the city's state; state searches skip it.
+pub const SYNTHETIC_STATE_NAME: &str = "—";
+
 /// Simple aggregate statistics for the database.
 ///
 /// Returned by [`GeoDb::stats`], these counts reflect the materialized
@@ -206,6 +247,17 @@ pub struct City<B: GeoBackend> {
     pub latitude: Option<B::Float>,
     pub longitude: Option<B::Float>,
     pub timezone: Option<B::Str>,
+    /// Alternative names, filled from a [`crate::CityMetaIndex`] via
+    /// [`GeoDb::apply_city_meta`]. Not part of the binary layout.
+    #[serde(skip, default = "Vec::new")]
+    pub aliases: Vec<B::Str>,
+    /// Informal regions (e.g. "Münsterland"), filled like `aliases`.
+    #[serde(skip, default = "Vec::new")]
+    pub regions: Vec<B::Str>,
+    /// Sources of the aliases, regions and overridden fields; see
+    /// [`City::provenance`]. Not part of the binary layout.
+    #[serde(skip, default = "Vec::new")]
+    pub provenance: Vec<FieldSource>,
 }
 
 /// A region / state within a country.
@@ -217,9 +269,35 @@ pub struct State<B: GeoBackend> {
     pub native_name: Option<B::Str>,
     pub latitude: Option<B::Float>,
     pub longitude: Option<B::Float>,
+    #[serde(
+        deserialize_with = "crate::limits::cities",
+        bound(deserialize = "City<B>: Deserialize<'de>")
+    )]
     pub cities: Vec<City<B>>,
     pub state_code: Option<B::Str>, // e.g. "CA"
     pub full_code: Option<B::Str>,  // e.g. "US-CA"
+    /// Names by language code, filled from a [`crate::StateTranslationTable`]
+    /// via [`GeoDb::apply_state_translations`]. Not part of the binary layout.
+    #[serde(skip)]
+    pub translations: BTreeMap<String, String>,
+    /// Districts below this state (feature `districts`).
+    #[cfg(feature = "districts")]
+    pub districts: Vec<District<B>>,
+}
+
+/// A district (county, department, ...) below a [`State`] (feature `districts`).
+///
+/// District cities are stored in the parent state's `cities` like all other
+/// cities; the district records the contiguous index range they occupy, so
+/// `iter_cities` and the state-level searches see them unchanged.
+#[cfg(feature = "districts")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct District<B: GeoBackend> {
+    pub name: B::Str,
+    pub code: Option<B::Str>,
+    /// Index range into the parent state's `cities`.
+    pub city_start: u32,
+    pub city_end: u32,
 }
 
 /// A timezone entry in the normalized GeoDb.
@@ -232,6 +310,19 @@ pub struct CountryTimezone<B: GeoBackend> {
     pub tz_name: Option<B::Str>,
 }
 
+/// ISO2 codes treated as territories by [`Country::is_territory`], sorted.
+///
+/// Every ISO 3166-1 entry that is neither a UN member nor an observer state:
+/// dependencies, overseas regions with their own code, Antarctica and
+/// uninhabited islands. Partially recognized states (e.g. "TW", "XK") are not
+/// listed.
+pub const TERRITORY_ISO2: &[&str] = &[
+    "AI", "AQ", "AS", "AW", "AX", "BL", "BM", "BQ", "BV", "CC", "CK", "CW", "CX", "EH", "FK", "FO",
+    "GF", "GG", "GI", "GL", "GP", "GS", "GU", "HK", "HM", "IM", "IO", "JE", "KY", "MF", "MO", "MP",
+    "MQ", "MS", "NC", "NF", "NU", "PF", "PM", "PN", "PR", "RE", "SH", "SJ", "SX", "TC", "TF", "TK",
+    "UM", "VG", "VI", "WF", "YT",
+];
+
 /// A country entry in the normalized GeoDb.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Country<B: GeoBackend> {
@@ -262,9 +353,28 @@ pub struct Country<B: GeoBackend> {
     pub emoji_u: Option<B::Str>,
 
     pub timezones: Vec<CountryTimezone<B>>,
+    /// Zone of the capital city, falling back to the first listed timezone.
+    pub primary_timezone: Option<B::Str>,
     pub translations: HashMap<String, B::Str>,
 
+    #[serde(
+        deserialize_with = "crate::limits::states",
+        bound(deserialize = "State<B>: Deserialize<'de>")
+    )]
     pub states: Vec<State<B>>,
+
+    /// Localization metadata, filled via [`GeoDb::apply_country_locale`].
+    /// Not part of the binary layout.
+    #[serde(skip)]
+    pub locale: Option<CountryLocale>,
+    /// Region names by language code, filled from a
+    /// [`crate::RegionTranslationTable`] via
+    /// [`GeoDb::apply_region_translations`]. Not part of the binary layout.
+    #[serde(skip)]
+    pub region_translations: BTreeMap<String, String>,
+    /// Subregion names by language code, filled like `region_translations`.
+    #[serde(skip)]
+    pub subregion_translations: BTreeMap<String, String>,
 }
 
 /// Top-level database structure.
@@ -274,7 +384,22 @@ pub struct Country<B: GeoBackend> {
 /// by ISO2 country codes.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GeoDb<B: GeoBackend> {
+    #[serde(
+        deserialize_with = "crate::limits::countries",
+        bound(deserialize = "Country<B>: Deserialize<'de>")
+    )]
     pub countries: Vec<Country<B>>,
+    /// Anomalies found by a validating load; see [`GeoDb::load_warnings`].
+    /// Not part of the binary layout.
+    #[serde(skip)]
+    pub load_warnings: Vec<LoadWarning>,
+    /// Changes across dataset versions; see [`GeoDb::set_changelog`].
+    /// Not part of the binary layout.
+    #[serde(skip)]
+    pub changelog: Option<Arc<Changelog>>,
+    /// Built on the first coordinate lookup; see [`GeoDb::city_index`].
+    #[serde(skip)]
+    pub(crate) spatial: SpatialCache,
 }
 
 impl<B: GeoBackend> GeoDb<B> {
@@ -295,6 +420,9 @@ pub type StandardBackend = DefaultBackend;
 #[derive(Debug, Clone, Copy)]
 pub struct SmartHit<'a, B: GeoBackend> {
     pub score: i32,
+    /// The alias a city matched by, if not by its name (e.g. "Genf" for
+    /// Geneva); see [`ScoringProfile::alias_penalty`](crate::ScoringProfile::alias_penalty).
+    pub alias: Option<&'a str>,
     pub item: SmartItem<'a, B>,
 }
 
@@ -311,33 +439,107 @@ pub enum SmartItem<'a, B: GeoBackend> {
         state: &'a State<B>,
         city: &'a City<B>,
     },
+    /// A district below `state` (feature `districts`), scored like states.
+    #[cfg(feature = "districts")]
+    District {
+        country: &'a Country<B>,
+        state: &'a State<B>,
+        district: &'a District<B>,
+    },
 }
 
 fn parse_opt_f64(s: &Option<String>) -> Option<f64> {
     s.as_ref().and_then(|v| v.trim().parse::<f64>().ok())
 }
 
+/// Year, month and day of a Unix timestamp (UTC).
+pub(crate) fn civil_date(secs: u64) -> (i64, i64, i64) {
+    // days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Pick the primary timezone of a raw country: the capital city's zone if the
+/// capital can be found among its cities, otherwise the first country zone.
+fn primary_timezone_raw(c: &CountryRaw) -> Option<&str> {
+    let from_capital = c.capital.as_deref().and_then(|capital| {
+        c.states
+            .iter()
+            .flat_map(|s| s.cities.iter())
+            .find(|city| city.name.eq_ignore_ascii_case(capital))
+            .and_then(|city| city.timezone.as_deref())
+    });
+
+    from_capital.or_else(|| c.timezones.iter().find_map(|tz| tz.zone_name.as_deref()))
+}
+
+fn build_city<B: GeoBackend>(city: &CityRaw) -> City<B> {
+    City::<B> {
+        name: B::str_from(&city.name),
+        latitude: parse_opt_f64(&city.latitude).map(B::float_from),
+        longitude: parse_opt_f64(&city.longitude).map(B::float_from),
+        timezone: city.timezone.as_deref().map(B::str_from),
+        aliases: Vec::new(),
+        regions: Vec::new(),
+        provenance: Vec::new(),
+    }
+}
+
 /// Convert raw JSON data into a [`GeoDb`] using the given backend.
+///
+/// # Panics
+///
+/// If an index doesn't fit its stored width; see [`try_build_geodb`],
+/// which the loaders use.
 pub fn build_geodb<B: GeoBackend>(raw: CountriesRaw) -> GeoDb<B> {
+    try_build_geodb(raw).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Convert raw JSON data into a [`GeoDb`], failing instead of truncating
+/// when the data outgrows a narrow index type.
+///
+/// With the `districts` feature, district city ranges are stored as `u32`
+/// offsets into their state's cities; a state with more cities than that
+/// is rejected with [`crate::GeoError::InvalidData`] naming the state.
+pub fn try_build_geodb<B: GeoBackend>(raw: CountriesRaw) -> Result<GeoDb<B>> {
     let countries = raw
         .into_iter()
         .map(|c| {
-            let states = c
+            let primary_timezone = primary_timezone_raw(&c).map(B::str_from);
+
+            let mut states: Vec<State<B>> = c
                 .states
                 .into_iter()
                 .map(|s| {
-                    let cities = s
-                        .cities
-                        .into_iter()
-                        .map(|city| City::<B> {
-                            name: B::str_from(&city.name),
-                            latitude: parse_opt_f64(&city.latitude).map(B::float_from),
-                            longitude: parse_opt_f64(&city.longitude).map(B::float_from),
-                            timezone: city.timezone.as_deref().map(B::str_from),
+                    #[allow(unused_mut)]
+                    let mut cities: Vec<City<B>> = s.cities.iter().map(build_city).collect();
+
+                    #[cfg(feature = "districts")]
+                    let districts = s
+                        .districts
+                        .iter()
+                        .map(|d| {
+                            let index = |i| district_city_index(i, &s.name, &c.iso2);
+                            let city_start = index(cities.len())?;
+                            cities.extend(d.cities.iter().map(build_city));
+                            Ok(District::<B> {
+                                name: B::str_from(&d.name),
+                                code: d.code.as_deref().map(B::str_from),
+                                city_start,
+                                city_end: index(cities.len())?,
+                            })
                         })
-                        .collect();
+                        .collect::<Result<_>>()?;
 
-                    State::<B> {
+                    Ok(State::<B> {
                         name: B::str_from(&s.name),
                         native_name: s.native.as_deref().map(B::str_from),
                         latitude: parse_opt_f64(&s.latitude).map(B::float_from),
@@ -345,9 +547,15 @@ pub fn build_geodb<B: GeoBackend>(raw: CountriesRaw) -> GeoDb<B> {
                         cities,
                         state_code: s.iso2.as_deref().map(B::str_from),
                         full_code: s.iso3166_2.as_deref().map(B::str_from),
-                    }
+                        translations: BTreeMap::new(),
+                        #[cfg(feature = "districts")]
+                        districts,
+                    })
                 })
-                .collect();
+                .collect::<Result<_>>()?;
+            if !c.cities.is_empty() {
+                states.push(State::synthetic(c.cities.iter().map(build_city).collect()));
+            }
 
             let timezones = c
                 .timezones
@@ -367,7 +575,7 @@ pub fn build_geodb<B: GeoBackend>(raw: CountriesRaw) -> GeoDb<B> {
                 .map(|(k, v)| (k, B::str_from(&v)))
                 .collect::<HashMap<_, _>>();
 
-            Country::<B> {
+            Ok(Country::<B> {
                 name: B::str_from(&c.name),
                 iso2: B::str_from(&c.iso2),
                 iso3: c.iso3.as_deref().map(B::str_from),
@@ -395,18 +603,28 @@ pub fn build_geodb<B: GeoBackend>(raw: CountriesRaw) -> GeoDb<B> {
                 emoji_u: c.emoji_u.as_deref().map(B::str_from),
 
                 timezones,
+                primary_timezone,
                 translations,
 
                 states,
-            }
+                locale: None,
+                region_translations: BTreeMap::new(),
+                subregion_translations: BTreeMap::new(),
+            })
         })
-        .collect();
-
-    GeoDb { countries }
+        .collect::<Result<_>>()?;
+
+    Ok(GeoDb {
+        countries,
+        load_warnings: Vec::new(),
+        changelog: None,
+        spatial: SpatialCache::default(),
+    })
 }
 
 impl<B: GeoBackend> GeoDb<B> {
-    /// All countries in the database.
+    /// All countries in the database, in dataset order unless sorted with
+    /// [`GeoDb::sort_countries`] (see [`crate::CountryOrder`]).
     pub fn countries(&self) -> &[Country<B>] {
         &self.countries
     }
@@ -447,15 +665,8 @@ impl<B: GeoBackend> GeoDb<B> {
     pub fn stats(&self) -> DbStats {
         let countries = self.countries.len();
 
-        let mut states = 0usize;
-        let mut cities = 0usize;
-
-        for country in &self.countries {
-            states += country.states.len();
-            for state in &country.states {
-                cities += state.cities.len();
-            }
-        }
+        let states = self.countries.iter().map(Country::state_count).sum();
+        let cities = self.countries.iter().map(Country::city_count).sum();
 
         DbStats {
             countries,
@@ -474,13 +685,73 @@ impl<B: GeoBackend> GeoDb<B> {
         })
     }
 
+    /// States of the country with this ISO2 or ISO3 code (see
+    /// [`Self::find_country_by_code`]); empty for an unknown code.
+    pub fn states_for_country(&self, code: &str) -> &[State<B>] {
+        self.find_country_by_code(code)
+            .map_or(&[], |country| country.states())
+    }
+
+    /// A state of the country with this ISO2 or ISO3 code, by the state's
+    /// code ("CA"), full code ("US-CA") or name ("California"), all
+    /// case-insensitive.
+    pub fn find_state(&self, country: &str, state: &str) -> Option<&State<B>> {
+        let state = state.trim();
+        self.states_for_country(country).iter().find(|s| {
+            (!s.state_code().is_empty() && s.state_code().eq_ignore_ascii_case(state))
+                || s.full_code
+                    .as_ref()
+                    .is_some_and(|c| c.as_ref().eq_ignore_ascii_case(state))
+                || s.name().to_lowercase() == state.to_lowercase()
+        })
+    }
+
+    /// Cities of the state [`Self::find_state`] finds; empty if the country
+    /// or state is unknown.
+    pub fn cities_for_state(&self, country: &str, state: &str) -> &[City<B>] {
+        self.find_state(country, state).map_or(&[], |s| s.cities())
+    }
+
+    /// Find all countries whose name contains the given substring (case-insensitive).
+    ///
+    /// Only the default (English) name is checked; see
+    /// [`Self::find_countries_by_substring_with`] to include native names and
+    /// translations.
+    pub fn find_countries_by_substring(&self, substr: &str) -> Vec<&Country<B>> {
+        self.find_countries_by_substring_with(substr, false)
+    }
+
+    /// Like [`Self::find_countries_by_substring`]; with `translations` set,
+    /// the native name and every translation are checked too, so "alemania"
+    /// finds Germany.
+    ///
+    /// Scanning translations touches a few dozen names per country, so leave
+    /// it off for hot paths that only need English names.
+    pub fn find_countries_by_substring_with(
+        &self,
+        substr: &str,
+        translations: bool,
+    ) -> Vec<&Country<B>> {
+        let q = substr.to_lowercase();
+        let matches = |name: &str| name.to_lowercase().contains(&q);
+        self.countries
+            .iter()
+            .filter(|c| {
+                matches(c.name())
+                    || (translations
+                        && (c.native_name.as_ref().is_some_and(|n| matches(n.as_ref()))
+                            || c.translations.values().any(|t| matches(t.as_ref()))))
+            })
+            .collect()
+    }
+
     /// Find all states whose name contains the given ASCII substring (case-insensitive).
     /// Returns pairs of (state, country) for convenience.
     pub fn find_states_by_substring(&self, substr: &str) -> Vec<(&State<B>, &Country<B>)> {
         let q = substr.to_ascii_lowercase();
         let mut out = Vec::new();
         for c in &self.countries {
-            for s in &c.states {
+            for s in c.states.iter().filter(|s| !s.is_synthetic()) {
                 if s.name().to_ascii_lowercase().contains(&q) {
                     out.push((s, c));
                 }
@@ -509,11 +780,48 @@ impl<B: GeoBackend> GeoDb<B> {
         out
     }
 
+    /// [`Self::find_cities_by_substring`] restricted by a [`CityFilter`].
+    ///
+    /// Countries failing the filter are skipped whole, so a country or
+    /// population filter also makes the scan cheaper. Results keep database
+    /// order, start after [`CityFilter::offset`] matches and stop at
+    /// [`CityFilter::limit`].
+    pub fn find_cities_by_substring_filtered(
+        &self,
+        substr: &str,
+        filter: &CityFilter,
+    ) -> Vec<(&City<B>, &State<B>, &Country<B>)> {
+        let q = substr.to_ascii_lowercase();
+        let limit = filter.limit.unwrap_or(usize::MAX);
+        let mut skip = filter.offset;
+        let mut out = Vec::new();
+        for c in self.countries.iter().filter(|c| filter.allows_country(c)) {
+            for s in &c.states {
+                for city in &s.cities {
+                    if out.len() >= limit {
+                        return out;
+                    }
+                    if city.name().to_ascii_lowercase().contains(&q)
+                        && filter.allows_position::<B>(city.latitude, city.longitude)
+                    {
+                        if skip > 0 {
+                            skip -= 1;
+                        } else {
+                            out.push((city, s, c));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
     /// Smart search across countries, states, cities, and phone codes.
     ///
     /// Scoring (descending priority):
     /// - Country ISO2 exact match: 100
     /// - Country name exact: 90
+    /// - State code exact (e.g. "CA"): 85
     /// - Country name starts with: 80
     /// - Country name contains: 70
     /// - State name starts with: 60
@@ -521,100 +829,14 @@ impl<B: GeoBackend> GeoDb<B> {
     /// - City name starts with: 40
     /// - City name contains: 30
     /// - Country phone code match: 20
+    ///
+    /// A trailing ISO2 token ("berlin de") adds 15 to cities of that country
+    /// matched by the rest of the query. With the `districts` feature,
+    /// district names are matched too and scored like state names.
+    ///
+    /// Uses default [`SearchOptions`]; see [`GeoDb::smart_search_with`].
     pub fn smart_search(&self, query: &str) -> Vec<SmartHit<'_, B>> {
-        let q = query.trim().to_ascii_lowercase();
-        if q.is_empty() {
-            return Vec::new();
-        }
-
-        let phone = q.trim_start_matches('+');
-        let mut out: Vec<SmartHit<'_, B>> = Vec::new();
-
-        // Countries
-        for c in self.countries() {
-            let name = c.name().to_ascii_lowercase();
-            if c.iso2().eq_ignore_ascii_case(&q) {
-                out.push(SmartHit {
-                    score: 100,
-                    item: SmartItem::Country(c),
-                });
-            } else if name == q {
-                out.push(SmartHit {
-                    score: 90,
-                    item: SmartItem::Country(c),
-                });
-            } else if name.starts_with(&q) {
-                out.push(SmartHit {
-                    score: 80,
-                    item: SmartItem::Country(c),
-                });
-            } else if name.contains(&q) {
-                out.push(SmartHit {
-                    score: 70,
-                    item: SmartItem::Country(c),
-                });
-            }
-        }
-
-        // States
-        for c in self.countries() {
-            for s in c.states() {
-                let sn = s.name().to_ascii_lowercase();
-                if sn.starts_with(&q) {
-                    out.push(SmartHit {
-                        score: 60,
-                        item: SmartItem::State {
-                            country: c,
-                            state: s,
-                        },
-                    });
-                } else if sn.contains(&q) {
-                    out.push(SmartHit {
-                        score: 50,
-                        item: SmartItem::State {
-                            country: c,
-                            state: s,
-                        },
-                    });
-                }
-            }
-        }
-
-        // Cities
-        for (city, state, country) in self.iter_cities() {
-            let cn = city.name().to_ascii_lowercase();
-            if cn.starts_with(&q) {
-                out.push(SmartHit {
-                    score: 40,
-                    item: SmartItem::City {
-                        country,
-                        state,
-                        city,
-                    },
-                });
-            } else if cn.contains(&q) {
-                out.push(SmartHit {
-                    score: 30,
-                    item: SmartItem::City {
-                        country,
-                        state,
-                        city,
-                    },
-                });
-            }
-        }
-
-        // Phone code
-        for c in self.find_countries_by_phone_code(phone) {
-            out.push(SmartHit {
-                score: 20,
-                item: SmartItem::Country(c),
-            });
-        }
-
-        // Sort by score desc (stable sort to preserve relative order within score)
-        out.sort_by(|a, b| b.score.cmp(&a.score));
-        out
+        self.smart_search_with(query, &SearchOptions::default())
     }
 }
 
@@ -670,6 +892,90 @@ impl<B: GeoBackend> Country<B> {
         self.population
     }
 
+    /// ISO 3166-1 numeric code (e.g. "276"), if provided by the dataset.
+    pub fn numeric_code(&self) -> Option<&str> {
+        self.numeric_code.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Currency name (e.g. "Euro"), if provided by the dataset.
+    pub fn currency_name(&self) -> Option<&str> {
+        self.currency_name.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Currency symbol (e.g. "€"), if provided by the dataset.
+    pub fn currency_symbol(&self) -> Option<&str> {
+        self.currency_symbol.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Country code top-level domain (e.g. ".de"), if provided by the dataset.
+    pub fn tld(&self) -> Option<&str> {
+        self.tld.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Name in the country's own language (e.g. "Deutschland"), if known.
+    pub fn native_name(&self) -> Option<&str> {
+        self.native_name.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Name in `lang` (e.g. "es" → "Alemania"), from the dataset's translations.
+    pub fn translation(&self, lang: &str) -> Option<&str> {
+        self.translations.get(lang).map(|s| s.as_ref())
+    }
+
+    /// Gross domestic product as given by the dataset (often missing).
+    pub fn gdp(&self) -> Option<i64> {
+        self.gdp
+    }
+
+    /// Dataset id of [`Self::region`].
+    pub fn region_id(&self) -> Option<i64> {
+        self.region_id
+    }
+
+    /// Subregion label (e.g. "Western Europe"), if known.
+    pub fn subregion(&self) -> Option<&str> {
+        self.subregion.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Dataset id of [`Self::subregion`].
+    pub fn subregion_id(&self) -> Option<i64> {
+        self.subregion_id
+    }
+
+    /// Demonym (e.g. "German"), if provided by the dataset.
+    pub fn nationality(&self) -> Option<&str> {
+        self.nationality.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Latitude of the country centroid, if known.
+    pub fn latitude(&self) -> Option<f64> {
+        self.latitude.map(B::float_to_f64)
+    }
+
+    /// Longitude of the country centroid, if known.
+    pub fn longitude(&self) -> Option<f64> {
+        self.longitude.map(B::float_to_f64)
+    }
+
+    /// Flag emoji (e.g. "🇩🇪"), if provided by the dataset.
+    pub fn emoji(&self) -> Option<&str> {
+        self.emoji.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Unicode code points of the flag emoji (e.g. "U+1F1E9 U+1F1EA").
+    pub fn emoji_u(&self) -> Option<&str> {
+        self.emoji_u.as_ref().map(|s| s.as_ref())
+    }
+
+    /// `true` for dependent territories and other ISO 3166-1 entries that are
+    /// not UN member or observer states (e.g. "PR", "GL", "HK", "AQ").
+    ///
+    /// The dataset has no such flag; see [`TERRITORY_ISO2`] for the list used.
+    pub fn is_territory(&self) -> bool {
+        let iso2 = self.iso2();
+        TERRITORY_ISO2.iter().any(|t| t.eq_ignore_ascii_case(iso2))
+    }
+
     /// Region/continent label (e.g. "Europe"), or empty string if unknown.
     pub fn region(&self) -> &str {
         self.region.as_ref().map(|s| s.as_ref()).unwrap_or("")
@@ -680,18 +986,83 @@ impl<B: GeoBackend> Country<B> {
         &self.states
     }
 
+    /// Number of states/regions (including the one holding cities listed
+    /// directly on the country, see [`State::is_synthetic`]).
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Number of cities in all of the country's states.
+    pub fn city_count(&self) -> usize {
+        self.states.iter().map(State::city_count).sum()
+    }
+
     /// List of country timezones as provided by the dataset.
     pub fn timezones(&self) -> &[CountryTimezone<B>] {
         &self.timezones
     }
 
-    /// We currently don't have area in the dataset; keep API but return None.
-    pub fn area(&self) -> Option<f64> {
-        None
+    /// Primary IANA timezone (e.g. "Europe/Berlin").
+    ///
+    /// Computed when the database is built: the timezone of the capital city,
+    /// or the first listed country timezone when the capital is unknown.
+    pub fn primary_timezone(&self) -> Option<&str> {
+        self.primary_timezone.as_ref().map(|s| s.as_ref())
+    }
+
+    /// Timezone entry (with UTC offset) matching [`Self::primary_timezone`].
+    pub fn primary_timezone_entry(&self) -> Option<&CountryTimezone<B>> {
+        let primary = self.primary_timezone()?;
+        self.timezones
+            .iter()
+            .find(|tz| tz.zone_name.as_ref().is_some_and(|z| z.as_ref() == primary))
+    }
+
+    /// UTC offset of [`Self::primary_timezone`] in seconds. The dataset only
+    /// has standard offsets, without daylight saving time.
+    pub fn utc_offset_seconds(&self) -> Option<i64> {
+        self.primary_timezone_entry()?.gmt_offset
+    }
+
+    /// Local time (`YYYY-MM-DD HH:MM`) in the primary timezone at `unix_secs`
+    /// seconds since the Unix epoch, at [`Self::utc_offset_seconds`].
+    pub fn local_time_at(&self, unix_secs: u64) -> Option<String> {
+        let local = i64::try_from(unix_secs).ok()? + self.utc_offset_seconds()?;
+        let local = u64::try_from(local).ok()?;
+        let (year, month, day) = civil_date(local);
+        let minutes = local % 86_400 / 60;
+        Some(format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+            minutes / 60,
+            minutes % 60
+        ))
     }
 }
 
 impl<B: GeoBackend> State<B> {
+    /// State holding cities listed directly on a country; see
+    /// [`SYNTHETIC_STATE_NAME`].
+    pub fn synthetic(cities: Vec<City<B>>) -> Self {
+        Self {
+            name: B::str_from(SYNTHETIC_STATE_NAME),
+            native_name: None,
+            latitude: None,
+            longitude: None,
+            cities,
+            state_code: None,
+            full_code: None,
+            translations: BTreeMap::new(),
+            #[cfg(feature = "districts")]
+            districts: Vec::new(),
+        }
+    }
+
+    /// `true` for the state that holds a country's directly listed cities
+    /// rather than a real subdivision.
+    pub fn is_synthetic(&self) -> bool {
+        self.name() == SYNTHETIC_STATE_NAME && self.state_code.is_none()
+    }
+
     /// State/region display name.
     pub fn name(&self) -> &str {
         self.name.as_ref()
@@ -706,6 +1077,40 @@ impl<B: GeoBackend> State<B> {
     pub fn cities(&self) -> &[City<B>] {
         &self.cities
     }
+
+    /// Number of cities in this state.
+    pub fn city_count(&self) -> usize {
+        self.cities.len()
+    }
+
+    /// Name in the state's own language, if the dataset has one.
+    pub fn native_name(&self) -> Option<&str> {
+        self.native_name.as_ref().map(|n| n.as_ref())
+    }
+
+    /// Name in `lang` (e.g. "de"), if translations were applied and list it.
+    pub fn translation(&self, lang: &str) -> Option<&str> {
+        self.translations
+            .get(&lang.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Best name for `lang`: the translation, else the native name, else
+    /// [`State::name`].
+    ///
+    /// The dataset's default names are English, so "en" always resolves to
+    /// [`State::name`] unless a translation overrides it.
+    pub fn localized_name(&self, lang: &str) -> &str {
+        if let Some(name) = self.translation(lang) {
+            return name;
+        }
+        if lang.eq_ignore_ascii_case("en") {
+            return self.name();
+        }
+        self.native_name()
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| self.name())
+    }
 }
 
 impl<B: GeoBackend> City<B> {
@@ -713,4 +1118,14 @@ impl<B: GeoBackend> City<B> {
     pub fn name(&self) -> &str {
         self.name.as_ref()
     }
+
+    /// Alternative names of this city (empty unless city meta was applied).
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        self.aliases.iter().map(|a| a.as_ref())
+    }
+
+    /// Informal regions this city belongs to (empty unless city meta was applied).
+    pub fn regions(&self) -> impl Iterator<Item = &str> {
+        self.regions.iter().map(|r| r.as_ref())
+    }
 }
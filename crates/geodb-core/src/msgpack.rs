@@ -0,0 +1,33 @@
+// src/msgpack.rs
+//! MessagePack encoding of views and result sets (feature `msgpack`).
+//!
+//! Produces the same structure as the JSON output (maps with field names),
+//! just in a more compact binary encoding, e.g. for mobile clients:
+//!
+//! ```
+//! use geodb_core::{msgpack, render_hits, GeoDb, ResponseFormat, StandardBackend};
+//!
+//! # fn demo(db: &GeoDb<StandardBackend>) -> geodb_core::Result<()> {
+//! let hits = db.smart_search("berlin");
+//! let bytes = msgpack::to_vec(&render_hits(&hits, ResponseFormat::Native))?;
+//! # let _ = bytes;
+//! # Ok(())
+//! # }
+//! ```
+use crate::error::Result;
+use serde::Serialize;
+
+/// Encode `value` as MessagePack with named fields (maps, not arrays).
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    Ok(rmp_serde::to_vec_named(value)?)
+}
+
+/// Encode `value` as MessagePack into `writer`.
+pub fn write<W: std::io::Write + ?Sized, T: Serialize + ?Sized>(
+    writer: &mut W,
+    value: &T,
+) -> Result<()> {
+    let mut ser = rmp_serde::Serializer::new(writer).with_struct_map();
+    value.serialize(&mut ser)?;
+    Ok(())
+}
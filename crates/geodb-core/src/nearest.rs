@@ -0,0 +1,295 @@
+// src/nearest.rs
+//! Coordinate lookups: the city closest to a point ("which city is this?")
+//! and the cities inside a bounding box (what a map view shows).
+//!
+//! Both go through a spatial index: a k-d tree over the city positions plus
+//! a latitude ordering, so each lookup only visits nearby cities. The index
+//! is built on the first lookup and kept with the database, so
+//! [`GeoDb::find_nearest_city`], [`GeoDb::cities_in_bbox`] and the
+//! [`DynGeoSearch`](crate::DynGeoSearch) methods all reuse it;
+//! [`GeoDb::city_index`] hands it out as a [`CityIndex`].
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let index = db.city_index();
+//! if let Some(near) = index.find_nearest_city(48.137, 11.575, 25.0) {
+//!     let (city, _, country) = near.city;
+//!     println!("{}, {} ({:.1} km)", city.name(), country.iso2(), near.distance_km);
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! The methods that move, add or reorder cities
+//! ([`GeoDb::apply_overrides`], [`GeoDb::sort_countries`],
+//! [`GeoDb::attach_cities`]) drop the index, and clones start without one.
+//! Code that edits [`GeoDb::countries`] directly calls
+//! [`GeoDb::invalidate_city_index`] afterwards.
+use crate::model::{GeoBackend, GeoDb};
+use crate::search::CityFilter;
+use crate::spatial::{haversine_km, to_xyz, CityRef, EARTH_RADIUS_KM};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Position of a city: (country, state, city) indices.
+type CityPos = (u32, u32, u32);
+
+/// A city found by a nearest-city lookup.
+#[derive(Debug, Clone, Copy)]
+pub struct NearestCity<'a, B: GeoBackend> {
+    pub city: CityRef<'a, B>,
+    /// Great-circle (haversine) distance to the query point.
+    pub distance_km: f64,
+}
+
+/// The cities with coordinates, arranged for lookups.
+#[derive(Debug, Default)]
+struct SpatialIndex {
+    /// Implicit k-d tree: each slice's median is the node splitting its
+    /// halves on axis `depth % 3`.
+    points: Vec<([f64; 3], CityPos)>,
+    /// The same cities by latitude, for bounding boxes.
+    by_lat: Vec<(f64, CityPos)>,
+}
+
+/// The spatial index of a [`GeoDb`], built on first use. Clones start
+/// empty, so a copy that is edited afterwards never sees a stale index.
+#[derive(Default)]
+pub(crate) struct SpatialCache(OnceLock<SpatialIndex>);
+
+impl Clone for SpatialCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for SpatialCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.get() {
+            Some(index) => write!(f, "SpatialCache({} cities)", index.points.len()),
+            None => f.write_str("SpatialCache(not built)"),
+        }
+    }
+}
+
+/// Spatial index over the cities of a [`GeoDb`].
+///
+/// Returned by [`GeoDb::city_index`]; cities without coordinates are left
+/// out.
+#[derive(Debug)]
+pub struct CityIndex<'a, B: GeoBackend> {
+    db: &'a GeoDb<B>,
+    index: &'a SpatialIndex,
+}
+
+impl<'a, B: GeoBackend> CityIndex<'a, B> {
+    /// The city closest to `(lat, lng)` if it lies within `radius_km`.
+    ///
+    /// Equally distant cities resolve to the first in database order.
+    pub fn find_nearest_city(
+        &self,
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+    ) -> Option<NearestCity<'a, B>> {
+        if !(lat.is_finite() && lng.is_finite()) || radius_km < 0.0 {
+            return None;
+        }
+        let query = to_xyz(lat, lng);
+        // chords grow with arcs, so the nearest chord is the nearest city
+        let max_chord = chord_km(radius_km);
+        let mut best = None;
+        nearest_in(
+            &self.index.points,
+            0,
+            &query,
+            max_chord * max_chord,
+            &mut best,
+        );
+
+        let (_, pos) = best?;
+        let (city, state, country) = self.resolve(pos)?;
+        let distance_km = haversine_km(
+            lat,
+            lng,
+            B::float_to_f64(city.latitude?),
+            B::float_to_f64(city.longitude?),
+        );
+        Some(NearestCity {
+            city: (city, state, country),
+            distance_km,
+        })
+    }
+
+    /// Cities inside the box from `(min_lat, min_lng)` to `(max_lat,
+    /// max_lng)`, bounds included, in database order. A box with
+    /// `min_lng > max_lng` crosses the antimeridian.
+    pub fn cities_in_bbox(
+        &self,
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+    ) -> Vec<CityRef<'a, B>> {
+        let filter = CityFilter::default().with_bounds(min_lat, min_lng, max_lat, max_lng);
+        let by_lat = &self.index.by_lat;
+        let start = by_lat.partition_point(|&(lat, _)| lat < min_lat);
+        let end = by_lat.partition_point(|&(lat, _)| lat <= max_lat);
+        let mut positions: Vec<CityPos> = by_lat
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|&(_, pos)| pos)
+            .collect();
+        positions.sort_unstable();
+        positions
+            .into_iter()
+            .filter_map(|pos| self.resolve(pos))
+            .filter(|(city, _, _)| filter.allows_position::<B>(city.latitude, city.longitude))
+            .collect()
+    }
+
+    /// Number of indexed cities.
+    pub fn len(&self) -> usize {
+        self.index.points.len()
+    }
+
+    /// `true` if no city has coordinates.
+    pub fn is_empty(&self) -> bool {
+        self.index.points.is_empty()
+    }
+
+    /// The city at `pos`; `None` if the database no longer has it.
+    fn resolve(&self, (ci, si, ti): CityPos) -> Option<CityRef<'a, B>> {
+        let country = self.db.countries.get(ci as usize)?;
+        let state = country.states.get(si as usize)?;
+        Some((state.cities.get(ti as usize)?, state, country))
+    }
+}
+
+impl SpatialIndex {
+    fn build<B: GeoBackend>(db: &GeoDb<B>) -> Self {
+        let mut points = Vec::new();
+        let mut by_lat = Vec::new();
+        for (ci, country) in db.countries.iter().enumerate() {
+            for (si, state) in country.states.iter().enumerate() {
+                for (ti, city) in state.cities.iter().enumerate() {
+                    if let (Some(lat), Some(lng)) = (city.latitude, city.longitude) {
+                        let pos = (ci as u32, si as u32, ti as u32);
+                        let lat = B::float_to_f64(lat);
+                        points.push((to_xyz(lat, B::float_to_f64(lng)), pos));
+                        by_lat.push((lat, pos));
+                    }
+                }
+            }
+        }
+        build(&mut points, 0);
+        by_lat.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points, by_lat }
+    }
+}
+
+/// Straight-line length of the chord under an arc of `arc_km`.
+fn chord_km(arc_km: f64) -> f64 {
+    let half_angle = (arc_km / (2.0 * EARTH_RADIUS_KM)).min(std::f64::consts::FRAC_PI_2);
+    2.0 * EARTH_RADIUS_KM * half_angle.sin()
+}
+
+fn dist2(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+fn build(points: &mut [([f64; 3], CityPos)], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = points.len() / 2;
+    points.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+/// Best `(squared chord, position)` so far; ties go to the lower position.
+fn nearest_in(
+    points: &[([f64; 3], CityPos)],
+    depth: usize,
+    query: &[f64; 3],
+    max_d2: f64,
+    best: &mut Option<(f64, CityPos)>,
+) {
+    if points.is_empty() {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = points.len() / 2;
+    let (point, pos) = &points[mid];
+
+    let d2 = dist2(point, query);
+    let better = match best {
+        Some((best_d2, best_pos)) => (d2, *pos) < (*best_d2, *best_pos),
+        None => d2 <= max_d2,
+    };
+    if better {
+        *best = Some((d2, *pos));
+    }
+
+    let diff = query[axis] - point[axis];
+    let (near, far) = if diff < 0.0 {
+        (&points[..mid], &points[mid + 1..])
+    } else {
+        (&points[mid + 1..], &points[..mid])
+    };
+    nearest_in(near, depth + 1, query, max_d2, best);
+    let bound = best.map_or(max_d2, |(d2, _)| d2);
+    if diff * diff <= bound {
+        nearest_in(far, depth + 1, query, max_d2, best);
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// The spatial index over the cities, built on the first call and
+    /// kept until the cities change (see the [module docs](self)).
+    pub fn city_index(&self) -> CityIndex<'_, B> {
+        CityIndex {
+            db: self,
+            index: self.spatial.0.get_or_init(|| SpatialIndex::build(self)),
+        }
+    }
+
+    /// Drop the spatial index, after editing [`Self::countries`] directly;
+    /// the next lookup builds it again.
+    pub fn invalidate_city_index(&mut self) {
+        self.spatial = SpatialCache::default();
+    }
+
+    /// The city closest to `(lat, lng)` if it lies within `radius_km`.
+    ///
+    /// Equally distant cities resolve to the first in database order. See
+    /// [`CityIndex::find_nearest_city`].
+    pub fn find_nearest_city(
+        &self,
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+    ) -> Option<NearestCity<'_, B>> {
+        self.city_index().find_nearest_city(lat, lng, radius_km)
+    }
+
+    /// Cities inside the box from `(min_lat, min_lng)` to `(max_lat,
+    /// max_lng)`, bounds included, in database order. A box with
+    /// `min_lng > max_lng` crosses the antimeridian. See
+    /// [`CityIndex::cities_in_bbox`].
+    pub fn cities_in_bbox(
+        &self,
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+    ) -> Vec<CityRef<'_, B>> {
+        self.city_index()
+            .cities_in_bbox(min_lat, min_lng, max_lat, max_lng)
+    }
+}
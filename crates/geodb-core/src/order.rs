@@ -0,0 +1,88 @@
+// src/order.rs
+//! Guaranteed order of [`GeoDb::countries`].
+//!
+//! By default countries keep the order of the source dataset, which changes
+//! whenever upstream reorders its entries. [`CountryOrder`] pins it instead:
+//! apply it at load time ([`LoadOptions::with_country_order`], or
+//! [`GeoDb::sort_countries`] after any loader) or at build time
+//! (`geodb build --country-order`), so UI snapshots and diffs of listings
+//! stay stable across dataset updates. States and cities keep their order
+//! within each country either way.
+//!
+//! ```no_run
+//! use geodb_core::{CountryOrder, GeoDb, StandardBackend};
+//!
+//! let mut db = GeoDb::<StandardBackend>::load()?;
+//! db.sort_countries(CountryOrder::Name);
+//! assert_eq!(db.countries()[0].iso2(), "AF"); // Afghanistan
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! [`LoadOptions::with_country_order`]: crate::LoadOptions::with_country_order
+use crate::model::{GeoBackend, GeoDb};
+use crate::search::{fold_key, SearchLanguage};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Order of [`GeoDb::countries`]; see the [module docs](self).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CountryOrder {
+    /// As in the source dataset (or the binary artifact). Not stable across
+    /// dataset updates.
+    #[default]
+    Dataset,
+    /// By ISO2 code, ascending.
+    Iso2,
+    /// By English name, compared by its [`fold_key`] under
+    /// [`SearchLanguage::Ascii`] (so "Åland Islands" sorts with "A"); ties
+    /// by ISO2.
+    Name,
+}
+
+impl FromStr for CountryOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dataset" => Ok(Self::Dataset),
+            "iso2" => Ok(Self::Iso2),
+            "name" => Ok(Self::Name),
+            other => Err(format!(
+                "unknown country order '{other}' (expected dataset, iso2 or name)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CountryOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Dataset => "dataset",
+            Self::Iso2 => "iso2",
+            Self::Name => "name",
+        })
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Reorder [`Self::countries`] by `order`; [`CountryOrder::Dataset`]
+    /// leaves them as they are.
+    ///
+    /// Call it before building a [`crate::SearchIndex`], which refers to
+    /// countries by position.
+    pub fn sort_countries(&mut self, order: CountryOrder) {
+        self.invalidate_city_index();
+        match order {
+            CountryOrder::Dataset => {}
+            CountryOrder::Iso2 => self.countries.sort_by(|a, b| a.iso2().cmp(b.iso2())),
+            CountryOrder::Name => self.countries.sort_by_cached_key(|c| {
+                (
+                    fold_key(c.name(), SearchLanguage::Ascii),
+                    c.iso2().to_string(),
+                )
+            }),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// src/overrides.rs
+//! Local corrections merged on top of the upstream dataset.
+//!
+//! Deployments often need a handful of fixes (a missing suburb, a city
+//! under an outdated name, wrong coordinates) without forking the whole
+//! dataset. An overrides file lists them per city, identified like the
+//! entries of `city_meta.json` by country, state and city:
+//!
+//! ```json
+//! {
+//!   "cities": [
+//!     { "iso2": "DE", "state": "BY", "city": "Muenchen", "rename": "München" },
+//!     { "iso2": "DE", "state": "Berlin", "city": "Berlin",
+//!       "latitude": 52.52, "longitude": 13.405 },
+//!     { "iso2": "CH", "state": "GE", "city": "Carouge", "action": "delete" },
+//!     { "iso2": "LI", "state": "Vaduz", "city": "Ebenholz", "action": "add",
+//!       "latitude": 47.14, "longitude": 9.52, "timezone": "Europe/Vaduz",
+//!       "aliases": ["Ebaholz"] }
+//!   ]
+//! }
+//! ```
+//!
+//! [`GeoDb::apply_overrides`] applies the entries in file order, so
+//! `geodb build --overrides overrides.json` bakes them into the binary.
+//! Aliases are not part of the binary layout (like those applied with
+//! [`GeoDb::apply_city_meta`]); apply the overrides again after loading a
+//! binary database to search by them.
+//!
+//! Every field an entry sets is recorded in
+//! [`City::provenance`](crate::City::provenance) under the file's name.
+use crate::model::{City, GeoBackend, GeoDb, State};
+use crate::provenance::MetaField;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "json")]
+use {crate::error::Result, std::path::Path};
+
+/// What a [`CityOverride`] does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideAction {
+    /// Change the fields given on an existing city.
+    #[default]
+    Update,
+    /// Add a new city with the fields given.
+    Add,
+    /// Remove the city.
+    Delete,
+}
+
+/// One correction to a city.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CityOverride {
+    pub iso2: String,
+    /// State code ("BY"), full code ("DE-BY") or name, as for
+    /// [`GeoDb::find_state`].
+    pub state: String,
+    /// Name of the city as in the dataset (for `add`, the new city's name).
+    pub city: String,
+    #[serde(default)]
+    pub action: OverrideAction,
+    /// New name (`update` only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Added to the city's aliases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+/// An overrides file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Overrides {
+    #[serde(default)]
+    pub cities: Vec<CityOverride>,
+    /// Name recorded as the source of the applied fields; the file name
+    /// when loaded from a path, `"overrides"` if empty.
+    #[serde(skip)]
+    pub source: String,
+}
+
+impl Overrides {
+    /// Load overrides from a JSON file (see the [module docs](self)).
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        let overrides: Self = serde_json::from_slice(&bytes)?;
+        Ok(overrides.with_source(crate::provenance::source_name(path)))
+    }
+
+    /// Set the name recorded as the source of the applied fields.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    fn source(&self) -> &str {
+        if self.source.is_empty() {
+            "overrides"
+        } else {
+            &self.source
+        }
+    }
+}
+
+/// An entry of [`Overrides`] that [`GeoDb::apply_overrides`] couldn't
+/// apply, e.g. because upstream already renamed the city.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedOverride {
+    /// Position in [`Overrides::cities`].
+    pub index: usize,
+    pub iso2: String,
+    pub state: String,
+    pub city: String,
+    pub reason: String,
+}
+
+impl fmt::Display for SkippedOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "override #{} ({} / {} / {}): {}",
+            self.index, self.iso2, self.state, self.city, self.reason
+        )
+    }
+}
+
+fn state_matches<B: GeoBackend>(state: &State<B>, query: &str) -> bool {
+    let query = query.trim();
+    (!state.state_code().is_empty() && state.state_code().eq_ignore_ascii_case(query))
+        || state
+            .full_code
+            .as_ref()
+            .is_some_and(|c| c.as_ref().eq_ignore_ascii_case(query))
+        || state.name().to_lowercase() == query.to_lowercase()
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Apply `overrides` in order and return the entries that didn't match:
+    /// an unknown country or state, an `update`/`delete` of a missing city,
+    /// or an `add` of a city the state already has.
+    ///
+    /// Cities are matched by name, case-insensitively; an entry applies to
+    /// the first match.
+    pub fn apply_overrides(&mut self, overrides: &Overrides) -> Vec<SkippedOverride> {
+        self.invalidate_city_index();
+        let mut skipped = Vec::new();
+        for (index, entry) in overrides.cities.iter().enumerate() {
+            if let Err(reason) = self.apply_override(entry, overrides.source()) {
+                skipped.push(SkippedOverride {
+                    index,
+                    iso2: entry.iso2.clone(),
+                    state: entry.state.clone(),
+                    city: entry.city.clone(),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+        skipped
+    }
+
+    fn apply_override(
+        &mut self,
+        entry: &CityOverride,
+        source: &str,
+    ) -> std::result::Result<(), &'static str> {
+        let country = self
+            .countries
+            .iter_mut()
+            .find(|c| c.iso2.as_ref().eq_ignore_ascii_case(entry.iso2.trim()))
+            .ok_or("unknown country")?;
+        let state = country
+            .states
+            .iter_mut()
+            .find(|s| state_matches(s, &entry.state))
+            .ok_or("unknown state")?;
+        let name = entry.city.trim().to_lowercase();
+        let position = state
+            .cities
+            .iter()
+            .position(|c| c.name.as_ref().to_lowercase() == name);
+
+        let city = match (entry.action, position) {
+            (OverrideAction::Add, Some(_)) => return Err("city already exists"),
+            (OverrideAction::Add, None) => {
+                state.cities.push(City {
+                    name: B::str_from(entry.city.trim()),
+                    latitude: None,
+                    longitude: None,
+                    timezone: None,
+                    aliases: Vec::new(),
+                    regions: Vec::new(),
+                    provenance: Vec::new(),
+                });
+                let city = state.cities.last_mut().expect("just pushed");
+                city.record_source(MetaField::Name, None, source);
+                city
+            }
+            (_, None) => return Err("no such city"),
+            (OverrideAction::Delete, Some(i)) => {
+                state.cities.remove(i);
+                return Ok(());
+            }
+            (OverrideAction::Update, Some(i)) => &mut state.cities[i],
+        };
+
+        if let Some(name) = &entry.rename {
+            city.name = B::str_from(name);
+            city.record_source(MetaField::Name, None, source);
+        }
+        if let Some(lat) = entry.latitude {
+            city.latitude = Some(B::float_from(lat));
+        }
+        if let Some(lng) = entry.longitude {
+            city.longitude = Some(B::float_from(lng));
+        }
+        if entry.latitude.is_some() || entry.longitude.is_some() {
+            city.record_source(MetaField::Coordinates, None, source);
+        }
+        if let Some(tz) = &entry.timezone {
+            city.timezone = Some(B::str_from(tz));
+            city.record_source(MetaField::Timezone, None, source);
+        }
+        for alias in &entry.aliases {
+            if !city.aliases.iter().any(|a| a.as_ref() == alias) {
+                city.aliases.push(B::str_from(alias));
+                city.record_source(MetaField::Alias, Some(alias), source);
+            }
+        }
+        Ok(())
+    }
+}
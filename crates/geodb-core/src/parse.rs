@@ -0,0 +1,343 @@
+// src/parse.rs
+//! Parsing the upstream JSON with the position of malformed entries.
+//!
+//! A bare `serde_json` error only says something like "invalid type: null,
+//! expected a string at line 1 column 38912345". [`parse_countries`] names
+//! the country, state and city (index and name) the error is in, with its
+//! byte offset, line and column, and with `skip_invalid` leaves those
+//! entries out instead of failing:
+//!
+//! ```no_run
+//! use geodb_core::parse::parse_countries;
+//!
+//! let json = std::fs::read_to_string("countries+states+cities.json")?;
+//! let parsed = parse_countries(&json, None, true)?;
+//! for entity in &parsed.skipped {
+//!     eprintln!("skipped {entity}");
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! A well-formed dataset is parsed in one pass; only a failing one is
+//! walked entry by entry. An entry is left out at the lowest level that
+//! fixes it: a malformed city drops the city, not its state.
+use crate::containment::AdminLevel;
+use crate::error::{GeoError, Result};
+use crate::model::{CityRaw, CountriesRaw, CountryRaw, StateRaw};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::fmt;
+
+/// Position and name of an entry in its JSON array.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityRef {
+    /// Index in the enclosing array (countries, a country's states, a
+    /// state's cities).
+    pub index: usize,
+    /// `name` of the entry, if it has a string one.
+    pub name: Option<String>,
+}
+
+impl fmt::Display for EntityRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.index)?;
+        match &self.name {
+            Some(name) => write!(f, " \"{name}\""),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A country, state or city of the source JSON that doesn't have the
+/// expected structure.
+///
+/// `state` and `city` are set according to [`Self::level`]; a city listed
+/// directly on its country has no `state`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidEntity {
+    pub level: AdminLevel,
+    pub country: EntityRef,
+    pub state: Option<EntityRef>,
+    pub city: Option<EntityRef>,
+    /// Byte offset of the error in the (decompressed) JSON, or of the
+    /// entry's start where `serde_json` has no position.
+    pub offset: usize,
+    /// 1-based line of `offset`.
+    pub line: usize,
+    /// 1-based column of `offset`, in bytes.
+    pub column: usize,
+    /// The `serde_json` message, without its position.
+    pub message: String,
+}
+
+impl fmt::Display for InvalidEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "country {}", self.country)?;
+        if let Some(state) = &self.state {
+            write!(f, ", state {state}")?;
+        }
+        if let Some(city) = &self.city {
+            write!(f, ", city {city}")?;
+        }
+        write!(
+            f,
+            ": {} (byte {}, line {} column {})",
+            self.message, self.offset, self.line, self.column
+        )
+    }
+}
+
+/// Result of [`parse_countries`].
+#[derive(Debug, Default)]
+pub struct ParsedCountries {
+    pub countries: CountriesRaw,
+    /// Entries left out (only with `skip_invalid`), in source order.
+    pub skipped: Vec<InvalidEntity>,
+}
+
+/// Parse the upstream `countries+states+cities` JSON.
+///
+/// With `iso2_filter`, only countries whose `iso2` is listed are kept (and
+/// checked). A malformed entry fails the parse with
+/// [`GeoError::InvalidEntity`] naming it, or with `skip_invalid` is left
+/// out and reported in [`ParsedCountries::skipped`]. JSON syntax errors
+/// (e.g. a truncated file) fail either way with [`GeoError::Json`].
+pub fn parse_countries(
+    json: &str,
+    iso2_filter: Option<&[&str]>,
+    skip_invalid: bool,
+) -> Result<ParsedCountries> {
+    if let Ok(mut countries) = serde_json::from_str::<CountriesRaw>(json) {
+        if let Some(filter) = iso2_filter {
+            countries.retain(|c| filter.contains(&c.iso2.as_str()));
+        }
+        return Ok(ParsedCountries {
+            countries,
+            skipped: Vec::new(),
+        });
+    }
+
+    let entries: Vec<&RawValue> = serde_json::from_str(json)?;
+    let mut walker = Walker {
+        json,
+        skipped: Vec::new(),
+    };
+    let mut countries = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let label: Label = serde_json::from_str(entry.get()).unwrap_or_default();
+        if let Some(filter) = iso2_filter {
+            let iso2 = label.iso2.as_ref().and_then(Value::as_str);
+            if !iso2.is_some_and(|iso2| filter.contains(&iso2)) {
+                continue;
+            }
+        }
+        if let Some(country) = walker.country(index, label.name(), entry) {
+            countries.push(country);
+        }
+        if !skip_invalid {
+            if let Some(first) = walker.skipped.drain(..).next() {
+                return Err(GeoError::InvalidEntity(Box::new(first)));
+            }
+        }
+    }
+    Ok(ParsedCountries {
+        countries,
+        skipped: walker.skipped,
+    })
+}
+
+/// The fields of an entry needed to name it.
+#[derive(Default, Deserialize)]
+struct Label {
+    #[serde(default)]
+    name: Option<Value>,
+    #[serde(default)]
+    iso2: Option<Value>,
+}
+
+impl Label {
+    fn name(&self) -> Option<String> {
+        self.name.as_ref()?.as_str().map(str::to_string)
+    }
+}
+
+/// The children of a country or state, unparsed.
+#[derive(Deserialize)]
+struct Children<'a> {
+    #[serde(borrow, default)]
+    states: Vec<&'a RawValue>,
+    #[serde(borrow, default)]
+    cities: Vec<&'a RawValue>,
+}
+
+/// Where an entry sits: its country and, below a country, its state.
+#[derive(Clone)]
+struct Parents {
+    country: EntityRef,
+    state: Option<EntityRef>,
+}
+
+struct Walker<'j> {
+    json: &'j str,
+    skipped: Vec<InvalidEntity>,
+}
+
+impl Walker<'_> {
+    /// The country `entry`, with malformed states and cities left out, or
+    /// `None` if it is malformed itself.
+    fn country(
+        &mut self,
+        index: usize,
+        name: Option<String>,
+        entry: &RawValue,
+    ) -> Option<CountryRaw> {
+        let error = match serde_json::from_str::<CountryRaw>(entry.get()) {
+            Ok(country) => return Some(country),
+            Err(error) => error,
+        };
+        let parents = Parents {
+            country: EntityRef { index, name },
+            state: None,
+        };
+        let Ok(children) = serde_json::from_str::<Children<'_>>(entry.get()) else {
+            self.record(AdminLevel::Country, &parents, None, entry, &error);
+            return None;
+        };
+        let mut value: Value = serde_json::from_str(entry.get()).ok()?;
+
+        let recorded = self.skipped.len();
+        let bad_states: Vec<usize> = children
+            .states
+            .iter()
+            .enumerate()
+            .filter(|&(si, state)| !self.state(&parents, si, state, &mut value["states"][si]))
+            .map(|(si, _)| si)
+            .collect();
+        let bad_cities = self.cities(&parents, &children.cities);
+        if self.skipped.len() == recorded {
+            // the country's own fields are malformed
+            self.record(AdminLevel::Country, &parents, None, entry, &error);
+            return None;
+        }
+        remove(&mut value, "states", &bad_states);
+        remove(&mut value, "cities", &bad_cities);
+        match CountryRaw::deserialize(value) {
+            Ok(country) => Some(country),
+            Err(error) => {
+                self.record(AdminLevel::Country, &parents, None, entry, &error);
+                None
+            }
+        }
+    }
+
+    /// `true` if the state `entry` is valid once its malformed cities are
+    /// removed from `value` (its parsed form).
+    fn state(
+        &mut self,
+        parents: &Parents,
+        index: usize,
+        entry: &RawValue,
+        value: &mut Value,
+    ) -> bool {
+        let Err(error) = serde_json::from_str::<StateRaw>(entry.get()) else {
+            return true;
+        };
+        let label: Label = serde_json::from_str(entry.get()).unwrap_or_default();
+        let parents = Parents {
+            state: Some(EntityRef {
+                index,
+                name: label.name(),
+            }),
+            ..parents.clone()
+        };
+        let bad_cities = match serde_json::from_str::<Children<'_>>(entry.get()) {
+            Ok(children) => self.cities(&parents, &children.cities),
+            Err(_) => Vec::new(),
+        };
+        if !bad_cities.is_empty() {
+            remove(value, "cities", &bad_cities);
+            match StateRaw::deserialize(&*value) {
+                Ok(_) => return true,
+                Err(error) => {
+                    self.record(AdminLevel::State, &parents, None, entry, &error);
+                    return false;
+                }
+            }
+        }
+        self.record(AdminLevel::State, &parents, None, entry, &error);
+        false
+    }
+
+    /// Indices of the malformed `cities`, each recorded.
+    fn cities(&mut self, parents: &Parents, cities: &[&RawValue]) -> Vec<usize> {
+        let mut bad = Vec::new();
+        for (index, entry) in cities.iter().enumerate() {
+            if let Err(error) = serde_json::from_str::<CityRaw>(entry.get()) {
+                let label: Label = serde_json::from_str(entry.get()).unwrap_or_default();
+                let city = EntityRef {
+                    index,
+                    name: label.name(),
+                };
+                self.record(AdminLevel::City, parents, Some(city), entry, &error);
+                bad.push(index);
+            }
+        }
+        bad
+    }
+
+    fn record(
+        &mut self,
+        level: AdminLevel,
+        parents: &Parents,
+        city: Option<EntityRef>,
+        entry: &RawValue,
+        error: &serde_json::Error,
+    ) {
+        let text = entry.get();
+        let start = text.as_ptr() as usize - self.json.as_ptr() as usize;
+        // errors from parsing `text` count lines and columns within it
+        let offset = start + offset_in(text, error.line(), error.column());
+        let before = &self.json[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let position = format!(" at line {} column {}", error.line(), error.column());
+        let message = error.to_string();
+        self.skipped.push(InvalidEntity {
+            level,
+            country: parents.country.clone(),
+            state: parents.state.clone(),
+            city,
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: offset - line_start + 1,
+            message: message
+                .strip_suffix(&position)
+                .unwrap_or(&message)
+                .to_string(),
+        });
+    }
+}
+
+/// Byte offset of the 1-based `line` and `column` in `text`; 0 for the
+/// line 0 of errors without a position.
+fn offset_in(text: &str, line: usize, column: usize) -> usize {
+    let line_start = match line {
+        0 => return 0,
+        1 => 0,
+        _ => text
+            .match_indices('\n')
+            .nth(line - 2)
+            .map_or(text.len(), |(i, _)| i + 1),
+    };
+    (line_start + column.saturating_sub(1)).min(text.len())
+}
+
+/// Remove the elements at `indices` (ascending) from the array `key` of
+/// the object `value`.
+fn remove(value: &mut Value, key: &str, indices: &[usize]) {
+    if let Some(Value::Array(items)) = value.get_mut(key) {
+        for &index in indices.iter().rev() {
+            items.remove(index);
+        }
+    }
+}
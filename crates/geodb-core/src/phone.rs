@@ -1,23 +1,174 @@
 // src/phone.rs
 use crate::model::{Country, GeoBackend, GeoDb};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// International call prefixes stripped by [`normalize_phone_code`], longest first.
+const INTERNATIONAL_PREFIXES: &[&str] = &["011", "00"];
+
+/// Country shown for a calling code shared by several countries, ahead of
+/// the population order of [`DialInfo::countries`].
+const PRIMARY_DIAL_COUNTRIES: &[(&str, &str)] = &[
+    ("1", "US"),
+    ("7", "RU"),
+    ("44", "GB"),
+    ("47", "NO"),
+    ("61", "AU"),
+    ("212", "MA"),
+    ("262", "RE"),
+    ("358", "FI"),
+    ("500", "FK"),
+    ("590", "GP"),
+    ("599", "CW"),
+    ("672", "NF"),
+];
+
+/// Normalize user input to the bare digits of a calling code.
+///
+/// Accepts the usual ways of writing an international prefix: a leading `+`,
+/// the `00` (ITU) or `011` (North America) dialing prefix, surrounding
+/// whitespace, parentheses and `-`/`.` separators. Returns `None` when the
+/// input contains anything else or no digits at all, so free-text queries
+/// never match as phone codes.
+///
+/// ```
+/// use geodb_core::phone::normalize_phone_code;
+///
+/// assert_eq!(normalize_phone_code("+49 ").as_deref(), Some("49"));
+/// assert_eq!(normalize_phone_code("0049").as_deref(), Some("49"));
+/// assert_eq!(normalize_phone_code("(49)").as_deref(), Some("49"));
+/// assert_eq!(normalize_phone_code("011 44").as_deref(), Some("44"));
+/// assert_eq!(normalize_phone_code("+1-684").as_deref(), Some("1684"));
+/// assert_eq!(normalize_phone_code("berlin"), None);
+/// ```
+pub fn normalize_phone_code(input: &str) -> Option<String> {
+    let mut digits = String::with_capacity(input.len());
+    let mut plus = false;
+    for ch in input.chars() {
+        match ch {
+            '0'..='9' => digits.push(ch),
+            // a single `+`, before any digit
+            '+' if !plus && digits.is_empty() => plus = true,
+            '(' | ')' | '-' | '.' => {}
+            c if c.is_whitespace() => {}
+            _ => return None,
+        }
+    }
+
+    let code = if plus {
+        digits.as_str()
+    } else {
+        INTERNATIONAL_PREFIXES
+            .iter()
+            .find_map(|p| digits.strip_prefix(p))
+            .unwrap_or(&digits)
+    };
+    (!code.is_empty()).then(|| code.to_string())
+}
 
 /// Trait providing phone-code based search helpers.
 pub trait PhoneCodeSearch<B: GeoBackend> {
     /// Find all countries whose phone code starts with the given prefix,
-    /// e.g. "+1", "+3", "0049".
+    /// e.g. "+1", "0049", "(44)"; see [`normalize_phone_code`].
     fn find_countries_by_phone_code<'a>(&'a self, prefix: &str) -> Vec<&'a Country<B>>;
 }
 
 impl<B: GeoBackend> PhoneCodeSearch<B> for GeoDb<B> {
     fn find_countries_by_phone_code<'a>(&'a self, prefix: &str) -> Vec<&'a Country<B>> {
+        let Some(prefix) = normalize_phone_code(prefix) else {
+            return Vec::new();
+        };
         self.countries
             .iter()
             .filter(|c| {
                 c.phonecode
                     .as_ref()
-                    .map(|p| p.as_ref().starts_with(prefix))
+                    .map(|p| p.as_ref().starts_with(prefix.as_str()))
                     .unwrap_or(false)
             })
             .collect()
     }
 }
+
+/// A calling code with the countries using it; see
+/// [`GeoDb::lookup_dial_code`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DialInfo<'a> {
+    /// Calling code without `+`, e.g. `44`.
+    pub code: String,
+    /// ISO2 codes of the countries using it, primary first, then by
+    /// population (largest first, unknown last).
+    pub countries: Vec<&'a str>,
+    /// The country to display for the code, e.g. `GB` for `+44` rather
+    /// than `GG`, `IM` or `JE`.
+    pub primary_country: &'a str,
+}
+
+impl<'a> DialInfo<'a> {
+    fn new<B: GeoBackend>(code: String, mut countries: Vec<&'a Country<B>>) -> Self {
+        let primary = PRIMARY_DIAL_COUNTRIES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, iso2)| *iso2);
+        // stable, so equal populations keep database order
+        countries.sort_by_key(|c| {
+            (
+                Some(c.iso2()) != primary,
+                std::cmp::Reverse(c.population.unwrap_or(-1)),
+            )
+        });
+        let countries: Vec<&str> = countries.iter().map(|c| c.iso2()).collect();
+        Self {
+            primary_country: countries[0],
+            code,
+            countries,
+        }
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Calling codes of all countries with their normalized codes, as
+    /// `(code, country)` pairs.
+    fn dial_codes_by_country(&self) -> impl Iterator<Item = (String, &Country<B>)> {
+        self.countries.iter().filter_map(|c| {
+            let code = normalize_phone_code(c.phonecode.as_ref()?.as_ref())?;
+            Some((code, c))
+        })
+    }
+
+    /// The calling code `input` starts with (`"+44"`, `"0044"`, or a whole
+    /// number such as `"+44 20 7946 0000"`), with the countries sharing it
+    /// and the one to display; `None` if no country has a matching code.
+    ///
+    /// Unlike [`PhoneCodeSearch::find_countries_by_phone_code`], which
+    /// returns every code starting with a prefix (`"+3"` finds `+30`,
+    /// `+31`, ...), this picks the longest code that is a prefix of the
+    /// input.
+    pub fn lookup_dial_code(&self, input: &str) -> Option<DialInfo<'_>> {
+        let digits = normalize_phone_code(input)?;
+        let code = self
+            .dial_codes_by_country()
+            .map(|(code, _)| code)
+            .filter(|code| digits.starts_with(code.as_str()))
+            .max_by_key(String::len)?;
+        let countries = self
+            .dial_codes_by_country()
+            .filter(|(c, _)| *c == code)
+            .map(|(_, country)| country)
+            .collect();
+        Some(DialInfo::new(code, countries))
+    }
+
+    /// All calling codes, in numeric order, e.g. for a dial-code picker.
+    pub fn dial_codes(&self) -> Vec<DialInfo<'_>> {
+        // keyed by length first: numeric order for codes without leading zeros
+        let mut by_code: BTreeMap<(usize, String), Vec<&Country<B>>> = BTreeMap::new();
+        for (code, country) in self.dial_codes_by_country() {
+            by_code.entry((code.len(), code)).or_default().push(country);
+        }
+        by_code
+            .into_iter()
+            .map(|((_, code), countries)| DialInfo::new(code, countries))
+            .collect()
+    }
+}
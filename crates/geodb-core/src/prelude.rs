@@ -3,10 +3,17 @@
 #![allow(unused_imports)]
 
 pub use crate::alias::{CityMeta, CityMetaIndex};
+pub use crate::cache::GeoDbCache;
+pub use crate::containment::{AdminLevel, AdminPath};
+pub use crate::country_id::{CityId, CountryId, StateId};
 pub use crate::error::{GeoDbError, GeoError, Result};
 pub use crate::model::{
-    build_geodb, City, Country, CountryTimezone, DefaultBackend, DefaultGeoDb, GeoBackend, GeoDb,
-    StandardBackend, State,
+    build_geodb, try_build_geodb, City, Country, CountryTimezone, DefaultBackend, DefaultGeoDb,
+    GeoBackend, GeoDb, StandardBackend, State,
 };
 pub use crate::phone::PhoneCodeSearch;
 pub use crate::region::*;
+pub use crate::search::{
+    CityFilter, ExpansionTable, LevelScores, NameMatch, ScoringProfile, SearchLanguage,
+    SearchOptions,
+};
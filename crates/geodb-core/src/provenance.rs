@@ -0,0 +1,116 @@
+// src/provenance.rs
+//! Where the sidecar data on a city came from.
+//!
+//! Aliases and regions come from city meta files, names, coordinates and
+//! timezones may be corrected by an overrides file. Each applied value is
+//! recorded on the city with the name of its source (the file name when
+//! loaded from a path), so a curator can trace a wrong alias back to the
+//! file that introduced it:
+//!
+//! ```no_run
+//! use geodb_core::{CityMetaIndex, GeoDb, MetaField, StandardBackend};
+//!
+//! let mut db = GeoDb::<StandardBackend>::load()?;
+//! db.apply_city_meta(&CityMetaIndex::load_default()?);
+//! let (city, _, _) = db.find_cities_by_substring("Geneva")[0];
+//! for entry in city.provenance() {
+//!     println!("{entry}"); // alias "Genf" from city_meta.json
+//! }
+//! assert_eq!(city.source_of(MetaField::Alias, Some("Genf")), Some("city_meta.json"));
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::model::{City, GeoBackend};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "json")]
+use std::path::Path;
+
+/// A city field filled or corrected from a sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetaField {
+    /// The city's name (renamed, or the city was added).
+    Name,
+    /// Latitude and longitude.
+    Coordinates,
+    Timezone,
+    /// One alias.
+    Alias,
+    /// One informal region.
+    Region,
+}
+
+impl fmt::Display for MetaField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Name => "name",
+            Self::Coordinates => "coordinates",
+            Self::Timezone => "timezone",
+            Self::Alias => "alias",
+            Self::Region => "region",
+        })
+    }
+}
+
+/// One recorded value of a [`MetaField`] and its source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSource {
+    pub field: MetaField,
+    /// The alias or region; `None` for single-valued fields.
+    pub value: Option<String>,
+    /// Name of the sidecar, e.g. `city_meta.json`.
+    pub source: String,
+}
+
+impl fmt::Display for FieldSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{} {value:?} from {}", self.field, self.source),
+            None => write!(f, "{} from {}", self.field, self.source),
+        }
+    }
+}
+
+impl<B: GeoBackend> City<B> {
+    /// Sources of the sidecar data applied to this city, in the order it
+    /// was applied (empty for data straight from the dataset).
+    pub fn provenance(&self) -> &[FieldSource] {
+        &self.provenance
+    }
+
+    /// Source of `field` (for aliases and regions, of the one equal to
+    /// `value`, case-insensitively); the latest one if it was set twice.
+    pub fn source_of(&self, field: MetaField, value: Option<&str>) -> Option<&str> {
+        self.provenance
+            .iter()
+            .rev()
+            .find(|p| {
+                p.field == field
+                    && match (value, &p.value) {
+                        (Some(value), Some(v)) => v.to_lowercase() == value.to_lowercase(),
+                        (None, _) => true,
+                        (Some(_), None) => false,
+                    }
+            })
+            .map(|p| p.source.as_str())
+    }
+
+    /// Record that `field` (with `value`, for aliases and regions) came
+    /// from `source`.
+    pub(crate) fn record_source(&mut self, field: MetaField, value: Option<&str>, source: &str) {
+        self.provenance.push(FieldSource {
+            field,
+            value: value.map(str::to_string),
+            source: source.to_string(),
+        });
+    }
+}
+
+/// Source name for a sidecar loaded from `path`: its file name.
+#[cfg(feature = "json")]
+pub(crate) fn source_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,346 @@
+// src/query_cache.rs
+//! Cached search results for repetitive query workloads.
+//!
+//! Autocomplete inputs send the same prefixes over and over ("b", "be",
+//! "ber", ...). [`CachedGeoDb`] wraps a shared database and keeps the owned
+//! results of recent searches in a bounded LRU cache, optionally expiring
+//! them after a TTL. It implements [`DynGeoSearch`], so it can replace the
+//! plain database wherever a `dyn DynGeoSearch` is injected:
+//!
+//! ```no_run
+//! use geodb_core::{CachedGeoDb, DynGeoSearch, GeoDb, StandardBackend};
+//! use std::time::Duration;
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let cached = CachedGeoDb::new(db)
+//!     .with_max_entries(4096)
+//!     .with_ttl(Duration::from_secs(300));
+//!
+//! let first = cached.smart_search("ber");
+//! let again = cached.smart_search("  BER "); // same normalized query: cache hit
+//! assert_eq!(first, again);
+//! println!("{:?}", cached.cache_stats());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Queries are folded with the options' [`SearchLanguage`] (see
+//! [`fold_key`]) and their whitespace collapsed before they are searched
+//! and used as cache key. The [`SearchOptions`] used by
+//! `smart_search` are fixed per wrapper, so every cached entry belongs to
+//! them; [`CachedGeoDb::set_options`] swaps them and clears the cache.
+//! Cheap lookups (`country_by_code`, `states_of`, ...) are passed through.
+use crate::country_id::{CityId, CountryId, StateId};
+use crate::dyn_search::{CityDto, CountryDto, DynGeoSearch, HitDto, StateDto};
+use crate::model::{DbStats, DefaultBackend, GeoBackend, GeoDb};
+#[cfg(doc)]
+use crate::search::{fold_key, SearchLanguage};
+use crate::search::{fold_words, SearchOptions};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Entries kept by default.
+pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+/// Which search produced a cached result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum QueryKind {
+    Smart,
+    /// Autocomplete hits, at most `limit` of them.
+    Autocomplete {
+        limit: usize,
+    },
+    PhoneCode,
+    States,
+    Cities,
+}
+
+#[derive(Debug, Clone)]
+enum CachedResult {
+    Hits(Arc<Vec<HitDto>>),
+    Countries(Arc<Vec<CountryDto>>),
+    States(Arc<Vec<StateDto>>),
+    Cities(Arc<Vec<CityDto>>),
+}
+
+struct Entry {
+    result: CachedResult,
+    /// Only set when a TTL is configured (no clock needed otherwise).
+    inserted: Option<Instant>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<(QueryKind, String), Entry>,
+    clock: u64,
+}
+
+/// Hit/miss counters of a [`CachedGeoDb`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Results currently cached.
+    pub entries: usize,
+}
+
+/// A [`GeoDb`] with an LRU cache of search results; see the
+/// [module docs](self).
+///
+/// Thread-safe; share it by reference or in an `Arc`. The database itself
+/// is held in an [`Arc`], so several wrappers (e.g. with different options)
+/// can share one loaded database.
+pub struct CachedGeoDb<B: GeoBackend = DefaultBackend> {
+    db: Arc<GeoDb<B>>,
+    options: SearchOptions,
+    max_entries: usize,
+    ttl: Option<Duration>,
+    inner: Mutex<Inner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<B: GeoBackend> std::fmt::Debug for CachedGeoDb<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedGeoDb")
+            .field("max_entries", &self.max_entries)
+            .field("ttl", &self.ttl)
+            .field("stats", &self.cache_stats())
+            .finish()
+    }
+}
+
+impl<B: GeoBackend> CachedGeoDb<B> {
+    /// Wrap `db` with default search options, [`DEFAULT_MAX_ENTRIES`] and
+    /// no TTL.
+    pub fn new(db: impl Into<Arc<GeoDb<B>>>) -> Self {
+        Self {
+            db: db.into(),
+            options: SearchOptions::default(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            ttl: None,
+            inner: Mutex::new(Inner::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Keep at most `max` results, evicting the least recently used one.
+    /// A limit of 0 disables caching.
+    pub fn with_max_entries(mut self, max: usize) -> Self {
+        self.max_entries = max;
+        self
+    }
+
+    /// Expire results `ttl` after they were computed.
+    ///
+    /// Uses `std::time::Instant`, which isn't available on
+    /// `wasm32-unknown-unknown`; leave the TTL unset there.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Search options used by `smart_search`.
+    pub fn with_options(mut self, options: SearchOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Replace the search options; cached results are dropped, since they
+    /// were computed with the old ones.
+    pub fn set_options(&mut self, options: SearchOptions) {
+        self.options = options;
+        self.clear();
+    }
+
+    /// The wrapped database.
+    pub fn db(&self) -> &Arc<GeoDb<B>> {
+        &self.db
+    }
+
+    /// The search options used by `smart_search`.
+    pub fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    /// Drop all cached results (counters are kept).
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Hit/miss counters and the current number of entries.
+    pub fn cache_stats(&self) -> QueryCacheStats {
+        QueryCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().entries.len(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // entries are replaced whole, so a poisoned map is still consistent
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Cached result for `(kind, query)`, computing it with `search` (on the
+    /// normalized query) on a miss. The lock isn't held while searching.
+    fn cached(
+        &self,
+        kind: QueryKind,
+        query: &str,
+        search: impl FnOnce(&str) -> CachedResult,
+    ) -> CachedResult {
+        // folded like the search folds it, so equal keys give equal results
+        let key = (kind, fold_words(query, self.options.language));
+        {
+            let mut inner = self.lock();
+            inner.clock += 1;
+            let clock = inner.clock;
+            if let Some(entry) = inner.entries.get_mut(&key) {
+                let fresh = match (self.ttl, entry.inserted) {
+                    (Some(ttl), Some(inserted)) => inserted.elapsed() < ttl,
+                    _ => true,
+                };
+                if fresh {
+                    entry.last_used = clock;
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return entry.result.clone();
+                }
+                inner.entries.remove(&key);
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = search(&key.1);
+        if self.max_entries == 0 {
+            return result;
+        }
+
+        let mut inner = self.lock();
+        while inner.entries.len() >= self.max_entries {
+            let oldest = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone())
+                .expect("non-empty");
+            inner.entries.remove(&oldest);
+        }
+        inner.clock += 1;
+        let entry = Entry {
+            result: result.clone(),
+            inserted: self.ttl.map(|_| Instant::now()),
+            last_used: inner.clock,
+        };
+        inner.entries.insert(key, entry);
+        result
+    }
+
+    /// `smart_search` results as a shared list, without copying them out of
+    /// the cache.
+    pub fn smart_search_shared(&self, query: &str) -> Arc<Vec<HitDto>> {
+        let result = self.cached(QueryKind::Smart, query, |q| {
+            let hits = self.db.smart_search_with(q, &self.options);
+            CachedResult::Hits(Arc::new(hits.iter().map(HitDto::from).collect()))
+        });
+        match result {
+            CachedResult::Hits(hits) => hits,
+            _ => unreachable!("smart search entries hold hits"),
+        }
+    }
+}
+
+impl<B: GeoBackend> DynGeoSearch for CachedGeoDb<B> {
+    fn stats(&self) -> DbStats {
+        self.db.stats()
+    }
+
+    fn countries(&self) -> Vec<CountryDto> {
+        DynGeoSearch::countries(&*self.db)
+    }
+
+    fn country_by_code(&self, code: &str) -> Option<CountryDto> {
+        self.db.country_by_code(code)
+    }
+
+    fn country_by_id(&self, id: CountryId) -> Option<CountryDto> {
+        self.db.country_by_id(id)
+    }
+
+    fn state_by_id(&self, id: StateId) -> Option<StateDto> {
+        DynGeoSearch::state_by_id(&*self.db, id)
+    }
+
+    fn city_by_id(&self, id: CityId) -> Option<CityDto> {
+        DynGeoSearch::city_by_id(&*self.db, id)
+    }
+
+    fn states_of(&self, iso2: &str) -> Vec<StateDto> {
+        self.db.states_of(iso2)
+    }
+
+    fn countries_by_phone_code(&self, prefix: &str) -> Vec<CountryDto> {
+        let result = self.cached(QueryKind::PhoneCode, prefix, |q| {
+            CachedResult::Countries(Arc::new(self.db.countries_by_phone_code(q)))
+        });
+        match result {
+            CachedResult::Countries(countries) => countries.to_vec(),
+            _ => unreachable!("phone code entries hold countries"),
+        }
+    }
+
+    fn countries_by_currency(&self, code: &str) -> Vec<CountryDto> {
+        self.db.countries_by_currency(code)
+    }
+
+    fn states_by_substring(&self, substr: &str) -> Vec<StateDto> {
+        let result = self.cached(QueryKind::States, substr, |q| {
+            CachedResult::States(Arc::new(self.db.states_by_substring(q)))
+        });
+        match result {
+            CachedResult::States(states) => states.to_vec(),
+            _ => unreachable!("state entries hold states"),
+        }
+    }
+
+    fn cities_by_substring(&self, substr: &str) -> Vec<CityDto> {
+        let result = self.cached(QueryKind::Cities, substr, |q| {
+            CachedResult::Cities(Arc::new(self.db.cities_by_substring(q)))
+        });
+        match result {
+            CachedResult::Cities(cities) => cities.to_vec(),
+            _ => unreachable!("city entries hold cities"),
+        }
+    }
+
+    fn smart_search(&self, query: &str) -> Vec<HitDto> {
+        self.smart_search_shared(query).to_vec()
+    }
+
+    fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<HitDto> {
+        let result = self.cached(QueryKind::Autocomplete { limit }, prefix, |q| {
+            let hits = self.db.autocomplete_with(q, limit, &self.options);
+            CachedResult::Hits(Arc::new(hits.iter().map(HitDto::from).collect()))
+        });
+        match result {
+            CachedResult::Hits(hits) => hits.to_vec(),
+            _ => unreachable!("autocomplete entries hold hits"),
+        }
+    }
+
+    fn nearest_city(&self, lat: f64, lng: f64, radius_km: f64) -> Option<CityDto> {
+        self.db.nearest_city(lat, lng, radius_km)
+    }
+
+    fn cities_in_bbox(
+        &self,
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+    ) -> Vec<CityDto> {
+        DynGeoSearch::cities_in_bbox(&*self.db, min_lat, min_lng, max_lat, max_lng)
+    }
+}
@@ -0,0 +1,1376 @@
+// src/search.rs
+//! Search options and query normalization.
+//!
+//! [`GeoDb::smart_search`] uses the default [`SearchOptions`]. Callers that
+//! need to tune matching (e.g. regional abbreviations like "St." → "Saint")
+//! use [`GeoDb::smart_search_with`] instead.
+use crate::country_id::{CityId, CountryId};
+use crate::model::{City, Country, GeoBackend, GeoDb, SmartHit, SmartItem, State};
+use crate::phone::PhoneCodeSearch;
+use crate::search_index::SearchIndex;
+use crate::spatial::CityRef;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "json")]
+use {crate::error::Result, std::fs, std::path::Path};
+#[cfg(feature = "multilingual-cities")]
+use {crate::labels::CityLabelIndex, std::sync::Arc};
+
+/// Token expansion table applied during query normalization.
+///
+/// Each entry maps an abbreviation (e.g. "st", "ft", "mt") to its expansion
+/// ("saint", "fort", "mount"). Keys are matched case-insensitively against
+/// whole tokens; a trailing dot on the token is ignored, so "St." and "st"
+/// expand the same way.
+///
+/// When a table is used, it is applied to both the query and the candidate
+/// names, so "st louis", "St. Louis" and "saint louis" all compare equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "HashMap<String, String>", into = "HashMap<String, String>")]
+pub struct ExpansionTable {
+    entries: HashMap<String, String>,
+}
+
+impl From<HashMap<String, String>> for ExpansionTable {
+    /// Entries are normalized like [`ExpansionTable::insert`].
+    fn from(raw: HashMap<String, String>) -> Self {
+        let mut table = Self::new();
+        for (k, v) in &raw {
+            table.insert(k, v);
+        }
+        table
+    }
+}
+
+impl From<ExpansionTable> for HashMap<String, String> {
+    fn from(table: ExpansionTable) -> Self {
+        table.entries
+    }
+}
+
+impl ExpansionTable {
+    /// Create an empty table (no expansions).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style variant of [`Self::insert`].
+    pub fn with(mut self, abbreviation: &str, expansion: &str) -> Self {
+        self.insert(abbreviation, expansion);
+        self
+    }
+
+    /// Add or replace an expansion.
+    pub fn insert(&mut self, abbreviation: &str, expansion: &str) {
+        let key = abbreviation
+            .trim()
+            .trim_end_matches('.')
+            .to_ascii_lowercase();
+        if !key.is_empty() {
+            self.entries
+                .insert(key, expansion.trim().to_ascii_lowercase());
+        }
+    }
+
+    /// Number of entries in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` if the table contains no expansions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Load a table from a JSON object file.
+    ///
+    /// Expected format:
+    /// { "st": "saint", "ft": "fort", "mt": "mount" }
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        let raw: HashMap<String, String> = serde_json::from_slice(&bytes)?;
+        Ok(raw.into())
+    }
+
+    /// Normalize `text` into its comparison form.
+    ///
+    /// Lowercases (ASCII), collapses whitespace and replaces every token found
+    /// in the table by its expansion.
+    pub fn normalize(&self, text: &str) -> String {
+        self.normalize_with(text, SearchLanguage::Simple)
+    }
+
+    /// Like [`Self::normalize`], folding `text` with the analyzer of
+    /// `language` instead of plain ASCII lowercasing.
+    pub fn normalize_with(&self, text: &str, language: SearchLanguage) -> String {
+        let lower = fold_key(text, language);
+        if self.is_empty() {
+            return lower;
+        }
+
+        let mut out = String::with_capacity(lower.len());
+        for token in lower.split_whitespace() {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            match self.entries.get(token.trim_end_matches('.')) {
+                Some(expansion) => out.push_str(expansion),
+                None => out.push_str(token),
+            }
+        }
+        out
+    }
+}
+
+/// Normalization pipeline ("analyzer") used to compare queries and names.
+///
+/// Lowercasing and accent handling are language-specific: a German user
+/// types "Muenchen" for "München", a Turkish user types "İstanbul", whose
+/// Unicode lowercase form ("i̇stanbul") carries a combining dot and no longer
+/// matches "istanbul". Both the query and the candidate names are folded
+/// with the same analyzer.
+///
+/// In config files the variants are written in lowercase or as language
+/// codes (`language = "de"`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchLanguage {
+    /// ASCII lowercasing only; other letters must match exactly. The
+    /// historical behavior and the default.
+    #[default]
+    Simple,
+    /// Unicode lowercasing with Latin diacritics folded to ASCII, so
+    /// "São Paulo", "SAO PAULO" and "sao paulo" compare equal.
+    #[serde(alias = "en")]
+    Ascii,
+    /// Unicode lowercasing with German transliteration: "ä", "ö", "ü" →
+    /// "ae", "oe", "ue" and "ß" → "ss", so "Muenchen" finds "München".
+    #[serde(alias = "de")]
+    German,
+    /// Unicode lowercasing where dotted "İ", dotless "ı" and "I" all fold
+    /// to "i", so "İstanbul", "ISTANBUL" and "Istanbul" compare equal.
+    #[serde(alias = "tr")]
+    Turkish,
+}
+
+impl SearchLanguage {
+    /// Analyzer for an ISO 639-1 language code ("de", "tr", "en");
+    /// `None` for languages without a dedicated analyzer.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "en" => Some(Self::Ascii),
+            "de" => Some(Self::German),
+            "tr" => Some(Self::Turkish),
+            _ => None,
+        }
+    }
+
+    /// Fold `text` into its comparison form (whitespace is kept as is).
+    pub fn fold(self, text: &str) -> String {
+        if self == Self::Simple {
+            return text.to_ascii_lowercase();
+        }
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            match (self, c) {
+                // combining marks: accents of decomposed input, and the dot
+                // that Unicode lowercasing leaves behind for "İ"
+                (_, c) if is_combining_mark(c) => {}
+                (Self::Turkish, 'İ' | 'I' | 'ı') => out.push('i'),
+                (Self::German, 'ä' | 'Ä') => out.push_str("ae"),
+                (Self::German, 'ö' | 'Ö') => out.push_str("oe"),
+                (Self::German, 'ü' | 'Ü') => out.push_str("ue"),
+                (Self::German, 'ß' | 'ẞ') => out.push_str("ss"),
+                _ => {
+                    for lower in c.to_lowercase() {
+                        match (self, fold_latin(lower)) {
+                            (_, None) if is_combining_mark(lower) => {}
+                            (Self::Ascii, Some(ascii)) => out.push_str(ascii),
+                            _ => out.push(lower),
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+impl std::str::FromStr for SearchLanguage {
+    type Err = String;
+
+    /// Parse the config spelling: a variant name in lowercase or a language
+    /// code ("simple", "ascii"/"en", "german"/"de", "turkish"/"tr").
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "simple" => Ok(Self::Simple),
+            "ascii" => Ok(Self::Ascii),
+            "german" => Ok(Self::German),
+            "turkish" => Ok(Self::Turkish),
+            code => Self::from_code(code).ok_or_else(|| format!("unknown search language '{s}'")),
+        }
+    }
+}
+
+/// Comparison form of `text` under `language`: how smart search folds both
+/// queries and names before comparing them (without an expansion table).
+///
+/// The bindings export it, so clients can pre-normalize input and key their
+/// caches the same way the engine compares.
+pub fn fold_key(text: &str, language: SearchLanguage) -> String {
+    language.fold(text.trim())
+}
+
+/// [`fold_key`] with runs of whitespace collapsed to one space, for
+/// comparing whole names ("Frankfurt  am Main").
+pub(crate) fn fold_words(text: &str, language: SearchLanguage) -> String {
+    fold_key(text, language)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `true` if `a` and `b` fold to the same [`fold_key`].
+pub fn equals_folded(a: &str, b: &str, language: SearchLanguage) -> bool {
+    fold_key(a, language) == fold_key(b, language)
+}
+
+/// Edit distance between `a` and `b` counting insertions, deletions,
+/// substitutions and swaps of adjacent characters ("zurihc" → "zurich" is
+/// one edit), or `None` if it is above `max`.
+///
+/// This is the optimal string alignment variant of Damerau-Levenshtein;
+/// fuzzy smart search compares folded names with it.
+pub fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    if a.chars().count().abs_diff(b.chars().count()) > max {
+        return None;
+    }
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    // rows i - 2, i - 1 and i of the distance matrix
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        row[0] = i;
+        for j in 1..=b.len() {
+            let substitution = previous[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            row[j] = substitution.min(previous[j] + 1).min(row[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        // the distance never drops below the smallest entry of a row
+        if row.iter().min().is_some_and(|&m| m > max) {
+            return None;
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut row);
+    }
+    Some(previous[b.len()]).filter(|&d| d <= max)
+}
+
+fn is_combining_mark(c: char) -> bool {
+    ('\u{300}'..='\u{36f}').contains(&c)
+}
+
+/// ASCII spelling of a lowercase Latin letter with diacritics.
+fn fold_latin(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+/// Options controlling [`GeoDb::smart_search_with`].
+///
+/// Construct with `SearchOptions::default()` and override the fields you
+/// need. Deserializable from config files; missing fields keep their
+/// defaults:
+///
+/// ```toml
+/// [search]
+/// language = "de"
+/// include_phone = false
+/// limit = 20
+///
+/// [search.scoring]
+/// alias_penalty = 10
+///
+/// [search.expansions]
+/// st = "saint"
+/// mt = "mount"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    /// Abbreviation expansions applied during normalization (empty by default).
+    pub expansions: ExpansionTable,
+    /// Multilingual city labels; cities also match by any of their labels.
+    /// Not part of the serialized form; attach after loading a config.
+    #[cfg(feature = "multilingual-cities")]
+    #[serde(skip)]
+    pub city_labels: Option<Arc<CityLabelIndex>>,
+    /// Skip countries, states and cities without coordinates.
+    pub must_have_coords: bool,
+    /// Skip territories (see [`Country::is_territory`]) and their states and
+    /// cities.
+    pub exclude_territories: bool,
+    /// Skip countries with a smaller (or unknown) population. The dataset
+    /// has no population for states and cities, so they are not affected.
+    pub min_population: Option<i64>,
+    /// Analyzer used to fold the query and the names it is compared with.
+    pub language: SearchLanguage,
+    /// Return countries whose calling code matches the query ("+41",
+    /// "0041", "41"). On by default; autocomplete inputs where a number is
+    /// more likely a street number turn it off.
+    pub include_phone: bool,
+    /// Return countries whose currency code or full currency name equals
+    /// the query ("CHF", "swiss franc"). On by default.
+    pub include_currency: bool,
+    /// Scan cities (names, aliases and labels). On by default; turning it
+    /// off leaves the cheap country and state hits, see
+    /// [`GeoDb::smart_search_tiered`].
+    pub include_cities: bool,
+    /// Scores of the different kinds of matches.
+    pub scoring: ScoringProfile,
+    /// Return at most this many hits (after `offset`).
+    pub limit: Option<usize>,
+    /// Skip this many of the best hits, for paging.
+    pub offset: usize,
+    /// Drop hits scoring below this.
+    pub min_score: Option<i32>,
+    /// Retry a query without hits with its words joined ("newyork",
+    /// "sanfrancisco"); see [`GeoDb::smart_search_with`]. On by default.
+    pub word_segmentation: bool,
+    /// Also match names within this many typos of the query (0, the
+    /// default, turns typo tolerance off); see [`GeoDb::smart_search_fuzzy`].
+    pub max_edit_distance: usize,
+    /// What the current user picked before; hits matching it get small
+    /// boosts (empty by default).
+    pub session: SearchSessionHints,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            expansions: ExpansionTable::default(),
+            #[cfg(feature = "multilingual-cities")]
+            city_labels: None,
+            must_have_coords: false,
+            exclude_territories: false,
+            min_population: None,
+            language: SearchLanguage::default(),
+            include_phone: true,
+            include_currency: true,
+            include_cities: true,
+            scoring: ScoringProfile::default(),
+            limit: None,
+            offset: 0,
+            min_score: None,
+            word_segmentation: true,
+            max_edit_distance: 0,
+            session: SearchSessionHints::default(),
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Options using the given expansion table.
+    pub fn with_expansions(mut self, expansions: ExpansionTable) -> Self {
+        self.expansions = expansions;
+        self
+    }
+
+    /// Options matching cities by their multilingual labels as well.
+    #[cfg(feature = "multilingual-cities")]
+    pub fn with_city_labels(mut self, labels: Arc<CityLabelIndex>) -> Self {
+        self.city_labels = Some(labels);
+        self
+    }
+
+    /// Only return hits with coordinates.
+    pub fn with_must_have_coords(mut self, yes: bool) -> Self {
+        self.must_have_coords = yes;
+        self
+    }
+
+    /// Leave out territories and everything inside them.
+    pub fn with_exclude_territories(mut self, yes: bool) -> Self {
+        self.exclude_territories = yes;
+        self
+    }
+
+    /// Only return countries with at least `population` inhabitants.
+    pub fn with_min_population(mut self, population: i64) -> Self {
+        self.min_population = Some(population);
+        self
+    }
+
+    /// Fold query and names with the analyzer of `language`.
+    pub fn with_language(mut self, language: SearchLanguage) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Include or leave out phone-code hits.
+    pub fn with_include_phone(mut self, yes: bool) -> Self {
+        self.include_phone = yes;
+        self
+    }
+
+    /// Include or leave out currency hits.
+    pub fn with_include_currency(mut self, yes: bool) -> Self {
+        self.include_currency = yes;
+        self
+    }
+
+    /// Include cities (the default), or return country and state hits only.
+    pub fn with_include_cities(mut self, yes: bool) -> Self {
+        self.include_cities = yes;
+        self
+    }
+
+    /// Rank hits with `scoring` instead of the default profile.
+    pub fn with_scoring(mut self, scoring: ScoringProfile) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Return at most `limit` hits.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the `offset` best hits; with [`Self::with_limit`], page `n`
+    /// starts at `n * limit`.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Only return hits scoring at least `score`.
+    pub fn with_min_score(mut self, score: i32) -> Self {
+        self.min_score = Some(score);
+        self
+    }
+
+    /// Retry queries without hits with their words joined, or don't.
+    pub fn with_word_segmentation(mut self, yes: bool) -> Self {
+        self.word_segmentation = yes;
+        self
+    }
+
+    /// Also match names within `max` typos of the query.
+    pub fn with_max_edit_distance(mut self, max: usize) -> Self {
+        self.max_edit_distance = max;
+        self
+    }
+
+    /// Boost hits the current user is likely after.
+    pub fn with_session(mut self, session: SearchSessionHints) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Typos allowed for the normalized query `q`: `max_edit_distance`, but
+    /// none below 3 characters and at most one below 6, where a typo would
+    /// make the query match nearly anything.
+    fn typos_for(&self, q: &str) -> usize {
+        let by_length = match q.chars().count() {
+            0..=2 => 0,
+            3..=5 => 1,
+            _ => 2,
+        };
+        self.max_edit_distance.min(by_length)
+    }
+
+    /// Normalize `text` like [`GeoDb::smart_search_with`] does: the
+    /// language analyzer followed by the expansion table.
+    pub fn normalize(&self, text: &str) -> String {
+        self.expansions.normalize_with(text, self.language)
+    }
+
+    /// `true` if states and cities of `country` may be returned.
+    fn allows_within<B: GeoBackend>(&self, country: &Country<B>) -> bool {
+        !(self.exclude_territories && country.is_territory())
+    }
+
+    /// `true` if `country` itself may be returned.
+    fn allows_country<B: GeoBackend>(&self, country: &Country<B>) -> bool {
+        self.allows_within(country)
+            && self.allows_position::<B>(country.latitude, country.longitude)
+            && self
+                .min_population
+                .is_none_or(|min| country.population().is_some_and(|p| p >= min))
+    }
+
+    fn allows_position<B: GeoBackend>(&self, lat: Option<B::Float>, lng: Option<B::Float>) -> bool {
+        !self.must_have_coords || (lat.is_some() && lng.is_some())
+    }
+}
+
+/// Per-user context for personalized autocomplete, set as
+/// [`SearchOptions::session`].
+///
+/// Cities the user recently selected get
+/// [`ScoringProfile::recent_city_boost`], and countries, states and cities
+/// in their preferred countries get
+/// [`ScoringProfile::preferred_country_boost`]. The boosts are small, so
+/// they reorder hits of similar quality (which "Paris" comes first) without
+/// lifting weak matches above strong ones. Hints never add hits.
+///
+/// ```
+/// # use geodb_core::{CityId, CountryId, SearchOptions, SearchSessionHints};
+/// let session = SearchSessionHints {
+///     recent_city_ids: vec!["US/43/2187".parse::<CityId>().unwrap()],
+///     preferred_countries: vec![CountryId::new("US").unwrap()],
+/// };
+/// let options = SearchOptions::default().with_session(session);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchSessionHints {
+    /// Cities the user picked, e.g. from earlier autocomplete sessions. Ids
+    /// of cities the database lacks are ignored.
+    pub recent_city_ids: Vec<CityId>,
+    /// Countries the user is most likely searching in (locale, shipping
+    /// address, ...).
+    pub preferred_countries: Vec<CountryId>,
+}
+
+impl SearchSessionHints {
+    /// `true` if there is nothing to boost.
+    pub fn is_empty(&self) -> bool {
+        self.recent_city_ids.is_empty() && self.preferred_countries.is_empty()
+    }
+
+    /// Add the boosts of `scoring` to the matching `hits`.
+    fn boost<'a, B: GeoBackend>(
+        &self,
+        db: &'a GeoDb<B>,
+        hits: &mut [SmartHit<'a, B>],
+        scoring: &ScoringProfile,
+    ) {
+        if self.is_empty() {
+            return;
+        }
+        let recent: Vec<&City<B>> = self
+            .recent_city_ids
+            .iter()
+            .filter_map(|&id| db.city_by_id(id))
+            .collect();
+        for hit in hits {
+            let country = match hit.item {
+                SmartItem::Country(country)
+                | SmartItem::State { country, .. }
+                | SmartItem::City { country, .. } => country,
+                #[cfg(feature = "districts")]
+                SmartItem::District { country, .. } => country,
+            };
+            if let SmartItem::City { city, .. } = hit.item {
+                if recent.iter().any(|&r| std::ptr::eq(r, city)) {
+                    hit.score += scoring.recent_city_boost;
+                }
+            }
+            if self
+                .preferred_countries
+                .iter()
+                .any(|id| id.as_str() == country.iso2())
+            {
+                hit.score += scoring.preferred_country_boost;
+            }
+        }
+    }
+}
+
+/// Hits of [`GeoDb::smart_search_tiered`]: country and state hits first,
+/// all hits once [`Self::load_more`] scanned the cities.
+pub struct TieredHits<'a, B: GeoBackend> {
+    db: &'a GeoDb<B>,
+    query: String,
+    options: SearchOptions,
+    hits: Vec<SmartHit<'a, B>>,
+    complete: bool,
+}
+
+impl<'a, B: GeoBackend> TieredHits<'a, B> {
+    /// The hits found so far, ranked like [`GeoDb::smart_search_with`].
+    pub fn hits(&self) -> &[SmartHit<'a, B>] {
+        &self.hits
+    }
+
+    /// `true` once [`Self::load_more`] scanned the cities.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Scan the cities as well and return all hits, exactly what
+    /// [`GeoDb::smart_search_with`] returns for the query and options.
+    /// Later calls return the same hits without searching again.
+    pub fn load_more(&mut self) -> &[SmartHit<'a, B>] {
+        if !self.complete {
+            let options = self.options.clone().with_include_cities(true);
+            self.hits = self.db.smart_search_with(&self.query, &options);
+            self.complete = true;
+        }
+        &self.hits
+    }
+
+    /// The hits found so far.
+    pub fn into_hits(self) -> Vec<SmartHit<'a, B>> {
+        self.hits
+    }
+}
+
+/// Filters for [`GeoDb::find_cities_by_substring_filtered`].
+///
+/// The dataset has no city populations, so the population range applies to
+/// the city's country, like [`SearchOptions::min_population`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CityFilter {
+    /// Only cities of this country (ISO2, case-insensitive).
+    pub country: Option<String>,
+    /// Only cities of countries with at least this many inhabitants.
+    pub min_population: Option<i64>,
+    /// Only cities of countries with at most this many inhabitants.
+    pub max_population: Option<i64>,
+    /// Only cities with a latitude in `(min, max)`, inclusive.
+    pub lat_range: Option<(f64, f64)>,
+    /// Only cities with a longitude in `(min, max)`, inclusive. A range with
+    /// `min > max` crosses the antimeridian, e.g. `(170.0, -170.0)`.
+    pub lng_range: Option<(f64, f64)>,
+    /// Stop after this many matches (after `offset`).
+    pub limit: Option<usize>,
+    /// Skip this many matches, for paging.
+    pub offset: usize,
+}
+
+impl CityFilter {
+    /// Only cities of the country with this ISO2 code.
+    pub fn with_country(mut self, iso2: impl Into<String>) -> Self {
+        self.country = Some(iso2.into());
+        self
+    }
+
+    /// Only cities of countries with a population in `min..=max`; either
+    /// bound may be left open.
+    pub fn with_population(mut self, min: Option<i64>, max: Option<i64>) -> Self {
+        self.min_population = min;
+        self.max_population = max;
+        self
+    }
+
+    /// Only cities inside the box from `(south, west)` to `(north, east)`.
+    pub fn with_bounds(mut self, south: f64, west: f64, north: f64, east: f64) -> Self {
+        self.lat_range = Some((south, north));
+        self.lng_range = Some((west, east));
+        self
+    }
+
+    /// Return at most `limit` cities.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` matching cities.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// `true` if cities of `country` may be returned.
+    pub(crate) fn allows_country<B: GeoBackend>(&self, country: &Country<B>) -> bool {
+        let population = country.population();
+        self.country
+            .as_deref()
+            .is_none_or(|iso2| country.iso2().eq_ignore_ascii_case(iso2.trim()))
+            && self
+                .min_population
+                .is_none_or(|min| population.is_some_and(|p| p >= min))
+            && self
+                .max_population
+                .is_none_or(|max| population.is_some_and(|p| p <= max))
+    }
+
+    /// `true` if a city at this position may be returned. Cities without
+    /// coordinates fail any coordinate range.
+    pub(crate) fn allows_position<B: GeoBackend>(
+        &self,
+        lat: Option<B::Float>,
+        lng: Option<B::Float>,
+    ) -> bool {
+        let lat = lat.map(B::float_to_f64);
+        let lng = lng.map(B::float_to_f64);
+        let lat_ok = self
+            .lat_range
+            .is_none_or(|(min, max)| lat.is_some_and(|v| (min..=max).contains(&v)));
+        let lng_ok = self.lng_range.is_none_or(|(min, max)| {
+            lng.is_some_and(|v| {
+                if min <= max {
+                    (min..=max).contains(&v)
+                } else {
+                    v >= min || v <= max
+                }
+            })
+        });
+        lat_ok && lng_ok
+    }
+}
+
+/// How a normalized name matched a normalized query.
+///
+/// Variants are ordered by strength, so `Exact > Prefix > Contains`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NameMatch {
+    /// The query occurs somewhere inside the name.
+    Contains,
+    /// The name starts with the query.
+    Prefix,
+    /// The name equals the query.
+    Exact,
+}
+
+impl NameMatch {
+    /// Classify `name` against `query`; both must already be normalized.
+    pub fn classify(name: &str, query: &str) -> Option<Self> {
+        if name == query {
+            Some(Self::Exact)
+        } else if name.starts_with(query) {
+            Some(Self::Prefix)
+        } else if name.contains(query) {
+            Some(Self::Contains)
+        } else {
+            None
+        }
+    }
+}
+
+/// Smart-search scores of one entity level, by [`NameMatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelScores {
+    pub exact: i32,
+    pub prefix: i32,
+    pub contains: i32,
+}
+
+impl LevelScores {
+    /// Score of a match of strength `m`.
+    pub fn score(&self, m: NameMatch) -> i32 {
+        match m {
+            NameMatch::Exact => self.exact,
+            NameMatch::Prefix => self.prefix,
+            NameMatch::Contains => self.contains,
+        }
+    }
+}
+
+/// Scores [`GeoDb::smart_search_with`] assigns, part of [`SearchOptions`].
+///
+/// The defaults are the long-standing smart-search ranking; missing fields
+/// of a deserialized profile keep them:
+///
+/// ```toml
+/// [search.scoring]
+/// alias_penalty = 10
+/// city = { exact = 45, prefix = 40, contains = 30 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringProfile {
+    /// Country ISO2 code equals the query ("de").
+    pub iso2: i32,
+    /// State code equals the query ("CA" → California).
+    pub state_code: i32,
+    pub country: LevelScores,
+    pub state: LevelScores,
+    pub city: LevelScores,
+    /// Country found by calling code.
+    pub phone: i32,
+    /// Country found by currency code or name.
+    pub currency: i32,
+    /// Added to city scores for "<city> <ISO2>" queries when the country
+    /// matches.
+    pub country_hint_boost: i32,
+    /// Subtracted from city scores when the city matched by one of its
+    /// aliases (see [`City::aliases`](crate::City::aliases)) rather than
+    /// its name. With 0, alias and name matches tie.
+    pub alias_penalty: i32,
+    /// Subtracted per typo from the exact-match score of names matched
+    /// within [`SearchOptions::max_edit_distance`].
+    pub typo_penalty: i32,
+    /// Added to cities listed in [`SearchSessionHints::recent_city_ids`].
+    pub recent_city_boost: i32,
+    /// Added to hits in [`SearchSessionHints::preferred_countries`].
+    pub preferred_country_boost: i32,
+}
+
+impl Default for ScoringProfile {
+    fn default() -> Self {
+        Self {
+            iso2: 100,
+            // below an exact country name, above country prefix matches and
+            // anything matched by name only
+            state_code: 85,
+            country: LevelScores {
+                exact: 90,
+                prefix: 80,
+                contains: 70,
+            },
+            // states and cities don't rank exact matches above prefix matches
+            state: LevelScores {
+                exact: 60,
+                prefix: 60,
+                contains: 50,
+            },
+            city: LevelScores {
+                exact: 40,
+                prefix: 40,
+                contains: 30,
+            },
+            phone: 20,
+            currency: 10,
+            country_hint_boost: 15,
+            alias_penalty: 5,
+            typo_penalty: 10,
+            recent_city_boost: 10,
+            preferred_country_boost: 5,
+        }
+    }
+}
+
+impl ScoringProfile {
+    /// Score of `name` (normalized) for the query `q`, including the
+    /// "<city> <ISO2>" boost when `hint` is the city's country.
+    fn city_score(&self, name: &str, q: &str, hint: Option<&str>) -> Option<i32> {
+        let score = NameMatch::classify(name, q).map(|m| self.city.score(m));
+        let hinted = hint
+            .and_then(|head| NameMatch::classify(name, head))
+            .map(|m| self.city.score(m) + self.country_hint_boost);
+        score.max(hinted)
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Smart search with explicit [`SearchOptions`].
+    ///
+    /// Scoring is identical to [`GeoDb::smart_search`]. A query of four or
+    /// more letters without any hit is retried once with the spaces and
+    /// hyphens of names ignored, so "newyork" and "sanfrancisco" find what
+    /// "new york" and "san francisco" do, with the same scores; disable with
+    /// [`SearchOptions::with_word_segmentation`].
+    pub fn smart_search_with(&self, query: &str, options: &SearchOptions) -> Vec<SmartHit<'_, B>> {
+        self.smart_search_in(query, options, None)
+    }
+
+    /// Smart search that tolerates typos: names within `max_distance` edits
+    /// of the query ([`edit_distance`], swapped letters count once) match
+    /// too, so "Berln" finds Berlin and "Zurihc" Zürich. Each typo costs
+    /// [`ScoringProfile::typo_penalty`] off the exact-match score, so exact
+    /// and substring matches still rank first.
+    ///
+    /// Queries under 3 characters get no typos and queries under 6 at most
+    /// one. Every name is compared, so this is slower than
+    /// [`Self::smart_search`]; use [`SearchOptions::with_max_edit_distance`]
+    /// to combine it with other options.
+    pub fn smart_search_fuzzy(&self, query: &str, max_distance: usize) -> Vec<SmartHit<'_, B>> {
+        let options = SearchOptions::default().with_max_edit_distance(max_distance);
+        self.smart_search_with(query, &options)
+    }
+
+    /// Two-tier smart search for inputs that paint results as they come:
+    /// the returned [`TieredHits`] holds the country and state hits
+    /// (without scanning the cities, which is most of a search's cost), and
+    /// [`TieredHits::load_more`] adds the cities.
+    ///
+    /// `options.include_cities` is ignored here; the other options apply
+    /// to both tiers. With a `limit`, the first tier may hold hits that
+    /// better-scoring cities push out of the complete list.
+    pub fn smart_search_tiered(&self, query: &str, options: &SearchOptions) -> TieredHits<'_, B> {
+        let options = options.clone();
+        let first = options.clone().with_include_cities(false);
+        TieredHits {
+            db: self,
+            query: query.to_string(),
+            hits: self.smart_search_with(query, &first),
+            options,
+            complete: false,
+        }
+    }
+
+    /// Autocomplete for as-you-type inputs: countries, states and cities
+    /// whose name (or city alias) starts with `prefix`, at most `limit` of
+    /// them.
+    ///
+    /// Unlike [`Self::smart_search`], names merely containing the prefix,
+    /// codes, phone codes and currencies don't match, so "ber" lists Bern
+    /// and Berlin rather than every "Heidelberg". Hits are ranked by
+    /// population, then score; the dataset only has country populations, so
+    /// matching countries come first, biggest first, followed by states and
+    /// cities by score. Hits that would show up identically (same kind, name,
+    /// state and country) are listed once.
+    pub fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<SmartHit<'_, B>> {
+        self.autocomplete_with(prefix, limit, &SearchOptions::default())
+    }
+
+    /// [`Self::autocomplete`] with the analyzer, expansions, filters,
+    /// scoring and session hints of `options`; `options.limit`, `offset`
+    /// and the fuzzy and phone settings don't apply.
+    pub fn autocomplete_with(
+        &self,
+        prefix: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Vec<SmartHit<'_, B>> {
+        let q = options.normalize(prefix);
+        if q.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let scoring = &options.scoring;
+        let prefix_score = |name: &str, level: &LevelScores| match NameMatch::classify(
+            &options.normalize(name),
+            &q,
+        )? {
+            NameMatch::Contains => None,
+            m => Some(level.score(m)),
+        };
+
+        let mut out: Vec<SmartHit<'_, B>> = Vec::new();
+        for country in &self.countries {
+            if options.allows_country(country) {
+                if let Some(score) = prefix_score(country.name(), &scoring.country) {
+                    out.push(SmartHit {
+                        score,
+                        alias: None,
+                        item: SmartItem::Country(country),
+                    });
+                }
+            }
+            if !options.allows_within(country) {
+                continue;
+            }
+            for state in &country.states {
+                if !state.is_synthetic()
+                    && options.allows_position::<B>(state.latitude, state.longitude)
+                {
+                    if let Some(score) = prefix_score(state.name(), &scoring.state) {
+                        out.push(SmartHit {
+                            score,
+                            alias: None,
+                            item: SmartItem::State { country, state },
+                        });
+                    }
+                }
+                for city in &state.cities {
+                    if !options.allows_position::<B>(city.latitude, city.longitude) {
+                        continue;
+                    }
+                    let mut best = prefix_score(city.name(), &scoring.city).map(|s| (s, None));
+                    for alias in city.aliases() {
+                        let Some(score) = prefix_score(alias, &scoring.city) else {
+                            continue;
+                        };
+                        let score = score - scoring.alias_penalty;
+                        if best.is_none_or(|(b, _)| score > b) {
+                            best = Some((score, Some(alias)));
+                        }
+                    }
+                    if let Some((score, alias)) = best {
+                        out.push(SmartHit {
+                            score,
+                            alias,
+                            item: SmartItem::City {
+                                country,
+                                state,
+                                city,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+        options.session.boost(self, &mut out, scoring);
+
+        let population = |hit: &SmartHit<'_, B>| match hit.item {
+            SmartItem::Country(country) => country.population(),
+            _ => None,
+        };
+        out.sort_by_key(|h| (std::cmp::Reverse(population(h)), std::cmp::Reverse(h.score)));
+        let mut seen = HashSet::new();
+        out.retain(|hit| {
+            seen.insert(match hit.item {
+                SmartItem::Country(c) => (0, c.name(), "", c.iso2()),
+                SmartItem::State { country, state } => (1, state.name(), "", country.iso2()),
+                SmartItem::City {
+                    country,
+                    state,
+                    city,
+                } => (2, city.name(), state.name(), country.iso2()),
+                #[cfg(feature = "districts")]
+                SmartItem::District {
+                    country,
+                    state,
+                    district,
+                } => (3, district.name(), state.name(), country.iso2()),
+            })
+        });
+        out.truncate(limit);
+        out
+    }
+
+    /// Smart search that only scores the entities `index` matches, or all
+    /// of them without an index.
+    pub(crate) fn smart_search_in(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        index: Option<&SearchIndex>,
+    ) -> Vec<SmartHit<'_, B>> {
+        let q = options.normalize(query);
+        if q.is_empty() {
+            return Vec::new();
+        }
+
+        let mut out = self.score_entities(query, &q, options, index, false);
+        // fast typists drop spaces ("newyork"); names with their words joined
+        // match what splitting the query at every boundary would find
+        if out.is_empty() && options.word_segmentation {
+            let joined = join_words(&q);
+            if joined.chars().count() >= MIN_JOINED_QUERY {
+                out = self.score_entities(query, &joined, options, index, true);
+            }
+        }
+
+        options.session.boost(self, &mut out, &options.scoring);
+
+        if let Some(min) = options.min_score {
+            out.retain(|h| h.score >= min);
+        }
+        // Sort by score desc (stable sort to preserve relative order within score)
+        out.sort_by_key(|h| std::cmp::Reverse(h.score));
+        out.drain(..options.offset.min(out.len()));
+        if let Some(limit) = options.limit {
+            out.truncate(limit);
+        }
+        out
+    }
+
+    /// Unsorted hits for the normalized query `q`. With `joined`, names are
+    /// compared with their words joined ([`join_words`]), and codes, phone
+    /// codes and country hints are skipped.
+    fn score_entities(
+        &self,
+        query: &str,
+        q: &str,
+        options: &SearchOptions,
+        index: Option<&SearchIndex>,
+        joined: bool,
+    ) -> Vec<SmartHit<'_, B>> {
+        let key = |s: &str| {
+            let name = options.normalize(s);
+            if joined {
+                join_words(&name)
+            } else {
+                name
+            }
+        };
+        let scoring = &options.scoring;
+        // names that don't contain the query may still be a few typos off
+        let typos = options.typos_for(q);
+        let name_score = |name: &str, level: &LevelScores| {
+            NameMatch::classify(name, q)
+                .map(|m| level.score(m))
+                .or_else(|| {
+                    // typos are off by default; skip the distance entirely then
+                    if typos == 0 {
+                        return None;
+                    }
+                    let distance = edit_distance(name, q, typos)?;
+                    Some(level.exact - scoring.typo_penalty * distance as i32)
+                })
+        };
+
+        // "<city> <ISO2>" (e.g. "berlin de"): the trailing token names a country
+        let country_hint = q
+            .rsplit_once(' ')
+            .filter(|_| !joined)
+            .and_then(|(head, tail)| {
+                let country = (tail.len() == 2)
+                    .then(|| self.find_country_by_iso2(tail))
+                    .flatten()?;
+                Some((head.trim(), country))
+            });
+
+        type Countries<'a, B> = Box<dyn Iterator<Item = &'a Country<B>> + 'a>;
+        type States<'a, B> = Box<dyn Iterator<Item = (&'a Country<B>, &'a State<B>)> + 'a>;
+        type Cities<'a, B> = Box<dyn Iterator<Item = CityRef<'a, B>> + 'a>;
+        let (countries, states, cities): (Countries<'_, B>, States<'_, B>, Cities<'_, B>) =
+            match index {
+                Some(index) => {
+                    let hint = country_hint.and_then(|(head, hinted)| {
+                        let ci = self
+                            .countries
+                            .iter()
+                            .position(|c| std::ptr::eq(c, hinted))?;
+                        Some((head, ci))
+                    });
+                    let candidates = index.candidates(q, hint, joined);
+                    // entities the database lacks (cities not attached yet) are skipped
+                    (
+                        Box::new(
+                            candidates
+                                .countries
+                                .into_iter()
+                                .filter_map(|ci| self.countries.get(ci)),
+                        ),
+                        Box::new(candidates.states.into_iter().filter_map(|(ci, si)| {
+                            let country = self.countries.get(ci)?;
+                            Some((country, country.states.get(si)?))
+                        })),
+                        Box::new(candidates.cities.into_iter().filter_map(|(ci, si, ti)| {
+                            let country = self.countries.get(ci)?;
+                            let state = country.states.get(si)?;
+                            Some((state.cities.get(ti)?, state, country))
+                        })),
+                    )
+                }
+                None => (
+                    Box::new(self.countries.iter()),
+                    Box::new(
+                        self.countries
+                            .iter()
+                            .flat_map(|c| c.states.iter().map(move |s| (c, s))),
+                    ),
+                    Box::new(self.iter_cities()),
+                ),
+            };
+
+        let mut out: Vec<SmartHit<'_, B>> = Vec::new();
+
+        // Filters are checked before matching, so restrictive options make
+        // the scan cheaper instead of trimming a full result list afterwards.
+
+        // Countries
+        for c in countries {
+            if !options.allows_country(c) {
+                continue;
+            }
+            let score = if !joined && c.iso2().eq_ignore_ascii_case(q) {
+                Some(scoring.iso2)
+            } else {
+                name_score(&key(c.name()), &scoring.country)
+            };
+            if let Some(score) = score {
+                out.push(SmartHit {
+                    score,
+                    alias: None,
+                    item: SmartItem::Country(c),
+                });
+            }
+        }
+
+        // States
+        for (c, s) in states {
+            if s.is_synthetic()
+                || !options.allows_within(c)
+                || !options.allows_position::<B>(s.latitude, s.longitude)
+            {
+                continue;
+            }
+            let code = s.state_code();
+            let score = if !joined && !code.is_empty() && code.eq_ignore_ascii_case(q) {
+                Some(scoring.state_code)
+            } else {
+                name_score(&key(s.name()), &scoring.state)
+            };
+            if let Some(score) = score {
+                out.push(SmartHit {
+                    score,
+                    alias: None,
+                    item: SmartItem::State {
+                        country: c,
+                        state: s,
+                    },
+                });
+            }
+        }
+
+        // Districts; few enough to scan without the index. They have no
+        // coordinates, so position filters leave them out.
+        #[cfg(feature = "districts")]
+        for c in &self.countries {
+            if !options.allows_within(c) || !options.allows_position::<B>(None, None) {
+                continue;
+            }
+            for s in &c.states {
+                for d in &s.districts {
+                    if let Some(score) = name_score(&key(d.name()), &scoring.state) {
+                        out.push(SmartHit {
+                            score,
+                            alias: None,
+                            item: SmartItem::District {
+                                country: c,
+                                state: s,
+                                district: d,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        // Cities
+        let cities: Cities<'_, B> = if options.include_cities {
+            cities
+        } else {
+            Box::new(std::iter::empty())
+        };
+        for (city, state, country) in cities {
+            if !options.allows_within(country)
+                || !options.allows_position::<B>(city.latitude, city.longitude)
+            {
+                continue;
+            }
+            let hint = country_hint
+                .filter(|(_, hint)| std::ptr::eq(country, *hint))
+                .map(|(head, _)| head);
+            let city_score = |name: &str| {
+                scoring
+                    .city_score(name, q, hint)
+                    .or_else(|| name_score(name, &scoring.city))
+            };
+            let mut best = city_score(&key(city.name())).map(|score| (score, None));
+            // an alias only wins if it scores higher, penalty included
+            for alias in city.aliases() {
+                let Some(score) = city_score(&key(alias)) else {
+                    continue;
+                };
+                let score = score - scoring.alias_penalty;
+                if best.is_none_or(|(b, _)| score > b) {
+                    best = Some((score, Some(alias)));
+                }
+            }
+            if let Some((score, alias)) = best {
+                out.push(SmartHit {
+                    score,
+                    alias,
+                    item: SmartItem::City {
+                        country,
+                        state,
+                        city,
+                    },
+                });
+            }
+        }
+
+        // City labels (other languages); cities already matched by name keep their hit
+        #[cfg(feature = "multilingual-cities")]
+        if let Some(index) = options
+            .city_labels
+            .as_ref()
+            .filter(|_| options.include_cities)
+        {
+            for entry in &index.entries {
+                let best = entry
+                    .labels
+                    .values()
+                    .filter_map(|label| NameMatch::classify(&key(label), q))
+                    .max();
+                let Some(m) = best else { continue };
+                let Some((city, state, country)) = self.resolve_city_labels(entry) else {
+                    continue;
+                };
+                if !options.allows_within(country)
+                    || !options.allows_position::<B>(city.latitude, city.longitude)
+                {
+                    continue;
+                }
+                let seen = out.iter().any(|h| match h.item {
+                    SmartItem::City { city: c, .. } => std::ptr::eq(c, city),
+                    _ => false,
+                });
+                if !seen {
+                    out.push(SmartHit {
+                        score: scoring.city.score(m),
+                        alias: None,
+                        item: SmartItem::City {
+                            country,
+                            state,
+                            city,
+                        },
+                    });
+                }
+            }
+        }
+
+        // Phone code
+        let phone_hits = if options.include_phone && !joined {
+            self.find_countries_by_phone_code(query)
+        } else {
+            Vec::new()
+        };
+        for c in phone_hits {
+            if !options.allows_country(c) {
+                continue;
+            }
+            out.push(SmartHit {
+                score: scoring.phone,
+                alias: None,
+                item: SmartItem::Country(c),
+            });
+        }
+
+        // Currency: exact code or name only, "eu" shouldn't list the eurozone
+        if options.include_currency && !joined {
+            for c in &self.countries {
+                let matches = c.currency().eq_ignore_ascii_case(q)
+                    || c.currency_name()
+                        .is_some_and(|name| options.normalize(name) == q);
+                if matches && options.allows_country(c) {
+                    out.push(SmartHit {
+                        score: scoring.currency,
+                        alias: None,
+                        item: SmartItem::Country(c),
+                    });
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Shortest joined query [`SearchOptions::word_segmentation`] retries;
+/// shorter ones would match inside too many joined names.
+const MIN_JOINED_QUERY: usize = 4;
+
+/// `text` without the spaces and hyphens between its words, so "new york"
+/// and "Winston-Salem" compare like "newyork" and "winstonsalem".
+pub(crate) fn join_words(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect()
+}
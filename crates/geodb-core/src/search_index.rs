@@ -0,0 +1,241 @@
+// src/search_index.rs
+//! Prebuilt name index for [`GeoDb::smart_search_with`].
+//!
+//! Smart search folds every country, state and city name with the
+//! [`SearchOptions`] analyzer on each query. A [`SearchIndex`] stores those
+//! folded names once, so a query only compares strings: names are matched
+//! with a substring test (smart search ranks "contains" matches too, which
+//! rules out a pure binary search), and only the entities that match are
+//! scored. Results are identical to the unindexed search.
+//!
+//! The index is serializable, so it can be built together with a binary
+//! database and shipped next to it (the WASM crate embeds one):
+//!
+//! ```no_run
+//! use geodb_core::search_index::SearchIndex;
+//! use geodb_core::{GeoDb, SearchOptions, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let options = SearchOptions::default();
+//! let bytes = db.search_index(&options).to_bytes()?; // at build time
+//!
+//! let index = SearchIndex::from_bytes(&bytes)?; // at run time
+//! let hits = db.smart_search_indexed("berlin", &options, &index);
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! An index only applies to the database it was built from, after any
+//! [`GeoDb::apply_city_meta`] (aliases are indexed too), and to options with
+//! the same [`SearchOptions::language`] and [`SearchOptions::expansions`];
+//! [`GeoDb::smart_search_indexed`] falls back to the full scan for other
+//! options. Entities the index lists but the database lacks (e.g. cities
+//! that are still being attached, see [`crate::staged`]) are skipped.
+use crate::error::Result;
+use crate::model::{GeoBackend, GeoDb, SmartHit};
+use crate::search::{join_words, ExpansionTable, SearchLanguage, SearchOptions};
+use serde::{Deserialize, Serialize};
+
+/// Folded keys of one entity level; key `i` is
+/// `text[ends[i - 1]..ends[i]]` and belongs to `owners[i]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Keys<P> {
+    text: String,
+    ends: Vec<u32>,
+    owners: Vec<P>,
+}
+
+impl<P: Copy + PartialEq> Keys<P> {
+    fn push(&mut self, key: &str, owner: P) {
+        self.text.push_str(key);
+        self.ends.push(self.text.len() as u32);
+        self.owners.push(owner);
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&str, P)> + '_ {
+        let starts = std::iter::once(0).chain(self.ends.iter().copied());
+        starts
+            .zip(&self.ends)
+            .zip(&self.owners)
+            .map(|((start, &end), &owner)| (&self.text[start as usize..end as usize], owner))
+    }
+
+    /// Owners of the keys `accept`ed, in key order without repeats.
+    fn matching(&self, mut accept: impl FnMut(&str, P) -> bool) -> Vec<P> {
+        let mut out: Vec<P> = Vec::new();
+        for (key, owner) in self.iter() {
+            if out.last() != Some(&owner) && accept(key, owner) {
+                out.push(owner);
+            }
+        }
+        out
+    }
+
+    fn len(&self) -> usize {
+        self.ends.len()
+    }
+}
+
+/// Folded names of a [`GeoDb`]; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    language: SearchLanguage,
+    expansions: ExpansionTable,
+    country_names: Keys<u32>,
+    country_codes: Keys<u32>,
+    state_names: Keys<[u32; 2]>,
+    state_codes: Keys<[u32; 2]>,
+    /// City names and aliases.
+    city_names: Keys<[u32; 3]>,
+}
+
+/// Entities whose keys match a query, in database order.
+pub(crate) struct Candidates {
+    pub countries: Vec<usize>,
+    pub states: Vec<(usize, usize)>,
+    pub cities: Vec<(usize, usize, usize)>,
+}
+
+impl SearchIndex {
+    /// `true` if the index was built with the analyzer and expansions of
+    /// `options`, and `options` don't ask for typo tolerance (fuzzy matches
+    /// need every name, not the ones containing the query).
+    pub fn supports(&self, options: &SearchOptions) -> bool {
+        self.language == options.language
+            && self.expansions == options.expansions
+            && options.max_edit_distance == 0
+    }
+
+    /// Number of indexed names and codes.
+    pub fn len(&self) -> usize {
+        self.country_names.len()
+            + self.country_codes.len()
+            + self.state_names.len()
+            + self.state_codes.len()
+            + self.city_names.len()
+    }
+
+    /// `true` for the index of an empty database.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Serialize the index (bincode, like [`GeoDb::to_bytes`]).
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    /// Read an index written by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        use bincode::Options;
+        Ok(crate::loader::bincode_options(bytes.len() as u64).deserialize(bytes)?)
+    }
+
+    /// Entities matching the normalized query `q`: names containing it,
+    /// codes equal to it, and for "<city> <ISO2>" queries the cities of the
+    /// hinted country (index `hint.1`) whose names contain `hint.0`. With
+    /// `joined`, `q` is compared with names whose words are joined
+    /// ([`join_words`]) and codes aren't matched.
+    pub(crate) fn candidates(
+        &self,
+        q: &str,
+        hint: Option<(&str, usize)>,
+        joined: bool,
+    ) -> Candidates {
+        let code = |key: &str| !joined && key.eq_ignore_ascii_case(q);
+        let name = |key: &str| {
+            if joined {
+                join_words(key).contains(q)
+            } else {
+                key.contains(q)
+            }
+        };
+        let mut countries = self.country_names.matching(|key, _| name(key));
+        countries.extend(self.country_codes.matching(|key, _| code(key)));
+        let mut states = self.state_names.matching(|key, _| name(key));
+        states.extend(self.state_codes.matching(|key, _| code(key)));
+        let cities = self.city_names.matching(|key, [ci, _, _]| {
+            name(key)
+                || hint.is_some_and(|(head, hinted)| ci as usize == hinted && key.contains(head))
+        });
+
+        let mut candidates = Candidates {
+            countries: countries.into_iter().map(|ci| ci as usize).collect(),
+            states: states
+                .into_iter()
+                .map(|[ci, si]| (ci as usize, si as usize))
+                .collect(),
+            cities: cities
+                .into_iter()
+                .map(|[ci, si, ti]| (ci as usize, si as usize, ti as usize))
+                .collect(),
+        };
+        // names and codes were matched separately
+        candidates.countries.sort_unstable();
+        candidates.countries.dedup();
+        candidates.states.sort_unstable();
+        candidates.states.dedup();
+        candidates
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Fold all names and codes with the analyzer of `options`; see the
+    /// [module docs](crate::search_index).
+    pub fn search_index(&self, options: &SearchOptions) -> SearchIndex {
+        let mut index = SearchIndex {
+            language: options.language,
+            expansions: options.expansions.clone(),
+            country_names: Keys::default(),
+            country_codes: Keys::default(),
+            state_names: Keys::default(),
+            state_codes: Keys::default(),
+            city_names: Keys::default(),
+        };
+        for (ci, country) in self.countries.iter().enumerate() {
+            let ci = ci as u32;
+            index
+                .country_names
+                .push(&options.normalize(country.name()), ci);
+            index.country_codes.push(country.iso2(), ci);
+            for (si, state) in country.states.iter().enumerate() {
+                let si = si as u32;
+                if !state.is_synthetic() {
+                    index
+                        .state_names
+                        .push(&options.normalize(state.name()), [ci, si]);
+                }
+                if !state.state_code().is_empty() {
+                    index.state_codes.push(state.state_code(), [ci, si]);
+                }
+                for (ti, city) in state.cities.iter().enumerate() {
+                    let owner = [ci, si, ti as u32];
+                    index
+                        .city_names
+                        .push(&options.normalize(city.name()), owner);
+                    for alias in city.aliases() {
+                        index.city_names.push(&options.normalize(alias), owner);
+                    }
+                }
+            }
+        }
+        index
+    }
+
+    /// [`Self::smart_search_with`] using a prebuilt [`SearchIndex`]; same
+    /// results, without folding every name per query.
+    ///
+    /// Falls back to the full scan if `index` doesn't
+    /// [support](SearchIndex::supports) `options`.
+    pub fn smart_search_indexed(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        index: &SearchIndex,
+    ) -> Vec<SmartHit<'_, B>> {
+        if index.supports(options) {
+            self.smart_search_in(query, options, Some(index))
+        } else {
+            self.smart_search_with(query, options)
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// src/shared.rs
+//! One read-only copy of the database shared by worker processes (feature
+//! `shared`, Unix only).
+//!
+//! Pre-fork servers (gunicorn-style) that load a [`GeoDb`] per worker keep
+//! one heap copy per process. [`SharedDb::create`] writes the zero-copy
+//! layout (see [`crate::zerocopy`]) once into an unlinked file on a
+//! shared-memory filesystem (`/dev/shm` where available) and maps it
+//! read-only; every worker maps the same pages instead of holding a copy.
+//!
+//! ```no_run
+//! use geodb_core::shared::{SharedDb, SharedHandle};
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! // parent, before forking the workers
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let shared = SharedDb::create(&db)?;
+//! drop(db);
+//! std::env::set_var("GEODB_SHARED", shared.handle().to_string());
+//!
+//! // in a forked worker
+//! let handle: SharedHandle = std::env::var("GEODB_SHARED").unwrap().parse()?;
+//! let worker = SharedDb::open(handle)?;
+//! let view = worker.view()?;
+//! println!("{} countries", view.countries().len());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! A [`SharedHandle`] names the parent's file descriptor, so it is only
+//! valid in the parent and in processes forked from it (descriptors are
+//! closed on `exec`). Workers forked after [`SharedDb::create`] may also
+//! just keep using the inherited `SharedDb`. The memory is released when
+//! the last process holding the descriptor or a mapping exits or drops it.
+//!
+//! The view covers what the zero-copy layout holds: the hierarchy, names,
+//! state codes, coordinates and timezones. Workers that need the full
+//! search API build a [`GeoDb`] for the countries they serve from the
+//! view's city chunks, or use [`GeoDb::load_mmap`] on a file artifact.
+use crate::error::{GeoError, Result};
+use crate::model::{GeoBackend, GeoDb};
+use crate::zerocopy::ZeroCopyDb;
+use memmap2::Mmap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::os::fd::{AsRawFd, RawFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How a worker finds a [`SharedDb`]: the descriptor of the shared file and
+/// its length. Formats as `fd:len` (e.g. `5:10485760`) and parses back, to
+/// pass it through an environment variable or a command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedHandle {
+    pub fd: RawFd,
+    pub len: u64,
+}
+
+impl fmt::Display for SharedHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.fd, self.len)
+    }
+}
+
+impl FromStr for SharedHandle {
+    type Err = GeoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid =
+            || GeoError::InvalidData(format!("shared handle: expected fd:len, got {s:?}"));
+        let (fd, len) = s.trim().split_once(':').ok_or_else(invalid)?;
+        Ok(Self {
+            fd: fd.parse().map_err(|_| invalid())?,
+            len: len.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// A zero-copy artifact in shared memory, mapped read-only; see the
+/// [module docs](self).
+pub struct SharedDb {
+    file: File,
+    map: Mmap,
+}
+
+impl fmt::Debug for SharedDb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedDb")
+            .field("handle", &self.handle())
+            .finish()
+    }
+}
+
+/// `/dev/shm` if it exists (memory-backed on Linux), the temp directory
+/// otherwise.
+fn default_dir() -> PathBuf {
+    let shm = Path::new("/dev/shm");
+    if shm.is_dir() {
+        shm.to_path_buf()
+    } else {
+        std::env::temp_dir()
+    }
+}
+
+impl SharedDb {
+    /// Write `db` in the zero-copy layout to shared memory and map it.
+    pub fn create<B: GeoBackend>(db: &GeoDb<B>) -> Result<Self> {
+        Self::create_in(default_dir(), db)
+    }
+
+    /// Like [`Self::create`], with the file created in `dir` (removed from
+    /// the directory again before this returns).
+    pub fn create_in<B: GeoBackend>(dir: impl AsRef<Path>, db: &GeoDb<B>) -> Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let bytes = db.to_zerocopy_bytes()?;
+        let path = dir.as_ref().join(format!(
+            "geodb-{}-{}.zc",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let written = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            // nobody can reopen it for writing through /proc/<pid>/fd
+            .mode(0o400)
+            .open(&path)
+            .and_then(|mut writer| {
+                writer.write_all(&bytes)?;
+                // the descriptor handed to workers can't write
+                File::open(&path)
+            });
+        // unlinked right away: the data lives as long as a descriptor or a
+        // mapping does, and no file is left behind when the processes exit
+        let removed = std::fs::remove_file(&path);
+        let file = written?;
+        removed?;
+        Self::map(file)
+    }
+
+    /// Map the shared database of `handle` in a worker (see
+    /// [`SharedHandle`] for where it is valid).
+    pub fn open(handle: SharedHandle) -> Result<Self> {
+        // reopened read-only through the descriptor, so this process owns
+        // its own one and never touches the parent's
+        let file = File::open(format!("/dev/fd/{}", handle.fd))?;
+        let db = Self::map(file)?;
+        if db.map.len() as u64 != handle.len {
+            return Err(GeoError::InvalidData(format!(
+                "shared handle {handle}: descriptor has {} bytes",
+                db.map.len()
+            )));
+        }
+        Ok(db)
+    }
+
+    fn map(file: File) -> Result<Self> {
+        // SAFETY: the file is unlinked, read-only for its owner and only
+        // reachable through read-only descriptors, so the mapped bytes can't
+        // change (short of root writing to it, as for any mapped file).
+        let map = unsafe { Mmap::map(&file)? };
+        ZeroCopyDb::new(&map)?;
+        Ok(Self { file, map })
+    }
+
+    /// Handle for workers forked from this process.
+    pub fn handle(&self) -> SharedHandle {
+        SharedHandle {
+            fd: self.file.as_raw_fd(),
+            len: self.map.len() as u64,
+        }
+    }
+
+    /// The mapped artifact.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// The database, read in place. Checks the layout again (a pass over
+    /// the string table), so keep the view rather than calling this per
+    /// query.
+    pub fn view(&self) -> Result<ZeroCopyDb<'_>> {
+        ZeroCopyDb::new(&self.map)
+    }
+}
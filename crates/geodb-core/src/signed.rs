@@ -0,0 +1,224 @@
+// src/signed.rs
+//! Integrity-checked and signed database artifacts (feature `signed`).
+//!
+//! Prebuilt databases served from a CDN can be wrapped in a small envelope
+//! that carries the SHA-256 of the payload and, optionally, an ed25519
+//! signature. Loaders check the envelope before deserializing, so a
+//! truncated, corrupted or tampered blob is rejected instead of loaded.
+//!
+//! Envelope layout (all sizes in bytes):
+//!
+//! | offset | size | content                                   |
+//! |--------|------|-------------------------------------------|
+//! | 0      | 8    | magic `GEODBSIG`                          |
+//! | 8      | 1    | format version (`1`)                      |
+//! | 9      | 1    | flags (bit 0: signature present)          |
+//! | 10     | 32   | SHA-256 of the payload                    |
+//! | 42     | 64   | ed25519 signature of the SHA-256 (if set) |
+//! | 42/106 | …    | payload (a regular `GeoDb::to_bytes` blob) |
+//!
+//! ```no_run
+//! use geodb_core::signed::{SigningKey, VerifyOptions};
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! // Publisher side
+//! let key = SigningKey::from_bytes(&[7u8; 32]);
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let artifact = db.to_signed_bytes(Some(&key))?;
+//!
+//! // Consumer side: trust the publisher's public key
+//! let options = VerifyOptions::default().with_trusted_key(&key.verifying_key());
+//! let db = GeoDb::<StandardBackend>::from_signed_bytes(&artifact, &options)?;
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! With the `fetch` feature, set `DownloadOptions::verify` to check a
+//! downloaded artifact before it replaces the previous file.
+use crate::error::{GeoError, Result};
+use crate::hex::to_hex;
+use crate::model::{GeoBackend, GeoDb};
+use ed25519_dalek::{Signature, Signer, Verifier};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+/// Magic bytes at the start of every envelope.
+pub const MAGIC: &[u8; 8] = b"GEODBSIG";
+/// Envelope format version written by [`seal`].
+pub const VERSION: u8 = 1;
+
+const FLAG_SIGNED: u8 = 0b0000_0001;
+const HASH_LEN: usize = 32;
+const SIG_LEN: usize = 64;
+const HEADER_LEN: usize = MAGIC.len() + 2 + HASH_LEN;
+
+/// How [`open`] checks an envelope.
+///
+/// With no trusted keys, only integrity is checked (the SHA-256 in the header,
+/// plus `expected_sha256` if set). As soon as one key is configured, a valid
+/// signature by one of the keys is required. Deserializable, so keys can come
+/// from a config file:
+///
+/// ```toml
+/// [verify]
+/// trusted_keys = ["3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerifyOptions {
+    /// Expected SHA-256 of the payload (hex), e.g. published next to the URL.
+    pub expected_sha256: Option<String>,
+    /// Trusted ed25519 public keys as hex (32 bytes each).
+    pub trusted_keys: Vec<String>,
+}
+
+impl VerifyOptions {
+    /// Also trust `key`.
+    pub fn with_trusted_key(mut self, key: &VerifyingKey) -> Self {
+        self.trusted_keys.push(to_hex(key.as_bytes()));
+        self
+    }
+
+    /// Require the payload to hash to `sha256` (hex).
+    pub fn with_expected_sha256(mut self, sha256: &str) -> Self {
+        self.expected_sha256 = Some(sha256.trim().to_ascii_lowercase());
+        self
+    }
+
+    fn verifying_keys(&self) -> Result<Vec<VerifyingKey>> {
+        self.trusted_keys
+            .iter()
+            .map(|hex| {
+                let bytes: [u8; 32] =
+                    from_hex(hex)
+                        .and_then(|b| b.try_into().ok())
+                        .ok_or_else(|| {
+                            GeoError::InvalidData(format!(
+                                "trusted key is not 32 bytes of hex: {hex}"
+                            ))
+                        })?;
+                VerifyingKey::from_bytes(&bytes)
+                    .map_err(|e| GeoError::InvalidData(format!("invalid trusted key {hex}: {e}")))
+            })
+            .collect()
+    }
+}
+
+/// Wrap `payload` in an envelope, signing it if a key is given.
+pub fn seal(payload: &[u8], key: Option<&SigningKey>) -> Vec<u8> {
+    let digest: [u8; HASH_LEN] = Sha256::digest(payload).into();
+
+    let mut out = Vec::with_capacity(HEADER_LEN + SIG_LEN + payload.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.push(if key.is_some() { FLAG_SIGNED } else { 0 });
+    out.extend_from_slice(&digest);
+    if let Some(key) = key {
+        out.extend_from_slice(&key.sign(&digest).to_bytes());
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+/// `true` if `bytes` starts with the envelope magic.
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Check an envelope and return its payload.
+///
+/// Fails with [`GeoError::ChecksumMismatch`] if the payload doesn't match its
+/// hash, with [`GeoError::Signature`] if a required signature is missing or
+/// invalid, and with [`GeoError::InvalidData`] for malformed envelopes.
+pub fn open<'a>(artifact: &'a [u8], options: &VerifyOptions) -> Result<&'a [u8]> {
+    if artifact.len() < HEADER_LEN || !is_sealed(artifact) {
+        return Err(GeoError::InvalidData(
+            "not a signed geodb artifact (missing GEODBSIG header)".into(),
+        ));
+    }
+    let version = artifact[MAGIC.len()];
+    if version != VERSION {
+        return Err(GeoError::InvalidData(format!(
+            "unsupported artifact version {version}"
+        )));
+    }
+    let flags = artifact[MAGIC.len() + 1];
+    let digest = &artifact[MAGIC.len() + 2..HEADER_LEN];
+
+    let (signature, payload) = if flags & FLAG_SIGNED != 0 {
+        if artifact.len() < HEADER_LEN + SIG_LEN {
+            return Err(GeoError::InvalidData("truncated artifact signature".into()));
+        }
+        let (sig, payload) = artifact[HEADER_LEN..].split_at(SIG_LEN);
+        (Some(Signature::from_slice(sig).map_err(sig_err)?), payload)
+    } else {
+        (None, &artifact[HEADER_LEN..])
+    };
+
+    let actual = Sha256::digest(payload);
+    if actual.as_slice() != digest {
+        return Err(GeoError::ChecksumMismatch {
+            expected: to_hex(digest),
+            actual: to_hex(&actual),
+        });
+    }
+    if let Some(expected) = &options.expected_sha256 {
+        if !expected.trim().eq_ignore_ascii_case(&to_hex(&actual)) {
+            return Err(GeoError::ChecksumMismatch {
+                expected: expected.trim().to_ascii_lowercase(),
+                actual: to_hex(&actual),
+            });
+        }
+    }
+
+    let keys = options.verifying_keys()?;
+    if !keys.is_empty() {
+        let signature =
+            signature.ok_or_else(|| GeoError::Signature("artifact is not signed".into()))?;
+        if !keys.iter().any(|k| k.verify(digest, &signature).is_ok()) {
+            return Err(GeoError::Signature(
+                "signature does not match any trusted key".into(),
+            ));
+        }
+    }
+
+    Ok(payload)
+}
+
+impl<B> GeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    /// Serialize into a sealed envelope (see [`seal`]).
+    pub fn to_signed_bytes(&self, key: Option<&SigningKey>) -> Result<Vec<u8>> {
+        Ok(seal(&self.to_bytes()?, key))
+    }
+
+    /// Verify a sealed envelope (see [`open`]) and load its payload.
+    pub fn from_signed_bytes(artifact: &[u8], options: &VerifyOptions) -> Result<Self> {
+        Self::from_bytes(open(artifact, options)?)
+    }
+
+    /// Read, verify and load a sealed artifact from disk.
+    pub fn load_signed(path: impl AsRef<std::path::Path>, options: &VerifyOptions) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_signed_bytes(&bytes, options)
+    }
+}
+
+fn sig_err(e: ed25519_dalek::SignatureError) -> GeoError {
+    GeoError::Signature(e.to_string())
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim();
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -0,0 +1,649 @@
+// src/spatial.rs
+//! Distance helpers and proximity-ordered search.
+//!
+//! [`GeoDb::smart_search_near`] matches by name like
+//! [`GeoDb::smart_search`], then keeps only hits within a radius of a
+//! reference point and orders them by distance ("find the nearest city
+//! called …").
+//!
+//! Distances default to kilometres on a spherical Earth (haversine). Use
+//! [`DistanceOptions`] to switch to miles or nautical miles, or to the WGS84
+//! ellipsoid (Vincenty), which is accurate to millimetres instead of ~0.5 %.
+//!
+//! [`cluster_cities`] groups cities lying close together, e.g. to pick one
+//! label per group for a map zoom level; [`GeoDb::find_duplicate_cities`]
+//! uses it to flag probable duplicate entries.
+use crate::exact::fold_name;
+use crate::model::{City, Country, GeoBackend, GeoDb, SmartHit, SmartItem, State};
+use crate::search::SearchOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Mean Earth radius in kilometres (IUGG).
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+// WGS84 ellipsoid
+const WGS84_A_KM: f64 = 6378.137;
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Unit for distances and radii.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DistanceUnit {
+    #[default]
+    #[serde(alias = "km")]
+    Kilometers,
+    /// Statute miles (1.609344 km).
+    #[serde(alias = "mi")]
+    Miles,
+    /// Nautical miles (1.852 km).
+    #[serde(alias = "nm")]
+    NauticalMiles,
+}
+
+impl DistanceUnit {
+    /// Length of one unit in kilometres.
+    pub fn km_per_unit(self) -> f64 {
+        match self {
+            Self::Kilometers => 1.0,
+            Self::Miles => 1.609_344,
+            Self::NauticalMiles => 1.852,
+        }
+    }
+
+    /// Convert kilometres to this unit.
+    pub fn from_km(self, km: f64) -> f64 {
+        km / self.km_per_unit()
+    }
+
+    /// Convert a value in this unit to kilometres.
+    pub fn to_km(self, value: f64) -> f64 {
+        value * self.km_per_unit()
+    }
+}
+
+impl FromStr for DistanceUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "km" | "kilometers" | "kilometres" => Ok(Self::Kilometers),
+            "mi" | "miles" => Ok(Self::Miles),
+            "nm" | "nmi" | "nauticalmiles" | "nautical_miles" => Ok(Self::NauticalMiles),
+            other => Err(format!(
+                "unknown distance unit '{other}' (expected km, mi or nm)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DistanceUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Kilometers => "km",
+            Self::Miles => "mi",
+            Self::NauticalMiles => "nm",
+        })
+    }
+}
+
+/// Shape of the Earth used for distance calculations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EarthModel {
+    /// Sphere with the mean radius (haversine); fast, error up to ~0.5 %.
+    #[default]
+    Sphere,
+    /// WGS84 ellipsoid (Vincenty's inverse formula).
+    Wgs84,
+}
+
+/// Unit and Earth model for distance calculations.
+///
+/// Deserializable with missing fields defaulting, e.g. from a config file:
+///
+/// ```toml
+/// [distance]
+/// unit = "mi"
+/// model = "wgs84"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DistanceOptions {
+    pub unit: DistanceUnit,
+    pub model: EarthModel,
+}
+
+impl DistanceOptions {
+    /// Options using `unit`.
+    pub fn with_unit(mut self, unit: DistanceUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Options using `model`.
+    pub fn with_model(mut self, model: EarthModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Distance between two points in kilometres, using the configured model.
+    pub fn distance_km(&self, lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+        match self.model {
+            EarthModel::Sphere => haversine_km(lat1, lng1, lat2, lng2),
+            EarthModel::Wgs84 => vincenty_km(lat1, lng1, lat2, lng2),
+        }
+    }
+
+    /// Distance between two points in the configured unit and model.
+    pub fn distance(&self, lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+        self.unit.from_km(self.distance_km(lat1, lng1, lat2, lng2))
+    }
+}
+
+/// Great-circle distance between two points in kilometres (haversine).
+pub fn haversine_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lng2 - lng1).to_radians();
+
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Geodesic distance on the WGS84 ellipsoid in kilometres (Vincenty).
+///
+/// For nearly antipodal points, where the iteration doesn't converge, falls
+/// back to [`haversine_km`].
+pub fn vincenty_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let b = WGS84_A_KM * (1.0 - WGS84_F);
+    let l = (lng2 - lng1).to_radians();
+    let u1 = ((1.0 - WGS84_F) * lat1.to_radians().tan()).atan();
+    let u2 = ((1.0 - WGS84_F) * lat2.to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            return 0.0; // coincident points
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+        // equatorial line: cos_sq_alpha == 0
+        let cos_2sigma_m = if cos_sq_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+        };
+        let c = WGS84_F / 16.0 * cos_sq_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos_sq_alpha));
+        let prev = lambda;
+        lambda = l
+            + (1.0 - c)
+                * WGS84_F
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+        if (lambda - prev).abs() < 1e-12 {
+            let u_sq = cos_sq_alpha * (WGS84_A_KM.powi(2) - b * b) / (b * b);
+            let big_a =
+                1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+            let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+            let delta_sigma = big_b
+                * sin_sigma
+                * (cos_2sigma_m
+                    + big_b / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                            - big_b / 6.0
+                                * cos_2sigma_m
+                                * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+            return b * big_a * (sigma - delta_sigma);
+        }
+    }
+    haversine_km(lat1, lng1, lat2, lng2)
+}
+
+/// A smart-search hit with its distance to the reference point.
+#[derive(Debug, Clone, Copy)]
+pub struct NearHit<'a, B: GeoBackend> {
+    pub hit: SmartHit<'a, B>,
+    pub distance_km: f64,
+}
+
+impl<B: GeoBackend> NearHit<'_, B> {
+    /// Distance to the reference point in `unit`.
+    pub fn distance(&self, unit: DistanceUnit) -> f64 {
+        unit.from_km(self.distance_km)
+    }
+}
+
+impl<'a, B: GeoBackend> SmartItem<'a, B> {
+    /// Coordinates of the matched entity as `(lat, lng)`, if known.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        let (lat, lng) = match *self {
+            SmartItem::Country(c) => (c.latitude, c.longitude),
+            SmartItem::State { state, .. } => (state.latitude, state.longitude),
+            SmartItem::City { city, .. } => (city.latitude, city.longitude),
+            // districts have no coordinates of their own
+            #[cfg(feature = "districts")]
+            SmartItem::District { .. } => return None,
+        };
+        Some((B::float_to_f64(lat?), B::float_to_f64(lng?)))
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Smart search restricted to `radius_km` around `(lat, lng)`, nearest first.
+    ///
+    /// Hits without coordinates are dropped. Equal distances keep the
+    /// smart-search score order.
+    pub fn smart_search_near(
+        &self,
+        query: &str,
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+    ) -> Vec<NearHit<'_, B>> {
+        self.smart_search_near_with(query, lat, lng, radius_km, &SearchOptions::default())
+    }
+
+    /// [`Self::smart_search_near`] with explicit [`SearchOptions`].
+    pub fn smart_search_near_with(
+        &self,
+        query: &str,
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+        options: &SearchOptions,
+    ) -> Vec<NearHit<'_, B>> {
+        self.smart_search_near_in(
+            query,
+            lat,
+            lng,
+            radius_km,
+            &DistanceOptions::default(),
+            options,
+        )
+    }
+
+    /// Proximity search with explicit [`DistanceOptions`].
+    ///
+    /// `radius` is given in `distance.unit` and distances are computed with
+    /// `distance.model`; [`NearHit::distance_km`] stays in kilometres, use
+    /// [`NearHit::distance`] for other units.
+    pub fn smart_search_near_in(
+        &self,
+        query: &str,
+        lat: f64,
+        lng: f64,
+        radius: f64,
+        distance: &DistanceOptions,
+        options: &SearchOptions,
+    ) -> Vec<NearHit<'_, B>> {
+        let radius_km = distance.unit.to_km(radius);
+        let mut out: Vec<NearHit<'_, B>> = self
+            .smart_search_with(query, options)
+            .into_iter()
+            .filter_map(|hit| {
+                let (hit_lat, hit_lng) = hit.item.coordinates()?;
+                let distance_km = distance.distance_km(lat, lng, hit_lat, hit_lng);
+                (distance_km <= radius_km).then_some(NearHit { hit, distance_km })
+            })
+            .collect();
+
+        // Stable: ties keep the score order from smart_search
+        out.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+        out
+    }
+
+    /// [`cluster_cities`] over every city of the database.
+    pub fn city_clusters(&self, options: &ClusterOptions) -> Vec<CityCluster<'_, B>> {
+        cluster_cities(self.iter_cities(), options)
+    }
+
+    /// Probable duplicate entries: cities of the same country whose names
+    /// fold to the same string (ignoring case, accents and repeated
+    /// whitespace, like [`GeoDb::exact_lookup`]) and that lie within
+    /// `radius_km` of each other.
+    ///
+    /// Each group has at least two members, in database order.
+    pub fn find_duplicate_cities(&self, radius_km: f64) -> Vec<Vec<CityRef<'_, B>>> {
+        let mut by_name: HashMap<(&str, String), Vec<CityRef<'_, B>>> = HashMap::new();
+        let mut order = Vec::new();
+        for entry in self.iter_cities() {
+            let key = (entry.2.iso2(), fold_name(entry.0.name()));
+            let list = by_name.entry(key.clone()).or_default();
+            if list.is_empty() {
+                order.push(key);
+            }
+            list.push(entry);
+        }
+
+        let options = ClusterOptions::new(radius_km);
+        let mut groups: Vec<Vec<CityRef<'_, B>>> = Vec::new();
+        for key in order {
+            let same_name = &by_name[&key];
+            if same_name.len() < 2 {
+                continue;
+            }
+            groups.extend(
+                cluster_cities(same_name.iter().copied(), &options)
+                    .into_iter()
+                    .filter(|c| c.len() > 1)
+                    .map(|c| c.members),
+            );
+        }
+        groups
+    }
+}
+
+/// A city with its state and country, as yielded by [`GeoDb::iter_cities`].
+pub type CityRef<'a, B> = (&'a City<B>, &'a State<B>, &'a Country<B>);
+
+/// How [`cluster_cities`] forms clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusterMethod {
+    /// Greedy grid clustering: the most prominent unclustered city (a
+    /// capital, then the one with the most neighbours) takes every
+    /// unclustered city within the radius, and so on. Every member lies
+    /// within the radius of the representative, which suits map labels.
+    #[default]
+    Leader,
+    /// DBSCAN: cities with at least `min_points` cities within the radius
+    /// link up transitively. Finds connected areas of any shape, but dense
+    /// regions can chain into a single large cluster.
+    Dbscan,
+}
+
+impl FromStr for ClusterMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "leader" | "grid" => Ok(Self::Leader),
+            "dbscan" => Ok(Self::Dbscan),
+            other => Err(format!(
+                "unknown cluster method '{other}' (expected leader or dbscan)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ClusterMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Leader => "leader",
+            Self::Dbscan => "dbscan",
+        })
+    }
+}
+
+/// Settings for [`cluster_cities`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClusterOptions {
+    /// Neighbourhood radius in kilometres.
+    pub radius_km: f64,
+    pub method: ClusterMethod,
+    /// [`ClusterMethod::Dbscan`] only: cities (including itself) a city
+    /// needs within `radius_km` to grow a cluster. `1` links every pair
+    /// closer than the radius (single linkage).
+    pub min_points: usize,
+}
+
+impl Default for ClusterOptions {
+    fn default() -> Self {
+        Self {
+            radius_km: 10.0,
+            method: ClusterMethod::Leader,
+            min_points: 1,
+        }
+    }
+}
+
+impl ClusterOptions {
+    /// Leader clustering with the given radius.
+    pub fn new(radius_km: f64) -> Self {
+        Self {
+            radius_km,
+            ..Self::default()
+        }
+    }
+
+    /// Options using `method`.
+    pub fn with_method(mut self, method: ClusterMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Require `min_points` cities within the radius to grow a DBSCAN cluster.
+    pub fn with_min_points(mut self, min_points: usize) -> Self {
+        self.min_points = min_points.max(1);
+        self
+    }
+}
+
+/// A group of nearby cities returned by [`cluster_cities`].
+#[derive(Debug, Clone)]
+pub struct CityCluster<'a, B: GeoBackend> {
+    /// The city to label the cluster with: the most prominent member, i.e. a
+    /// capital, otherwise the city with the most neighbours.
+    pub representative: CityRef<'a, B>,
+    /// All members in input order, representative included.
+    pub members: Vec<CityRef<'a, B>>,
+    /// Geographic centre of the members as `(lat, lng)`.
+    pub center: (f64, f64),
+}
+
+impl<B: GeoBackend> CityCluster<'_, B> {
+    /// Number of cities in the cluster.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Always `false`; clusters have at least one member.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+/// Point on a sphere of radius [`EARTH_RADIUS_KM`]. Chords are never longer
+/// than arcs, so a grid over these coordinates finds every pair within a
+/// great-circle radius, across the antimeridian and at the poles alike.
+pub(crate) fn to_xyz(lat: f64, lng: f64) -> [f64; 3] {
+    let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+    let (sin_lng, cos_lng) = lng.to_radians().sin_cos();
+    [
+        EARTH_RADIUS_KM * cos_lat * cos_lng,
+        EARTH_RADIUS_KM * cos_lat * sin_lng,
+        EARTH_RADIUS_KM * sin_lat,
+    ]
+}
+
+/// Group cities that lie within `options.radius_km` of each other.
+///
+/// Every input city with coordinates ends up in exactly one cluster;
+/// isolated cities form clusters of one. With the default
+/// [`ClusterMethod::Leader`] the representatives are at least the radius
+/// apart, so they make a decluttered label set for a map zoom level; a
+/// small radius (a few hundred metres) surfaces probable duplicates.
+/// Cities without coordinates are skipped.
+///
+/// Clusters are ordered by size, largest first; equal sizes keep input
+/// order. Neighbours are found through a grid with cells of the radius, so
+/// the cost grows with the number of close pairs rather than quadratically.
+///
+/// ```no_run
+/// use geodb_core::spatial::{cluster_cities, ClusterOptions};
+/// use geodb_core::{GeoDb, StandardBackend};
+///
+/// let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"])?;
+/// for cluster in cluster_cities(db.iter_cities(), &ClusterOptions::new(25.0)).iter().take(5) {
+///     let (city, _, _) = cluster.representative;
+///     println!("{} (+{} nearby)", city.name(), cluster.len() - 1);
+/// }
+/// # Ok::<(), geodb_core::GeoError>(())
+/// ```
+pub fn cluster_cities<'a, B, I>(cities: I, options: &ClusterOptions) -> Vec<CityCluster<'a, B>>
+where
+    B: GeoBackend + 'a,
+    I: IntoIterator<Item = CityRef<'a, B>>,
+{
+    let points: Vec<(CityRef<'a, B>, f64, f64)> = cities
+        .into_iter()
+        .filter_map(|entry| {
+            let city = entry.0;
+            let (lat, lng) = (city.latitude?, city.longitude?);
+            Some((entry, B::float_to_f64(lat), B::float_to_f64(lng)))
+        })
+        .collect();
+    if points.is_empty() {
+        return Vec::new();
+    }
+
+    let radius_km = options.radius_km.max(0.0);
+    // a zero radius still groups identical coordinates
+    let cell_km = radius_km.max(1e-6);
+    let cell_of = |p: &[f64; 3]| p.map(|v| (v / cell_km).floor() as i64);
+    let xyz: Vec<[f64; 3]> = points
+        .iter()
+        .map(|&(_, lat, lng)| to_xyz(lat, lng))
+        .collect();
+    let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    for (i, p) in xyz.iter().enumerate() {
+        grid.entry(cell_of(p)).or_default().push(i);
+    }
+
+    // neighbours[i] excludes i itself
+    let neighbours: Vec<Vec<usize>> = (0..points.len())
+        .map(|i| {
+            let [cx, cy, cz] = cell_of(&xyz[i]);
+            let (_, lat, lng) = points[i];
+            let mut out = Vec::new();
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(bucket) = grid.get(&[cx + dx, cy + dy, cz + dz]) else {
+                            continue;
+                        };
+                        out.extend(bucket.iter().copied().filter(|&j| {
+                            j != i && haversine_km(lat, lng, points[j].1, points[j].2) <= radius_km
+                        }));
+                    }
+                }
+            }
+            out
+        })
+        .collect();
+
+    let is_capital = |i: usize| {
+        let (city, _, country) = points[i].0;
+        country
+            .capital
+            .as_ref()
+            .is_some_and(|c| c.as_ref().eq_ignore_ascii_case(city.name()))
+    };
+    // most prominent first: capitals, then by neighbour count, then input order
+    let prominence = |i: usize| {
+        (
+            std::cmp::Reverse(is_capital(i)),
+            std::cmp::Reverse(neighbours[i].len()),
+            i,
+        )
+    };
+
+    const UNASSIGNED: usize = usize::MAX;
+    let mut label = vec![UNASSIGNED; points.len()];
+    // (representative, members)
+    let mut groups: Vec<(usize, Vec<usize>)> = Vec::new();
+    match options.method {
+        ClusterMethod::Leader => {
+            let mut order: Vec<usize> = (0..points.len()).collect();
+            order.sort_by_key(|&i| prominence(i));
+            for leader in order {
+                if label[leader] != UNASSIGNED {
+                    continue;
+                }
+                let id = groups.len();
+                label[leader] = id;
+                let mut group = vec![leader];
+                for &j in &neighbours[leader] {
+                    if label[j] == UNASSIGNED {
+                        label[j] = id;
+                        group.push(j);
+                    }
+                }
+                groups.push((leader, group));
+            }
+        }
+        ClusterMethod::Dbscan => {
+            let min_points = options.min_points.max(1);
+            let is_core = |i: usize| neighbours[i].len() + 1 >= min_points;
+            for start in 0..points.len() {
+                if label[start] != UNASSIGNED || !is_core(start) {
+                    continue;
+                }
+                let id = groups.len();
+                label[start] = id;
+                let mut group = vec![start];
+                let mut queue = vec![start];
+                while let Some(i) = queue.pop() {
+                    for &j in &neighbours[i] {
+                        if label[j] != UNASSIGNED {
+                            continue;
+                        }
+                        label[j] = id;
+                        group.push(j);
+                        if is_core(j) {
+                            queue.push(j);
+                        }
+                    }
+                }
+                let best = group.iter().copied().min_by_key(|&i| prominence(i));
+                groups.push((best.expect("non-empty group"), group));
+            }
+            // noise: cities no core city reaches
+            for (i, l) in label.iter().enumerate() {
+                if *l == UNASSIGNED {
+                    groups.push((i, vec![i]));
+                }
+            }
+        }
+    }
+
+    let mut clusters: Vec<(usize, CityCluster<'a, B>)> = groups
+        .into_iter()
+        .map(|(best, mut group)| {
+            group.sort_unstable();
+            let [x, y, z] = group.iter().fold([0.0; 3], |acc, &i| {
+                [acc[0] + xyz[i][0], acc[1] + xyz[i][1], acc[2] + xyz[i][2]]
+            });
+            let center = if x == 0.0 && y == 0.0 && z == 0.0 {
+                (points[best].1, points[best].2)
+            } else {
+                (z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees())
+            };
+            let cluster = CityCluster {
+                representative: points[best].0,
+                members: group.iter().map(|&i| points[i].0).collect(),
+                center,
+            };
+            (group[0], cluster)
+        })
+        .collect();
+
+    clusters.sort_by(|(a_first, a), (b_first, b)| b.len().cmp(&a.len()).then(a_first.cmp(b_first)));
+    clusters.into_iter().map(|(_, c)| c).collect()
+}
@@ -0,0 +1,29 @@
+// src/stable.rs
+//! Stable API surface.
+//!
+//! Everything re-exported here is covered by semver: it is only removed or
+//! changed in a breaking release, after a deprecation period. Items outside
+//! this module (raw JSON structs, DTOs, response envelopes, optional
+//! features) may still evolve in minor releases.
+//!
+//! Downstream crates that want to be insulated from internal reshuffles can
+//! import from here instead of the crate root:
+//!
+//! ```
+//! use geodb_core::stable::{GeoDb, StandardBackend};
+//! # fn _f(_: Option<GeoDb<StandardBackend>>) {}
+//! ```
+//!
+//! The surface is pinned by `tests/public_api.rs`, which spells out the
+//! signatures of the items below. A failing build there means a change is
+//! breaking for downstream users.
+pub use crate::api::{CityView, CountryView, StateView};
+pub use crate::error::{GeoDbError, GeoError, Result};
+pub use crate::model::{
+    City, Country, CountryTimezone, DbStats, DefaultBackend, DefaultGeoDb, GeoBackend, GeoDb,
+    SmartHit, SmartItem, StandardBackend, State,
+};
+pub use crate::phone::PhoneCodeSearch;
+pub use crate::search::{
+    ExpansionTable, LevelScores, ScoringProfile, SearchLanguage, SearchOptions,
+};
@@ -0,0 +1,161 @@
+// src/staged.rs
+//! Two-stage loading: countries and states first, cities later.
+//!
+//! Cities make up almost all of a database. A country picker doesn't need
+//! them, so a client can start from a city-less *skeleton* and attach the
+//! cities country by country as they are decoded (or downloaded), e.g. in
+//! the idle time of a browser tab:
+//!
+//! ```no_run
+//! use geodb_core::staged::CityChunks;
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let full = GeoDb::<StandardBackend>::load()?;
+//! let (skeleton, cities) = full.to_staged_bytes()?;
+//!
+//! let mut db = GeoDb::<StandardBackend>::from_bytes(&skeleton)?; // small, fast
+//! assert_eq!(db.stats().cities, 0);
+//! for chunk in CityChunks::<StandardBackend>::new(&cities) {
+//!     db.attach_cities(chunk?)?;
+//! }
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! The city stream is a sequence of [`CityChunk`]s, each a little-endian
+//! `u64` byte length followed by the bincode encoding of the chunk.
+use crate::error::{GeoError, Result};
+use crate::model::{City, GeoBackend, GeoDb};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// The cities of one country, one list per state in state order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "City<B>: Serialize",
+    deserialize = "City<B>: Deserialize<'de>"
+))]
+pub struct CityChunk<B: GeoBackend> {
+    /// Index of the country in the skeleton.
+    pub country: usize,
+    pub states: Vec<Vec<City<B>>>,
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Split into a skeleton without cities and one [`CityChunk`] per
+    /// country that has any; attaching all chunks restores the database.
+    pub fn split_cities(mut self) -> (Self, Vec<CityChunk<B>>) {
+        self.invalidate_city_index();
+        let chunks = self
+            .countries
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, c)| c.states.iter().any(|s| !s.cities.is_empty()))
+            .map(|(i, c)| CityChunk {
+                country: i,
+                states: c
+                    .states
+                    .iter_mut()
+                    .map(|s| std::mem::take(&mut s.cities))
+                    .collect(),
+            })
+            .collect();
+        (self, chunks)
+    }
+
+    /// Add the cities of `chunk` to their country, after any it already has.
+    ///
+    /// Fails if the chunk doesn't fit this database (unknown country or a
+    /// different number of states); the database is left unchanged then.
+    pub fn attach_cities(&mut self, chunk: CityChunk<B>) -> Result<()> {
+        let country = self.countries.get_mut(chunk.country).ok_or_else(|| {
+            GeoError::InvalidData(format!("city chunk for unknown country {}", chunk.country))
+        })?;
+        if country.states.len() != chunk.states.len() {
+            return Err(GeoError::InvalidData(format!(
+                "city chunk for {} has {} states, the database {}",
+                country.iso2(),
+                chunk.states.len(),
+                country.states.len()
+            )));
+        }
+        for (state, cities) in country.states.iter_mut().zip(chunk.states) {
+            state.cities.extend(cities);
+        }
+        self.invalidate_city_index();
+        Ok(())
+    }
+}
+
+impl<B> GeoDb<B>
+where
+    B: GeoBackend + Serialize + DeserializeOwned,
+{
+    /// Binary skeleton (read with [`GeoDb::from_bytes`]) and city stream
+    /// (read with [`CityChunks`]) of this database.
+    pub fn to_staged_bytes(&self) -> Result<(Vec<u8>, Vec<u8>)> {
+        let (skeleton, chunks) = self.clone().split_cities();
+        let mut stream = Vec::new();
+        for chunk in &chunks {
+            let bytes = bincode::serialize(chunk)?;
+            stream.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            stream.extend_from_slice(&bytes);
+        }
+        Ok((skeleton.to_bytes()?, stream))
+    }
+}
+
+/// Iterator over the [`CityChunk`]s of a city stream; see the
+/// [module docs](self).
+///
+/// Stops after the first error.
+pub struct CityChunks<'a, B: GeoBackend> {
+    bytes: &'a [u8],
+    _backend: PhantomData<B>,
+}
+
+impl<'a, B: GeoBackend> CityChunks<'a, B> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            _backend: PhantomData,
+        }
+    }
+
+    /// Bytes not read yet.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn read_chunk(&mut self) -> Result<CityChunk<B>> {
+        let (len, rest) = self
+            .bytes
+            .split_first_chunk::<8>()
+            .ok_or_else(|| GeoError::InvalidData("truncated city chunk length".into()))?;
+        let len = usize::try_from(u64::from_le_bytes(*len)).unwrap_or(usize::MAX);
+        if len > rest.len() {
+            return Err(GeoError::InvalidData(format!(
+                "city chunk of {len} bytes, only {} left",
+                rest.len()
+            )));
+        }
+        let (chunk, rest) = rest.split_at(len);
+        self.bytes = rest;
+        Ok(bincode::deserialize(chunk)?)
+    }
+}
+
+impl<B: GeoBackend> Iterator for CityChunks<'_, B> {
+    type Item = Result<CityChunk<B>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let chunk = self.read_chunk();
+        if chunk.is_err() {
+            self.bytes = &[];
+        }
+        Some(chunk)
+    }
+}
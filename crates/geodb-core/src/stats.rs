@@ -0,0 +1,123 @@
+// src/stats.rs
+//! Machine-readable dataset statistics for release tooling (feature `json`).
+//!
+//! [`DatasetStats`] is what `geodb stats --format json` writes: entity
+//! counts, where the data came from and when the report was made. The JSON
+//! layout is versioned by [`DatasetStats::schema`]; fields are only added
+//! within a schema version, never renamed or removed.
+//!
+//! ```no_run
+//! use geodb_core::stats::DatasetSource;
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let path = GeoDb::<StandardBackend>::default_data_dir()
+//!     .join(GeoDb::<StandardBackend>::default_dataset_filename());
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let source = DatasetSource::from_path(&path).with_version("2024.06.01");
+//! let stats = db.dataset_stats(source, &[]);
+//! println!("{}", serde_json::to_string_pretty(&stats).unwrap());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+use crate::model::{civil_date, GeoBackend, GeoDb};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current [`DatasetStats::schema`].
+pub const STATS_SCHEMA: u32 = 1;
+
+/// The dataset a [`DatasetStats`] report describes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetSource {
+    /// File name of the source dataset; `None` for embedded data.
+    pub file: Option<String>,
+    /// Size in bytes.
+    pub size: Option<u64>,
+    /// FNV-1a 64 fingerprint of the file (hex), as in
+    /// [`CacheStatus`](crate::CacheStatus).
+    pub fingerprint: Option<String>,
+    /// Modification time in seconds since the Unix epoch.
+    pub modified: Option<u64>,
+    /// Dataset version, e.g. `2024.06.01`; defaults to the latest version
+    /// of the attached changelog.
+    pub version: Option<String>,
+}
+
+impl DatasetSource {
+    /// Describe the dataset file at `path`; missing files leave the fields
+    /// other than `file` empty.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let meta = std::fs::metadata(path).ok();
+        Self {
+            file: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+            size: meta.as_ref().map(|m| m.len()),
+            fingerprint: crate::loader::file_fingerprint(path).ok(),
+            modified: meta
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs()),
+            version: None,
+        }
+    }
+
+    /// The same source with an explicit version.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+}
+
+/// Entity counts and provenance of a database; see the
+/// [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetStats {
+    /// Layout version ([`STATS_SCHEMA`]).
+    pub schema: u32,
+    /// Crate and version that made the report, e.g. `geodb-core 0.1.3`.
+    pub generator: String,
+    /// Report time in seconds since the Unix epoch.
+    pub generated_at: u64,
+    /// `generated_at` as a `YYYY-MM-DD` date (UTC).
+    pub build_date: String,
+    pub dataset: DatasetSource,
+    /// ISO2 codes the database was filtered to; empty for all countries.
+    pub filter: Vec<String>,
+    pub countries: usize,
+    pub states: usize,
+    pub cities: usize,
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Statistics report for this database, loaded from `source` with the
+    /// ISO2 `filter` (empty for all countries).
+    pub fn dataset_stats(&self, mut source: DatasetSource, filter: &[&str]) -> DatasetStats {
+        if source.version.is_none() {
+            source.version = self
+                .changelog
+                .as_ref()
+                .and_then(|c| c.latest_version())
+                .map(str::to_string);
+        }
+        let generated_at = unix_now();
+        let (year, month, day) = civil_date(generated_at);
+        let stats = self.stats();
+        DatasetStats {
+            schema: STATS_SCHEMA,
+            generator: format!("geodb-core {}", env!("CARGO_PKG_VERSION")),
+            generated_at,
+            build_date: format!("{year:04}-{month:02}-{day:02}"),
+            dataset: source,
+            filter: filter.iter().map(|s| s.to_string()).collect(),
+            countries: stats.countries,
+            states: stats.states,
+            cities: stats.cities,
+        }
+    }
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
@@ -0,0 +1,132 @@
+// src/timezone_audit.rs
+//! Consistency of country timezones with their cities' timezones.
+//!
+//! Upstream lists timezones twice: per country (`Country::timezones`) and per
+//! city (`City::timezone`). The two drift apart, e.g. when a city is assigned
+//! a zone its country doesn't list, or when all cities of a multi-zone
+//! country collapse onto one zone. [`GeoDb::audit_timezones`] reports both
+//! directions so the data can be fixed before artifacts are baked.
+use crate::model::{GeoBackend, GeoDb};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// How many example cities a [`TimezoneIssue`] names.
+const MAX_EXAMPLES: usize = 3;
+
+/// Kind of problem found by [`GeoDb::audit_timezones`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimezoneIssueKind {
+    /// Cities use a zone their country doesn't list; timezone pickers built
+    /// from the country miss it.
+    CityZoneNotListed,
+    /// The country lists a zone none of its cities use, although other
+    /// cities have timezones; usually cities were assigned a neighbouring
+    /// zone upstream.
+    ListedZoneUnused,
+}
+
+/// One timezone mismatch of a country.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimezoneIssue {
+    pub kind: TimezoneIssueKind,
+    pub iso2: String,
+    /// The zone in question (IANA name).
+    pub timezone: String,
+    /// Cities using the zone (0 for [`TimezoneIssueKind::ListedZoneUnused`]).
+    pub cities: usize,
+    /// Up to three of those cities as `state/city`.
+    pub examples: Vec<String>,
+    pub suggestion: String,
+}
+
+impl fmt::Display for TimezoneIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TimezoneIssueKind::CityZoneNotListed => write!(
+                f,
+                "{}: {} is used by {} cities (e.g. {}) but not listed for the country",
+                self.iso2,
+                self.timezone,
+                self.cities,
+                self.examples.join(", ")
+            ),
+            TimezoneIssueKind::ListedZoneUnused => write!(
+                f,
+                "{}: {} is listed for the country but used by no city",
+                self.iso2, self.timezone
+            ),
+        }
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Compare each country's listed timezones with its cities' timezones.
+    ///
+    /// Zones are compared by exact IANA name. Countries whose cities carry
+    /// no timezone at all are skipped for [`TimezoneIssueKind::ListedZoneUnused`],
+    /// since there is nothing to compare against. Issues are ordered by
+    /// country (database order), kind and zone name.
+    pub fn audit_timezones(&self) -> Vec<TimezoneIssue> {
+        let mut issues = Vec::new();
+        for country in &self.countries {
+            let listed: BTreeSet<&str> = country
+                .timezones()
+                .iter()
+                .filter_map(|tz| tz.zone_name.as_ref().map(|z| z.as_ref()))
+                .collect();
+
+            // zone -> (city count, examples)
+            let mut used: BTreeMap<&str, (usize, Vec<String>)> = BTreeMap::new();
+            for state in country.states() {
+                for city in state.cities() {
+                    let Some(zone) = city.timezone.as_ref().map(|z| z.as_ref()) else {
+                        continue;
+                    };
+                    if zone.is_empty() {
+                        continue;
+                    }
+                    let (count, examples) = used.entry(zone).or_default();
+                    *count += 1;
+                    if examples.len() < MAX_EXAMPLES {
+                        examples.push(format!("{}/{}", state.name(), city.name()));
+                    }
+                }
+            }
+
+            for (zone, (count, examples)) in &used {
+                if !listed.contains(zone) {
+                    issues.push(TimezoneIssue {
+                        kind: TimezoneIssueKind::CityZoneNotListed,
+                        iso2: country.iso2().to_string(),
+                        timezone: zone.to_string(),
+                        cities: *count,
+                        examples: examples.clone(),
+                        suggestion: format!(
+                            "add {zone} to the timezones of {}, or correct the cities' zone",
+                            country.iso2()
+                        ),
+                    });
+                }
+            }
+            if used.is_empty() {
+                continue;
+            }
+            for zone in listed.iter().filter(|z| !used.contains_key(*z)) {
+                issues.push(TimezoneIssue {
+                    kind: TimezoneIssueKind::ListedZoneUnused,
+                    iso2: country.iso2().to_string(),
+                    timezone: zone.to_string(),
+                    cities: 0,
+                    examples: Vec::new(),
+                    suggestion: format!(
+                        "assign {zone} to the cities it covers, or drop it from {}",
+                        country.iso2()
+                    ),
+                });
+            }
+        }
+        issues
+    }
+}
@@ -0,0 +1,286 @@
+// src/translations.rs
+//! State and region name translations.
+//!
+//! The upstream dataset has an English name and, for some states, a
+//! `native_name`. This module merges translated names from a sidecar file
+//! (`data/state_translations.json`) onto [`State`] with
+//! [`GeoDb::apply_state_translations`], so [`State::localized_name`] can
+//! pick the right label for a UI language.
+//!
+//! Region and subregion labels ("Europe", "Western Europe") are English
+//! only as well; `data/region_translations.json` is merged onto [`Country`]
+//! with [`GeoDb::apply_region_translations`] for
+//! [`Country::region_localized`] and [`Country::subregion_localized`].
+use crate::model::{Country, GeoBackend, GeoDb, State};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "json")]
+use {crate::error::Result, std::fs, std::path::Path};
+
+/// Translated names of one state.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StateTranslations {
+    pub iso2: String,  // "DE"
+    pub state: String, // state name as in the dataset, e.g. "Bavaria"
+    /// Language code → name, e.g. `{"de": "Bayern", "fr": "Bavière"}`.
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StateTranslationsFile {
+    pub states: Vec<StateTranslations>,
+}
+
+/// Translations indexed by (iso2, state name).
+#[derive(Debug, Default)]
+pub struct StateTranslationTable {
+    pub entries: Vec<StateTranslations>,
+    /// (iso2.lower, state.lower) → index into `entries`
+    index: HashMap<(String, String), usize>,
+}
+
+impl StateTranslationTable {
+    /// Build a table from translation entries.
+    ///
+    /// Language codes are lowercased; later entries for the same state win.
+    pub fn from_entries(entries: Vec<StateTranslations>) -> Self {
+        let mut table = StateTranslationTable {
+            entries,
+            index: HashMap::new(),
+        };
+        for (i, entry) in table.entries.iter_mut().enumerate() {
+            entry.names = std::mem::take(&mut entry.names)
+                .into_iter()
+                .map(|(lang, name)| (lang.to_ascii_lowercase(), name))
+                .collect();
+            let key = (entry.iso2.to_ascii_lowercase(), entry.state.to_lowercase());
+            table.index.insert(key, i);
+        }
+        table
+    }
+
+    /// Load translations from a JSON file.
+    ///
+    /// Expected format:
+    /// {
+    ///   "states": [
+    ///     { "iso2": "DE", "state": "Bavaria",
+    ///       "names": { "de": "Bayern", "fr": "Bavière", "it": "Baviera" }
+    ///     },
+    ///     ...
+    ///   ]
+    /// }
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        let file: StateTranslationsFile = serde_json::from_slice(&bytes)?;
+        Ok(Self::from_entries(file.states))
+    }
+
+    /// Load `state_translations.json` from the crate's default `data/` directory.
+    #[cfg(feature = "json")]
+    pub fn load_default() -> Result<Self> {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let path: std::path::PathBuf = [manifest_dir, "data", "state_translations.json"]
+            .iter()
+            .collect();
+        Self::load_from_path(path)
+    }
+
+    /// Translations of a state, by ISO2 and dataset name (case-insensitive).
+    pub fn get(&self, iso2: &str, state: &str) -> Option<&StateTranslations> {
+        let key = (iso2.to_ascii_lowercase(), state.to_lowercase());
+        self.index.get(&key).map(|&i| &self.entries[i])
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Attach translated names from `table` to the matching states.
+    ///
+    /// Returns the number of states updated.
+    pub fn apply_state_translations(&mut self, table: &StateTranslationTable) -> usize {
+        let mut updated = 0;
+        for country in &mut self.countries {
+            let iso2 = country.iso2.as_ref().to_string();
+            for state in &mut country.states {
+                if let Some(entry) = table.get(&iso2, state.name.as_ref()) {
+                    state.translations = entry.names.clone();
+                    updated += 1;
+                }
+            }
+        }
+        updated
+    }
+}
+
+/// Translated names of one region or subregion.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegionTranslations {
+    pub region: String, // name as in the dataset, e.g. "Western Europe"
+    /// Language code → name, e.g. `{"de": "Westeuropa", "fr": "Europe de l'Ouest"}`.
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegionTranslationsFile {
+    pub regions: Vec<RegionTranslations>,
+}
+
+/// Region and subregion translations indexed by dataset name.
+#[derive(Debug, Default)]
+pub struct RegionTranslationTable {
+    pub entries: Vec<RegionTranslations>,
+    /// region.lower → index into `entries`
+    index: HashMap<String, usize>,
+}
+
+impl RegionTranslationTable {
+    /// Build a table from translation entries.
+    ///
+    /// Language codes are lowercased; later entries for the same region win.
+    pub fn from_entries(entries: Vec<RegionTranslations>) -> Self {
+        let mut table = RegionTranslationTable {
+            entries,
+            index: HashMap::new(),
+        };
+        for (i, entry) in table.entries.iter_mut().enumerate() {
+            entry.names = std::mem::take(&mut entry.names)
+                .into_iter()
+                .map(|(lang, name)| (lang.to_ascii_lowercase(), name))
+                .collect();
+            table.index.insert(entry.region.to_lowercase(), i);
+        }
+        table
+    }
+
+    /// Load translations from a JSON file.
+    ///
+    /// Expected format (regions and subregions share one list):
+    /// {
+    ///   "regions": [
+    ///     { "region": "Europe", "names": { "de": "Europa", "fr": "Europe" } },
+    ///     { "region": "Western Europe", "names": { "de": "Westeuropa" } },
+    ///     ...
+    ///   ]
+    /// }
+    #[cfg(feature = "json")]
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        let file: RegionTranslationsFile = serde_json::from_slice(&bytes)?;
+        Ok(Self::from_entries(file.regions))
+    }
+
+    /// Load `region_translations.json` from the crate's default `data/` directory.
+    #[cfg(feature = "json")]
+    pub fn load_default() -> Result<Self> {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let path: std::path::PathBuf = [manifest_dir, "data", "region_translations.json"]
+            .iter()
+            .collect();
+        Self::load_from_path(path)
+    }
+
+    /// Translations of a region or subregion by dataset name (case-insensitive).
+    pub fn get(&self, region: &str) -> Option<&RegionTranslations> {
+        self.index
+            .get(&region.to_lowercase())
+            .map(|&i| &self.entries[i])
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Attach translated region and subregion names from `table` to every
+    /// country.
+    ///
+    /// Returns the number of countries updated.
+    pub fn apply_region_translations(&mut self, table: &RegionTranslationTable) -> usize {
+        let names = |region: Option<&B::Str>| {
+            region
+                .and_then(|r| table.get(r.as_ref()))
+                .map(|entry| entry.names.clone())
+        };
+        let mut updated = 0;
+        for country in &mut self.countries {
+            let region = names(country.region.as_ref());
+            let subregion = names(country.subregion.as_ref());
+            if region.is_some() || subregion.is_some() {
+                updated += 1;
+            }
+            country.region_translations = region.unwrap_or_default();
+            country.subregion_translations = subregion.unwrap_or_default();
+        }
+        updated
+    }
+
+    /// Countries grouped by localized region, for a country picker with
+    /// one section per continent.
+    ///
+    /// Groups are sorted by their localized region name and hold
+    /// `(iso2, localized country name)` pairs sorted by name; countries
+    /// without a region form a group with an empty name.
+    pub fn countries_by_region(&self, lang: &str) -> Vec<(&str, Vec<(&str, &str)>)> {
+        let mut groups: Vec<(&str, Vec<(&str, &str)>)> = Vec::new();
+        for country in &self.countries {
+            let region = country.region_localized(lang);
+            let entry = (country.iso2(), country.localized_name(lang));
+            match groups.iter_mut().find(|(r, _)| *r == region) {
+                Some((_, countries)) => countries.push(entry),
+                None => groups.push((region, vec![entry])),
+            }
+        }
+        groups.sort_by_cached_key(|(region, _)| region.to_lowercase());
+        for (_, countries) in &mut groups {
+            countries.sort_by_cached_key(|(_, name)| name.to_lowercase());
+        }
+        groups
+    }
+}
+
+impl<B: GeoBackend> Country<B> {
+    /// Name in `lang` (dataset codes like "de" or "pt-BR"), from the
+    /// dataset's translations, falling back to [`Country::name`] (English).
+    pub fn localized_name(&self, lang: &str) -> &str {
+        self.translations
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(lang))
+            .map(|(_, name)| name.as_ref())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| self.name())
+    }
+
+    /// Region in `lang` (e.g. "Europa" for "de"), falling back to
+    /// [`Country::region`]. Translations come from
+    /// [`GeoDb::apply_region_translations`].
+    pub fn region_localized(&self, lang: &str) -> &str {
+        self.region_translations
+            .get(&lang.to_ascii_lowercase())
+            .map(String::as_str)
+            .unwrap_or_else(|| self.region())
+    }
+
+    /// Subregion in `lang` (e.g. "Westeuropa" for "de"), falling back to
+    /// [`Country::subregion`].
+    pub fn subregion_localized(&self, lang: &str) -> Option<&str> {
+        self.subregion_translations
+            .get(&lang.to_ascii_lowercase())
+            .map(String::as_str)
+            .or_else(|| self.subregion())
+    }
+
+    /// `(state code, localized name)` pairs sorted by name, e.g. for the
+    /// options of a state `<select>` in a form.
+    ///
+    /// Names follow [`State::localized_name`]; states without a code use an
+    /// empty string.
+    pub fn state_options(&self, lang: &str) -> Vec<(&str, &str)> {
+        let mut options: Vec<(&str, &str)> = self
+            .states()
+            .iter()
+            .map(|s: &State<B>| (s.state_code(), s.localized_name(lang)))
+            .collect();
+        options.sort_by_cached_key(|(_, name)| name.to_lowercase());
+        options
+    }
+}
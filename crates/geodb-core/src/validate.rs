@@ -0,0 +1,200 @@
+// src/validate.rs
+//! Light dataset validation.
+//!
+//! [`GeoDb::validate`] looks for anomalies that indicate a broken or
+//! hand-edited artifact: duplicate or malformed country codes, empty names
+//! and coordinates outside the valid range. Loading with
+//! [`LoadOptions::validate`](crate::LoadOptions::validate) runs it once and
+//! keeps the result, available via [`GeoDb::load_warnings`], so services can
+//! alert on a bad artifact instead of silently serving it.
+use crate::model::{GeoBackend, GeoDb};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// One anomaly found by [`GeoDb::validate`].
+///
+/// `path` fields locate the entity as `ISO2`, `ISO2/state` or
+/// `ISO2/state/city`; positions (`#3`) stand in for empty names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LoadWarning {
+    /// Several countries share one ISO2 code (case-insensitive).
+    DuplicateIso2 { iso2: String, count: usize },
+    /// Several countries share one ISO3 code (case-insensitive).
+    DuplicateIso3 { iso3: String, count: usize },
+    /// A country code that isn't two ASCII letters.
+    InvalidIso2 { country: String, iso2: String },
+    /// A country, state or city with an empty (or blank) name.
+    EmptyName { path: String },
+    /// Latitude outside -90..=90 or longitude outside -180..=180.
+    CoordinatesOutOfRange { path: String, lat: f64, lng: f64 },
+    /// A district whose city range lies outside its state's cities.
+    #[cfg(feature = "districts")]
+    DistrictOutOfRange {
+        path: String,
+        start: u32,
+        end: u32,
+        cities: usize,
+    },
+}
+
+impl fmt::Display for LoadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateIso2 { iso2, count } => {
+                write!(f, "ISO2 code {iso2} is used by {count} countries")
+            }
+            Self::DuplicateIso3 { iso3, count } => {
+                write!(f, "ISO3 code {iso3} is used by {count} countries")
+            }
+            Self::InvalidIso2 { country, iso2 } => {
+                write!(f, "country {country} has an invalid ISO2 code '{iso2}'")
+            }
+            Self::EmptyName { path } => write!(f, "{path} has an empty name"),
+            Self::CoordinatesOutOfRange { path, lat, lng } => {
+                write!(f, "{path} has coordinates out of range ({lat}, {lng})")
+            }
+            #[cfg(feature = "districts")]
+            Self::DistrictOutOfRange {
+                path,
+                start,
+                end,
+                cities,
+            } => write!(
+                f,
+                "district {path} covers cities {start}..{end}, but the state has {cities}"
+            ),
+        }
+    }
+}
+
+fn coordinates_in_range(lat: f64, lng: f64) -> bool {
+    (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng)
+}
+
+fn check_position<B: GeoBackend>(
+    warnings: &mut Vec<LoadWarning>,
+    path: impl FnOnce() -> String,
+    lat: Option<B::Float>,
+    lng: Option<B::Float>,
+) {
+    if let (Some(lat), Some(lng)) = (lat, lng) {
+        let (lat, lng) = (B::float_to_f64(lat), B::float_to_f64(lng));
+        if !coordinates_in_range(lat, lng) {
+            warnings.push(LoadWarning::CoordinatesOutOfRange {
+                path: path(),
+                lat,
+                lng,
+            });
+        }
+    }
+}
+
+fn label(name: &str, index: usize) -> String {
+    if name.trim().is_empty() {
+        format!("#{index}")
+    } else {
+        name.to_string()
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Check the database for anomalies.
+    ///
+    /// Runs in one pass over all entities; an empty result means nothing
+    /// suspicious was found.
+    pub fn validate(&self) -> Vec<LoadWarning> {
+        let mut warnings = Vec::new();
+        let mut iso2_counts: HashMap<String, usize> = HashMap::new();
+        let mut iso3_counts: HashMap<String, usize> = HashMap::new();
+
+        for (ci, country) in self.countries.iter().enumerate() {
+            let iso2 = country.iso2();
+            let country_path = if iso2.is_empty() {
+                label(country.name(), ci)
+            } else {
+                iso2.to_string()
+            };
+
+            *iso2_counts.entry(iso2.to_ascii_uppercase()).or_default() += 1;
+            let iso3 = country.iso3();
+            if !iso3.is_empty() {
+                *iso3_counts.entry(iso3.to_ascii_uppercase()).or_default() += 1;
+            }
+            if iso2.len() != 2 || !iso2.bytes().all(|b| b.is_ascii_alphabetic()) {
+                warnings.push(LoadWarning::InvalidIso2 {
+                    country: label(country.name(), ci),
+                    iso2: iso2.to_string(),
+                });
+            }
+            if country.name().trim().is_empty() {
+                warnings.push(LoadWarning::EmptyName {
+                    path: country_path.clone(),
+                });
+            }
+            check_position::<B>(
+                &mut warnings,
+                || country_path.clone(),
+                country.latitude,
+                country.longitude,
+            );
+
+            for (si, state) in country.states().iter().enumerate() {
+                let state_path = || format!("{country_path}/{}", label(state.name(), si));
+                if state.name().trim().is_empty() {
+                    warnings.push(LoadWarning::EmptyName { path: state_path() });
+                }
+                check_position::<B>(&mut warnings, state_path, state.latitude, state.longitude);
+
+                #[cfg(feature = "districts")]
+                for (di, district) in state.districts.iter().enumerate() {
+                    let cities = state.city_count();
+                    if district.city_start > district.city_end
+                        || district.city_end as usize > cities
+                    {
+                        warnings.push(LoadWarning::DistrictOutOfRange {
+                            path: format!("{}/{}", state_path(), label(district.name.as_ref(), di)),
+                            start: district.city_start,
+                            end: district.city_end,
+                            cities,
+                        });
+                    }
+                }
+
+                for (ti, city) in state.cities().iter().enumerate() {
+                    let city_path = || format!("{}/{}", state_path(), label(city.name(), ti));
+                    if city.name().trim().is_empty() {
+                        warnings.push(LoadWarning::EmptyName { path: city_path() });
+                    }
+                    check_position::<B>(&mut warnings, city_path, city.latitude, city.longitude);
+                }
+            }
+        }
+
+        let mut duplicates: Vec<LoadWarning> = iso2_counts
+            .into_iter()
+            .filter(|(code, n)| *n > 1 && !code.is_empty())
+            .map(|(iso2, count)| LoadWarning::DuplicateIso2 { iso2, count })
+            .chain(
+                iso3_counts
+                    .into_iter()
+                    .filter(|(_, n)| *n > 1)
+                    .map(|(iso3, count)| LoadWarning::DuplicateIso3 { iso3, count }),
+            )
+            .collect();
+        duplicates.sort_by_key(|w| w.to_string());
+        duplicates.extend(warnings);
+        duplicates
+    }
+
+    /// Warnings recorded by a validating load (see [`LoadOptions::validate`]).
+    ///
+    /// Empty if the database was loaded without validation or nothing was
+    /// found.
+    ///
+    /// [`LoadOptions::validate`]: crate::LoadOptions::validate
+    pub fn load_warnings(&self) -> &[LoadWarning] {
+        &self.load_warnings
+    }
+}
@@ -0,0 +1,169 @@
+// src/version.rs
+//! Code and data versions in one report, for bug reports.
+//!
+//! A search result depends on both the crate and the data it ran on, and
+//! the two are released independently. [`GeoDb::version_info`] puts them
+//! side by side: the crate version and features, the dataset version (from
+//! the attached changelog, or set by the caller) and a fingerprint of the
+//! loaded content, which identifies the data even when no version is known
+//! (embedded artifacts, filtered loads, local rebuilds).
+//!
+//! ```no_run
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let db = GeoDb::<StandardBackend>::load()?;
+//! let info = db.version_info().with_dataset_version("2024.06.01");
+//! println!("{info}"); // geodb-core 0.1.3, dataset 2024.06.01 (data 3f2a…)
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! `geodb --version`, `version_info()` in WASM and `GeoDb.version_info()`
+//! in Python print or return the same fields.
+use crate::model::{GeoBackend, GeoDb};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Crate and data versions of a [`GeoDb`]; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionInfo {
+    /// Version of geodb-core, e.g. `0.1.3`.
+    pub crate_version: String,
+    /// Binding or tool on top of geodb-core, e.g. `geodb-py 0.1.3`.
+    pub binding: Option<String>,
+    /// Cargo features geodb-core was built with.
+    pub features: Vec<String>,
+    /// Field set of the JSON views, see [`crate::api::VIEW_VERSION`].
+    #[serde(default)]
+    pub view_version: u32,
+    /// Dataset version, e.g. `2024.06.01`; defaults to the latest version
+    /// of the attached changelog.
+    pub dataset_version: Option<String>,
+    /// FNV-1a 64 fingerprint (hex) of the loaded codes, names and
+    /// coordinates. Equal for the same data whether it came from the JSON
+    /// dataset or a binary artifact; sidecar tables (aliases, translations)
+    /// don't change it.
+    pub data_fingerprint: String,
+    pub countries: usize,
+    pub states: usize,
+    pub cities: usize,
+}
+
+impl VersionInfo {
+    /// The same report with an explicit dataset version.
+    pub fn with_dataset_version(mut self, version: impl Into<String>) -> Self {
+        self.dataset_version = Some(version.into());
+        self
+    }
+
+    /// The same report naming the binding or tool, e.g. `geodb-py 0.1.3`.
+    pub fn with_binding(mut self, binding: impl Into<String>) -> Self {
+        self.binding = Some(binding.into());
+        self
+    }
+}
+
+impl fmt::Display for VersionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(binding) = &self.binding {
+            write!(f, "{binding}, ")?;
+        }
+        write!(f, "geodb-core {}, dataset ", self.crate_version)?;
+        match &self.dataset_version {
+            Some(version) => write!(f, "{version}")?,
+            None => f.write_str("unversioned")?,
+        }
+        write!(f, " (data {})", self.data_fingerprint)
+    }
+}
+
+/// Features geodb-core was compiled with.
+fn enabled_features() -> Vec<String> {
+    [
+        ("json", cfg!(feature = "json")),
+        ("compact", cfg!(feature = "compact")),
+        ("districts", cfg!(feature = "districts")),
+        ("multilingual-cities", cfg!(feature = "multilingual-cities")),
+        ("embed-data", cfg!(feature = "embed-data")),
+        ("msgpack", cfg!(feature = "msgpack")),
+        ("fetch", cfg!(feature = "fetch")),
+        ("signed", cfg!(feature = "signed")),
+        ("geonames", cfg!(feature = "geonames")),
+        ("geo-interop", cfg!(feature = "geo-interop")),
+        ("bundle", cfg!(feature = "bundle")),
+        ("mmap", cfg!(feature = "mmap")),
+        ("zerocopy", cfg!(feature = "zerocopy")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name.to_string())
+    .collect()
+}
+
+/// FNV-1a 64, fed field by field.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// A string followed by a separator, so ("ab", "c") and ("a", "bc")
+    /// hash differently.
+    fn str(&mut self, s: &str) {
+        self.bytes(s.as_bytes());
+        self.bytes(&[0xff]);
+    }
+
+    fn coord(&mut self, value: Option<f64>) {
+        match value {
+            Some(v) => self.bytes(&v.to_bits().to_le_bytes()),
+            None => self.bytes(&[0]),
+        }
+    }
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Crate version, features, dataset version and a fingerprint of the
+    /// loaded data; see [`VersionInfo`].
+    ///
+    /// Hashes every city, so call it once per report rather than per query.
+    pub fn version_info(&self) -> VersionInfo {
+        let mut hash = Fnv::new();
+        for country in &self.countries {
+            hash.str(country.iso2());
+            hash.str(country.name());
+            for state in country.states() {
+                hash.str(state.state_code());
+                hash.str(state.name());
+                for city in state.cities() {
+                    hash.str(city.name());
+                    hash.coord(city.latitude.map(B::float_to_f64));
+                    hash.coord(city.longitude.map(B::float_to_f64));
+                }
+            }
+        }
+        let stats = self.stats();
+        VersionInfo {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            binding: None,
+            features: enabled_features(),
+            view_version: crate::api::VIEW_VERSION,
+            dataset_version: self
+                .changelog
+                .as_ref()
+                .and_then(|c| c.latest_version())
+                .map(str::to_string),
+            data_fingerprint: format!("{:016x}", hash.0),
+            countries: stats.countries,
+            states: stats.states,
+            cities: stats.cities,
+        }
+    }
+}
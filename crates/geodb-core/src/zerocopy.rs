@@ -0,0 +1,536 @@
+// src/zerocopy.rs
+//! A serde-free binary layout that is read in place (feature `zerocopy`).
+//!
+//! Decoding the bincode artifact walks every city through serde and
+//! allocates its strings, which dominates the start-up of the WASM module.
+//! This layout is fixed-size little-endian records plus one UTF-8 string
+//! blob, so [`ZeroCopyDb`] borrows names straight from the bytes: opening it
+//! checks the bounds once, after which lookups are plain slice reads.
+//!
+//! The layout holds what is needed to walk and search the hierarchy and
+//! everything the binary format keeps per city (name, coordinates,
+//! timezone). Country and state details stay in the bincode skeleton:
+//! [`ZeroCopyDb::city_chunks`] fills a skeleton from
+//! [`GeoDb::to_staged_bytes`] without serde, which is how the WASM bindings
+//! use it.
+//!
+//! ```no_run
+//! use geodb_core::zerocopy::ZeroCopyDb;
+//! use geodb_core::{GeoDb, StandardBackend};
+//!
+//! let full = GeoDb::<StandardBackend>::load()?;
+//! let bytes = full.to_zerocopy_bytes()?;
+//!
+//! let view = ZeroCopyDb::new(&bytes)?;
+//! let de = view.country("DE").expect("DE");
+//! println!("{}: {} cities", de.name(), de.city_count());
+//! # Ok::<(), geodb_core::GeoError>(())
+//! ```
+//!
+//! Layout
+//! ------
+//!
+//! The layout is a stable contract for readers in other languages (C,
+//! JS over an `ArrayBuffer`, a process sharing the file through `mmap`):
+//! they can walk the tables with the offsets below and nothing else. The
+//! constants in [`layout`] spell out the same numbers. A change that
+//! breaks existing readers gets a new magic; `tests/zerocopy_layout.rs`
+//! reads artifacts with an independent reader that only knows this table.
+//!
+//! All integers are little-endian, floats are IEEE 754 `f64`. Sections
+//! follow each other without padding, so records are not aligned; read
+//! fields with unaligned loads (`memcpy` in C).
+//!
+//! | offset | size | header field |
+//! |-------:|-----:|--------------|
+//! | 0  | 8 | magic `GEODBZC1` (the `1` is the layout version) |
+//! | 8  | 4 | `u32` number of countries `C` |
+//! | 12 | 4 | `u32` number of states `S` |
+//! | 16 | 4 | `u32` number of cities `T` |
+//! | 20 | 4 | `u32` byte length of the string blob `N` |
+//!
+//! Then the country table (`C` × 20 bytes, from offset 24), the state
+//! table (`S` × 20 bytes), the city table (`T` × 32 bytes) and the string
+//! blob (`N` bytes of UTF-8); the file ends there.
+//!
+//! | record  | offset | size | field |
+//! |---------|-------:|-----:|-------|
+//! | country | 0  | 8 | string: ISO2 code |
+//! | country | 8  | 8 | string: name |
+//! | country | 16 | 4 | `u32` end of its states (exclusive index into the state table) |
+//! | state   | 0  | 8 | string: name |
+//! | state   | 8  | 8 | string: state code (may be absent) |
+//! | state   | 16 | 4 | `u32` end of its cities (exclusive index into the city table) |
+//! | city    | 0  | 8 | string: name |
+//! | city    | 8  | 8 | string: IANA timezone (may be absent) |
+//! | city    | 16 | 8 | `f64` latitude (NaN if absent) |
+//! | city    | 24 | 8 | `f64` longitude (NaN if absent) |
+//!
+//! A string is a `u32` byte offset into the blob followed by a `u32` byte
+//! length; offset `0xFFFF_FFFF` marks an absent string. Strings may share
+//! bytes (equal strings are stored once) and are not NUL-terminated.
+//!
+//! The states of country `i` are `end(i - 1)..end(i)` (the first starts at
+//! 0), likewise the cities of a state, so the tables list states country
+//! by country and cities state by state. The last end equals `S`
+//! (respectively `T`). [`ZeroCopyDb::new`] checks all of this; a reader
+//! that trusts its input can skip the checks.
+use crate::error::{GeoError, Result};
+use crate::model::{City, DbStats, GeoBackend, GeoDb};
+use crate::staged::CityChunk;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Leading bytes of a zero-copy artifact.
+pub const ZEROCOPY_MAGIC: &[u8; 8] = b"GEODBZC1";
+
+/// Sizes and field offsets of the layout; see the [module docs](self).
+pub mod layout {
+    /// Bytes before the country table.
+    pub const HEADER_LEN: usize = 24;
+    /// Header offsets of the country, state, city and string byte counts.
+    pub const HEADER_COUNTRIES: usize = 8;
+    pub const HEADER_STATES: usize = 12;
+    pub const HEADER_CITIES: usize = 16;
+    pub const HEADER_STRING_BYTES: usize = 20;
+
+    pub const COUNTRY_LEN: usize = 20;
+    pub const COUNTRY_ISO2: usize = 0;
+    pub const COUNTRY_NAME: usize = 8;
+    pub const COUNTRY_STATES_END: usize = 16;
+
+    pub const STATE_LEN: usize = 20;
+    pub const STATE_NAME: usize = 0;
+    pub const STATE_CODE: usize = 8;
+    pub const STATE_CITIES_END: usize = 16;
+
+    pub const CITY_LEN: usize = 32;
+    pub const CITY_NAME: usize = 0;
+    pub const CITY_TIMEZONE: usize = 8;
+    pub const CITY_LATITUDE: usize = 16;
+    pub const CITY_LONGITUDE: usize = 24;
+
+    /// Size of a string reference (offset and length).
+    pub const STRING_REF_LEN: usize = 8;
+    /// String offset of an absent string.
+    pub const ABSENT: u32 = u32::MAX;
+}
+
+use layout::*;
+
+/// Whether `bytes` start like a zero-copy artifact.
+pub fn is_zerocopy(bytes: &[u8]) -> bool {
+    bytes.starts_with(ZEROCOPY_MAGIC)
+}
+
+fn u32_at(record: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(*record[at..].first_chunk().expect("record is in bounds"))
+}
+
+fn f64_at(record: &[u8], at: usize) -> Option<f64> {
+    let v = f64::from_le_bytes(*record[at..].first_chunk().expect("record is in bounds"));
+    (!v.is_nan()).then_some(v)
+}
+
+/// A validated zero-copy artifact; see the [module docs](self).
+#[derive(Clone, Copy)]
+pub struct ZeroCopyDb<'a> {
+    countries: &'a [u8],
+    states: &'a [u8],
+    cities: &'a [u8],
+    strings: &'a str,
+}
+
+impl<'a> ZeroCopyDb<'a> {
+    /// Check the layout of `bytes`; fails on anything that isn't a
+    /// complete, consistent zero-copy artifact.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let invalid = |msg: &str| GeoError::InvalidData(format!("zero-copy artifact: {msg}"));
+        if !is_zerocopy(bytes) {
+            return Err(invalid("missing magic bytes"));
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(invalid("truncated header"));
+        }
+        let [countries, states, cities, strings] = [
+            HEADER_COUNTRIES,
+            HEADER_STATES,
+            HEADER_CITIES,
+            HEADER_STRING_BYTES,
+        ]
+        .map(|at| u32_at(bytes, at) as usize);
+        let sizes = [
+            countries as u64 * COUNTRY_LEN as u64,
+            states as u64 * STATE_LEN as u64,
+            cities as u64 * CITY_LEN as u64,
+            strings as u64,
+        ];
+        if HEADER_LEN as u64 + sizes.iter().sum::<u64>() != bytes.len() as u64 {
+            return Err(invalid("section sizes don't match the artifact"));
+        }
+
+        let (countries, rest) = bytes[HEADER_LEN..].split_at(sizes[0] as usize);
+        let (states, rest) = rest.split_at(sizes[1] as usize);
+        let (cities, strings) = rest.split_at(sizes[2] as usize);
+        let strings = std::str::from_utf8(strings).map_err(|e| invalid(&e.to_string()))?;
+        let db = Self {
+            countries,
+            states,
+            cities,
+            strings,
+        };
+        db.validate().map_err(|msg| invalid(&msg))?;
+        Ok(db)
+    }
+
+    /// Every string reference in bounds, every range contiguous.
+    fn validate(&self) -> std::result::Result<(), String> {
+        let check_str = |record: &[u8], at: usize| {
+            let (start, len) = (u32_at(record, at), u32_at(record, at + 4));
+            if start == ABSENT {
+                return Ok(());
+            }
+            // in u64: offset + length may overflow a 32-bit usize
+            let end = u64::from(start) + u64::from(len);
+            let (start, end) = (start as usize, end as usize);
+            if end as u64 <= self.strings.len() as u64
+                && self.strings.is_char_boundary(start)
+                && self.strings.is_char_boundary(end)
+            {
+                Ok(())
+            } else {
+                Err(format!("string {start}..{end} out of bounds"))
+            }
+        };
+        let check_ends = |records: &[u8], size: usize, at: usize, total: usize| {
+            let mut previous = 0;
+            for record in records.chunks_exact(size) {
+                let end = u32_at(record, at) as usize;
+                if end < previous || end > total {
+                    return Err(format!("range ending at {end} is out of order"));
+                }
+                previous = end;
+            }
+            if previous == total {
+                Ok(())
+            } else {
+                Err("ranges don't cover all records".to_string())
+            }
+        };
+
+        for record in self.countries.chunks_exact(COUNTRY_LEN) {
+            check_str(record, COUNTRY_ISO2)?;
+            check_str(record, COUNTRY_NAME)?;
+        }
+        for record in self.states.chunks_exact(STATE_LEN) {
+            check_str(record, STATE_NAME)?;
+            check_str(record, STATE_CODE)?;
+        }
+        for record in self.cities.chunks_exact(CITY_LEN) {
+            check_str(record, CITY_NAME)?;
+            check_str(record, CITY_TIMEZONE)?;
+        }
+        let (states, cities) = (self.states.len() / STATE_LEN, self.cities.len() / CITY_LEN);
+        check_ends(self.countries, COUNTRY_LEN, COUNTRY_STATES_END, states)?;
+        check_ends(self.states, STATE_LEN, STATE_CITIES_END, cities)
+    }
+
+    fn str_at(&self, record: &[u8], at: usize) -> Option<&'a str> {
+        let (start, len) = (u32_at(record, at), u32_at(record, at + 4));
+        (start != ABSENT).then(|| &self.strings[start as usize..][..len as usize])
+    }
+
+    /// Counts of countries, states and cities.
+    pub fn stats(&self) -> DbStats {
+        DbStats {
+            countries: self.countries.len() / COUNTRY_LEN,
+            states: self.states.len() / STATE_LEN,
+            cities: self.cities.len() / CITY_LEN,
+        }
+    }
+
+    /// All countries in artifact order.
+    pub fn countries(&self) -> impl ExactSizeIterator<Item = ZeroCopyCountry<'a>> + 'a {
+        let db = *self;
+        (0..self.countries.len() / COUNTRY_LEN).map(move |index| ZeroCopyCountry { db, index })
+    }
+
+    /// The country with ISO2 code `iso2` (case-insensitive).
+    pub fn country(&self, iso2: &str) -> Option<ZeroCopyCountry<'a>> {
+        self.countries()
+            .find(|c| c.iso2().eq_ignore_ascii_case(iso2))
+    }
+
+    /// One [`CityChunk`] per country with cities, as
+    /// [`GeoDb::split_cities`] makes them, built without serde. Attach them
+    /// to the skeleton of the same database.
+    pub fn city_chunks<B: GeoBackend>(&self) -> impl Iterator<Item = CityChunk<B>> + 'a {
+        self.countries()
+            .filter(|c| c.city_count() > 0)
+            .map(|c| CityChunk {
+                country: c.index,
+                states: c
+                    .states()
+                    .map(|s| s.cities().map(|city| city.to_city()).collect())
+                    .collect(),
+            })
+    }
+}
+
+/// A country of a [`ZeroCopyDb`].
+#[derive(Clone, Copy)]
+pub struct ZeroCopyCountry<'a> {
+    db: ZeroCopyDb<'a>,
+    index: usize,
+}
+
+impl<'a> ZeroCopyCountry<'a> {
+    /// Position in the country table.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    fn record(&self) -> &'a [u8] {
+        &self.db.countries[self.index * COUNTRY_LEN..][..COUNTRY_LEN]
+    }
+
+    pub fn iso2(&self) -> &'a str {
+        self.db
+            .str_at(self.record(), COUNTRY_ISO2)
+            .unwrap_or_default()
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.db
+            .str_at(self.record(), COUNTRY_NAME)
+            .unwrap_or_default()
+    }
+
+    /// Positions of the country's states in the state table.
+    fn state_range(&self) -> Range<usize> {
+        let start = match self.index {
+            0 => 0,
+            i => u32_at(
+                &self.db.countries[(i - 1) * COUNTRY_LEN..],
+                COUNTRY_STATES_END,
+            ) as usize,
+        };
+        start..u32_at(self.record(), COUNTRY_STATES_END) as usize
+    }
+
+    /// The country's states, in order.
+    pub fn states(&self) -> impl ExactSizeIterator<Item = ZeroCopyState<'a>> + 'a {
+        let db = self.db;
+        self.state_range()
+            .map(move |index| ZeroCopyState { db, index })
+    }
+
+    /// Number of states, read from the table without visiting them.
+    pub fn state_count(&self) -> usize {
+        self.state_range().len()
+    }
+
+    /// Number of cities in all states; the states' cities are contiguous,
+    /// so this reads two records.
+    pub fn city_count(&self) -> usize {
+        let states = self.state_range();
+        if states.is_empty() {
+            return 0;
+        }
+        let db = self.db;
+        let first = ZeroCopyState {
+            db,
+            index: states.start,
+        }
+        .city_range();
+        let last = ZeroCopyState {
+            db,
+            index: states.end - 1,
+        }
+        .city_range();
+        last.end - first.start
+    }
+}
+
+/// A state of a [`ZeroCopyDb`].
+#[derive(Clone, Copy)]
+pub struct ZeroCopyState<'a> {
+    db: ZeroCopyDb<'a>,
+    index: usize,
+}
+
+impl<'a> ZeroCopyState<'a> {
+    /// Position in the state table.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    fn record(&self) -> &'a [u8] {
+        &self.db.states[self.index * STATE_LEN..][..STATE_LEN]
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.db
+            .str_at(self.record(), STATE_NAME)
+            .unwrap_or_default()
+    }
+
+    /// Short code (e.g. "CA"), if the state has one.
+    pub fn state_code(&self) -> Option<&'a str> {
+        self.db.str_at(self.record(), STATE_CODE)
+    }
+
+    /// Positions of the state's cities in the city table.
+    fn city_range(&self) -> Range<usize> {
+        let start = match self.index {
+            0 => 0,
+            i => u32_at(&self.db.states[(i - 1) * STATE_LEN..], STATE_CITIES_END) as usize,
+        };
+        start..u32_at(self.record(), STATE_CITIES_END) as usize
+    }
+
+    /// The state's cities, in order.
+    pub fn cities(&self) -> impl ExactSizeIterator<Item = ZeroCopyCity<'a>> + 'a {
+        let db = self.db;
+        self.city_range()
+            .map(move |index| ZeroCopyCity { db, index })
+    }
+
+    /// Number of cities, read from the table without visiting them.
+    pub fn city_count(&self) -> usize {
+        self.city_range().len()
+    }
+}
+
+/// A city of a [`ZeroCopyDb`].
+#[derive(Clone, Copy)]
+pub struct ZeroCopyCity<'a> {
+    db: ZeroCopyDb<'a>,
+    index: usize,
+}
+
+impl<'a> ZeroCopyCity<'a> {
+    /// Position in the city table.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    fn record(&self) -> &'a [u8] {
+        &self.db.cities[self.index * CITY_LEN..][..CITY_LEN]
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.db.str_at(self.record(), CITY_NAME).unwrap_or_default()
+    }
+
+    pub fn timezone(&self) -> Option<&'a str> {
+        self.db.str_at(self.record(), CITY_TIMEZONE)
+    }
+
+    pub fn latitude(&self) -> Option<f64> {
+        f64_at(self.record(), CITY_LATITUDE)
+    }
+
+    pub fn longitude(&self) -> Option<f64> {
+        f64_at(self.record(), CITY_LONGITUDE)
+    }
+
+    /// An owned [`City`] with the same data (no aliases or regions, like a
+    /// city from the binary format).
+    pub fn to_city<B: GeoBackend>(&self) -> City<B> {
+        City {
+            name: B::str_from(self.name()),
+            latitude: self.latitude().map(B::float_from),
+            longitude: self.longitude().map(B::float_from),
+            timezone: self.timezone().map(B::str_from),
+            aliases: Vec::new(),
+            regions: Vec::new(),
+            provenance: Vec::new(),
+        }
+    }
+}
+
+/// String blob of an artifact being written, each distinct string once.
+#[derive(Default)]
+struct Strings {
+    blob: String,
+    offsets: HashMap<String, u32>,
+}
+
+impl Strings {
+    fn push(&mut self, record: &mut Vec<u8>, s: Option<&str>) -> Result<()> {
+        let Some(s) = s else {
+            record.extend_from_slice(&ABSENT.to_le_bytes());
+            record.extend_from_slice(&0u32.to_le_bytes());
+            return Ok(());
+        };
+        let start = match self.offsets.get(s) {
+            Some(&start) => start,
+            None => {
+                let start = to_u32(self.blob.len())?;
+                self.blob.push_str(s);
+                self.offsets.insert(s.to_string(), start);
+                start
+            }
+        };
+        record.extend_from_slice(&start.to_le_bytes());
+        record.extend_from_slice(&to_u32(s.len())?.to_le_bytes());
+        Ok(())
+    }
+}
+
+fn to_u32(n: usize) -> Result<u32> {
+    u32::try_from(n)
+        .ok()
+        .filter(|&n| n != ABSENT)
+        .ok_or_else(|| GeoError::InvalidData(format!("{n} doesn't fit the zero-copy layout")))
+}
+
+fn push_coordinate(record: &mut Vec<u8>, v: Option<f64>) {
+    record.extend_from_slice(&v.unwrap_or(f64::NAN).to_le_bytes());
+}
+
+impl<B: GeoBackend> GeoDb<B> {
+    /// Serialize into the zero-copy layout; see the [module docs](self).
+    pub fn to_zerocopy_bytes(&self) -> Result<Vec<u8>> {
+        let mut strings = Strings::default();
+        let (mut countries, mut states, mut cities) = (Vec::new(), Vec::new(), Vec::new());
+        let (mut state_count, mut city_count) = (0, 0);
+        for country in &self.countries {
+            strings.push(&mut countries, Some(country.iso2()))?;
+            strings.push(&mut countries, Some(country.name()))?;
+            for state in &country.states {
+                strings.push(&mut states, Some(state.name()))?;
+                strings.push(&mut states, state.state_code.as_ref().map(AsRef::as_ref))?;
+                for city in &state.cities {
+                    strings.push(&mut cities, Some(city.name()))?;
+                    strings.push(&mut cities, city.timezone.as_ref().map(AsRef::as_ref))?;
+                    push_coordinate(&mut cities, city.latitude.map(B::float_to_f64));
+                    push_coordinate(&mut cities, city.longitude.map(B::float_to_f64));
+                }
+                city_count += state.cities.len();
+                states.extend_from_slice(&to_u32(city_count)?.to_le_bytes());
+            }
+            state_count += country.states.len();
+            countries.extend_from_slice(&to_u32(state_count)?.to_le_bytes());
+        }
+
+        let mut out = Vec::with_capacity(
+            HEADER_LEN + countries.len() + states.len() + cities.len() + strings.blob.len(),
+        );
+        out.extend_from_slice(ZEROCOPY_MAGIC);
+        for n in [
+            self.countries.len(),
+            state_count,
+            city_count,
+            strings.blob.len(),
+        ] {
+            out.extend_from_slice(&to_u32(n)?.to_le_bytes());
+        }
+        out.extend_from_slice(&countries);
+        out.extend_from_slice(&states);
+        out.extend_from_slice(&cities);
+        out.extend_from_slice(strings.blob.as_bytes());
+        Ok(out)
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "json")]
+
+use geodb_core::area::country_area_km2;
+use geodb_core::{CountrySort, CountryView, GeoDb, StandardBackend};
+
+#[test]
+fn every_country_has_an_area() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+    let missing: Vec<&str> = db
+        .countries()
+        .iter()
+        .filter(|c| c.area().is_none())
+        .map(|c| c.iso2())
+        .collect();
+    assert!(missing.is_empty(), "no area for {missing:?}");
+
+    assert_eq!(country_area_km2("de"), Some(357_588.0));
+    assert_eq!(country_area_km2("ZZ"), None);
+}
+
+#[test]
+fn density_and_sorting() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+    let de = db.find_country_by_iso2("DE").unwrap();
+    let density = de.density().unwrap();
+    assert!((200.0..260.0).contains(&density), "{density}");
+
+    let by_area = db.countries_sorted(CountrySort::Area);
+    assert_eq!(by_area[0].iso2(), "RU");
+    assert!(by_area.windows(2).all(|w| w[0].area() >= w[1].area()));
+
+    // countries without population (and so without density) come last
+    let by_density = db.countries_sorted(CountrySort::Density);
+    let first_unknown = by_density.iter().position(|c| c.density().is_none());
+    if let Some(pos) = first_unknown {
+        assert!(by_density[pos..].iter().all(|c| c.density().is_none()));
+    }
+
+    let by_name = db.countries_sorted(CountrySort::Name);
+    assert!(by_name.windows(2).all(|w| w[0].name() <= w[1].name()));
+    assert_eq!("Area".parse(), Ok(CountrySort::Area));
+}
+
+#[test]
+fn country_view_includes_area_and_density() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let view = serde_json::to_value(CountryView(&db.countries()[0])).unwrap();
+    assert_eq!(view["area"], 160.0);
+    assert!(view["density"].as_f64().is_some());
+}
@@ -1,4 +1,6 @@
-use geodb_core::{GeoDb, StandardBackend};
+#![cfg(feature = "json")]
+
+use geodb_core::{GeoDb, SearchOptions, SmartItem, StandardBackend};
 
 #[test]
 fn load_filtered_us_and_basic_queries_work() {
@@ -62,3 +64,167 @@ fn load_from_path_and_multi_filter() {
         assert_eq!(c.iso2().len(), 2);
     }
 }
+
+#[test]
+fn states_and_cities_by_country_and_state_code() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load US");
+
+    assert_eq!(
+        db.states_for_country("usa").len(),
+        db.countries()[0].states().len()
+    );
+    assert!(db.states_for_country("DE").is_empty());
+
+    let by_code = db.cities_for_state("US", "ca");
+    assert!(by_code.iter().any(|c| c.name() == "Los Angeles"));
+    assert_eq!(db.cities_for_state("us", "US-CA").len(), by_code.len());
+    assert_eq!(db.cities_for_state("US", "california").len(), by_code.len());
+    assert!(db.cities_for_state("US", "Bavaria").is_empty());
+}
+
+#[test]
+fn country_substring_search_optionally_covers_translations() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "FR", "ES"]).expect("load DB");
+
+    let iso2 = |hits: Vec<&geodb_core::Country<StandardBackend>>| -> Vec<String> {
+        hits.iter().map(|c| c.iso2().to_string()).collect()
+    };
+    assert_eq!(iso2(db.find_countries_by_substring("GERM")), ["DE"]);
+    assert!(db.find_countries_by_substring("alemania").is_empty());
+
+    assert_eq!(
+        iso2(db.find_countries_by_substring_with("Alemania", true)),
+        ["DE"]
+    );
+    // native name
+    assert_eq!(
+        iso2(db.find_countries_by_substring_with("españa", true)),
+        ["ES"]
+    );
+}
+
+#[test]
+fn primary_timezone_uses_capital() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load filtered DB");
+
+    let us = db.find_country_by_iso2("US").expect("US present");
+    assert_eq!(us.primary_timezone(), Some("America/New_York"));
+    let entry = us.primary_timezone_entry().expect("zone listed for US");
+    assert_eq!(entry.gmt_offset_name.as_deref(), Some("UTC-05:00"));
+
+    // 2023-11-14 22:13:20 UTC, at the standard offset
+    assert_eq!(us.utc_offset_seconds(), Some(-5 * 3600));
+    assert_eq!(
+        us.local_time_at(1_700_000_000).as_deref(),
+        Some("2023-11-14 17:13")
+    );
+}
+
+#[test]
+fn cache_status_reports_paths_and_source() {
+    use geodb_core::loader::cache_path_for;
+    use geodb_core::LoadSource;
+
+    let dataset = GeoDb::<StandardBackend>::default_data_dir()
+        .join(GeoDb::<StandardBackend>::default_dataset_filename());
+
+    let all = GeoDb::<StandardBackend>::cache_status(&dataset, None);
+    assert!(all.dataset_size.is_some());
+    assert_eq!(all.dataset_fingerprint.as_deref().map(str::len), Some(16));
+    // `….ALL.bin`, or `….ALL.districts.bin` with the `districts` feature
+    assert_eq!(all.cache_path, cache_path_for(&dataset, None));
+    assert!(all.cache_path.to_string_lossy().contains(".json.gz.ALL."));
+
+    let missing = GeoDb::<StandardBackend>::cache_status(&dataset, Some(&["ZZ", "YY"]));
+    assert_eq!(
+        missing.cache_path,
+        cache_path_for(&dataset, Some(&["ZZ", "YY"]))
+    );
+    assert!(missing.cache_path.to_string_lossy().contains(".ZZ_YY."));
+    assert!(!missing.cache_exists);
+    assert_eq!(missing.source, LoadSource::Source);
+}
+
+#[test]
+fn sample_is_deterministic_and_self_contained() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+
+    let a = db.sample(5, 42);
+    let b = db.sample(5, 42);
+    let iso = |db: &GeoDb<StandardBackend>| {
+        db.countries()
+            .iter()
+            .map(|c| c.iso2().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(a.stats().countries, 5);
+    assert_eq!(iso(&a), iso(&b));
+    assert_ne!(iso(&a), iso(&db.sample(5, 43)));
+
+    // Whole countries are copied
+    for c in a.countries() {
+        let original = db.find_country_by_iso2(c.iso2()).unwrap();
+        assert_eq!(c.states().len(), original.states().len());
+    }
+
+    assert_eq!(db.sample(10_000, 1).stats().countries, db.stats().countries);
+    assert_eq!(
+        iso(&db.filtered_by_iso2(&["fr", "DE"])),
+        ["FR", "DE"].map(String::from)
+    );
+}
+
+#[test]
+fn country_locale_metadata() {
+    use geodb_core::{CountryLocaleTable, MeasurementSystem, Weekday};
+
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US", "DE", "EG"])
+        .expect("load filtered DB");
+    let table = CountryLocaleTable::load_default().expect("load country_locale.json");
+
+    assert!(db.find_country_by_iso2("US").unwrap().locale().is_none());
+    assert_eq!(db.apply_country_locale(&table), 3);
+
+    let us = db.find_country_by_iso2("US").unwrap();
+    assert_eq!(us.measurement_system(), Some(MeasurementSystem::Imperial));
+    assert_eq!(us.date_format(), Some("MM/DD/YYYY"));
+    assert_eq!(us.first_day_of_week(), Some(Weekday::Sunday));
+
+    let de = db.find_country_by_iso2("DE").unwrap();
+    assert_eq!(de.measurement_system(), Some(MeasurementSystem::Metric));
+    assert_eq!(de.date_format(), Some("DD.MM.YYYY"));
+    assert_eq!(de.first_day_of_week(), Some(Weekday::Monday));
+
+    // Defaults fill the fields an override leaves out
+    let eg = db.find_country_by_iso2("EG").unwrap();
+    assert_eq!(eg.first_day_of_week(), Some(Weekday::Saturday));
+    assert_eq!(eg.date_format(), Some("DD/MM/YYYY"));
+}
+
+#[test]
+fn pruned_for_queries_keeps_matched_cities_only() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "FR"]).expect("load DB");
+    let options = SearchOptions::default();
+    let log = ["berlin", "paris", "de"];
+
+    let pruned = db.pruned_for_queries(log, &options, 5);
+    let (before, after) = (db.stats(), pruned.stats());
+    assert_eq!(after.countries, before.countries);
+    assert_eq!(after.states, before.states);
+    assert!(after.cities > 0 && after.cities <= 10);
+
+    // Replaying the log yields the same city hits
+    let cities = |db: &GeoDb<StandardBackend>, q: &str| {
+        db.smart_search_with(q, &options)
+            .iter()
+            .take(5)
+            .filter_map(|h| match h.item {
+                SmartItem::City { city, .. } => Some(city.name().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    for q in log {
+        assert_eq!(cities(&pruned, q), cities(&db, q), "{q}");
+    }
+}
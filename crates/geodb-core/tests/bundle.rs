@@ -0,0 +1,100 @@
+#![cfg(feature = "bundle")]
+
+use geodb_core::bundle::{ArtifactEncoding, BundleIndex, BundleOptions, Provenance, INDEX_FILE};
+use geodb_core::{GeoDb, GeoError, StandardBackend};
+use std::fs;
+
+#[test]
+fn release_bundle_round_trips() {
+    let root = std::env::temp_dir().join(format!("geodb-bundle-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let options = BundleOptions::new("1.2.3").with_filter(["li"]);
+    let index = db.write_release_bundle(&root, &options).unwrap();
+
+    let dir = root.join("1.2.3");
+    let loaded = BundleIndex::load_from_path(dir.join(INDEX_FILE)).unwrap();
+    assert_eq!(loaded, index);
+    loaded.verify(&dir).unwrap();
+
+    let artifact = &index.artifacts[0];
+    assert_eq!(artifact.file, "geodb-1.2.3.bin");
+    assert_eq!(artifact.encoding, ArtifactEncoding::Bincode);
+    assert_eq!(artifact.filter, ["LI"]);
+    assert_eq!(artifact.cities, db.stats().cities);
+    let bytes = fs::read(dir.join(&artifact.file)).unwrap();
+    let reloaded = GeoDb::<StandardBackend>::from_bytes(&bytes).unwrap();
+    assert_eq!(reloaded.stats().cities, db.stats().cities);
+
+    let provenance: Provenance =
+        serde_json::from_slice(&fs::read(dir.join(&index.provenance)).unwrap()).unwrap();
+    assert!(provenance.generator.starts_with("geodb-core "));
+    let sums = fs::read_to_string(dir.join(&index.checksums)).unwrap();
+    assert!(sums.contains(&format!("{}  geodb-1.2.3.bin", artifact.sha256)));
+
+    // same version again needs overwrite
+    assert!(db.write_release_bundle(&root, &options).is_err());
+    db.write_release_bundle(&root, &options.clone().with_overwrite(true))
+        .unwrap();
+
+    // tampered artifact fails verification
+    fs::write(dir.join(&artifact.file), b"tampered").unwrap();
+    assert!(matches!(
+        index.verify(&dir),
+        Err(GeoError::ChecksumMismatch { .. })
+    ));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn stripped_release_bundle_has_no_coordinates() {
+    let root = std::env::temp_dir().join(format!("geodb-bundle-stripped-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let options = BundleOptions::new("1.0.0").with_strip_coords(true);
+    let index = db.write_release_bundle(&root, &options).unwrap();
+
+    let dir = root.join("1.0.0");
+    let provenance: Provenance =
+        serde_json::from_slice(&fs::read(dir.join(&index.provenance)).unwrap()).unwrap();
+    assert!(provenance.coords_stripped);
+
+    let bytes = fs::read(dir.join(&index.artifacts[0].file)).unwrap();
+    let stripped = GeoDb::<StandardBackend>::from_bytes(&bytes).unwrap();
+    assert_eq!(stripped.stats().cities, db.stats().cities);
+    assert!(stripped.countries.iter().all(|c| c.latitude.is_none()));
+    assert!(stripped
+        .iter_cities()
+        .all(|(city, state, _)| city.latitude.is_none() && state.longitude.is_none()));
+    assert!(stripped.find_nearest_city(47.14, 9.52, 50.0).is_none());
+    assert!(!stripped.smart_search("vaduz").is_empty());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[cfg(feature = "signed")]
+#[test]
+fn signed_release_bundle_is_sealed() {
+    use geodb_core::signed::{SigningKey, VerifyOptions};
+
+    let root = std::env::temp_dir().join(format!("geodb-bundle-signed-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let options = BundleOptions::new("signed").with_signing_key(key.clone());
+    let index = db.write_release_bundle(&root, &options).unwrap();
+
+    let artifact = &index.artifacts[0];
+    assert_eq!(artifact.encoding, ArtifactEncoding::Sealed);
+    assert!(artifact.signed);
+    let bytes = fs::read(root.join("signed").join(&artifact.file)).unwrap();
+    let verify = VerifyOptions::default().with_trusted_key(&key.verifying_key());
+    let loaded = GeoDb::<StandardBackend>::from_signed_bytes(&bytes, &verify).unwrap();
+    assert_eq!(loaded.stats().cities, db.stats().cities);
+
+    let _ = fs::remove_dir_all(&root);
+}
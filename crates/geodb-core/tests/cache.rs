@@ -0,0 +1,76 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{GeoDb, GeoDbCache, StandardBackend};
+use std::sync::Arc;
+
+fn load(iso2: &str) -> geodb_core::Result<GeoDb<StandardBackend>> {
+    GeoDb::<StandardBackend>::load_filtered_by_iso2(&[iso2])
+}
+
+#[test]
+fn cache_reuses_entries_by_name() {
+    let cache = GeoDbCache::<StandardBackend>::new();
+    let a = cache.load_filtered(&["li"]).unwrap();
+    let b = cache.load_filtered(&[" LI "]).unwrap();
+    assert!(Arc::ptr_eq(&a, &b));
+    assert_eq!(cache.names(), ["LI"]);
+    // the codes are normalized before loading, not only for the key
+    assert_eq!(a.countries().len(), 1);
+    assert_eq!(a.countries()[0].iso2(), "LI");
+    assert!(a.stats().cities > 0);
+
+    let dach = cache.load_filtered(&["ch", "de", "AT", "de"]).unwrap();
+    let mut codes: Vec<&str> = dach.countries().iter().map(|c| c.iso2()).collect();
+    codes.sort();
+    assert_eq!(codes, ["AT", "CH", "DE"]);
+    cache.remove("AT_CH_DE");
+
+    let mut calls = 0;
+    for _ in 0..2 {
+        let ch = cache
+            .get_or_try_insert_with("ch", || {
+                calls += 1;
+                load("CH")
+            })
+            .unwrap();
+        assert_eq!(ch.countries()[0].iso2(), "CH");
+    }
+    assert_eq!(calls, 1);
+    assert_eq!(cache.len(), 2);
+
+    // removal only drops the cache's handle
+    let removed = cache.remove("LI").unwrap();
+    assert!(Arc::ptr_eq(&removed, &a));
+    assert!(cache.get("LI").is_none());
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn cache_evicts_least_recently_used() {
+    let cache = GeoDbCache::<StandardBackend>::new().with_max_entries(2);
+    cache.get_or_try_insert_with("li", || load("LI")).unwrap();
+    cache.get_or_try_insert_with("ch", || load("CH")).unwrap();
+    cache.get("li"); // now "ch" is the oldest
+    cache.get_or_try_insert_with("de", || load("DE")).unwrap();
+
+    assert_eq!(cache.names(), ["de", "li"]);
+    assert_eq!(cache.get("li").unwrap().countries()[0].iso2(), "LI");
+    assert_eq!(cache.get("de").unwrap().countries()[0].iso2(), "DE");
+}
+
+#[test]
+fn failed_loads_are_not_cached() {
+    let cache = GeoDbCache::<StandardBackend>::new();
+    let err = cache.get_or_try_insert_with("missing", || {
+        GeoDb::load_from_path("/nonexistent/dataset.json.gz", None)
+    });
+    assert!(err.is_err());
+    assert!(cache.is_empty());
+
+    let disabled = GeoDbCache::<StandardBackend>::new().with_max_entries(0);
+    disabled
+        .get_or_try_insert_with("li", || load("LI"))
+        .unwrap();
+    assert!(disabled.is_empty());
+}
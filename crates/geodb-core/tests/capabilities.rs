@@ -0,0 +1,33 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CityMetaIndex, GeoDb, StandardBackend, StateTranslationTable};
+
+#[test]
+fn capabilities_follow_applied_tables_and_stripping() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+
+    let plain = db.capabilities();
+    assert!(plain.coordinates);
+    assert!(plain.city_timezones);
+    assert!(plain.country_populations);
+    assert!(!plain.city_aliases);
+    assert!(!plain.state_translations);
+    assert!(!plain.changelog);
+
+    db.apply_city_meta(&CityMetaIndex::load_default().expect("load city_meta.json"));
+    db.apply_state_translations(&StateTranslationTable::load_default().expect("translations"));
+    let enriched = db.capabilities();
+    assert!(enriched.city_aliases);
+    assert!(enriched.state_translations);
+
+    let stripped = db.without_coordinates().capabilities();
+    assert!(!stripped.coordinates);
+    assert_eq!(
+        (stripped.city_aliases, stripped.city_timezones),
+        (true, true)
+    );
+
+    let json = serde_json::to_value(enriched).unwrap();
+    assert_eq!(json["city_aliases"], true);
+    assert_eq!(json["coordinates"], true);
+}
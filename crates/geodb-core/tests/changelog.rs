@@ -0,0 +1,126 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    ChangeEntity, ChangeKind, ChangeSet, Changelog, EntityChange, GeoDb, StandardBackend,
+};
+
+fn find(changes: &[EntityChange], entity: ChangeEntity, kind: ChangeKind) -> Vec<&EntityChange> {
+    changes
+        .iter()
+        .filter(|c| c.entity == entity && c.kind == kind)
+        .collect()
+}
+
+#[test]
+fn diff_reports_added_removed_and_renamed_entities() {
+    let new = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    assert!(new.diff(&new).is_empty());
+
+    // the "old" dataset: no LI, a renamed state and city, one city missing
+    // and one that no longer exists
+    let mut old = new.filtered_by_iso2(&["DE"]);
+    let state = old.countries[0]
+        .states
+        .iter_mut()
+        .find(|s| s.name() == "Berlin")
+        .expect("Berlin state");
+    state.name = "Land Berlin".to_string();
+    state.cities[0].name = "Old Berlin".to_string();
+    let state = old.countries[0]
+        .states
+        .iter_mut()
+        .find(|s| s.name() == "Bavaria")
+        .expect("Bavaria");
+    let missing = state.cities.remove(1).name;
+    let mut gone = state.cities[0].clone();
+    gone.name = "Atlantis".to_string();
+    (gone.latitude, gone.longitude) = (Some(0.0), Some(0.0));
+    state.cities.push(gone);
+
+    let changes = old.diff(&new);
+
+    let countries = find(&changes, ChangeEntity::Country, ChangeKind::Added);
+    assert_eq!(countries.len(), 1);
+    assert_eq!(countries[0].iso2, "LI");
+    let li_cities = changes
+        .iter()
+        .filter(|c| c.iso2 == "LI" && c.entity == ChangeEntity::City)
+        .count();
+    let li = &new.countries[1];
+    assert_eq!(
+        li_cities,
+        li.states().iter().map(|s| s.cities().len()).sum::<usize>()
+    );
+
+    let states = find(&changes, ChangeEntity::State, ChangeKind::Renamed);
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].name, "Berlin");
+    assert_eq!(states[0].previous_name.as_deref(), Some("Land Berlin"));
+
+    let renamed = find(&changes, ChangeEntity::City, ChangeKind::Renamed);
+    assert_eq!(renamed.len(), 1);
+    assert_eq!(renamed[0].previous_name.as_deref(), Some("Old Berlin"));
+    assert_eq!(renamed[0].state.as_deref(), Some("Berlin"));
+
+    let added: Vec<_> = find(&changes, ChangeEntity::City, ChangeKind::Added)
+        .into_iter()
+        .filter(|c| c.iso2 == "DE")
+        .collect();
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0].name, missing);
+
+    let removed = find(&changes, ChangeEntity::City, ChangeKind::Removed);
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].name, "Atlantis");
+    assert_eq!(removed[0].state_code.as_deref(), Some("BY"));
+}
+
+#[test]
+fn changes_since_walks_the_changelog() {
+    let change = |name: &str| EntityChange {
+        entity: ChangeEntity::City,
+        kind: ChangeKind::Added,
+        iso2: "DE".to_string(),
+        state: None,
+        state_code: None,
+        name: name.to_string(),
+        previous_name: None,
+    };
+    let mut log = Changelog::new();
+    log.push(ChangeSet {
+        version: "2".to_string(),
+        previous: "1".to_string(),
+        changes: vec![change("a")],
+    })
+    .unwrap();
+    log.push(ChangeSet {
+        version: "3".to_string(),
+        previous: "2".to_string(),
+        changes: vec![change("b"), change("c")],
+    })
+    .unwrap();
+    // must continue from the latest version
+    assert!(log
+        .push(ChangeSet {
+            version: "5".to_string(),
+            previous: "4".to_string(),
+            changes: Vec::new(),
+        })
+        .is_err());
+
+    let json = serde_json::to_string(&log).unwrap();
+    let log: Changelog = serde_json::from_str(&json).unwrap();
+
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    assert!(db.changes_since("1").is_none());
+    db.set_changelog(log);
+
+    let names = |version: &str| -> Option<Vec<String>> {
+        db.changes_since(version)
+            .map(|changes| changes.iter().map(|c| c.name.clone()).collect())
+    };
+    assert_eq!(names("1").unwrap(), ["a", "b", "c"]);
+    assert_eq!(names("2").unwrap(), ["b", "c"]);
+    assert!(names("3").unwrap().is_empty());
+    assert!(names("0").is_none());
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CityMetaIndex, CityView, GeoDb, StandardBackend, ViewOptions};
+
+#[test]
+fn city_meta_fills_aliases_and_regions() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let index = CityMetaIndex::load_default().expect("load city_meta.json");
+
+    let resolved = db.resolve_city_alias_with_index("Duelmen", &index);
+    assert_eq!(resolved.map(|(_, _, city)| city.as_str()), Some("Dülmen"));
+
+    assert!(db.apply_city_meta(&index) > 0);
+
+    let (city, state, country) = db
+        .iter_cities()
+        .find(|(c, s, _)| c.name() == "Dülmen" && s.name() == "North Rhine-Westphalia")
+        .expect("Dülmen");
+    assert_eq!(city.aliases().collect::<Vec<_>>(), ["Duelmen"]);
+    assert_eq!(city.regions().collect::<Vec<_>>(), ["Münsterland"]);
+
+    let view = CityView {
+        country,
+        state,
+        city,
+    };
+    let plain = serde_json::to_value(&view).unwrap();
+    assert!(plain.get("aliases").is_none());
+
+    let verbose =
+        serde_json::to_value(view.with_options(ViewOptions::default().with_city_meta(true)))
+            .unwrap();
+    assert_eq!(verbose["aliases"][0], "Duelmen");
+    assert_eq!(verbose["regions"][0], "Münsterland");
+}
+
+#[test]
+fn alias_audit_reports_conflicts() {
+    use geodb_core::{AliasConflictKind, CityMeta};
+
+    let meta = |state: &str, city: &str, aliases: &[&str]| CityMeta {
+        iso2: "DE".into(),
+        state: state.into(),
+        city: city.into(),
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        regions: Vec::new(),
+    };
+    let index = CityMetaIndex::from_entries(vec![
+        meta("North Rhine-Westphalia", "Münster", &["Munster", "MS"]),
+        meta("Bavaria", "Nürnberg", &["ms"]),
+    ]);
+
+    // Meta file only: "MS" is shared by two cities
+    let conflicts = index.audit();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, AliasConflictKind::SharedAlias);
+    assert_eq!(conflicts[0].owners.len(), 2);
+
+    // Against the dataset: "Munster" is also a real city in Lower Saxony
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let conflicts = db.audit_city_aliases(&index);
+    let shadow = conflicts
+        .iter()
+        .find(|c| c.kind == AliasConflictKind::ShadowsCanonicalName)
+        .expect("shadowed canonical name");
+    assert_eq!(shadow.alias, "Munster");
+    assert_eq!(shadow.shadowed[0].1, "Lower Saxony");
+
+    // The bundled meta file is clean
+    let bundled = CityMetaIndex::load_default().expect("load city_meta.json");
+    assert!(db.audit_city_aliases(&bundled).is_empty());
+}
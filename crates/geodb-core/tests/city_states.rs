@@ -0,0 +1,130 @@
+#![cfg(feature = "json")]
+
+use geodb_core::model::{CountriesRaw, SYNTHETIC_STATE_NAME};
+use geodb_core::{
+    build_geodb, render_hits, CityView, GeoDb, ResponseFormat, SearchOptions, SmartItem,
+    StandardBackend,
+};
+
+/// City-states as the upstream exports list them: Monaco with quarters that
+/// have no cities plus cities on the country, the Vatican with no states at
+/// all, and Singapore with regular regions.
+fn fixture() -> GeoDb<StandardBackend> {
+    let raw: CountriesRaw = serde_json::from_str(
+        r#"[
+            { "name": "Monaco", "iso2": "MC", "iso3": "MCO",
+              "states": [{ "name": "La Condamine" }, { "name": "Moneghetti" }],
+              "cities": [{ "name": "Monaco" }, { "name": "Monte-Carlo" }] },
+            { "name": "Vatican City State (Holy See)", "iso2": "VA", "iso3": "VAT",
+              "cities": [{ "name": "Vatican City", "latitude": "41.90", "longitude": "12.45" }] },
+            { "name": "Singapore", "iso2": "SG", "iso3": "SGP",
+              "states": [{ "name": "Central Singapore", "iso2": "01",
+                           "cities": [{ "name": "Singapore" }] }] }
+        ]"#,
+    )
+    .expect("valid fixture");
+    build_geodb(raw)
+}
+
+#[test]
+fn country_level_cities_get_a_synthetic_state() {
+    let db = fixture();
+    let mc = db.find_country_by_iso2("MC").unwrap();
+    let names: Vec<&str> = mc.states().iter().map(|s| s.name()).collect();
+    assert_eq!(names, ["La Condamine", "Moneghetti", SYNTHETIC_STATE_NAME]);
+    assert!(mc.states()[2].is_synthetic());
+    assert_eq!(mc.states()[2].cities().len(), 2);
+
+    let va = db.find_country_by_iso2("VA").unwrap();
+    assert_eq!(va.states().len(), 1);
+    assert!(va.states()[0].is_synthetic());
+
+    // regular states are untouched
+    let sg = db.find_country_by_iso2("SG").unwrap();
+    assert!(sg.states().iter().all(|s| !s.is_synthetic()));
+    assert_eq!(db.stats().cities, 4);
+}
+
+#[test]
+fn synthetic_states_are_not_search_results() {
+    let db = fixture();
+    assert!(db
+        .find_states_by_substring("")
+        .iter()
+        .all(|(s, _)| !s.is_synthetic()));
+    assert!(db.find_states_by_substring(SYNTHETIC_STATE_NAME).is_empty());
+    let hits = db.smart_search_with(SYNTHETIC_STATE_NAME, &SearchOptions::default());
+    assert!(hits
+        .iter()
+        .all(|h| !matches!(h.item, SmartItem::State { .. })));
+
+    let index = db.search_index(&SearchOptions::default());
+    let indexed = db.smart_search_indexed("monte", &SearchOptions::default(), &index);
+    assert_eq!(indexed.len(), 1);
+}
+
+#[test]
+fn city_state_cities_are_found_with_their_country() {
+    let db = fixture();
+
+    let hits = db.smart_search("vatican");
+    let city = hits
+        .iter()
+        .find_map(|h| match h.item {
+            SmartItem::City {
+                country,
+                state,
+                city,
+            } => Some((country, state, city)),
+            _ => None,
+        })
+        .expect("Vatican City as a city hit");
+    assert_eq!(city.0.iso2(), "VA");
+    assert!(city.1.is_synthetic());
+
+    // views name the synthetic state, ids and lookups work as for any city
+    let view = serde_json::to_value(CityView {
+        country: city.0,
+        state: city.1,
+        city: city.2,
+    })
+    .unwrap();
+    assert_eq!(view["state"], SYNTHETIC_STATE_NAME);
+    assert_eq!(view["id"], "VA/0/0");
+    let path = db.ancestors_of(view["id"].as_str().unwrap().parse().unwrap());
+    assert_eq!(
+        path.and_then(|p| p.city).map(|c| c.name()),
+        Some("Vatican City")
+    );
+
+    // flat formats leave the state out instead of showing a placeholder
+    let nominatim = serde_json::to_value(render_hits(&hits, ResponseFormat::Nominatim)).unwrap();
+    let display = nominatim
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|h| h["display_name"].as_str())
+        .find(|name| name.starts_with("Vatican City,"))
+        .unwrap();
+    assert_eq!(display, "Vatican City, Vatican City State (Holy See)");
+}
+
+#[test]
+fn stateless_and_cityless_countries_in_the_dataset_load() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["MC", "VA", "SG"]).expect("load");
+    assert_eq!(db.country_count(), 3);
+
+    // the upstream dataset has no cities for Monaco and the Vatican; their
+    // names still find the country
+    for (query, iso2) in [("monaco", "MC"), ("vatican", "VA"), ("singapore", "SG")] {
+        match db.smart_search(query).first().map(|h| &h.item) {
+            Some(SmartItem::Country(c)) => assert_eq!(c.iso2(), iso2),
+            _ => panic!("{query}: expected the country as the first hit"),
+        }
+    }
+    assert!(db
+        .countries()
+        .iter()
+        .flat_map(|c| c.states())
+        .all(|s| !s.is_synthetic()));
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{ExportLevel, GeoDb, StandardBackend};
+
+// the checked-in output for LI, compiled as part of this test
+mod li {
+    include!("fixtures/codegen_li.rs");
+}
+
+fn source(db: &GeoDb<StandardBackend>, level: ExportLevel) -> String {
+    let mut out = Vec::new();
+    db.write_rust_source(&mut out, level).expect("write");
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn rust_source_matches_the_compiled_fixture() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+
+    let fixture = include_str!("fixtures/codegen_li.rs");
+    let generated = source(&db, ExportLevel::Cities);
+    // the first line names the crate version
+    let body = |s: &str| s.split_once('\n').unwrap().1.to_string();
+    assert_eq!(body(&generated), body(fixture));
+
+    let vaduz = li::country_by_iso2("li").expect("LI");
+    assert_eq!(vaduz.capital, Some("Vaduz"));
+    assert_eq!(vaduz.states.len(), db.stats().states);
+    let cities: usize = li::COUNTRIES[0].states.iter().map(|s| s.cities.len()).sum();
+    assert_eq!(cities, db.stats().cities);
+    assert!(li::country_by_iso2("DE").is_none());
+}
+
+#[test]
+fn rust_source_stops_at_the_level() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+
+    let mut out = Vec::new();
+    let stats = db
+        .write_rust_source(&mut out, ExportLevel::Countries)
+        .unwrap();
+    assert_eq!((stats.countries, stats.states, stats.cities), (2, 0, 0));
+    let countries = String::from_utf8(out).unwrap();
+    assert!(countries.contains("name: \"Germany\","));
+    assert!(!countries.contains("pub struct State"));
+    assert!(!countries.contains("states: &["));
+
+    let states = source(&db, ExportLevel::States);
+    assert!(states.contains("name: \"Bavaria\","));
+    assert!(!states.contains("pub struct City"));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{render_hits, GeoDb, ResponseFormat, StandardBackend};
+
+#[test]
+fn response_format_parses_names() {
+    assert_eq!("Nominatim".parse(), Ok(ResponseFormat::Nominatim));
+    assert_eq!("google".parse(), Ok(ResponseFormat::Google));
+    assert_eq!("".parse(), Ok(ResponseFormat::Native));
+    assert!("xml".parse::<ResponseFormat>().is_err());
+}
+
+#[test]
+fn hits_render_in_compat_formats() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "FR"]).expect("load DB");
+    let hits = db.smart_search("berlin");
+    assert!(!hits.is_empty());
+
+    let nominatim =
+        serde_json::to_value(render_hits(&hits, ResponseFormat::Nominatim)).expect("json");
+    let first = &nominatim[0];
+    assert_eq!(first["address"]["country_code"], "de");
+    assert!(first["display_name"].as_str().unwrap().contains("Germany"));
+    assert!(first["lat"].is_string());
+
+    let google = serde_json::to_value(render_hits(&hits, ResponseFormat::Google)).expect("json");
+    assert_eq!(google["status"], "OK");
+    let components = google["results"][0]["address_components"]
+        .as_array()
+        .unwrap();
+    assert_eq!(components.last().unwrap()["short_name"], "DE");
+
+    let empty = serde_json::to_value(render_hits(
+        &db.smart_search("zzzzqqq"),
+        ResponseFormat::Google,
+    ))
+    .expect("json");
+    assert_eq!(empty["status"], "ZERO_RESULTS");
+
+    let native = serde_json::to_value(render_hits(&hits, ResponseFormat::Native)).expect("json");
+    assert!(native[0]["kind"].is_string());
+}
+
+#[test]
+fn hit_views_match_the_native_rendering() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let hits = db.smart_search("berlin");
+    assert!(!hits.is_empty());
+
+    let native = serde_json::to_value(render_hits(&hits, ResponseFormat::Native)).expect("json");
+    for (i, hit) in hits.iter().enumerate() {
+        let value = hit.to_json_value().expect("json");
+        assert_eq!(value, native[i]);
+        assert_eq!(serde_json::to_value(hit.to_view()).unwrap(), value);
+    }
+    let kinds: Vec<_> = hits
+        .iter()
+        .map(|h| h.to_json_value().unwrap()["kind"].clone())
+        .collect();
+    assert!(kinds.iter().any(|k| k == "state"));
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{consts, CountryId, GeoDb, StandardBackend};
+
+#[test]
+fn country_constants_cover_the_dataset() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+
+    // Regenerate with `cargo run --example gen_country_consts` if this fails
+    assert_eq!(consts::ALL.len(), db.stats().countries);
+    for id in consts::ALL {
+        assert!(db.country(*id).is_some(), "{id} not in dataset");
+    }
+    assert!(consts::ALL.windows(2).all(|w| w[0] < w[1]));
+
+    assert_eq!(db.country(consts::DE).map(|c| c.name()), Some("Germany"));
+    assert_eq!(
+        db.find_country_by_iso2("ch").and_then(|c| c.id()),
+        Some(consts::CH)
+    );
+}
+
+#[test]
+fn country_id_parses_and_serializes_as_code() {
+    assert_eq!(" us ".parse::<CountryId>(), Ok(consts::US));
+    assert!(CountryId::new("USA").is_none());
+    assert!(CountryId::new("1A").is_none());
+    assert_eq!(consts::FR.to_string(), "FR");
+
+    let json = serde_json::to_string(&consts::AT).unwrap();
+    assert_eq!(json, r#""AT""#);
+    assert_eq!(
+        serde_json::from_str::<CountryId>(r#""at""#).unwrap(),
+        consts::AT
+    );
+    assert!(serde_json::from_str::<CountryId>(r#""Austria""#).is_err());
+}
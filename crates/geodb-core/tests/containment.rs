@@ -0,0 +1,152 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{consts, AdminLevel, CityId, CountryId, GeoDb, StandardBackend, StateId};
+
+#[test]
+fn ancestors_lead_from_country_to_city() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    let (city, _, _) = db
+        .find_cities_by_substring("Heidelberg")
+        .into_iter()
+        .find(|(c, _, _)| c.name() == "Heidelberg")
+        .expect("Heidelberg");
+
+    let id = db.city_id(city).expect("city of this database");
+    assert!(std::ptr::eq(db.city_by_id(id).unwrap(), city));
+
+    let path = db.ancestors_of(id).expect("valid id");
+    assert_eq!(path.level(), AdminLevel::City);
+    assert_eq!(path.names(), ["Germany", "Baden-Württemberg", "Heidelberg"]);
+    assert_eq!(path.to_string(), "Germany → Baden-Württemberg → Heidelberg");
+
+    let foreign = city.clone();
+    assert_eq!(db.city_id(&foreign), None);
+    let outside = CityId::new(StateId::new(consts::FR, 0), 0);
+    assert!(db.ancestors_of(outside).is_none());
+}
+
+#[test]
+fn descendants_are_listed_per_level() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    let de = db.country(consts::DE).unwrap();
+
+    let country = db.descendants_of(consts::DE, AdminLevel::Country);
+    assert_eq!(country.len(), 1);
+    assert_eq!(country[0].level(), AdminLevel::Country);
+
+    let states = db.descendants_of(consts::DE, AdminLevel::State);
+    assert_eq!(states.len(), de.states().len());
+    assert!(states.iter().all(|p| p.level() == AdminLevel::State));
+
+    let cities = db.descendants_of(consts::DE, AdminLevel::City);
+    let expected: usize = de.states().iter().map(|s| s.cities().len()).sum();
+    assert_eq!(cities.len(), expected);
+    assert!(cities.iter().all(|p| std::ptr::eq(p.country, de)));
+
+    assert!(db
+        .descendants_of(CountryId::new("FR").unwrap(), AdminLevel::State)
+        .is_empty());
+}
+
+#[test]
+fn is_within_matches_ancestors_and_regions() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    let (city, _, _) = db
+        .iter_cities()
+        .find(|(c, s, _)| c.name() == "Heidelberg" && s.name() == "Baden-Württemberg")
+        .expect("Heidelberg");
+    let id = db.city_id(city).unwrap();
+
+    for area in [
+        "Europe",
+        "western europe",
+        "Germany",
+        "DE",
+        "deu",
+        "Baden-Württemberg",
+        "BW",
+    ] {
+        assert!(db.is_within(id, area), "{area}");
+    }
+    for area in ["Asia", "Liechtenstein", "Bavaria", "Heidelberg", ""] {
+        assert!(!db.is_within(id, area), "{area}");
+    }
+
+    let states = db.descendants_of(consts::LI, AdminLevel::State);
+    assert!(states[0].is_within("Liechtenstein"));
+    assert!(!states[0].is_within(states[0].state.unwrap().name()));
+}
+
+#[test]
+fn ids_round_trip_and_survive_other_filters() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    let (city, state, country) = db
+        .iter_cities()
+        .find(|(c, _, country)| c.name() == "Vaduz" && country.iso2() == "LI")
+        .expect("Vaduz");
+
+    let state_id = country.state_id(state).expect("state of this country");
+    let id = country.city_id(state, city).expect("city of this country");
+    assert_eq!(db.state_id(state), Some(state_id));
+    assert_eq!(db.city_id(city), Some(id));
+    assert_eq!(id.state(), state_id);
+    assert_eq!(id.country(), consts::LI);
+    assert!(std::ptr::eq(db.state_by_id(state_id).unwrap(), state));
+
+    let text = id.to_string();
+    assert_eq!(text, format!("LI/{}/{}", state_id.index(), id.index()));
+    assert_eq!(text.parse::<CityId>(), Ok(id));
+    assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{text}\""));
+    for bad in ["LI", "LI/x/0", "LI/1/2/3", "L/1/2"] {
+        assert!(bad.parse::<CityId>().is_err(), "{bad}");
+    }
+    assert!("de/3".parse::<StateId>().is_ok());
+
+    // positions count within the country, so other filters keep the id
+    let li = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    assert_eq!(li.city_by_id(id).map(|c| c.name()), Some("Vaduz"));
+    assert!(li
+        .city_by_id(CityId::new(StateId::new(consts::DE, 0), 0))
+        .is_none());
+}
+
+#[test]
+fn places_resolve_to_city_paths() {
+    use geodb_core::PlaceQuery;
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["CH", "US"]).expect("load DB");
+    let resolve = |input: &str| {
+        let place = PlaceQuery::parse(input).expect(input);
+        db.resolve_place(&place)
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(resolve("Geneva, CH"), ["Switzerland → Geneva → Geneva"]);
+    assert_eq!(resolve(" geneva , switzerland"), resolve("Geneva, CHE"));
+    let springfields = resolve("Springfield, US");
+    assert!(springfields.len() > 1);
+    assert_eq!(
+        resolve("Springfield, IL, US"),
+        ["United States → Illinois → Springfield"]
+    );
+    assert_eq!(
+        resolve("springfield, us-il, usa"),
+        resolve("Springfield, Illinois, US")
+    );
+
+    for unknown in ["Zermatt, US", "Geneva, XX", "Springfield, Bavaria, US"] {
+        assert!(resolve(unknown).is_empty(), "{unknown}");
+    }
+    for bad in ["Geneva", "Geneva, ", "a, b, c, d", ""] {
+        assert_eq!(PlaceQuery::parse(bad), None, "{bad}");
+    }
+    assert_eq!(
+        PlaceQuery::parse("Springfield, IL, US")
+            .unwrap()
+            .state
+            .as_deref(),
+        Some("IL")
+    );
+}
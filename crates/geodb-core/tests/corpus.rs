@@ -0,0 +1,95 @@
+#![cfg(feature = "json")]
+//! Runs the shared query corpus (`tests/fixtures/query_corpus.json`) through
+//! the core API. The Python (`geodb-py/tests/test_corpus.py`) and WASM
+//! (`geodb-wasm/tests/corpus.rs`) bindings check their output against the
+//! same expectations, so the three surfaces can't drift apart.
+//!
+//! After an intended change of the output, regenerate the expectations with
+//! `GEODB_BLESS=1 cargo test -p geodb-core --test corpus`.
+
+use geodb_core::{
+    CityView, CountryView, GeoDb, PhoneCodeSearch, SearchOptions, StandardBackend, StateView,
+};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+fn corpus_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/query_corpus.json")
+}
+
+fn run(db: &GeoDb<StandardBackend>, op: &str, query: &str, options: &Value) -> Value {
+    let value = match op {
+        "smart_search" => {
+            // bindings differ in their defaults; cases spell out what matters
+            let options: SearchOptions = serde_json::from_value(options.clone()).unwrap();
+            let views: Vec<_> = db
+                .smart_search_with(query, &options)
+                .iter()
+                .map(|h| h.to_view())
+                .collect();
+            serde_json::to_value(views)
+        }
+        "countries_by_phone" => {
+            let views: Vec<_> = db
+                .find_countries_by_phone_code(query)
+                .into_iter()
+                .map(CountryView)
+                .collect();
+            serde_json::to_value(views)
+        }
+        "states_by_substring" => {
+            let views: Vec<_> = db
+                .find_states_by_substring(query)
+                .into_iter()
+                .map(|(state, country)| StateView { country, state })
+                .collect();
+            serde_json::to_value(views)
+        }
+        "cities_by_substring" => {
+            let views: Vec<_> = db
+                .find_cities_by_substring(query)
+                .into_iter()
+                .map(|(city, state, country)| CityView {
+                    country,
+                    state,
+                    city,
+                })
+                .collect();
+            serde_json::to_value(views)
+        }
+        "normalize_phone_code" => {
+            serde_json::to_value(geodb_core::phone::normalize_phone_code(query))
+        }
+        "fold_key" => {
+            let language = options["language"].as_str().unwrap_or("simple");
+            serde_json::to_value(geodb_core::fold_key(query, language.parse().unwrap()))
+        }
+        other => panic!("unknown corpus op {other:?}"),
+    };
+    value.expect("serialize")
+}
+
+#[test]
+fn core_matches_the_shared_corpus() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+    let path = corpus_path();
+    let mut corpus: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let bless = std::env::var_os("GEODB_BLESS").is_some();
+
+    for case in corpus["queries"].as_array_mut().unwrap() {
+        let op = case["op"].as_str().unwrap().to_string();
+        let query = case["query"].as_str().unwrap().to_string();
+        let options = case.get("options").cloned().unwrap_or_else(|| json!({}));
+        let actual = run(&db, &op, &query, &options);
+        if bless {
+            case["expected"] = actual;
+        } else {
+            assert_eq!(actual, case["expected"], "{op}({query:?})");
+        }
+    }
+
+    if bless {
+        let json = serde_json::to_string_pretty(&corpus).unwrap();
+        std::fs::write(&path, json + "\n").unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "json")]
+
+use flate2::read::GzDecoder;
+use geodb_core::model::CountriesRaw;
+use geodb_core::{CountryView, GeoDb, StandardBackend};
+use std::fs::File;
+use std::io::BufReader;
+
+fn raw_countries() -> CountriesRaw {
+    let path = GeoDb::<StandardBackend>::default_data_dir()
+        .join(GeoDb::<StandardBackend>::default_dataset_filename());
+    let file = File::open(path).expect("open raw dataset");
+    serde_json::from_reader(BufReader::new(GzDecoder::new(file))).expect("parse raw dataset")
+}
+
+#[test]
+fn country_accessors_match_the_raw_dataset() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+    let raw = raw_countries();
+    assert_eq!(db.countries().len(), raw.len());
+
+    for r in &raw {
+        let c = db
+            .find_country_by_iso2(&r.iso2)
+            .expect("country from dataset");
+        let iso2 = c.iso2();
+        assert_eq!(c.numeric_code(), r.numeric_code.as_deref(), "{iso2}");
+        assert_eq!(c.currency_name(), r.currency_name.as_deref(), "{iso2}");
+        assert_eq!(c.currency_symbol(), r.currency_symbol.as_deref(), "{iso2}");
+        assert_eq!(c.tld(), r.tld.as_deref(), "{iso2}");
+        assert_eq!(c.native_name(), r.native.as_deref(), "{iso2}");
+        assert_eq!(c.gdp(), r.gdp, "{iso2}");
+        assert_eq!(c.region_id(), r.region_id, "{iso2}");
+        assert_eq!(c.subregion(), r.subregion.as_deref(), "{iso2}");
+        assert_eq!(c.subregion_id(), r.subregion_id, "{iso2}");
+        assert_eq!(c.nationality(), r.nationality.as_deref(), "{iso2}");
+        assert_eq!(c.emoji(), r.emoji.as_deref(), "{iso2}");
+        assert_eq!(c.emoji_u(), r.emoji_u.as_deref(), "{iso2}");
+        let parse = |v: &Option<String>| v.as_deref().and_then(|s| s.parse::<f64>().ok());
+        assert_eq!(c.latitude(), parse(&r.latitude), "{iso2}");
+        assert_eq!(c.longitude(), parse(&r.longitude), "{iso2}");
+        for (lang, name) in &r.translations {
+            assert_eq!(c.translation(lang), Some(name.as_str()), "{iso2}/{lang}");
+        }
+    }
+}
+
+#[test]
+fn country_view_serializes_the_accessor_values() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let de = &db.countries()[0];
+    let json = serde_json::to_value(CountryView(de)).unwrap();
+
+    assert_eq!(json["numeric_code"], "276");
+    assert_eq!(json["currency_name"].as_str(), de.currency_name());
+    assert_eq!(json["currency_symbol"].as_str(), de.currency_symbol());
+    assert_eq!(json["tld"], ".de");
+    assert_eq!(json["native_name"].as_str(), de.native_name());
+    assert_eq!(json["gdp"].as_i64(), de.gdp());
+    assert_eq!(json["subregion"].as_str(), de.subregion());
+    assert_eq!(json["subregion_id"].as_i64(), de.subregion_id());
+    assert_eq!(json["nationality"].as_str(), Some("German"));
+    assert_eq!(json["latitude"].as_f64(), de.latitude());
+    assert_eq!(json["translations"]["es"].as_str(), de.translation("es"));
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CountryBenchmark, CoverageBenchmark, CoverageIssueKind, GeoDb, StandardBackend};
+
+#[test]
+fn coverage_counts_each_country() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+
+    let coverage = db.country_coverage();
+    assert_eq!(coverage.len(), 2);
+    let de = coverage.iter().find(|c| c.iso2 == "DE").unwrap();
+    assert_eq!(de.states, 16);
+    assert!(de.cities > 1000);
+    assert_eq!(
+        coverage.iter().map(|c| c.cities).sum::<usize>(),
+        db.stats().cities
+    );
+    assert!(de.cities_with_coords <= de.cities);
+    assert!(de.coords_share() > 0.9);
+}
+
+#[test]
+fn audit_coverage_flags_drops_below_the_benchmark() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    let benchmark = CoverageBenchmark::from_db(&db, 0.05);
+    assert!(db.audit_coverage(&benchmark).passed());
+
+    // lose a third of the German cities and the coordinates of the rest
+    let de = db.countries.iter_mut().find(|c| c.iso2 == "DE").unwrap();
+    let cities = de.states.iter().map(|s| s.cities.len()).sum::<usize>();
+    let mut dropped = 0;
+    for state in &mut de.states {
+        while dropped < cities / 3 && state.cities.pop().is_some() {
+            dropped += 1;
+        }
+        for city in &mut state.cities {
+            city.latitude = None;
+        }
+    }
+    db.countries.retain(|c| c.iso2 != "LI");
+
+    let report = db.audit_coverage(&benchmark);
+    let kinds: Vec<_> = report
+        .issues
+        .iter()
+        .map(|i| (i.iso2.as_str(), i.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ("DE", CoverageIssueKind::TooFewCities),
+            ("DE", CoverageIssueKind::MissingCoords),
+            ("LI", CoverageIssueKind::MissingCountry),
+        ]
+    );
+    assert_eq!(report.issues[0].actual, (cities - cities / 3) as f64);
+    assert!(report.issues[0].to_string().contains("expected at least"));
+
+    // unset minimums aren't checked; the filter drops LI
+    let mut loose = CoverageBenchmark::default();
+    loose
+        .countries
+        .insert("LI".to_string(), CountryBenchmark::default());
+    loose.countries.insert(
+        "DE".to_string(),
+        CountryBenchmark {
+            min_states: Some(16),
+            ..CountryBenchmark::default()
+        },
+    );
+    loose.retain_iso2(&["de"]);
+    assert!(db.audit_coverage(&loose).passed());
+}
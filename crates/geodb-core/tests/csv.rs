@@ -0,0 +1,71 @@
+#![cfg(feature = "json")]
+
+use geodb_core::csv::countries_from_csv;
+use geodb_core::{GeoDb, GeoError, StandardBackend};
+
+fn export(db: &GeoDb<StandardBackend>) -> (String, String, String) {
+    let (mut countries, mut states, mut cities) = (Vec::new(), Vec::new(), Vec::new());
+    db.export_csv(&mut countries, &mut states, &mut cities)
+        .expect("export");
+    let text = |bytes| String::from_utf8(bytes).unwrap();
+    (text(countries), text(states), text(cities))
+}
+
+#[test]
+fn csv_export_round_trips() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI", "VA"]).expect("load DB");
+    let dir = std::env::temp_dir().join(format!("geodb-csv-{}", std::process::id()));
+    let stats = db.export_csv_dir(&dir).expect("export");
+    assert_eq!(
+        (stats.countries, stats.states, stats.cities),
+        (db.stats().countries, db.stats().states, db.stats().cities)
+    );
+
+    let loaded = GeoDb::<StandardBackend>::load_csv_dir(&dir).expect("import");
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(export(&loaded), export(&db));
+
+    let de = loaded.find_country_by_iso2("DE").unwrap();
+    assert_eq!(de.primary_timezone(), Some("Europe/Berlin"));
+    assert_eq!(
+        de.translations.get("fr").map(String::as_str),
+        Some("Allemagne")
+    );
+    let (states, cities) = (export(&db).1, export(&db).2);
+    assert!(states.starts_with("id,country,name,"));
+    assert!(cities
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with("DE/0/0,DE/0,DE,"));
+}
+
+#[test]
+fn edited_csv_rows_become_the_database() {
+    let countries = "iso2,name,population,timezones\n\
+        XX,\"Land, the\",12,\"[{\"\"zoneName\"\":\"\"Europe/Berlin\"\"}]\"\n";
+    // columns in another order, an extra one and a blank line
+    let states = "name,id,country,code,note\nNorth,XX/a,XX,N,\"multi\nline\"\n\n";
+    let cities = "state,name,latitude,longitude\nXX/a,Alpha,1.5,2\nXX/a,Beta,,\n";
+    let raw = countries_from_csv(countries, states, cities).expect("parse");
+    assert_eq!(raw[0].name, "Land, the");
+    assert_eq!(raw[0].population, Some(12));
+    assert_eq!(
+        raw[0].timezones[0].zone_name.as_deref(),
+        Some("Europe/Berlin")
+    );
+    assert_eq!(raw[0].states[0].iso2.as_deref(), Some("N"));
+    let names: Vec<_> = raw[0].states[0].cities.iter().map(|c| &c.name).collect();
+    assert_eq!(names, ["Alpha", "Beta"]);
+    assert_eq!(raw[0].states[0].cities[1].latitude, None);
+
+    let unknown = countries_from_csv(countries, states, "state,name\nXX/a,A\nXX/b,B\n");
+    match unknown {
+        Err(GeoError::InvalidData(message)) => {
+            assert_eq!(message, "cities.csv line 3: unknown state XX/b")
+        }
+        _ => panic!("expected an unknown state error"),
+    }
+    let missing = countries_from_csv("iso2,name\nXX,\n", "id", "state");
+    assert!(matches!(missing, Err(GeoError::InvalidData(m)) if m.contains("line 2: missing name")));
+}
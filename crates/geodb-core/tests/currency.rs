@@ -0,0 +1,92 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CountryOrder, GeoDb, SearchOptions, SmartItem, StandardBackend};
+
+#[test]
+fn countries_are_found_by_currency() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH", "LI"])
+        .expect("load DB");
+    db.sort_countries(CountryOrder::Iso2);
+
+    let iso2 = |code: &str| -> Vec<&str> {
+        db.find_countries_by_currency(code)
+            .iter()
+            .map(|c| c.iso2())
+            .collect()
+    };
+    assert_eq!(iso2("eur"), ["AT", "DE"]);
+    assert_eq!(iso2(" CHF "), ["CH", "LI"]);
+    assert!(iso2("USD").is_empty());
+    assert!(iso2("").is_empty());
+
+    let chf = db.currency("chf").expect("CHF");
+    assert_eq!(chf.code, "CHF");
+    assert!(chf.name.is_some() && chf.symbol.is_some());
+    assert_eq!(chf.countries, ["CH", "LI"]);
+    assert!(db.currency("XXX").is_none());
+
+    let codes: Vec<&str> = db.currencies().iter().map(|c| c.code).collect();
+    assert_eq!(codes, ["CHF", "EUR"]);
+    assert_eq!(db.currencies()[1], db.currency("EUR").unwrap());
+}
+
+#[test]
+fn countries_are_found_by_currency_name_and_symbol() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH", "LI"])
+        .expect("load DB");
+    db.sort_countries(CountryOrder::Iso2);
+    let iso2 = |countries: Vec<&geodb_core::Country<StandardBackend>>| -> Vec<String> {
+        countries.iter().map(|c| c.iso2().to_string()).collect()
+    };
+
+    assert_eq!(
+        iso2(db.find_countries_by_currency_name("franc")),
+        ["CH", "LI"]
+    );
+    assert_eq!(
+        iso2(db.find_countries_by_currency_name("EURO")),
+        ["AT", "DE"]
+    );
+    assert!(db.find_countries_by_currency_name("dollar").is_empty());
+    assert!(db.find_countries_by_currency_name(" ").is_empty());
+
+    let eur = db.currency("EUR").unwrap();
+    let symbol = eur.symbol.unwrap();
+    assert_eq!(
+        iso2(db.find_countries_by_currency_symbol(symbol)),
+        ["AT", "DE"]
+    );
+    assert!(db.find_countries_by_currency_symbol("").is_empty());
+}
+
+#[test]
+fn smart_search_finds_countries_by_exact_currency() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH", "LI"])
+        .expect("load DB");
+    let currency_hits = |query: &str, options: &SearchOptions| -> Vec<(i32, String)> {
+        let mut hits: Vec<_> = db
+            .smart_search_with(query, options)
+            .iter()
+            .filter_map(|h| match h.item {
+                SmartItem::Country(c) => Some((h.score, c.iso2().to_string())),
+                _ => None,
+            })
+            .collect();
+        hits.sort();
+        hits
+    };
+    let options = SearchOptions::default();
+    let score = options.scoring.currency;
+    let chf = vec![(score, "CH".to_string()), (score, "LI".to_string())];
+    assert_eq!(currency_hits("chf", &options), chf);
+    let name = db.currency("CHF").unwrap().name.unwrap();
+    assert_eq!(currency_hits(name, &options), chf);
+
+    // exact only, and only on request
+    assert!(currency_hits("ch", &options)
+        .iter()
+        .all(|(s, _)| *s != score));
+    assert!(currency_hits("franc", &options).is_empty());
+    let off = SearchOptions::default().with_include_currency(false);
+    assert!(currency_hits("chf", &off).is_empty());
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{GeoDb, StandardBackend, ValueCount};
+
+#[test]
+fn distinct_values_are_counted_and_sorted() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH"]).expect("load DB");
+
+    assert_eq!(
+        db.distinct_currencies(),
+        vec![
+            ValueCount {
+                value: "CHF",
+                count: 1
+            },
+            ValueCount {
+                value: "EUR",
+                count: 2
+            },
+        ]
+    );
+    assert_eq!(
+        db.distinct_regions(),
+        vec![ValueCount {
+            value: "Europe",
+            count: 3
+        }]
+    );
+
+    let zones = db.distinct_timezones();
+    assert!(zones.windows(2).all(|w| w[0].value < w[1].value));
+    let berlin = zones
+        .iter()
+        .find(|z| z.value == "Europe/Berlin")
+        .expect("Europe/Berlin");
+    assert!(berlin.count > 0);
+    assert_eq!(
+        zones.iter().map(|z| z.count).sum::<usize>(),
+        db.iter_cities()
+            .filter(|(c, _, _)| c.timezone.is_some())
+            .count()
+    );
+}
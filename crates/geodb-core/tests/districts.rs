@@ -0,0 +1,120 @@
+#![cfg(feature = "districts")]
+
+use geodb_core::district::district_city_index;
+use geodb_core::model::CountriesRaw;
+use geodb_core::{
+    build_geodb, try_build_geodb, GeoDb, GeoError, SearchOptions, SmartItem, StandardBackend,
+};
+
+fn fixture() -> GeoDb<StandardBackend> {
+    let raw: CountriesRaw = serde_json::from_str(
+        r#"[{
+            "name": "Germany", "iso2": "DE", "iso3": "DEU",
+            "states": [{
+                "name": "Nordrhein-Westfalen", "iso2": "NW",
+                "cities": [{ "name": "Düsseldorf" }],
+                "districts": [
+                    { "name": "Kreis Coesfeld", "code": "COE",
+                      "cities": [{ "name": "Coesfeld" }, { "name": "Dülmen" }] },
+                    { "name": "Kreis Warendorf", "code": "WAF",
+                      "cities": [{ "name": "Warendorf" }] }
+                ]
+            }]
+        }]"#,
+    )
+    .expect("valid fixture");
+    build_geodb(raw)
+}
+
+#[test]
+fn district_cities_are_part_of_the_state() {
+    let db = fixture();
+    assert_eq!(db.stats().cities, 4);
+
+    let state = &db.countries()[0].states()[0];
+    assert_eq!(state.districts().len(), 2);
+    assert!(state.district_of_city(0).is_none());
+    assert_eq!(state.district_of_city(3).unwrap().code(), "WAF");
+
+    let names: Vec<_> = db
+        .cities_for_district("de", "NW", "kreis coesfeld")
+        .iter()
+        .map(|c| c.name())
+        .collect();
+    assert_eq!(names, ["Coesfeld", "Dülmen"]);
+}
+
+#[test]
+fn districts_are_searchable() {
+    let db = fixture();
+    let hits = db.find_districts_by_substring("waren");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].0.name(), "Kreis Warendorf");
+    assert_eq!(hits[0].1.name(), "Nordrhein-Westfalen");
+}
+
+#[test]
+fn smart_search_finds_districts() {
+    let db = fixture();
+    let hits = db.smart_search("warendorf");
+    let (state, district) = hits
+        .iter()
+        .find_map(|h| match h.item {
+            SmartItem::District {
+                state, district, ..
+            } => Some((state.name(), district.name())),
+            _ => None,
+        })
+        .expect("district hit");
+    assert_eq!(state, "Nordrhein-Westfalen");
+    assert_eq!(district, "Kreis Warendorf");
+    // scored like states, so above the city of the same name
+    assert!(matches!(hits[0].item, SmartItem::District { .. }));
+    assert!(hits
+        .iter()
+        .any(|h| matches!(h.item, SmartItem::City { city, .. } if city.name() == "Warendorf")));
+
+    // cities left out, districts stay; position filters drop them
+    let options = SearchOptions::default().with_include_cities(false);
+    assert_eq!(db.smart_search_with("coesfeld", &options).len(), 1);
+    let options = options.with_must_have_coords(true);
+    assert!(db.smart_search_with("coesfeld", &options).is_empty());
+
+    let json = serde_json::to_value(hits.iter().map(|h| h.to_view()).collect::<Vec<_>>()).unwrap();
+    let view = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["kind"] == "district")
+        .expect("district view");
+    assert_eq!(view["code"], "WAF");
+    assert_eq!(view["state_code"], "NW");
+    assert_eq!(view["city_count"], 1);
+}
+
+#[test]
+fn district_indices_fail_instead_of_truncating() {
+    let max = u32::MAX as usize;
+    assert_eq!(district_city_index(max, "NW", "DE").unwrap(), u32::MAX);
+    match district_city_index(max + 1, "Nordrhein-Westfalen", "DE") {
+        Err(GeoError::InvalidData(msg)) => {
+            assert!(msg.contains("'Nordrhein-Westfalen' (DE)"), "{msg}");
+            assert!(msg.contains("load_filtered_by_iso2"), "{msg}");
+        }
+        other => panic!("expected InvalidData, got {other:?}"),
+    }
+
+    // an index past u32 must not wrap onto a district's range
+    let state = &fixture().countries[0].states[0];
+    assert_eq!(state.district_of_city(3).unwrap().code(), "WAF");
+    assert!(state.district_of_city(max + 1 + 3).is_none());
+}
+
+#[test]
+fn try_build_accepts_regular_data() {
+    let raw: CountriesRaw =
+        serde_json::from_str(r#"[{ "name": "Liechtenstein", "iso2": "LI", "states": [] }]"#)
+            .unwrap();
+    let db: GeoDb<StandardBackend> = try_build_geodb(raw).unwrap();
+    assert_eq!(db.stats().countries, 1);
+}
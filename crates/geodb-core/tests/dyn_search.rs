@@ -0,0 +1,49 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{DynGeoSearch, EntityDto, GeoDb, StandardBackend};
+use std::sync::Arc;
+
+#[test]
+fn geodb_can_be_used_as_trait_object() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load filtered DB");
+    let search: Arc<dyn DynGeoSearch> = Arc::new(db);
+
+    assert_eq!(search.stats().countries, 1);
+
+    let us = search.country_by_code("usa").expect("US by iso3");
+    assert_eq!(us.iso2, "US");
+    assert!(!search.states_of("US").is_empty());
+    assert_eq!(search.countries_by_currency("usd")[0].iso2, "US");
+    assert!(search.countries_by_currency("EUR").is_empty());
+
+    let hits = search.smart_search("US");
+    match &hits[0].item {
+        EntityDto::Country(c) => assert_eq!(c.iso2, "US"),
+        other => panic!("expected country hit first, got {other:?}"),
+    }
+
+    let completions = search.autocomplete("springf", 3);
+    assert_eq!(completions.len(), 3);
+    assert!(completions
+        .iter()
+        .all(|h| matches!(&h.item, EntityDto::City(c) if c.name.starts_with("Springf"))));
+
+    let cities = search.cities_by_substring("springfield");
+    assert!(cities.iter().all(|c| c.country_iso2 == "US"));
+
+    let near = search
+        .nearest_city(40.7128, -74.006, 20.0)
+        .expect("city near NYC");
+    assert_eq!(near.country_iso2, "US");
+    assert!(search.nearest_city(0.0, -30.0, 100.0).is_none());
+
+    let manhattan = search.cities_in_bbox(40.70, -74.02, 40.88, -73.90);
+    assert!(manhattan.iter().any(|c| c.name == "New York City"));
+
+    // ids of earlier results look the entities up again
+    let id = near.id.expect("city id");
+    assert_eq!(search.city_by_id(id), Some(near.clone()));
+    let state = &search.states_of("US")[0];
+    assert_eq!(search.state_by_id(state.id.unwrap()).as_ref(), Some(state));
+    assert_eq!(search.country_by_id(us.id.unwrap()), Some(us));
+}
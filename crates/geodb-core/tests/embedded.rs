@@ -0,0 +1,11 @@
+#![cfg(all(feature = "embed-data", not(feature = "districts")))]
+
+use geodb_core::{GeoDb, StandardBackend};
+
+#[test]
+fn embedded_database_loads_without_data_dir() {
+    let db = GeoDb::<StandardBackend>::load_embedded().expect("embedded DB");
+    assert_eq!(db.stats().countries, 250);
+    assert!(db.find_country_by_iso2("DE").is_some());
+    assert!(!GeoDb::<StandardBackend>::embedded_bytes().is_empty());
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CityMetaIndex, GeoDb, StandardBackend};
+
+#[test]
+fn exact_lookup_matches_folded_names_per_country() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "FR"]).expect("load DB");
+
+    let lookup = db.exact_lookup();
+    assert!(lookup.city_exists("DE", "Berlin"));
+    assert!(lookup.city_exists("de", "  BERLIN "));
+    assert!(lookup.city_exists("DE", "KÖLN"));
+    assert!(lookup.city_exists("DE", "Koln"));
+    assert!(lookup.city_exists("DE", "  Frankfurt   am Main "));
+    assert!(!lookup.city_exists("FR", "Berlin"));
+    assert!(!lookup.city_exists("DE", "Berl"));
+    assert!(!lookup.city_exists("US", "Berlin"));
+    assert_eq!(lookup.country("fr").map(|c| c.name()), Some("France"));
+
+    let hits = lookup.find_cities("DE", "berlin");
+    assert!(!hits.is_empty());
+    assert!(hits
+        .iter()
+        .all(|(c, _, country)| c.name() == "Berlin" && country.iso2() == "DE"));
+
+    // Aliases applied from city meta are indexed as well
+    let index = CityMetaIndex::load_default().expect("city meta");
+    db.apply_city_meta(&index);
+    let lookup = db.exact_lookup();
+    assert!(lookup.city_exists("DE", "münchen"));
+    assert_eq!(lookup.find_cities("DE", "Muenchen")[0].0.name(), "Munich");
+}
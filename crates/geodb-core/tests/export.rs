@@ -0,0 +1,37 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CityDto, ExportLevel, GeoDb, StandardBackend, StateDto};
+
+#[test]
+fn export_ndjson_writes_one_record_per_line() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let stats = db.stats();
+
+    let mut out = Vec::new();
+    let written = db.export_ndjson(&mut out, ExportLevel::Cities).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert_eq!(written, stats.cities);
+    assert_eq!(text.lines().count(), stats.cities);
+
+    let first: CityDto = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+    assert_eq!(first.country_iso2, "LI");
+    assert!(first.latitude.is_some());
+
+    let mut out = Vec::new();
+    let written = db.export_ndjson(&mut out, ExportLevel::States).unwrap();
+    assert_eq!(written, stats.states);
+    let states: Vec<StateDto> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert!(states.iter().any(|s| s.name == "Vaduz"));
+}
+
+#[test]
+fn export_level_parses_from_cli_strings() {
+    assert_eq!("Cities".parse(), Ok(ExportLevel::Cities));
+    assert_eq!("country".parse(), Ok(ExportLevel::Countries));
+    assert!("towns".parse::<ExportLevel>().is_err());
+    assert_eq!(ExportLevel::States.to_string(), "states");
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "fetch")]
+
+#[cfg(feature = "json")]
+use flate2::{write::GzEncoder, Compression};
+use geodb_core::fetch::{sha256_file, DownloadOptions, Downloader, Progress};
+#[cfg(feature = "json")]
+use geodb_core::loader::cache_path_for;
+use geodb_core::GeoError;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+/// Accept one connection on a local port and write what `respond` returns
+/// for the request head. Returns the address and a handle yielding the head.
+fn serve(
+    respond: impl FnOnce(&str) -> Vec<u8> + Send + 'static,
+) -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 4096];
+        let n = stream.read(&mut request).unwrap_or(0);
+        let head = String::from_utf8_lossy(&request[..n]).into_owned();
+        stream.write_all(&respond(&head)).unwrap();
+        head
+    });
+    (addr, handle)
+}
+
+fn response(status: &str, headers: &str, body: &[u8]) -> Vec<u8> {
+    let mut out = format!(
+        "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )
+    .into_bytes();
+    out.extend_from_slice(body);
+    out
+}
+
+/// Answer one GET on a local port with `body`; returns the URL.
+#[cfg(feature = "json")]
+fn serve_once(body: Vec<u8>) -> String {
+    let (addr, _) = serve(move |_| response("200 OK", "", &body));
+    format!("http://{addr}/countries.json.gz")
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("geodb-fetch-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(feature = "json")]
+fn gzip(json: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn refresh_replaces_the_dataset_and_its_caches() {
+    let dir = temp_dir("refresh");
+    let dataset = dir.join("world.json.gz");
+    std::fs::write(&dataset, b"old").unwrap();
+    let stale = cache_path_for(&dataset, Some(&["DE"]));
+    std::fs::write(&stale, b"stale").unwrap();
+    let all = cache_path_for(&dataset, None);
+    std::fs::write(&all, b"stale").unwrap();
+
+    let downloader = Downloader::new(DownloadOptions {
+        resume: false,
+        ..DownloadOptions::default()
+    })
+    .unwrap();
+
+    // not a dataset: nothing is replaced
+    let url = serve_once(b"<html>moved</html>".to_vec());
+    assert!(downloader
+        .refresh_dataset(&url, &dataset, None, |_| {})
+        .is_err());
+    assert_eq!(std::fs::read(&dataset).unwrap(), b"old");
+    assert!(stale.exists());
+    assert_eq!(std::fs::read(&all).unwrap(), b"stale");
+
+    let json = r#"[{ "name": "Testland", "iso2": "TL",
+        "states": [{ "name": "North", "cities": [{ "name": "Alpha" }] }] },
+        { "name": "Otherland", "iso2": "OL", "states": [] }]"#;
+    let body = gzip(json);
+    let url = serve_once(body.clone());
+    let (db, report) = downloader
+        .refresh_dataset(&url, &dataset, Some(&["TL"]), |_| {})
+        .expect("refresh");
+    assert_eq!(report.path, dataset);
+    assert_eq!(report.bytes, body.len() as u64);
+    assert_eq!(db.countries().len(), 1);
+    assert_eq!(db.stats().cities, 1);
+    assert_eq!(std::fs::read(&dataset).unwrap(), body);
+    assert!(!stale.exists());
+    assert!(cache_path_for(&dataset, Some(&["TL"])).exists());
+
+    // a filtered refresh still rewrites the unfiltered cache
+    let all_db = geodb_core::GeoDb::<geodb_core::StandardBackend>::load_binary(&all)
+        .expect("unfiltered cache rewritten");
+    assert_eq!(all_db.countries().len(), 2);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn download_resumes_a_partial_file() {
+    let dir = temp_dir("resume");
+    let dest = dir.join("data.bin");
+    let body: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    std::fs::write(dir.join("data.bin.part"), &body[..1000]).unwrap();
+
+    let served = body.clone();
+    let (addr, request) = serve(move |head| {
+        let range = head
+            .lines()
+            .find_map(|l| l.strip_prefix("range: bytes="))
+            .expect("range header");
+        let start: usize = range.trim_end_matches('-').parse().unwrap();
+        let headers = format!(
+            "Content-Range: bytes {start}-{}/{}\r\n",
+            served.len() - 1,
+            served.len()
+        );
+        response("206 Partial Content", &headers, &served[start..])
+    });
+
+    let mut seen = Vec::new();
+    let report = Downloader::new(DownloadOptions::default())
+        .unwrap()
+        .download_to(&format!("http://{addr}/data.bin"), &dest, |p: Progress| {
+            seen.push(p)
+        })
+        .expect("download");
+    request.join().unwrap();
+
+    assert_eq!(report.resumed_from, 1000);
+    assert_eq!(report.bytes, body.len() as u64);
+    assert_eq!(std::fs::read(&dest).unwrap(), body);
+    assert!(!dir.join("data.bin.part").exists());
+    assert_eq!(seen[0].downloaded, 1000);
+    assert_eq!(seen.last().unwrap().downloaded, body.len() as u64);
+    assert!(seen.iter().all(|p| p.total == Some(body.len() as u64)));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn download_checks_the_sha256() {
+    let dir = temp_dir("sha256");
+    let dest = dir.join("data.bin");
+    let url = |addr: &str| format!("http://{addr}/data.bin");
+
+    let (addr, _) = serve(|_| response("200 OK", "", b"payload"));
+    let wrong = Downloader::new(DownloadOptions {
+        expected_sha256: Some("00".repeat(32)),
+        ..DownloadOptions::default()
+    })
+    .unwrap()
+    .download_to(&url(&addr), &dest, |_| {});
+    assert!(matches!(wrong, Err(GeoError::ChecksumMismatch { .. })));
+    // nothing is kept for a resume to build on
+    assert!(!dest.exists());
+    assert!(!dir.join("data.bin.part").exists());
+
+    std::fs::write(dir.join("expected"), b"payload").unwrap();
+    let expected = sha256_file(dir.join("expected")).unwrap();
+    let (addr, _) = serve(|_| response("200 OK", "", b"payload"));
+    let report = Downloader::new(DownloadOptions {
+        expected_sha256: Some(expected.to_uppercase()),
+        ..DownloadOptions::default()
+    })
+    .unwrap()
+    .download_to(&url(&addr), &dest, |_| {})
+    .expect("matching checksum");
+    assert_eq!(report.sha256, expected);
+    assert_eq!(std::fs::read(&dest).unwrap(), b"payload");
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn download_goes_through_the_proxy() {
+    let dir = temp_dir("proxy");
+    let (proxy, request) = serve(|_| response("200 OK", "", b"via proxy"));
+
+    let downloader = Downloader::new(DownloadOptions {
+        proxy: Some(format!("http://{proxy}")),
+        ..DownloadOptions::default()
+    })
+    .unwrap();
+    downloader
+        .download_to(
+            "http://example.invalid/data.bin",
+            dir.join("data.bin"),
+            |_| {},
+        )
+        .expect("download through proxy");
+
+    // a forward proxy gets the absolute URL
+    assert!(request
+        .join()
+        .unwrap()
+        .starts_with("GET http://example.invalid/data.bin "));
+    assert_eq!(std::fs::read(dir.join("data.bin")).unwrap(), b"via proxy");
+
+    std::fs::remove_dir_all(&dir).ok();
+}
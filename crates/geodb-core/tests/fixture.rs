@@ -0,0 +1,97 @@
+#![cfg(feature = "json")]
+
+use geodb_core::fixture::{sample_source_countries, sample_source_dataset, FixtureOptions};
+use geodb_core::model::CountriesRaw;
+use geodb_core::{build_geodb, sample_indices, GeoDb, StandardBackend};
+use serde_json::{json, Value};
+
+fn iso2s(countries: &[Value]) -> Vec<&str> {
+    countries
+        .iter()
+        .map(|c| c["iso2"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn sample_indices_are_deterministic_and_sorted() {
+    let a = sample_indices(100, 10, 7);
+    assert_eq!(a, sample_indices(100, 10, 7));
+    assert_ne!(a, sample_indices(100, 10, 8));
+    assert_eq!(a.len(), 10);
+    assert!(a.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(sample_indices(3, 10, 7), [0, 1, 2]);
+}
+
+#[test]
+fn source_sample_keeps_countries_states_and_a_few_cities() {
+    let countries: Vec<Value> = (0..6)
+        .map(|i| {
+            let cities: Vec<Value> = (0..20)
+                .map(|c| json!({ "id": c, "name": format!("City {c}"), "wikiDataId": "Q1" }))
+                .collect();
+            json!({
+                "name": format!("Country {i}"),
+                "iso2": format!("C{i}"),
+                "states": [
+                    { "name": "A", "cities": cities.clone() },
+                    { "name": "B", "cities": cities[..3].to_vec() },
+                ],
+            })
+        })
+        .collect();
+    let options = FixtureOptions {
+        countries: 2,
+        cities_per_state: 5,
+        seed: 42,
+    };
+
+    let sample = sample_source_countries(countries.clone(), &options).unwrap();
+    assert_eq!(
+        sample,
+        sample_source_countries(countries.clone(), &options).unwrap()
+    );
+    assert_eq!(sample.len(), 2);
+    for country in &sample {
+        let states = country["states"].as_array().unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0]["cities"].as_array().unwrap().len(), 5);
+        assert_eq!(states[1]["cities"].as_array().unwrap().len(), 3);
+        // unknown source fields survive
+        assert_eq!(states[0]["cities"][0]["wikiDataId"], "Q1");
+    }
+    let picked: Vec<String> = sample
+        .iter()
+        .map(|c| c["states"][0]["cities"][0]["name"].to_string())
+        .collect();
+    assert_ne!(picked[0], picked[1], "states are sampled independently");
+
+    assert!(sample_source_countries(vec![json!("DE")], &options).is_err());
+}
+
+#[test]
+fn source_sample_matches_geodb_sample_and_loads() {
+    let path = GeoDb::<StandardBackend>::default_data_dir()
+        .join(GeoDb::<StandardBackend>::default_dataset_filename());
+    let filter = ["DE", "AT", "LI", "CH"];
+    let options = FixtureOptions {
+        countries: 2,
+        cities_per_state: 3,
+        seed: 42,
+    };
+    let fixture = sample_source_dataset(&path, Some(&filter), &options).unwrap();
+    let countries = fixture.as_array().unwrap();
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&filter).expect("load DB");
+    let sampled = db.sample(2, 42);
+    let expected: Vec<&str> = sampled.countries().iter().map(|c| c.iso2()).collect();
+    assert_eq!(iso2s(countries), expected);
+
+    let raw: CountriesRaw = serde_json::from_value(fixture.clone()).unwrap();
+    let built = build_geodb::<StandardBackend>(raw);
+    for (country, full) in built.countries().iter().zip(sampled.countries()) {
+        assert_eq!(country.states().len(), full.states().len());
+        for (state, full) in country.states().iter().zip(full.states()) {
+            assert_eq!(state.cities().len(), full.cities().len().min(3));
+        }
+    }
+}
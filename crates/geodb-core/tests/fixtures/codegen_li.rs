@@ -0,0 +1,159 @@
+// Generated by geodb-core 0.1.3 (cities); do not edit.
+// Data: countries-states-cities-database by dr5hn, CC-BY-4.0.
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Country {
+    pub iso2: &'static str,
+    pub iso3: &'static str,
+    pub name: &'static str,
+    pub capital: Option<&'static str>,
+    pub phone_code: &'static str,
+    pub currency: &'static str,
+    pub region: &'static str,
+    pub timezone: Option<&'static str>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub states: &'static [State],
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct State {
+    pub name: &'static str,
+    pub code: Option<&'static str>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub cities: &'static [City],
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct City {
+    pub name: &'static str,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub timezone: Option<&'static str>,
+}
+
+pub static COUNTRIES: &[Country] = &[
+    Country {
+        iso2: "LI",
+        iso3: "LIE",
+        name: "Liechtenstein",
+        capital: Some("Vaduz"),
+        phone_code: "423",
+        currency: "CHF",
+        region: "Europe",
+        timezone: Some("Europe/Vaduz"),
+        latitude: Some(47.26666666),
+        longitude: Some(9.53333333),
+        states: &[
+            State {
+                name: "Balzers",
+                code: Some("01"),
+                latitude: Some(47.0688822),
+                longitude: Some(9.506159),
+                cities: &[
+                    City { name: "Balzers", latitude: Some(47.06665), longitude: Some(9.50251), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Eschen",
+                code: Some("02"),
+                latitude: Some(47.2126274),
+                longitude: Some(9.5233202),
+                cities: &[
+                    City { name: "Eschen", latitude: Some(47.21071), longitude: Some(9.52223), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Gamprin",
+                code: Some("03"),
+                latitude: Some(47.213249),
+                longitude: Some(9.5025195),
+                cities: &[
+                    City { name: "Gamprin", latitude: Some(47.22038), longitude: Some(9.50935), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Mauren",
+                code: Some("04"),
+                latitude: Some(47.2189285),
+                longitude: Some(9.541735),
+                cities: &[
+                    City { name: "Mauren", latitude: Some(47.21805), longitude: Some(9.5442), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Planken",
+                code: Some("05"),
+                latitude: Some(47.1858848),
+                longitude: Some(9.5452211),
+                cities: &[
+                    City { name: "Planken", latitude: Some(47.18516), longitude: Some(9.54437), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Ruggell",
+                code: Some("06"),
+                latitude: Some(47.2397575),
+                longitude: Some(9.5262871),
+                cities: &[
+                    City { name: "Ruggell", latitude: Some(47.23799), longitude: Some(9.5254), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Schaan",
+                code: Some("07"),
+                latitude: Some(47.1663397),
+                longitude: Some(9.510312),
+                cities: &[
+                    City { name: "Schaan", latitude: Some(47.16498), longitude: Some(9.50867), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Schellenberg",
+                code: Some("08"),
+                latitude: Some(47.230966),
+                longitude: Some(9.5467843),
+                cities: &[
+                    City { name: "Schellenberg", latitude: Some(47.23123), longitude: Some(9.54678), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Triesen",
+                code: Some("09"),
+                latitude: Some(47.1097988),
+                longitude: Some(9.5248296),
+                cities: &[
+                    City { name: "Triesen", latitude: Some(47.10752), longitude: Some(9.52815), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Triesenberg",
+                code: Some("10"),
+                latitude: Some(47.1186181),
+                longitude: Some(9.5433663),
+                cities: &[
+                    City { name: "Triesenberg", latitude: Some(47.11815), longitude: Some(9.54197), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+            State {
+                name: "Vaduz",
+                code: Some("11"),
+                latitude: Some(47.1410303),
+                longitude: Some(9.5209277),
+                cities: &[
+                    City { name: "Vaduz", latitude: Some(47.14151), longitude: Some(9.52154), timezone: Some("Europe/Vaduz") },
+                ],
+            },
+        ],
+    },
+];
+
+/// Country by ISO2 code, case-insensitive.
+#[allow(dead_code)]
+pub fn country_by_iso2(iso2: &str) -> Option<&'static Country> {
+    COUNTRIES.iter().find(|c| c.iso2.eq_ignore_ascii_case(iso2))
+}
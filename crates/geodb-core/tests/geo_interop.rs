@@ -0,0 +1,40 @@
+#![cfg(feature = "geo-interop")]
+
+use geodb_core::geo_interop::geo_types::Point;
+use geodb_core::geo_interop::rstar::AABB;
+use geodb_core::{GeoDb, StandardBackend};
+
+#[test]
+fn entities_convert_to_points() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let (city, _, _) = db
+        .iter_cities()
+        .find(|(c, _, _)| c.name() == "Berlin")
+        .expect("Berlin");
+    let p = city.point().expect("coordinates");
+    assert!((p.x() - 13.4).abs() < 0.1, "x is the longitude: {p:?}");
+    assert!((p.y() - 52.5).abs() < 0.1, "y is the latitude: {p:?}");
+
+    let hit = &db.smart_search("berlin")[0];
+    assert!(hit.item.point().is_some());
+    assert!(db.countries[0].point().is_some());
+}
+
+#[test]
+fn city_rtree_answers_nearest_and_envelope_queries() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let tree = db.city_rtree();
+    assert_eq!(tree.size(), db.city_points().count());
+
+    let nearest = tree
+        .nearest_neighbor(&Point::new(13.405, 52.52))
+        .expect("non-empty tree");
+    assert_eq!(nearest.state.name(), "Berlin");
+
+    let ruhr = AABB::from_corners(Point::new(6.6, 51.3), Point::new(7.8, 51.7));
+    let inside: Vec<_> = tree.locate_in_envelope(&ruhr).collect();
+    assert!(inside.iter().any(|c| c.city.name() == "Dortmund"));
+    assert!(inside
+        .iter()
+        .all(|c| (6.6..=7.8).contains(&c.point.x()) && (51.3..=51.7).contains(&c.point.y())));
+}
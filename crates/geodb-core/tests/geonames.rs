@@ -0,0 +1,84 @@
+#![cfg(all(feature = "geonames", feature = "json"))]
+
+use geodb_core::geonames::GeonamesImportOptions;
+use geodb_core::{CityMeta, GeoDb, StandardBackend};
+
+// geonameid, name, asciiname, alternatenames, lat, lng, class, code, country
+const PLACES: &str = "\
+2867714\tMunich\tMunich\tMuenchen,München\t48.13743\t11.57549\tP\tPPLA\tDE
+2886242\tCologne\tCologne\tKoeln,Köln\t50.93333\t6.95\tP\tPPLA\tDE
+9999999\tMunich\tMunich\t\t52.0\t8.0\tP\tPPL\tDE
+2953481\tBavaria\tBavaria\t\t49.0\t11.5\tA\tADM1\tDE
+";
+
+// id, geonameid, language, name, preferred, short, colloquial, historic
+const ALTERNATE_NAMES: &str = "\
+1\t2867714\tde\tMünchen\t1\t\t\t
+2\t2867714\ten\tMunich\t1\t\t\t
+3\t2867714\tit\tMonaco di Baviera\t\t\t\t
+4\t2867714\tlink\thttps://en.wikipedia.org/wiki/Munich\t\t\t\t
+5\t2867714\tde\tMuenchen\t\t\t\t
+6\t2886242\ten\tCologne\t1\t\t\t
+7\t2886242\tla\tColonia Agrippina\t\t\t\t1
+8\t9999999\tde\tNirgendwo\t\t\t\t
+";
+
+fn run_import(options: &GeonamesImportOptions) -> geodb_core::geonames::GeonamesImport {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    db.import_geonames_aliases(PLACES.as_bytes(), ALTERNATE_NAMES.as_bytes(), options)
+        .expect("import")
+}
+
+fn aliases_of<'a>(entries: &'a [CityMeta], city: &str) -> Vec<&'a str> {
+    entries
+        .iter()
+        .find(|e| e.city == city)
+        .map(|e| e.aliases.iter().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn imports_aliases_for_matched_cities() {
+    let import = run_import(&GeonamesImportOptions::default());
+
+    // "Cologne" is matched through its listed alternate name "Köln";
+    // the far-away "Munich" doesn't match any dataset city
+    assert_eq!((import.matched_places, import.unmatched_places), (2, 1));
+    assert_eq!(
+        aliases_of(&import.entries, "Munich"),
+        ["München", "Monaco di Baviera", "Muenchen"]
+    );
+    // historic names are skipped by default
+    assert_eq!(aliases_of(&import.entries, "Köln"), ["Cologne"]);
+    assert_eq!(import.aliases, 4);
+}
+
+#[test]
+fn filters_by_language_and_preferred_flag() {
+    let options = GeonamesImportOptions::default()
+        .with_languages(["de", "en"])
+        .with_preferred_only(true);
+    let import = run_import(&options);
+    assert_eq!(aliases_of(&import.entries, "Munich"), ["München"]);
+    assert_eq!(aliases_of(&import.entries, "Köln"), ["Cologne"]);
+
+    let import = run_import(&GeonamesImportOptions::default().with_max_aliases(1));
+    assert_eq!(aliases_of(&import.entries, "Munich"), ["München"]);
+}
+
+#[test]
+fn merge_keeps_curated_entries_first() {
+    let import = run_import(&GeonamesImportOptions::default().with_languages(["de"]));
+    let mut base = vec![CityMeta {
+        iso2: "DE".into(),
+        state: "Bavaria".into(),
+        city: "Munich".into(),
+        aliases: vec!["Muenchen".into()],
+        regions: vec!["Oberbayern".into()],
+    }];
+    import.merge_into(&mut base);
+
+    assert_eq!(base.len(), 1);
+    assert_eq!(base[0].aliases, ["Muenchen", "München"]);
+    assert_eq!(base[0].regions, ["Oberbayern"]);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "json")]
+
+use geodb_core::inspect::{inspect_bytes, Compression};
+use geodb_core::{GeoDb, StandardBackend};
+
+#[test]
+fn sections_add_up_to_the_payload() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI"]).expect("load DB");
+    let bytes = db.to_bytes().unwrap();
+    let report = inspect_bytes::<StandardBackend>(&bytes).expect("inspect");
+
+    let stats = db.stats();
+    assert_eq!(
+        (report.countries, report.states, report.cities),
+        (stats.countries, stats.states, stats.cities)
+    );
+    assert_eq!(report.compression, Compression::None);
+    assert!(report.envelope.is_none());
+    assert_eq!(report.payload_size, bytes.len() as u64);
+
+    let s = report.sections;
+    assert_eq!(
+        s.countries + s.states + s.cities + s.trailing,
+        bytes.len() as u64
+    );
+    assert_eq!(s.trailing, 0);
+    assert!(s.strings > 0 && s.strings < bytes.len() as u64);
+
+    let total: u64 = report.per_country.iter().map(|c| c.bytes).sum();
+    assert_eq!(total + 8, bytes.len() as u64);
+    let de = report.per_country.iter().find(|c| c.iso2 == "DE").unwrap();
+    assert!(de.cities < de.states && de.states < de.bytes);
+}
+
+#[test]
+fn trailing_bytes_and_garbage_are_reported() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let mut bytes = db.to_bytes().unwrap();
+    bytes.extend_from_slice(&[0; 5]);
+    assert_eq!(
+        inspect_bytes::<StandardBackend>(&bytes)
+            .unwrap()
+            .sections
+            .trailing,
+        5
+    );
+
+    bytes.truncate(bytes.len() / 2);
+    assert!(inspect_bytes::<StandardBackend>(&bytes).is_err());
+    assert!(inspect_bytes::<StandardBackend>(&[]).is_err());
+}
+
+#[cfg(all(feature = "signed", feature = "json"))]
+#[test]
+fn sealed_artifacts_report_their_envelope() {
+    use geodb_core::signed::SigningKey;
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let payload = db.to_bytes().unwrap();
+    let sealed = db
+        .to_signed_bytes(Some(&SigningKey::from_bytes(&[7; 32])))
+        .unwrap();
+
+    let report = inspect_bytes::<StandardBackend>(&sealed).expect("inspect");
+    let envelope = report.envelope.expect("envelope");
+    assert!(envelope.signed);
+    assert_eq!(envelope.sha256.len(), 64);
+    assert_eq!(report.payload_size, payload.len() as u64);
+    assert_eq!(report.size, sealed.len() as u64);
+
+    let mut corrupted = sealed;
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 1;
+    assert!(inspect_bytes::<StandardBackend>(&corrupted).is_err());
+}
@@ -0,0 +1,43 @@
+#![cfg(all(feature = "multilingual-cities", feature = "json"))]
+
+use geodb_core::{CityLabelIndex, GeoDb, SearchOptions, SmartItem, StandardBackend};
+use std::sync::Arc;
+
+#[test]
+fn labels_resolve_to_the_same_city() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["AT", "DE"]).expect("load DB");
+    let index = CityLabelIndex::load_default().expect("load city_labels.json");
+
+    for label in ["Vienna", "wien", "Vienne", "Vídeň"] {
+        let hits = db.find_cities_by_label(label, &index);
+        assert_eq!(hits.len(), 1, "label {label}");
+        assert_eq!(hits[0].0.name(), "Vienna");
+        assert_eq!(hits[0].2.iso2(), "AT");
+    }
+
+    assert_eq!(
+        index.label("DE", "North Rhine-Westphalia", "Köln", "en"),
+        Some("Cologne")
+    );
+}
+
+#[test]
+fn smart_search_matches_city_labels() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let index = Arc::new(CityLabelIndex::load_default().expect("load city_labels.json"));
+
+    let city_of = |hits: &[geodb_core::SmartHit<'_, StandardBackend>]| {
+        hits.iter()
+            .filter_map(|h| match h.item {
+                SmartItem::City { city, .. } => Some(city.name().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert!(!city_of(&db.smart_search("colonia")).contains(&"Köln".to_string()));
+
+    let options = SearchOptions::default().with_city_labels(index);
+    let hits = db.smart_search_with("colonia", &options);
+    assert!(city_of(&hits).contains(&"Köln".to_string()));
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{GeoDb, GeoError, LoadLimits, LoadOptions, StandardBackend};
+
+fn li() -> GeoDb<StandardBackend> {
+    GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB")
+}
+
+fn decode(bytes: &[u8], limits: LoadLimits) -> Result<GeoDb<StandardBackend>, GeoError> {
+    let options = LoadOptions::default().with_limits(limits);
+    GeoDb::<StandardBackend>::from_bytes_with(bytes, &options).map(|(db, _)| db)
+}
+
+#[test]
+fn entity_limits_are_enforced_while_decoding() {
+    let db = li();
+    let cities = db.stats().cities;
+    let bytes = db.to_bytes().unwrap();
+
+    let exact = LoadLimits::default().with_max_cities(cities);
+    assert_eq!(decode(&bytes, exact).unwrap().stats().cities, cities);
+
+    let err = decode(&bytes, LoadLimits::default().with_max_cities(cities - 1))
+        .err()
+        .expect("limit");
+    assert!(matches!(err, GeoError::Bincode(_)), "{err}");
+    assert!(err.to_string().contains("cities"), "{err}");
+
+    let no_states = LoadLimits {
+        max_states: 0,
+        ..LoadLimits::default()
+    };
+    assert!(decode(&bytes, no_states).is_err());
+
+    // the limits only apply to the decode they were given to
+    assert!(GeoDb::<StandardBackend>::from_bytes(&bytes).is_ok());
+}
+
+#[test]
+fn long_strings_are_rejected() {
+    let mut db = li();
+    db.countries[0].states[0].cities[0].name = "x".repeat(5_000);
+    let bytes = db.to_bytes().unwrap();
+
+    let err = decode(&bytes, LoadLimits::default()).err().expect("limit");
+    assert!(err.to_string().contains("longer than 4096"), "{err}");
+    assert!(decode(&bytes, LoadLimits::default().with_max_string_len(5_000)).is_ok());
+    assert!(decode(&bytes, LoadLimits::unlimited()).is_ok());
+}
+
+#[test]
+fn hostile_length_prefixes_fail_fast() {
+    // a country count of 2^64 - 1, and nothing behind it
+    assert!(GeoDb::<StandardBackend>::from_bytes(&[0xff; 8]).is_err());
+
+    // one country claiming 2^40 states: the encodings of one and two
+    // states first differ in the low byte of the state count (clones keep
+    // the translation map order)
+    let db = li();
+    let encode = |states: usize| {
+        let mut db = db.clone();
+        db.countries[0].states.truncate(states);
+        db.to_bytes().unwrap()
+    };
+    let (mut one, two) = (encode(1), encode(2));
+    let at = one.iter().zip(&two).position(|(a, b)| a != b).unwrap();
+    assert_eq!(one[at..at + 8], 1u64.to_le_bytes());
+    one[at..at + 8].copy_from_slice(&(1u64 << 40).to_le_bytes());
+    let err = GeoDb::<StandardBackend>::from_bytes(&one)
+        .err()
+        .expect("rejected");
+    assert!(err.to_string().contains("states"), "{err}");
+}
@@ -0,0 +1,106 @@
+//! Also runs with `--no-default-features --features mmap`: the fixture
+//! comes from the bundled `ALL.bin`, which has the layout without
+//! districts.
+#![cfg(all(feature = "mmap", not(feature = "districts")))]
+
+use geodb_core::{GeoDb, SmartItem, StandardBackend};
+use std::path::PathBuf;
+
+fn artifacts(name: &str) -> (GeoDb<StandardBackend>, PathBuf, PathBuf) {
+    let bundled = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    let db = GeoDb::<StandardBackend>::load_binary(&bundled)
+        .expect("load DB")
+        .filtered_by_iso2(&["DE", "AT", "CH", "LI"]);
+    let dir = std::env::temp_dir().join(format!("geodb-mmap-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let plain = dir.join("plain.bin");
+    let indexed = dir.join("indexed.bin");
+    std::fs::write(&plain, db.to_bytes().unwrap()).unwrap();
+    std::fs::write(&indexed, db.to_indexed_bytes().unwrap()).unwrap();
+    (db, plain, indexed)
+}
+
+#[test]
+fn countries_are_decoded_on_first_access() {
+    let (db, plain, indexed) = artifacts("lazy");
+    for path in [&plain, &indexed] {
+        let mapped = GeoDb::<StandardBackend>::load_mmap(path).expect("map");
+        assert_eq!(mapped.country_count(), 4);
+        let codes: Vec<&str> = mapped.iso2_codes().collect();
+        let expected: Vec<&str> = db.countries().iter().map(|c| c.iso2()).collect();
+        assert_eq!(codes, expected);
+        assert_eq!(mapped.decoded_count(), 0);
+
+        let ch = mapped.country("ch").unwrap().expect("CH");
+        let expected = db.find_country_by_iso2("CH").unwrap();
+        assert_eq!(ch.name(), expected.name());
+        assert_eq!(ch.states().len(), expected.states().len());
+        assert_eq!(mapped.decoded_count(), 1);
+        // decoded once, then served from memory
+        assert!(std::ptr::eq(ch, mapped.country("CH").unwrap().unwrap()));
+        assert!(mapped.country("FR").unwrap().is_none());
+        assert_eq!(mapped.decoded_count(), 1);
+    }
+    std::fs::remove_dir_all(plain.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn filtered_databases_search_like_loaded_ones() {
+    let (db, _, indexed) = artifacts("filtered");
+    let mapped = GeoDb::<StandardBackend>::load_mmap(&indexed).expect("map");
+    let dach = mapped.filtered_by_iso2(&["de", "AT", "CH", "XX"]).unwrap();
+    assert_eq!(dach.country_count(), 3);
+    assert_eq!(mapped.decoded_count(), 3);
+
+    let expected = db.filtered_by_iso2(&["DE", "AT", "CH"]);
+    assert_eq!(dach.stats().cities, expected.stats().cities);
+    // score, country, state and city name of each hit
+    let hits = |db: &GeoDb<StandardBackend>| -> Vec<(i32, String, String, String)> {
+        db.smart_search("zürich")
+            .iter()
+            .map(|h| {
+                let (country, state, city) = match h.item {
+                    SmartItem::Country(c) => (c, "", ""),
+                    SmartItem::State { country, state } => (country, state.name(), ""),
+                    SmartItem::City {
+                        country,
+                        state,
+                        city,
+                    } => (country, state.name(), city.name()),
+                };
+                (
+                    h.score,
+                    country.iso2().to_string(),
+                    state.to_string(),
+                    city.to_string(),
+                )
+            })
+            .collect()
+    };
+    assert!(!hits(&dach).is_empty());
+    assert_eq!(hits(&dach), hits(&expected));
+    std::fs::remove_dir_all(indexed.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn unmappable_artifacts_are_rejected() {
+    let dir = std::env::temp_dir().join(format!("geodb-mmap-bad-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, bytes) in [
+        ("gzip.bin", &[0x1f, 0x8b, 8, 0][..]),
+        ("garbage.bin", b"not a database"),
+        ("empty.bin", b""),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        assert!(
+            GeoDb::<StandardBackend>::load_mmap(&path).is_err(),
+            "{name}"
+        );
+    }
+    assert!(GeoDb::<StandardBackend>::load_mmap(dir.join("missing.bin")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,30 @@
+#![cfg(all(feature = "msgpack", feature = "json"))]
+
+use geodb_core::{msgpack, render_hits, GeoDb, ResponseFormat, StandardBackend};
+
+#[test]
+fn msgpack_matches_json_and_is_smaller() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let hits = db.smart_search("berlin");
+    let response = render_hits(&hits, ResponseFormat::Native);
+
+    let packed = msgpack::to_vec(&response).expect("encode");
+    let json = serde_json::to_vec(&response).expect("encode");
+    assert!(packed.len() < json.len());
+
+    let decoded: serde_json::Value = rmp_serde::from_slice(&packed).expect("decode");
+    assert_eq!(decoded, serde_json::to_value(&response).unwrap());
+    assert_eq!(decoded[0]["name"], "Berlin");
+}
+
+#[test]
+fn msgpack_write_appends_values() {
+    let mut out = Vec::new();
+    msgpack::write(&mut out, &["a"]).unwrap();
+    msgpack::write(&mut out, &["b"]).unwrap();
+
+    let mut reader = out.as_slice();
+    let first: Vec<String> = rmp_serde::from_read(&mut reader).unwrap();
+    let second: Vec<String> = rmp_serde::from_read(&mut reader).unwrap();
+    assert_eq!((first[0].as_str(), second[0].as_str()), ("a", "b"));
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CountryOrder, GeoDb, LoadOptions, StandardBackend};
+
+fn iso2s(db: &GeoDb<StandardBackend>) -> Vec<&str> {
+    db.countries().iter().map(|c| c.iso2()).collect()
+}
+
+#[test]
+fn countries_follow_the_requested_order() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US", "AX", "DE", "AT"])
+        .expect("load DB");
+
+    let mut by_iso2 = db.clone();
+    by_iso2.sort_countries(CountryOrder::Iso2);
+    assert_eq!(iso2s(&by_iso2), ["AT", "AX", "DE", "US"]);
+
+    // "Åland Islands" folds to "aland islands" and sorts before "Austria"
+    let mut by_name = db.clone();
+    by_name.sort_countries(CountryOrder::Name);
+    assert_eq!(iso2s(&by_name), ["AX", "AT", "DE", "US"]);
+
+    let mut unchanged = db.clone();
+    unchanged.sort_countries(CountryOrder::Dataset);
+    assert_eq!(iso2s(&unchanged), iso2s(&db));
+
+    // a reversed artifact loads in the same order as a sorted one
+    let mut reversed = db.clone();
+    reversed.countries.reverse();
+    let options = LoadOptions::default().with_country_order(CountryOrder::Name);
+    let (loaded, _) =
+        GeoDb::<StandardBackend>::from_bytes_with(&reversed.to_bytes().unwrap(), &options).unwrap();
+    assert_eq!(iso2s(&loaded), iso2s(&by_name));
+}
+
+#[test]
+fn country_order_parses_and_displays() {
+    for order in [
+        CountryOrder::Dataset,
+        CountryOrder::Iso2,
+        CountryOrder::Name,
+    ] {
+        assert_eq!(order.to_string().parse(), Ok(order));
+    }
+    assert_eq!(" ISO2 ".parse(), Ok(CountryOrder::Iso2));
+    assert!("area".parse::<CountryOrder>().is_err());
+}
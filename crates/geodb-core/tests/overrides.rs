@@ -0,0 +1,67 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{GeoDb, OverrideAction, Overrides, StandardBackend};
+
+#[test]
+fn overrides_add_rename_delete_and_fix_cities() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let cities = db.stats().cities;
+    let overrides: Overrides = serde_json::from_str(
+        r#"{ "cities": [
+            { "iso2": "LI", "state": "Vaduz", "city": "Ebenholz", "action": "add",
+              "latitude": 47.14, "longitude": 9.52, "aliases": ["Ebaholz"] },
+            { "iso2": "li", "state": "LI-01", "city": "balzers", "rename": "Balzers FL" },
+            { "iso2": "LI", "state": "09", "city": "Triesen", "latitude": 47.1, "timezone": "Europe/Zurich" },
+            { "iso2": "LI", "state": "Planken", "city": "Planken", "action": "delete" },
+            { "iso2": "LI", "state": "Planken", "city": "Planken", "action": "delete" },
+            { "iso2": "LI", "state": "Vaduz", "city": "Vaduz", "action": "add" },
+            { "iso2": "LI", "state": "Nowhere", "city": "X" },
+            { "iso2": "XX", "state": "Vaduz", "city": "X" }
+        ] }"#,
+    )
+    .expect("parse overrides");
+    assert_eq!(overrides.cities[1].action, OverrideAction::Update);
+
+    let skipped = db.apply_overrides(&overrides);
+    let reasons: Vec<_> = skipped
+        .iter()
+        .map(|s| (s.index, s.reason.as_str()))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            (4, "no such city"),
+            (5, "city already exists"),
+            (6, "unknown state"),
+            (7, "unknown country"),
+        ]
+    );
+    assert_eq!(
+        skipped[0].to_string(),
+        "override #4 (LI / Planken / Planken): no such city"
+    );
+
+    assert_eq!(db.stats().cities, cities);
+    let city = |state: &str, name: &str| {
+        db.cities_for_state("LI", state)
+            .iter()
+            .find(|c| c.name() == name)
+            .cloned()
+    };
+    let ebenholz = city("Vaduz", "Ebenholz").expect("added");
+    assert_eq!(ebenholz.latitude, Some(47.14));
+    assert_eq!(ebenholz.aliases().collect::<Vec<_>>(), ["Ebaholz"]);
+    assert!(city("Balzers", "Balzers").is_none());
+    assert!(city("Balzers", "Balzers FL").is_some());
+    let triesen = city("Triesen", "Triesen").unwrap();
+    assert_eq!(triesen.latitude, Some(47.1));
+    assert!(triesen.longitude.is_some());
+    assert_eq!(triesen.timezone.as_deref(), Some("Europe/Zurich"));
+    assert!(db.cities_for_state("LI", "Planken").is_empty());
+
+    // corrections survive the binary round trip, aliases don't
+    let baked = GeoDb::<StandardBackend>::from_bytes(&db.to_bytes().unwrap()).unwrap();
+    let ebenholz = baked.cities_for_state("LI", "Vaduz").last().unwrap();
+    assert_eq!(ebenholz.name(), "Ebenholz");
+    assert_eq!(ebenholz.aliases().count(), 0);
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "json")]
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use geodb_core::parse::parse_countries;
+use geodb_core::{AdminLevel, GeoDb, GeoError, StandardBackend};
+use std::io::Write;
+
+/// Two countries; Bavaria's second city has a numeric name and Austria
+/// lacks its name.
+const JSON: &str = r#"[
+  {"name": "Germany", "iso2": "DE", "states": [
+    {"name": "Berlin", "cities": [{"name": "Berlin"}]},
+    {"name": "Bavaria", "cities": [
+      {"name": "Munich"},
+      {"name": 42, "latitude": "48.4"}
+    ]}
+  ]},
+  {"iso2": "AT", "states": []},
+  {"name": "Liechtenstein", "iso2": "LI", "cities": [{"name": "Vaduz", "timezone": 1}]}
+]"#;
+
+#[test]
+fn malformed_entries_are_named_with_their_position() {
+    let Err(GeoError::InvalidEntity(entity)) = parse_countries(JSON, None, false) else {
+        panic!("expected an invalid entity");
+    };
+    assert_eq!(entity.level, AdminLevel::City);
+    assert_eq!(entity.country.index, 0);
+    assert_eq!(entity.country.name.as_deref(), Some("Germany"));
+    let state = entity.state.as_ref().unwrap();
+    assert_eq!((state.index, state.name.as_deref()), (1, Some("Bavaria")));
+    let city = entity.city.as_ref().unwrap();
+    assert_eq!((city.index, city.name.as_deref()), (1, None));
+    assert!(
+        entity.message.starts_with("invalid type: integer `42`"),
+        "{}",
+        entity.message
+    );
+
+    // the position points into the source text
+    let number = JSON.find("42").unwrap();
+    assert!((number..number + 2).contains(&entity.offset));
+    let line_start = JSON[..entity.offset].rfind('\n').unwrap() + 1;
+    assert_eq!(entity.column, entity.offset - line_start + 1);
+    let text = entity.to_string();
+    assert!(text.starts_with(r#"country #0 "Germany", state #1 "Bavaria", city #1: invalid type"#));
+    assert!(text.ends_with(&format!(
+        "(byte {}, line 6 column {})",
+        entity.offset, entity.column
+    )));
+
+    // only the filtered countries are checked
+    let parsed = parse_countries(JSON, Some(&["DE", "LI"][..]), false);
+    assert!(matches!(parsed, Err(GeoError::InvalidEntity(e)) if e.country.index == 0));
+    let parsed = parse_countries(
+        r#"[{"name": "X", "iso2": "XX", "states": 1}]"#,
+        Some(&["DE"]),
+        false,
+    );
+    assert!(parsed.unwrap().countries.is_empty());
+}
+
+#[test]
+fn skip_invalid_drops_the_lowest_malformed_level() {
+    let parsed = parse_countries(JSON, None, true).unwrap();
+    for e in &parsed.skipped {
+        eprintln!("DBG {e}");
+    }
+    let names: Vec<_> = parsed.countries.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["Germany", "Liechtenstein"]);
+    let bavaria = &parsed.countries[0].states[1];
+    let cities: Vec<_> = bavaria.cities.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(cities, ["Munich"]);
+    assert!(parsed.countries[1].cities.is_empty());
+
+    let skipped: Vec<_> = parsed
+        .skipped
+        .iter()
+        .map(|e| (e.level, e.country.index, e.state.as_ref().map(|s| s.index)))
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            (AdminLevel::City, 0, Some(1)),
+            (AdminLevel::Country, 1, None),
+            (AdminLevel::City, 2, None),
+        ]
+    );
+    assert!(parsed.skipped[1].message.contains("missing field `name`"));
+
+    // syntax errors have nothing to skip
+    assert!(matches!(
+        parse_countries(&JSON[..JSON.len() - 1], None, true),
+        Err(GeoError::Json(_))
+    ));
+}
+
+#[test]
+fn datasets_load_skipping_invalid_entries() {
+    let path = std::env::temp_dir().join(format!("geodb-parse-{}.json.gz", std::process::id()));
+    let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+    gz.write_all(JSON.as_bytes()).unwrap();
+    std::fs::write(&path, gz.finish().unwrap()).unwrap();
+
+    let error = GeoDb::<StandardBackend>::load_from_path(&path, None)
+        .err()
+        .unwrap();
+    assert!(
+        error.to_string().contains(r#"state #1 "Bavaria", city #1"#),
+        "{error}"
+    );
+
+    let (db, skipped) =
+        GeoDb::<StandardBackend>::load_from_path_skipping_invalid(&path, None).unwrap();
+    assert_eq!(db.stats().countries, 2);
+    // Berlin and Munich; Vaduz has a numeric timezone
+    assert_eq!(db.stats().cities, 2);
+    assert_eq!(skipped.len(), 3);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{normalize_phone_code, GeoDb, PhoneCodeSearch, SmartItem, StandardBackend};
+
+#[test]
+fn phone_code_normalization() {
+    for input in [
+        "49", "+49", " +49 ", "0049", "(49)", "+(49)", "011 49", "00 49",
+    ] {
+        assert_eq!(
+            normalize_phone_code(input).as_deref(),
+            Some("49"),
+            "{input:?}"
+        );
+    }
+    assert_eq!(normalize_phone_code("+1-684").as_deref(), Some("1684"));
+    // a `+` wins over the dialing prefix heuristics
+    assert_eq!(normalize_phone_code("+0049").as_deref(), Some("0049"));
+
+    for input in ["", " ", "+", "00", "de", "+49a", "4+9"] {
+        assert_eq!(normalize_phone_code(input), None, "{input:?}");
+    }
+}
+
+#[test]
+fn phone_search_accepts_common_spellings() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "US"]).expect("load DB");
+
+    for input in ["+49 ", "0049", "(49)", "011 49"] {
+        let found: Vec<&str> = db
+            .find_countries_by_phone_code(input)
+            .iter()
+            .map(|c| c.iso2())
+            .collect();
+        assert_eq!(found, ["DE"], "{input:?}");
+    }
+    assert!(db.find_countries_by_phone_code("germany").is_empty());
+
+    let hits = db.smart_search("0049");
+    assert!(matches!(hits[0].item, SmartItem::Country(c) if c.iso2() == "DE"));
+}
+
+#[test]
+fn dial_code_lookup_picks_a_primary_country() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&[
+        "GG", "JE", "IM", "GB", "KZ", "RU", "CX", "AU", "DE",
+    ])
+    .expect("load DB");
+
+    let uk = db.lookup_dial_code("+44").expect("+44");
+    assert_eq!(uk.code, "44");
+    assert_eq!(uk.primary_country, "GB");
+    assert_eq!(uk.countries, ["GB", "JE", "IM", "GG"]);
+
+    // priority list, then population
+    assert_eq!(db.lookup_dial_code("007").unwrap().countries, ["RU", "KZ"]);
+    assert_eq!(db.lookup_dial_code("61").unwrap().primary_country, "AU");
+
+    // a whole number resolves to its code; unknown and free text don't
+    let de = db.lookup_dial_code("+49 30 1234567").expect("number");
+    assert_eq!((de.code.as_str(), de.primary_country), ("49", "DE"));
+    assert!(db.lookup_dial_code("+4").is_none());
+    assert!(db.lookup_dial_code("+1").is_none());
+    assert!(db.lookup_dial_code("germany").is_none());
+
+    let json = serde_json::to_value(&uk).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "code": "44",
+            "countries": ["GB", "JE", "IM", "GG"],
+            "primary_country": "GB",
+        })
+    );
+}
+
+#[test]
+fn dial_codes_are_listed_numerically() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+    let codes = db.dial_codes();
+    let numbers: Vec<u32> = codes.iter().map(|d| d.code.parse().unwrap()).collect();
+    assert!(numbers.windows(2).all(|w| w[0] < w[1]), "{numbers:?}");
+
+    let nanp = &codes[0];
+    assert_eq!((nanp.code.as_str(), nanp.primary_country), ("1", "US"));
+    assert_eq!(nanp.countries[1], "CA");
+    let total: usize = codes.iter().map(|d| d.countries.len()).sum();
+    assert!(total > 200);
+}
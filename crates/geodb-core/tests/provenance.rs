@@ -0,0 +1,122 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    AliasConflictKind, CityMeta, CityMetaIndex, CityOverride, GeoDb, MetaField, Overrides,
+    StandardBackend,
+};
+
+fn meta(state: &str, city: &str, aliases: &[&str], regions: &[&str]) -> CityMeta {
+    CityMeta {
+        iso2: "CH".into(),
+        state: state.into(),
+        city: city.into(),
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        regions: regions.iter().map(|r| r.to_string()).collect(),
+    }
+}
+
+fn geneva(db: &GeoDb<StandardBackend>) -> &geodb_core::City<StandardBackend> {
+    db.iter_cities()
+        .find(|(c, s, _)| c.name() == "Geneva" && s.name() == "Geneva")
+        .map(|(c, _, _)| c)
+        .expect("Geneva")
+}
+
+#[test]
+fn merged_sidecars_record_their_sources() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["CH"]).expect("load DB");
+
+    let overrides = Overrides {
+        cities: vec![CityOverride {
+            iso2: "CH".into(),
+            state: "GE".into(),
+            city: "Geneva".into(),
+            latitude: Some(46.2),
+            longitude: Some(6.15),
+            aliases: vec!["GVA".into()],
+            ..CityOverride::default()
+        }],
+        ..Overrides::default()
+    }
+    .with_source("local.json");
+    assert!(db.apply_overrides(&overrides).is_empty());
+
+    let curated =
+        CityMetaIndex::from_entries(vec![meta("Geneva", "Geneva", &["Genf"], &["Lake Geneva"])])
+            .with_source("city_meta.json");
+    let imported =
+        CityMetaIndex::from_entries(vec![meta("Geneva", "Geneva", &["genf", "Ginevra"], &[])])
+            .with_source("geonames");
+    let mut index = curated;
+    index.merge(imported);
+    assert_eq!(index.source(1), "geonames");
+    assert_eq!(db.apply_city_meta(&index), 1);
+
+    let city = geneva(&db);
+    // the overrides alias survives; "genf" is a duplicate of the curated one
+    assert_eq!(
+        city.aliases().collect::<Vec<_>>(),
+        ["GVA", "Genf", "Ginevra"]
+    );
+    assert_eq!(
+        city.source_of(MetaField::Alias, Some("GENF")),
+        Some("city_meta.json")
+    );
+    assert_eq!(
+        city.source_of(MetaField::Alias, Some("Ginevra")),
+        Some("geonames")
+    );
+    assert_eq!(
+        city.source_of(MetaField::Alias, Some("GVA")),
+        Some("local.json")
+    );
+    assert_eq!(
+        city.source_of(MetaField::Region, Some("Lake Geneva")),
+        Some("city_meta.json")
+    );
+    assert_eq!(
+        city.source_of(MetaField::Coordinates, None),
+        Some("local.json")
+    );
+    assert_eq!(city.source_of(MetaField::Timezone, None), None);
+    assert_eq!(
+        city.provenance()[0].to_string(),
+        "coordinates from local.json"
+    );
+
+    // A new version of one file replaces only what that file contributed
+    let curated = CityMetaIndex::from_entries(vec![meta("Geneva", "Geneva", &["Genève"], &[])])
+        .with_source("city_meta.json");
+    db.apply_city_meta(&curated);
+    let city = geneva(&db);
+    assert_eq!(
+        city.aliases().collect::<Vec<_>>(),
+        ["GVA", "Ginevra", "Genève"]
+    );
+    assert_eq!(city.regions().count(), 0);
+    assert_eq!(city.source_of(MetaField::Alias, Some("Genf")), None);
+    assert_eq!(
+        city.source_of(MetaField::Alias, Some("Genève")),
+        Some("city_meta.json")
+    );
+}
+
+#[test]
+fn alias_audit_names_the_sidecar_of_each_owner() {
+    let mut index = CityMetaIndex::from_entries(vec![meta("Geneva", "Geneva", &["Genf"], &[])])
+        .with_source("city_meta.json");
+    index.merge(
+        CityMetaIndex::from_entries(vec![
+            meta("Geneva", "Geneva", &["Genf"], &[]),
+            meta("Vaud", "Lausanne", &["genf"], &[]),
+        ])
+        .with_source("extra.json"),
+    );
+
+    // Geneva listed by both files is one owner, not a conflict of its own
+    let conflicts = index.audit();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, AliasConflictKind::SharedAlias);
+    assert_eq!(conflicts[0].owners.len(), 2);
+    assert_eq!(conflicts[0].sources, ["city_meta.json", "extra.json"]);
+}
@@ -0,0 +1,96 @@
+//! Pins the signatures of `geodb_core::stable`.
+//!
+//! Each binding below coerces an item to its documented signature, so any
+//! removal, rename or signature change of the stable surface fails to
+//! compile here. Update this file only together with a breaking release.
+#![allow(clippy::type_complexity)]
+
+use geodb_core::stable::*;
+
+type B = StandardBackend;
+
+#[test]
+fn geodb_surface() {
+    let _: fn(&GeoDb<B>) -> &[Country<B>] = GeoDb::<B>::countries;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Option<&'a Country<B>> =
+        GeoDb::<B>::find_country_by_iso2;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Option<&'a Country<B>> =
+        GeoDb::<B>::find_country_by_iso3;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Option<&'a Country<B>> =
+        GeoDb::<B>::find_country_by_code;
+    let _: fn(&GeoDb<B>) -> DbStats = GeoDb::<B>::stats;
+    let _: fn(&GeoDb<B>) -> usize = GeoDb::<B>::country_count;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Vec<(&'a State<B>, &'a Country<B>)> =
+        GeoDb::<B>::find_states_by_substring;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Vec<(&'a City<B>, &'a State<B>, &'a Country<B>)> =
+        GeoDb::<B>::find_cities_by_substring;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Vec<SmartHit<'a, B>> = GeoDb::<B>::smart_search;
+    let _: for<'a> fn(&'a GeoDb<B>, &str, &SearchOptions) -> Vec<SmartHit<'a, B>> =
+        GeoDb::<B>::smart_search_with;
+    let _: for<'a> fn(&'a GeoDb<B>, &str) -> Vec<&'a Country<B>> =
+        <GeoDb<B> as PhoneCodeSearch<B>>::find_countries_by_phone_code;
+
+    let _: fn(&[u8]) -> Result<GeoDb<B>> = GeoDb::<B>::from_bytes;
+    let _: fn(&GeoDb<B>) -> Result<Vec<u8>> = GeoDb::<B>::to_bytes;
+    let _: fn() -> &'static str = GeoDb::<B>::get_3rd_party_data_url;
+}
+
+#[test]
+fn entity_surface() {
+    let _: fn(&Country<B>) -> &str = Country::<B>::name;
+    let _: fn(&Country<B>) -> &str = Country::<B>::iso2;
+    let _: fn(&Country<B>) -> &str = Country::<B>::iso3;
+    let _: fn(&Country<B>) -> &str = Country::<B>::phone_code;
+    let _: fn(&Country<B>) -> &str = Country::<B>::currency;
+    let _: fn(&Country<B>) -> Option<&str> = Country::<B>::capital;
+    let _: fn(&Country<B>) -> Option<i64> = Country::<B>::population;
+    let _: fn(&Country<B>) -> &str = Country::<B>::region;
+    let _: fn(&Country<B>) -> &[State<B>] = Country::<B>::states;
+    let _: fn(&Country<B>) -> &[CountryTimezone<B>] = Country::<B>::timezones;
+    let _: fn(&Country<B>) -> Option<&str> = Country::<B>::primary_timezone;
+
+    let _: fn(&State<B>) -> &str = State::<B>::name;
+    let _: fn(&State<B>) -> &str = State::<B>::state_code;
+    let _: fn(&State<B>) -> &[City<B>] = State::<B>::cities;
+
+    let _: fn(&City<B>) -> &str = City::<B>::name;
+
+    let stats = DbStats {
+        countries: 0,
+        states: 0,
+        cities: 0,
+    };
+    let _ = (stats.countries, stats.states, stats.cities);
+}
+
+#[test]
+fn option_and_error_surface() {
+    let options =
+        SearchOptions::default().with_expansions(ExpansionTable::new().with("st", "saint"));
+    assert_eq!(options.expansions.normalize("St. Louis"), "saint louis");
+
+    let err: GeoDbError = GeoError::NotFound("x".into());
+    assert!(matches!(err, GeoError::NotFound(_)));
+}
+
+#[test]
+fn view_and_hit_shapes() {
+    fn _views<'a>(country: &'a Country<B>, state: &'a State<B>, city: &'a City<B>) {
+        let _ = CountryView(country);
+        let _ = StateView { country, state };
+        let _ = CityView {
+            country,
+            state,
+            city,
+        };
+    }
+
+    fn _hit(hit: SmartHit<'_, B>) -> i32 {
+        let _: Option<&str> = hit.alias;
+        match hit.item {
+            SmartItem::Country(_) | SmartItem::State { .. } | SmartItem::City { .. } => hit.score,
+            #[cfg(feature = "districts")]
+            SmartItem::District { .. } => hit.score,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{CachedGeoDb, DynGeoSearch, GeoDb, QueryCacheStats, StandardBackend};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn db() -> Arc<GeoDb<StandardBackend>> {
+    Arc::new(GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT"]).expect("load DB"))
+}
+
+#[test]
+fn repeated_queries_are_served_from_the_cache() {
+    let db = db();
+    let cached = CachedGeoDb::new(Arc::clone(&db));
+
+    let first = cached.smart_search("ber");
+    assert_eq!(first, DynGeoSearch::smart_search(&*db, "ber"));
+    assert_eq!(cached.smart_search("  BER "), first);
+    assert!(Arc::ptr_eq(
+        &cached.smart_search_shared("ber"),
+        &cached.smart_search_shared("Ber")
+    ));
+
+    let cities = cached.cities_by_substring("berlin");
+    assert_eq!(cities, db.cities_by_substring("berlin"));
+    cached.cities_by_substring("berlin");
+
+    // autocomplete results are cached per prefix and limit
+    let completions = cached.autocomplete("ber", 5);
+    assert_eq!(completions, DynGeoSearch::autocomplete(&*db, "ber", 5));
+    assert_eq!(cached.autocomplete("BER", 5), completions);
+    assert_eq!(cached.autocomplete("ber", 2), completions[..2]);
+
+    assert_eq!(
+        cached.cache_stats(),
+        QueryCacheStats {
+            hits: 5,
+            misses: 4,
+            entries: 4
+        }
+    );
+    cached.clear();
+    assert_eq!(cached.cache_stats().entries, 0);
+}
+
+#[test]
+fn least_recently_used_results_are_evicted() {
+    let cached = CachedGeoDb::new(db()).with_max_entries(2);
+    cached.smart_search("a");
+    cached.smart_search("b");
+    cached.smart_search("a"); // hit; "b" is now the oldest
+    cached.smart_search("c"); // evicts "b"
+    cached.smart_search("a"); // still cached
+    cached.smart_search("b"); // miss again
+
+    let stats = cached.cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (2, 4, 2));
+
+    let uncached = CachedGeoDb::new(db()).with_max_entries(0);
+    uncached.smart_search("a");
+    uncached.smart_search("a");
+    assert_eq!(uncached.cache_stats().misses, 2);
+}
+
+#[test]
+fn expired_results_are_recomputed() {
+    let cached = CachedGeoDb::new(db()).with_ttl(Duration::ZERO);
+    cached.smart_search("wien");
+    cached.smart_search("wien");
+    let stats = cached.cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 2, 1));
+
+    let cached = CachedGeoDb::new(db()).with_ttl(Duration::from_secs(3600));
+    cached.smart_search("wien");
+    cached.smart_search("wien");
+    assert_eq!(cached.cache_stats().hits, 1);
+}
@@ -0,0 +1,163 @@
+//! Binary read path; must also pass with `--no-default-features`
+//! (no serde_json / flate2), see the feature matrix in CI. The shipped
+//! `ALL.bin` has the layout without districts.
+#![cfg(not(feature = "districts"))]
+
+use geodb_core::{
+    ArenaBackend, ArenaStr, GeoBackend, GeoDb, LoadOptions, LoadWarning, SmartItem, StandardBackend,
+};
+
+#[test]
+fn from_bytes_reads_bundled_binary_cache() {
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    let db = GeoDb::<StandardBackend>::load_binary(&path).expect("load binary cache");
+    assert!(db.stats().countries > 200);
+
+    // Round-trip through the in-memory representation
+    let bytes = db.to_bytes().expect("serialize");
+    let again = GeoDb::<StandardBackend>::from_bytes(&bytes).expect("deserialize");
+    assert_eq!(again.stats().cities, db.stats().cities);
+}
+
+#[test]
+fn from_bytes_rejects_garbage() {
+    assert!(GeoDb::<StandardBackend>::from_bytes(b"not a database").is_err());
+}
+
+#[test]
+fn load_options_limit_and_metrics() {
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+
+    let (db, metrics) =
+        GeoDb::<StandardBackend>::load_binary_with(&path, &LoadOptions::default()).expect("load");
+    assert_eq!(metrics.cities, db.stats().cities);
+    assert_eq!(metrics.bytes_read, std::fs::metadata(&path).unwrap().len());
+    assert!(metrics.total_time() >= metrics.deserialize_time);
+
+    // A limit below the artifact size must be rejected instead of allocating
+    let tiny = LoadOptions::default().with_max_bytes(1024);
+    assert!(GeoDb::<StandardBackend>::load_binary_with(&path, &tiny).is_err());
+}
+
+#[test]
+fn validating_load_reports_anomalies() {
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    let db = GeoDb::<StandardBackend>::load_binary(&path).expect("load binary cache");
+    assert!(db.validate().is_empty(), "{:?}", db.validate());
+
+    let mut broken = db.filtered_by_iso2(&["LI", "AT"]);
+    let duplicate = broken.countries[0].clone();
+    broken.countries.push(duplicate);
+    broken.countries[0].states[0].cities[0].name = String::new();
+    broken.countries[0].states[0].cities[1].latitude = Some(123.0);
+    let bytes = broken.to_bytes().unwrap();
+
+    let (quiet, _) =
+        GeoDb::<StandardBackend>::from_bytes_with(&bytes, &LoadOptions::default()).expect("load");
+    assert!(quiet.load_warnings().is_empty());
+
+    let options = LoadOptions::default().with_validation(true);
+    let (checked, _) = GeoDb::<StandardBackend>::from_bytes_with(&bytes, &options).expect("load");
+    let warnings = checked.load_warnings();
+    let iso2 = broken.countries[0].iso2().to_string();
+    assert!(warnings.contains(&LoadWarning::DuplicateIso2 {
+        iso2: iso2.clone(),
+        count: 2
+    }));
+    assert!(warnings
+        .iter()
+        .any(|w| matches!(w, LoadWarning::EmptyName { path } if path.starts_with(&iso2) && path.ends_with("/#0"))));
+    assert!(warnings
+        .iter()
+        .any(|w| matches!(w, LoadWarning::CoordinatesOutOfRange { lat, .. } if *lat == 123.0)));
+    assert!(warnings.iter().all(|w| !w.to_string().is_empty()));
+}
+
+#[test]
+fn indexed_artifacts_read_like_plain_ones() {
+    use geodb_core::indexed::{is_indexed, split_index};
+
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    let db = GeoDb::<StandardBackend>::load_binary(&path).expect("load binary cache");
+    let plain = db.to_bytes().expect("serialize");
+    let indexed = db.to_indexed_bytes().expect("serialize indexed");
+    assert!(is_indexed(&indexed) && !is_indexed(&plain));
+
+    // the table covers the plain payload, one entry per country
+    let (entries, payload) = split_index(&indexed).unwrap().expect("indexed");
+    assert_eq!(payload, plain.as_slice());
+    assert_eq!(entries.len(), db.country_count());
+    assert_eq!(entries[0].iso2, db.countries()[0].iso2());
+    assert_eq!(split_index(&plain).unwrap(), None);
+
+    let again = GeoDb::<StandardBackend>::from_bytes(&indexed).expect("deserialize");
+    assert_eq!(again.stats().cities, db.stats().cities);
+
+    // a table that doesn't match the payload is rejected
+    let mut truncated = indexed.clone();
+    truncated.truncate(indexed.len() - plain.len() + 100);
+    assert!(split_index(&truncated).is_err());
+    assert!(GeoDb::<StandardBackend>::from_bytes(&truncated).is_err());
+}
+
+/// Score and name of each smart search hit.
+fn hits<B: GeoBackend>(db: &GeoDb<B>, query: &str) -> Vec<(i32, String)> {
+    db.smart_search(query)
+        .iter()
+        .map(|h| {
+            let name = match h.item {
+                SmartItem::Country(c) => c.name(),
+                SmartItem::State { state, .. } => state.name(),
+                SmartItem::City { city, .. } => city.name(),
+            };
+            (h.score, name.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn arena_backend_reads_the_same_artifact() {
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    let bytes = std::fs::read(&path).unwrap();
+    let (standard, plain) =
+        GeoDb::<StandardBackend>::from_bytes_with(&bytes, &LoadOptions::default()).unwrap();
+    let options = LoadOptions::default().with_arena_chunk_size(1 << 20);
+    let (arena, metrics) = GeoDb::<ArenaBackend>::from_bytes_with(&bytes, &options).unwrap();
+
+    assert_eq!(plain.arena_bytes, 0);
+    assert!(metrics.arena_bytes > 0);
+    // a few chunks, not one allocation per string
+    assert!(metrics.arena_bytes < bytes.len() + (1 << 20));
+    assert_eq!(arena.stats().cities, standard.stats().cities);
+    // same binary format both ways (translations are hash maps, so compare
+    // sizes rather than bytes)
+    let written = arena.to_bytes().unwrap();
+    assert_eq!(written.len(), standard.to_bytes().unwrap().len());
+    let reread = GeoDb::<StandardBackend>::from_bytes(&written).unwrap();
+    assert_eq!(hits(&reread, "zürich"), hits(&standard, "zürich"));
+
+    let a = hits(&arena, "zürich");
+    assert!(!a.is_empty());
+    assert_eq!(a, hits(&standard, "zürich"));
+
+    // strings made outside a load own their chunk
+    let own = ArenaStr::from("Genf");
+    assert_eq!(own.as_str(), "Genf");
+    assert_eq!(own.clone(), own);
+    assert_eq!(ArenaStr::from("").as_str(), "");
+}
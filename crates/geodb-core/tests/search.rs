@@ -0,0 +1,549 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    edit_distance, equals_folded, fold_key, CityFilter, CityMetaIndex, CountryId, ExpansionTable,
+    GeoDb, NameMatch, ScoringProfile, SearchLanguage, SearchOptions, SearchSessionHints, SmartItem,
+    StandardBackend,
+};
+
+fn city_names<'a>(hits: &[geodb_core::SmartHit<'a, StandardBackend>]) -> Vec<&'a str> {
+    hits.iter()
+        .filter_map(|h| match h.item {
+            SmartItem::City { city, .. } => Some(city.name()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn expansion_table_normalizes_tokens() {
+    let table = ExpansionTable::new()
+        .with("st", "saint")
+        .with("Mt.", "mount");
+
+    assert_eq!(table.normalize("St. Louis"), "saint louis");
+    assert_eq!(table.normalize("  mt   Vernon "), "mount vernon");
+    assert_eq!(table.normalize("Stuttgart"), "stuttgart");
+
+    // Empty table keeps the plain lowercase behavior
+    assert_eq!(ExpansionTable::new().normalize(" St. Louis "), "st. louis");
+}
+
+#[test]
+fn smart_search_with_expansions_matches_both_spellings() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load filtered DB");
+
+    let plain = db.smart_search("saint louis");
+    assert!(!city_names(&plain).contains(&"St. Louis"));
+
+    let options =
+        SearchOptions::default().with_expansions(ExpansionTable::new().with("st", "saint"));
+    let expanded = db.smart_search_with("st louis", &options);
+    let names = city_names(&expanded);
+    assert!(names.contains(&"St. Louis"));
+    assert!(names.contains(&"Saint Louis"));
+}
+
+#[test]
+fn name_match_classification_and_order() {
+    assert_eq!(
+        NameMatch::classify("berlin", "berlin"),
+        Some(NameMatch::Exact)
+    );
+    assert_eq!(
+        NameMatch::classify("berlingen", "berlin"),
+        Some(NameMatch::Prefix)
+    );
+    assert_eq!(
+        NameMatch::classify("neuberlin", "berlin"),
+        Some(NameMatch::Contains)
+    );
+    assert_eq!(NameMatch::classify("bern", "berlin"), None);
+    assert!(NameMatch::Exact > NameMatch::Prefix && NameMatch::Prefix > NameMatch::Contains);
+}
+
+#[test]
+fn state_code_ranks_above_name_matches() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US", "CA"]).expect("load DB");
+    let hits = db.smart_search("CA");
+
+    // Canada by ISO2 first, then California by its state code
+    assert!(matches!(hits[0].item, SmartItem::Country(c) if c.iso2() == "CA"));
+    assert!(matches!(hits[1].item, SmartItem::State { state, .. } if state.name() == "California"));
+}
+
+#[test]
+fn trailing_iso2_boosts_cities_of_that_country() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US", "DE"]).expect("load DB");
+    let hits = db.smart_search("berlin de");
+
+    match hits[0].item {
+        SmartItem::City { city, country, .. } => {
+            assert_eq!(city.name(), "Berlin");
+            assert_eq!(country.iso2(), "DE");
+        }
+        _ => panic!("expected a city first"),
+    }
+    // Cities elsewhere are not pulled in by the hint
+    assert!(hits.iter().all(|h| match h.item {
+        SmartItem::City { country, .. } => country.iso2() == "DE",
+        _ => true,
+    }));
+}
+
+#[test]
+fn search_options_deserialize_with_defaults() {
+    let options: SearchOptions =
+        serde_json::from_str(r#"{ "expansions": { "St": "saint" } }"#).expect("options");
+    assert_eq!(options.expansions.normalize("st louis"), "saint louis");
+
+    let empty: SearchOptions = serde_json::from_str("{}").expect("defaults");
+    assert!(empty.expansions.is_empty());
+    assert!(empty.include_phone);
+}
+
+#[test]
+fn phone_hits_can_be_left_out() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["CH", "DE"]).expect("load DB");
+    let is_ch = |h: &geodb_core::SmartHit<'_, StandardBackend>| matches!(h.item, SmartItem::Country(c) if c.iso2() == "CH");
+
+    assert!(db.smart_search("41").iter().any(is_ch));
+    let options = SearchOptions::default().with_include_phone(false);
+    assert!(!db.smart_search_with("41", &options).iter().any(is_ch));
+    assert!(!db.smart_search_with("+41", &options).iter().any(is_ch));
+    assert!(db
+        .smart_search_with("Switzerland", &options)
+        .iter()
+        .any(is_ch));
+}
+
+fn hit_iso2<'a>(hit: &geodb_core::SmartHit<'a, StandardBackend>) -> &'a str {
+    match hit.item {
+        SmartItem::Country(c) => c.iso2(),
+        SmartItem::State { country, .. } | SmartItem::City { country, .. } => country.iso2(),
+        #[cfg(feature = "districts")]
+        SmartItem::District { country, .. } => country.iso2(),
+    }
+}
+
+#[test]
+fn boolean_filters_apply_during_search() {
+    let db = GeoDb::<StandardBackend>::load().expect("load DB");
+
+    let hits = db.smart_search("puerto rico");
+    assert!(hits.iter().any(|h| hit_iso2(h) == "PR"));
+
+    let options = SearchOptions::default()
+        .with_exclude_territories(true)
+        .with_must_have_coords(true);
+    let hits = db.smart_search_with("puerto rico", &options);
+    assert!(hits.iter().all(|h| hit_iso2(h) != "PR"));
+    assert!(hits.iter().all(|h| h.item.coordinates().is_some()));
+
+    let options = SearchOptions::default().with_min_population(5_000_000);
+    let hits = db.smart_search_with("guinea", &options);
+    let countries: Vec<&str> = hits
+        .iter()
+        .filter_map(|h| match h.item {
+            SmartItem::Country(c) => Some(c.iso2()),
+            _ => None,
+        })
+        .collect();
+    assert!(countries.contains(&"GN"));
+    assert!(
+        !countries.contains(&"GQ"),
+        "Equatorial Guinea is below the limit"
+    );
+}
+
+#[test]
+fn city_substring_filters_by_country_population_and_bounds() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "LI"]).expect("load DB");
+    let all = db.find_cities_by_substring("berg");
+
+    let de =
+        db.find_cities_by_substring_filtered("berg", &CityFilter::default().with_country("de"));
+    assert!(!de.is_empty() && de.len() < all.len());
+    assert!(de.iter().all(|(_, _, c)| c.iso2() == "DE"));
+
+    // LI is far below a million inhabitants
+    let big = CityFilter::default().with_population(Some(1_000_000), None);
+    let hits = db.find_cities_by_substring_filtered("berg", &big);
+    assert!(hits.iter().all(|(_, _, c)| c.iso2() != "LI"));
+    let small = CityFilter::default().with_population(None, Some(1_000_000));
+    let hits = db.find_cities_by_substring_filtered("", &small);
+    assert!(!hits.is_empty() && hits.iter().all(|(_, _, c)| c.iso2() == "LI"));
+
+    // roughly Bavaria
+    let bounds = CityFilter::default().with_bounds(47.2, 9.0, 50.6, 13.9);
+    let hits = db.find_cities_by_substring_filtered("berg", &bounds);
+    assert!(!hits.is_empty() && hits.len() < all.len());
+    for (city, _, _) in &hits {
+        let (lat, lng) = (city.latitude.unwrap(), city.longitude.unwrap());
+        assert!((47.2..=50.6).contains(&lat) && (9.0..=13.9).contains(&lng));
+    }
+
+    let limited =
+        db.find_cities_by_substring_filtered("berg", &CityFilter::default().with_limit(3));
+    assert_eq!(limited.len(), 3);
+}
+
+#[test]
+fn search_languages_fold_locale_specific_letters() {
+    assert_eq!(SearchLanguage::Simple.fold("İstanbul"), "İstanbul");
+    assert_eq!(SearchLanguage::Turkish.fold("İSTANBUL"), "istanbul");
+    assert_eq!(SearchLanguage::Turkish.fold("ıstanbul"), "istanbul");
+    // Unicode lowercase of "İ" leaves a combining dot behind
+    assert_eq!(
+        SearchLanguage::Turkish.fold(&"İstanbul".to_lowercase()),
+        "istanbul"
+    );
+    assert_eq!(SearchLanguage::German.fold("MÜNCHEN"), "muenchen");
+    assert_eq!(SearchLanguage::German.fold("Großenhain"), "grossenhain");
+    assert_eq!(SearchLanguage::Ascii.fold("São Paulo"), "sao paulo");
+    assert_eq!(SearchLanguage::Ascii.fold("Ağrı"), "agri");
+    assert_eq!(
+        SearchLanguage::from_code("DE"),
+        Some(SearchLanguage::German)
+    );
+    assert_eq!(SearchLanguage::from_code("xx"), None);
+
+    let options = SearchOptions::default()
+        .with_language(SearchLanguage::German)
+        .with_expansions(ExpansionTable::new().with("st", "sankt"));
+    assert_eq!(options.normalize("St. Pölten"), "sankt poelten");
+}
+
+#[test]
+fn fold_key_matches_the_engine_normalization() {
+    for language in [
+        SearchLanguage::Simple,
+        SearchLanguage::Ascii,
+        SearchLanguage::German,
+        SearchLanguage::Turkish,
+    ] {
+        let options = SearchOptions::default().with_language(language);
+        for text in ["  São Paulo ", "MÜNCHEN", "İstanbul", "St. Louis"] {
+            assert_eq!(
+                fold_key(text, language),
+                options.normalize(text),
+                "{text:?}"
+            );
+        }
+    }
+    assert!(equals_folded("Muenchen", "München", SearchLanguage::German));
+    assert!(!equals_folded(
+        "Muenchen",
+        "München",
+        SearchLanguage::Simple
+    ));
+    assert!(equals_folded(
+        " ISTANBUL",
+        "İstanbul",
+        SearchLanguage::Turkish
+    ));
+
+    assert_eq!("de".parse(), Ok(SearchLanguage::German));
+    assert_eq!("Turkish".parse(), Ok(SearchLanguage::Turkish));
+    assert_eq!("simple".parse(), Ok(SearchLanguage::Simple));
+    assert!("xx".parse::<SearchLanguage>().is_err());
+}
+
+#[test]
+fn smart_search_uses_the_configured_language() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["TR"]).expect("load filtered DB");
+    let is_istanbul = |hits: &[geodb_core::SmartHit<'_, StandardBackend>]| {
+        hits.iter().any(|h| match h.item {
+            SmartItem::State { state, .. } => state.name() == "İstanbul",
+            _ => false,
+        })
+    };
+    assert!(!is_istanbul(&db.smart_search("istanbul")));
+
+    let turkish = SearchOptions::default().with_language(SearchLanguage::Turkish);
+    assert!(is_istanbul(&db.smart_search_with("istanbul", &turkish)));
+    assert!(is_istanbul(&db.smart_search_with("ISTANBUL", &turkish)));
+
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load filtered DB");
+    let german = SearchOptions::default().with_language(SearchLanguage::German);
+    assert!(city_names(&db.smart_search_with("Muenster", &german)).contains(&"Münster"));
+    assert!(city_names(&db.smart_search_with("duesseldorf", &german)).contains(&"Düsseldorf"));
+
+    let options: SearchOptions = serde_json::from_str(r#"{"language": "tr"}"#).unwrap();
+    assert_eq!(options.language, SearchLanguage::Turkish);
+}
+
+#[test]
+fn alias_matches_rank_below_name_matches_and_say_so() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["CH"]).expect("load DB");
+    db.apply_city_meta(&CityMetaIndex::load_default().expect("load city_meta.json"));
+    fn geneva<'a>(
+        hits: Vec<geodb_core::SmartHit<'a, StandardBackend>>,
+    ) -> Option<(i32, Option<&'a str>)> {
+        hits.into_iter()
+            .find(|h| matches!(h.item, SmartItem::City { city, .. } if city.name() == "Geneva"))
+            .map(|h| (h.score, h.alias))
+    }
+
+    let by_name = geneva(db.smart_search("Geneva")).expect("Geneva by name");
+    let by_alias = geneva(db.smart_search("genf")).expect("Geneva by alias");
+    assert_eq!(by_name.1, None);
+    assert_eq!(by_alias.1, Some("Genf"));
+    assert!(by_alias.0 < by_name.0);
+
+    let tie = SearchOptions::default().with_scoring(ScoringProfile {
+        alias_penalty: 0,
+        ..ScoringProfile::default()
+    });
+    assert_eq!(
+        geneva(db.smart_search_with("genf", &tie)),
+        Some((by_name.0, Some("Genf")))
+    );
+
+    let options: SearchOptions =
+        serde_json::from_str(r#"{"scoring": {"alias_penalty": 12}}"#).unwrap();
+    assert_eq!(options.scoring.alias_penalty, 12);
+    assert_eq!(options.scoring.city, ScoringProfile::default().city);
+}
+
+#[test]
+fn smart_search_pages_through_hits() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let labels = |hits: Vec<geodb_core::SmartHit<'_, StandardBackend>>| -> Vec<String> {
+        hits.iter()
+            .map(|h| serde_json::to_string(&h.item.to_view()).unwrap())
+            .collect()
+    };
+    let all = labels(db.smart_search("berg"));
+    assert!(all.len() > 20);
+
+    let page = |offset| {
+        let options = SearchOptions::default().with_limit(10).with_offset(offset);
+        labels(db.smart_search_with("berg", &options))
+    };
+    assert_eq!(page(0), all[..10]);
+    assert_eq!(page(10), all[10..20]);
+    assert!(page(all.len()).is_empty());
+
+    let strong = SearchOptions::default().with_min_score(40);
+    let hits = db.smart_search_with("berg", &strong);
+    assert!(!hits.is_empty());
+    assert!(hits.iter().all(|h| h.score >= 40));
+    assert!(hits.len() < db.smart_search("berg").len());
+}
+
+#[test]
+fn city_filter_offset_skips_matches() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let all = db.find_cities_by_substring("berg");
+    let filter = CityFilter::default().with_offset(5).with_limit(3);
+    let page = db.find_cities_by_substring_filtered("berg", &filter);
+    let names = |cities: &[(&geodb_core::City<StandardBackend>, _, _)]| {
+        cities
+            .iter()
+            .map(|(c, _, _)| c.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&page), names(&all[5..8]));
+}
+
+#[test]
+fn concatenated_queries_find_multi_word_names() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load DB");
+    for (joined, spaced) in [("newyork", "new york"), ("sanfrancisco", "san francisco")] {
+        let names = city_names(&db.smart_search(joined));
+        assert!(
+            names.iter().any(|n| n.to_lowercase().starts_with(spaced)),
+            "{joined}: {names:?}"
+        );
+        // same hits, same scores as the spaced query
+        let scores = |q| {
+            db.smart_search(q)
+                .iter()
+                .map(|h| (h.score, serde_json::to_string(&h.item.to_view()).unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(scores(joined), scores(spaced), "{joined}");
+    }
+
+    // only queries without hits are retried
+    assert!(city_names(&db.smart_search("york"))
+        .iter()
+        .all(|n| n.to_lowercase().contains("york")));
+    let off = SearchOptions::default().with_word_segmentation(false);
+    assert!(db.smart_search_with("newyork", &off).is_empty());
+}
+
+#[test]
+fn edit_distance_counts_swaps_once_and_stops_at_max() {
+    assert_eq!(edit_distance("zurich", "zurich", 2), Some(0));
+    assert_eq!(edit_distance("berln", "berlin", 2), Some(1));
+    assert_eq!(edit_distance("zurihc", "zurich", 2), Some(1));
+    assert_eq!(edit_distance("kitten", "sitting", 3), Some(3));
+    assert_eq!(edit_distance("kitten", "sitting", 2), None);
+    assert_eq!(edit_distance("a", "abcd", 2), None);
+    assert_eq!(edit_distance("", "ab", 2), Some(2));
+}
+
+#[test]
+fn fuzzy_search_tolerates_typos() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "CH"]).expect("load DB");
+    assert!(db.smart_search("berln").is_empty());
+    assert!(db.smart_search("zurihc").is_empty());
+
+    let ascii = SearchOptions {
+        language: SearchLanguage::Ascii,
+        ..SearchOptions::default()
+    };
+    for (typo, name, iso2) in [("berln", "Berlin", "DE"), ("zurihc", "Zürich", "CH")] {
+        let hits = db.smart_search_with(typo, &ascii.clone().with_max_edit_distance(2));
+        let city = |hits: &[geodb_core::SmartHit<'_, StandardBackend>]| {
+            hits.iter()
+                .find(|h| city_names(std::slice::from_ref(h)) == [name] && hit_iso2(h) == iso2)
+                .map(|h| h.score)
+        };
+        let score = city(&hits).unwrap_or_else(|| panic!("{typo}: {:?}", city_names(&hits)));
+        // a typo costs the penalty, so exact matches keep ranking above
+        let exact = city(&db.smart_search_with(name, &ascii)).unwrap();
+        assert_eq!(score, exact - ascii.scoring.typo_penalty);
+    }
+    assert!(!city_names(&db.smart_search_fuzzy("berln", 1)).is_empty());
+
+    // short queries get no typos at all, medium ones only one
+    assert!(db.smart_search_fuzzy("bx", 2).is_empty());
+    // "bonn" is two edits from "bern"
+    assert!(!city_names(&db.smart_search_fuzzy("bern", 2)).contains(&"Bonn"));
+    assert!(city_names(&db.smart_search_fuzzy("bonm", 2)).contains(&"Bonn"));
+    assert!(db.smart_search_fuzzy("brlni", 2).is_empty());
+}
+
+#[test]
+fn session_hints_boost_recent_cities_and_preferred_countries() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["FR", "US"]).expect("load DB");
+    let plain = db.smart_search("paris");
+    let us_paris = plain
+        .iter()
+        .find_map(|h| match h.item {
+            SmartItem::City { city, country, .. } if country.iso2() == "US" => Some(city),
+            _ => None,
+        })
+        .expect("a Paris in the US");
+    assert_ne!(hit_iso2(&plain[0]), "US");
+
+    let id = db.city_id(us_paris).unwrap();
+    let recent = SearchOptions::default().with_session(SearchSessionHints {
+        recent_city_ids: vec![id],
+        ..SearchSessionHints::default()
+    });
+    let hits = db.smart_search_with("paris", &recent);
+    assert_eq!(hits.len(), plain.len(), "hints never add hits");
+    // first among the cities; the boost is too small to pass the state "Paris"
+    let first_city = hits.iter().find_map(|h| match h.item {
+        SmartItem::City { city, .. } => Some(city),
+        _ => None,
+    });
+    assert!(std::ptr::eq(first_city.unwrap(), us_paris));
+
+    let us = SearchOptions::default().with_session(SearchSessionHints {
+        preferred_countries: vec![CountryId::new("us").unwrap()],
+        ..SearchSessionHints::default()
+    });
+    let boost = us.scoring.preferred_country_boost;
+    let hits = db.smart_search_with("paris", &us);
+    let view = |h: &geodb_core::SmartHit<'_, StandardBackend>| {
+        serde_json::to_string(&h.item.to_view()).unwrap()
+    };
+    for hit in &hits {
+        let before = plain.iter().find(|p| view(p) == view(hit)).unwrap();
+        let expected = before.score + if hit_iso2(hit) == "US" { boost } else { 0 };
+        assert_eq!(hit.score, expected);
+    }
+    // among cities matching equally well, US ones come first now
+    let exact_cities: Vec<_> = hits
+        .iter()
+        .filter(|h| matches!(h.item, SmartItem::City { city, .. } if city.name() == "Paris"))
+        .map(hit_iso2)
+        .collect();
+    assert_eq!(exact_cities.first(), Some(&"US"));
+}
+
+#[test]
+fn autocomplete_lists_prefix_matches_biggest_countries_first() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH", "US"])
+        .expect("load DB");
+    let hits = db.autocomplete("ber", 50);
+    assert!(!hits.is_empty() && hits.len() <= 50);
+    fn name<'a>(h: &geodb_core::SmartHit<'a, StandardBackend>) -> &'a str {
+        match h.item {
+            SmartItem::Country(c) => c.name(),
+            SmartItem::State { state, .. } => state.name(),
+            SmartItem::City { city, .. } => city.name(),
+            #[cfg(feature = "districts")]
+            SmartItem::District { district, .. } => district.name(),
+        }
+    }
+    // prefix matches only: no "Heidelberg"
+    for hit in &hits {
+        let matched = hit.alias.unwrap_or_else(|| name(hit));
+        assert!(matched.to_lowercase().starts_with("ber"), "{matched}");
+    }
+    assert!(hits.iter().any(|h| name(h) == "Berlin"));
+    assert!(db.smart_search("ber").len() > hits.len());
+    // ranked by score after the countries (none start with "ber" here)
+    assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+    assert_eq!(db.autocomplete("ber", 3).len(), 3);
+    assert!(db.autocomplete("ber", 0).is_empty());
+    assert!(db.autocomplete("  ", 5).is_empty());
+
+    // countries by population, then everything else
+    let hits = db.autocomplete("a", 100);
+    let countries: Vec<_> = hits
+        .iter()
+        .map_while(|h| match h.item {
+            SmartItem::Country(c) => Some(c.iso2()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(countries, ["AT"]);
+    let hits = db.autocomplete("s", 100);
+    assert!(matches!(hits[0].item, SmartItem::Country(c) if c.iso2() == "CH"));
+
+    // entries that would look the same in a dropdown show up once
+    let hits = db.autocomplete("springfield", 1000);
+    let mut shown: Vec<_> = hits
+        .iter()
+        .filter_map(|h| match h.item {
+            SmartItem::City { state, city, .. } => Some((city.name(), state.name())),
+            _ => None,
+        })
+        .collect();
+    let len = shown.len();
+    shown.sort();
+    shown.dedup();
+    assert_eq!(shown.len(), len);
+}
+
+#[test]
+fn tiered_search_returns_countries_and_states_before_cities() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["FR", "US"]).expect("load DB");
+    let options = SearchOptions::default().with_limit(20);
+
+    let mut tiered = db.smart_search_tiered("paris", &options);
+    assert!(!tiered.is_complete());
+    assert!(!tiered.hits().is_empty(), "the state Paris");
+    assert!(city_names(tiered.hits()).is_empty());
+
+    let view = |hits: &[geodb_core::SmartHit<'_, StandardBackend>]| {
+        hits.iter()
+            .map(|h| (h.score, serde_json::to_string(&h.item.to_view()).unwrap()))
+            .collect::<Vec<_>>()
+    };
+    let full = db.smart_search_with("paris", &options);
+    assert_eq!(view(tiered.load_more()), view(&full));
+    assert!(tiered.is_complete());
+    assert!(!city_names(tiered.hits()).is_empty());
+    assert_eq!(tiered.into_hits().len(), full.len());
+
+    let only_top = options.with_include_cities(false);
+    assert!(city_names(&db.smart_search_with("paris", &only_top)).is_empty());
+}
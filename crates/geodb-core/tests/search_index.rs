@@ -0,0 +1,113 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    CityMetaIndex, ExpansionTable, GeoDb, SearchIndex, SearchLanguage, SearchOptions, SmartHit,
+    SmartItem, StandardBackend,
+};
+
+/// Comparable summary of a result list.
+fn summary<'a>(hits: &[SmartHit<'a, StandardBackend>]) -> Vec<(i32, Option<&'a str>, String)> {
+    hits.iter()
+        .map(|h| {
+            let item = match h.item {
+                SmartItem::Country(c) => c.iso2().to_string(),
+                SmartItem::State { country, state } => {
+                    format!("{}/{}", country.iso2(), state.name())
+                }
+                SmartItem::City {
+                    country,
+                    state,
+                    city,
+                } => format!("{}/{}/{}", country.iso2(), state.name(), city.name()),
+                #[cfg(feature = "districts")]
+                SmartItem::District {
+                    country,
+                    state,
+                    district,
+                } => format!("{}/{}/{}", country.iso2(), state.name(), district.name()),
+            };
+            (h.score, h.alias, item)
+        })
+        .collect()
+}
+
+fn db() -> GeoDb<StandardBackend> {
+    let mut db =
+        GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH"]).expect("load DB");
+    db.apply_city_meta(&CityMetaIndex::load_default().expect("load city_meta.json"));
+    db
+}
+
+#[test]
+fn indexed_search_matches_the_full_scan() {
+    let db = db();
+    let options = SearchOptions::default();
+    let index = db.search_index(&options);
+    assert!(!index.is_empty());
+
+    for query in [
+        "berlin",
+        "Berlin DE",
+        "genf",
+        "de",
+        "BY",
+        "bayern",
+        "stadt",
+        "49",
+        "zz",
+        "frankfurtammain",
+        "sanktgallen",
+    ] {
+        let full = db.smart_search_with(query, &options);
+        let indexed = db.smart_search_indexed(query, &options, &index);
+        assert_eq!(summary(&indexed), summary(&full), "query {query:?}");
+    }
+    assert!(db.smart_search_indexed("", &options, &index).is_empty());
+
+    let filtered = options
+        .clone()
+        .with_include_phone(false)
+        .with_must_have_coords(true);
+    assert_eq!(
+        summary(&db.smart_search_indexed("wien", &filtered, &index)),
+        summary(&db.smart_search_with("wien", &filtered)),
+    );
+}
+
+#[test]
+fn index_built_for_other_options_falls_back_to_the_full_scan() {
+    let db = db();
+    let german = SearchOptions::default().with_language(SearchLanguage::German);
+    let index = db.search_index(&SearchOptions::default());
+    assert!(!index.supports(&german));
+    assert_eq!(
+        summary(&db.smart_search_indexed("muenchen", &german, &index)),
+        summary(&db.smart_search_with("muenchen", &german)),
+    );
+
+    let expanded =
+        SearchOptions::default().with_expansions(ExpansionTable::new().with("st", "sankt"));
+    assert!(!index.supports(&expanded));
+    assert!(db.search_index(&expanded).supports(&expanded));
+
+    // typos can hit names that don't contain the query
+    let fuzzy = SearchOptions::default().with_max_edit_distance(2);
+    assert!(!index.supports(&fuzzy));
+    let hits = db.smart_search_indexed("muenchn", &fuzzy, &index);
+    assert!(!hits.is_empty());
+    assert_eq!(
+        summary(&hits),
+        summary(&db.smart_search_with("muenchn", &fuzzy))
+    );
+}
+
+#[test]
+fn index_survives_a_bytes_round_trip() {
+    let db = db();
+    let options = SearchOptions::default();
+    let index = db.search_index(&options);
+
+    let decoded = SearchIndex::from_bytes(&index.to_bytes().unwrap()).unwrap();
+    assert_eq!(decoded, index);
+    assert!(SearchIndex::from_bytes(b"garbage").is_err());
+}
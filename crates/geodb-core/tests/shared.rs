@@ -0,0 +1,50 @@
+//! Also runs with `--no-default-features --features shared`: the fixture
+//! comes from the bundled `ALL.bin`, which has the layout without
+//! districts.
+#![cfg(all(feature = "shared", unix, not(feature = "districts")))]
+
+use geodb_core::shared::{SharedDb, SharedHandle};
+use geodb_core::{GeoDb, StandardBackend};
+
+#[test]
+fn shared_db_is_mapped_through_its_handle() {
+    let bundled = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    let db = GeoDb::<StandardBackend>::load_binary(&bundled)
+        .expect("load DB")
+        .filtered_by_iso2(&["LI", "CH"]);
+    let dir = std::env::temp_dir().join(format!("geodb-shared-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let shared = SharedDb::create_in(&dir, &db).expect("create");
+    // nothing is left in the directory; the descriptor keeps the data
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+    std::fs::remove_dir(&dir).unwrap();
+
+    let handle = shared.handle();
+    assert_eq!(handle.len, shared.as_bytes().len() as u64);
+    let parsed: SharedHandle = handle.to_string().parse().unwrap();
+    assert_eq!(parsed, handle);
+
+    // what a forked worker does
+    let worker = SharedDb::open(parsed).expect("open");
+    assert_ne!(worker.handle().fd, handle.fd);
+    assert_eq!(worker.as_bytes(), shared.as_bytes());
+    let view = worker.view().unwrap();
+    let (stats, expected) = (view.stats(), db.stats());
+    assert_eq!(
+        (stats.countries, stats.states, stats.cities),
+        (expected.countries, expected.states, expected.cities)
+    );
+    let li = view.country("li").expect("LI");
+    assert_eq!(li.name(), "Liechtenstein");
+
+    let wrong = SharedHandle {
+        len: handle.len + 1,
+        ..handle
+    };
+    assert!(SharedDb::open(wrong).is_err());
+    assert!("5".parse::<SharedHandle>().is_err());
+}
@@ -0,0 +1,82 @@
+#![cfg(all(feature = "signed", feature = "json"))]
+
+use geodb_core::signed::{self, SigningKey, VerifyOptions};
+use geodb_core::{GeoDb, GeoError, StandardBackend};
+
+#[test]
+fn signed_artifact_round_trips_with_trusted_key() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let artifact = db.to_signed_bytes(Some(&key)).unwrap();
+    assert!(signed::is_sealed(&artifact));
+
+    let options = VerifyOptions::default().with_trusted_key(&key.verifying_key());
+    let loaded = GeoDb::<StandardBackend>::from_signed_bytes(&artifact, &options).unwrap();
+    assert_eq!(loaded.stats().cities, db.stats().cities);
+
+    // Integrity-only check needs no key
+    assert!(signed::open(&artifact, &VerifyOptions::default()).is_ok());
+}
+
+#[test]
+fn tampered_or_untrusted_artifacts_are_rejected() {
+    let payload = b"not really a database";
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let other = SigningKey::from_bytes(&[9u8; 32]);
+    let trusting = VerifyOptions::default().with_trusted_key(&key.verifying_key());
+
+    let mut tampered = signed::seal(payload, Some(&key));
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        signed::open(&tampered, &trusting),
+        Err(GeoError::ChecksumMismatch { .. })
+    ));
+
+    let by_other = signed::seal(payload, Some(&other));
+    assert!(matches!(
+        signed::open(&by_other, &trusting),
+        Err(GeoError::Signature(_))
+    ));
+
+    let unsigned = signed::seal(payload, None);
+    assert!(matches!(
+        signed::open(&unsigned, &trusting),
+        Err(GeoError::Signature(_))
+    ));
+    assert_eq!(
+        signed::open(&unsigned, &VerifyOptions::default()).unwrap(),
+        payload
+    );
+
+    let wrong_hash = VerifyOptions::default().with_expected_sha256(&"0".repeat(64));
+    assert!(matches!(
+        signed::open(&unsigned, &wrong_hash),
+        Err(GeoError::ChecksumMismatch { .. })
+    ));
+
+    assert!(matches!(
+        signed::open(payload, &VerifyOptions::default()),
+        Err(GeoError::InvalidData(_))
+    ));
+}
+
+#[test]
+fn verify_options_deserialize_keys_from_config() {
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let hex: String = key
+        .verifying_key()
+        .as_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    let options: VerifyOptions =
+        serde_json::from_str(&format!(r#"{{ "trusted_keys": ["{hex}"] }}"#)).unwrap();
+    let artifact = signed::seal(b"payload", Some(&key));
+    assert_eq!(signed::open(&artifact, &options).unwrap(), b"payload");
+
+    let bad: VerifyOptions = serde_json::from_str(r#"{ "trusted_keys": ["abc"] }"#).unwrap();
+    assert!(matches!(
+        signed::open(&artifact, &bad),
+        Err(GeoError::InvalidData(_))
+    ));
+}
@@ -0,0 +1,255 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    haversine_km, vincenty_km, ClusterMethod, ClusterOptions, DistanceOptions, DistanceUnit,
+    EarthModel, GeoDb, SearchOptions, SmartItem, StandardBackend,
+};
+
+#[test]
+fn haversine_matches_known_distance() {
+    // Berlin → Paris is about 878 km
+    let d = haversine_km(52.52, 13.405, 48.8566, 2.3522);
+    assert!((d - 878.0).abs() < 5.0, "{d}");
+    assert_eq!(haversine_km(10.0, 20.0, 10.0, 20.0), 0.0);
+}
+
+#[test]
+fn smart_search_near_orders_by_distance_within_radius() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load DB");
+
+    // Springfield, Illinois area
+    let hits = db.smart_search_near("springfield", 39.80, -89.64, 400.0);
+    assert!(!hits.is_empty());
+    assert!(hits.iter().all(|h| h.distance_km <= 400.0));
+    assert!(hits
+        .windows(2)
+        .all(|w| w[0].distance_km <= w[1].distance_km));
+
+    match hits[0].hit.item {
+        SmartItem::City { state, .. } => assert_eq!(state.name(), "Illinois"),
+        _ => panic!("expected a city first"),
+    }
+}
+
+#[test]
+fn vincenty_and_units_match_reference_values() {
+    // Flinders Peak → Buninyong, Vincenty's classic test: 54 972.271 m
+    let d = vincenty_km(-37.951_033_4, 144.424_867_9, -37.652_821_4, 143.926_495_3);
+    assert!((d - 54.972_271).abs() < 1e-5, "{d}");
+    assert_eq!(vincenty_km(48.0, 11.0, 48.0, 11.0), 0.0);
+    // Nearly antipodal points still yield a finite distance
+    assert!(vincenty_km(0.0, 0.0, 0.5, 179.7).is_finite());
+
+    let wgs84 = DistanceOptions::default().with_model(EarthModel::Wgs84);
+    let km = wgs84.distance(52.52, 13.405, 48.8566, 2.3522);
+    let mi = wgs84
+        .with_unit(DistanceUnit::Miles)
+        .distance(52.52, 13.405, 48.8566, 2.3522);
+    assert!((km / mi - 1.609_344).abs() < 1e-9);
+    assert!((km - haversine_km(52.52, 13.405, 48.8566, 2.3522)).abs() < 5.0);
+
+    assert_eq!(
+        "NM".parse::<DistanceUnit>(),
+        Ok(DistanceUnit::NauticalMiles)
+    );
+    assert!((DistanceUnit::NauticalMiles.to_km(1.0) - 1.852).abs() < 1e-12);
+}
+
+#[test]
+fn smart_search_near_in_uses_radius_unit() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["US"]).expect("load DB");
+    let miles = DistanceOptions::default()
+        .with_unit(DistanceUnit::Miles)
+        .with_model(EarthModel::Wgs84);
+
+    let hits = db.smart_search_near_in(
+        "springfield",
+        39.80,
+        -89.64,
+        300.0,
+        &miles,
+        &SearchOptions::default(),
+    );
+    assert!(!hits.is_empty());
+    assert!(hits
+        .iter()
+        .all(|h| h.distance(DistanceUnit::Miles) <= 300.0));
+    // 300 mi reach further than 300 km
+    let in_km = db.smart_search_near("springfield", 39.80, -89.64, 300.0);
+    assert!(hits.len() > in_km.len());
+}
+
+#[test]
+fn cluster_cities_groups_nearby_cities() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let with_coords = db
+        .iter_cities()
+        .filter(|(c, _, _)| c.latitude.is_some() && c.longitude.is_some())
+        .count();
+
+    let clusters = db.city_clusters(&ClusterOptions::new(25.0));
+    assert_eq!(clusters.iter().map(|c| c.len()).sum::<usize>(), with_coords);
+    assert!(clusters.len() < with_coords);
+    assert!(clusters.windows(2).all(|w| w[0].len() >= w[1].len()));
+
+    // Berlin's cluster is labelled with the capital
+    let berlin = clusters
+        .iter()
+        .find(|c| c.members.iter().any(|(city, _, _)| city.name() == "Berlin"))
+        .expect("Berlin clustered");
+    assert_eq!(berlin.representative.0.name(), "Berlin");
+    let (lat, lng) = berlin.center;
+    assert!(haversine_km(lat, lng, 52.52, 13.405) < 25.0, "{lat},{lng}");
+    // leaders are spaced by at least the radius
+    let leaders: Vec<_> = clusters
+        .iter()
+        .take(50)
+        .map(|c| c.representative.0)
+        .collect();
+    for (i, a) in leaders.iter().enumerate() {
+        for b in &leaders[i + 1..] {
+            let d = haversine_km(
+                a.latitude.unwrap(),
+                a.longitude.unwrap(),
+                b.latitude.unwrap(),
+                b.longitude.unwrap(),
+            );
+            assert!(d > 25.0, "{} / {}: {d}", a.name(), b.name());
+        }
+    }
+
+    // DBSCAN: a larger min_points only splits clusters
+    let dbscan = ClusterOptions::new(5.0).with_method(ClusterMethod::Dbscan);
+    let linked = db.city_clusters(&dbscan);
+    let strict = db.city_clusters(&dbscan.with_min_points(10));
+    assert_eq!(linked.iter().map(|c| c.len()).sum::<usize>(), with_coords);
+    assert!(strict.len() >= linked.len());
+}
+
+#[test]
+fn find_duplicate_cities_requires_same_name_and_proximity() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    assert!(db.find_duplicate_cities(0.0).is_empty());
+
+    let state = &mut db.countries[0].states[0];
+    let mut copy = state.cities[0].clone();
+    let name = copy.name().to_string();
+    copy.name = name.to_uppercase();
+    state.cities.push(copy);
+
+    let dups = db.find_duplicate_cities(0.0);
+    assert_eq!(dups.len(), 1);
+    assert_eq!(dups[0].len(), 2);
+    assert!(dups[0]
+        .iter()
+        .all(|(c, _, _)| c.name().eq_ignore_ascii_case(&name)));
+}
+
+/// Nearest city within `radius_km` by scanning every city; ties go to the
+/// first in database order.
+fn nearest_by_scan(
+    db: &GeoDb<StandardBackend>,
+    lat: f64,
+    lng: f64,
+    radius_km: f64,
+) -> Option<&geodb_core::City<StandardBackend>> {
+    let mut best: Option<(f64, &geodb_core::City<StandardBackend>)> = None;
+    for (city, _, _) in db.iter_cities() {
+        let (Some(city_lat), Some(city_lng)) = (city.latitude, city.longitude) else {
+            continue;
+        };
+        let d = haversine_km(lat, lng, city_lat, city_lng);
+        if d <= radius_km && best.is_none_or(|(b, _)| d < b) {
+            best = Some((d, city));
+        }
+    }
+    best.map(|(_, city)| city)
+}
+
+#[test]
+fn nearest_city_index_agrees_with_a_full_scan() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT"]).expect("load DB");
+    let index = db.city_index();
+    assert!(!index.is_empty());
+
+    let munich = index
+        .find_nearest_city(48.137, 11.575, 25.0)
+        .expect("city near Munich");
+    assert_eq!(munich.city.0.name(), "Munich");
+    assert!(munich.distance_km < 5.0, "{}", munich.distance_km);
+
+    // middle of the Atlantic
+    assert!(index.find_nearest_city(40.0, -30.0, 500.0).is_none());
+    assert!(db.find_nearest_city(40.0, -30.0, 500.0).is_none());
+
+    for lat in (46..=55).map(f64::from) {
+        for lng in (6..=17).map(|x| f64::from(x) + 0.3) {
+            let scan = nearest_by_scan(&db, lat, lng, 50.0);
+            let indexed = db.find_nearest_city(lat, lng, 50.0);
+            assert_eq!(
+                scan.map(std::ptr::from_ref),
+                indexed.map(|n| std::ptr::from_ref(n.city.0)),
+                "({lat}, {lng})"
+            );
+        }
+    }
+}
+
+#[test]
+fn cities_in_bbox_match_a_full_scan() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "US"]).expect("load DB");
+    let names = |cities: Vec<geodb_core::CityRef<'_, StandardBackend>>| {
+        cities
+            .into_iter()
+            .map(|(city, _, _)| city.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    let scan = |min_lat: f64, min_lng: f64, max_lat: f64, max_lng: f64| {
+        let in_lng = |lng: f64| {
+            if min_lng <= max_lng {
+                (min_lng..=max_lng).contains(&lng)
+            } else {
+                lng >= min_lng || lng <= max_lng
+            }
+        };
+        names(
+            db.iter_cities()
+                .filter(|(city, _, _)| match (city.latitude, city.longitude) {
+                    (Some(lat), Some(lng)) => (min_lat..=max_lat).contains(&lat) && in_lng(lng),
+                    _ => false,
+                })
+                .collect(),
+        )
+    };
+
+    // Berlin and surroundings
+    let berlin = names(db.cities_in_bbox(52.3, 13.0, 52.7, 13.8));
+    assert!(berlin.contains(&"Berlin".to_string()), "{berlin:?}");
+    assert!(!berlin.contains(&"Munich".to_string()));
+    assert_eq!(berlin, scan(52.3, 13.0, 52.7, 13.8));
+
+    // across the antimeridian: the Aleutians
+    let aleutians = names(db.cities_in_bbox(50.0, 170.0, 56.0, -165.0));
+    assert!(!aleutians.is_empty());
+    assert_eq!(aleutians, scan(50.0, 170.0, 56.0, -165.0));
+
+    assert!(db.cities_in_bbox(10.0, 0.0, 5.0, 1.0).is_empty());
+}
+
+#[test]
+fn spatial_index_follows_overrides() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let vaduz = db.find_nearest_city(47.141, 9.521, 5.0).expect("Vaduz");
+    assert_eq!(vaduz.city.0.name(), "Vaduz");
+
+    // move Vaduz to the middle of the Atlantic
+    let overrides: geodb_core::Overrides = serde_json::from_str(
+        r#"{ "cities": [{ "iso2": "LI", "state": "Vaduz", "city": "Vaduz",
+            "latitude": 40.0, "longitude": -30.0 }] }"#,
+    )
+    .unwrap();
+    assert!(db.apply_overrides(&overrides).is_empty());
+    let moved = db.find_nearest_city(40.0, -30.0, 5.0).expect("moved Vaduz");
+    assert_eq!(moved.city.0.name(), "Vaduz");
+    assert!(db.cities_in_bbox(47.13, 9.51, 47.15, 9.53).is_empty());
+}
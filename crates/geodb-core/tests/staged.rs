@@ -0,0 +1,79 @@
+#![cfg(feature = "json")]
+
+use geodb_core::staged::{CityChunk, CityChunks};
+use geodb_core::{GeoDb, GeoError, SmartItem, StandardBackend};
+
+fn dach() -> GeoDb<StandardBackend> {
+    GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "AT", "CH"]).expect("load DB")
+}
+
+#[test]
+fn skeleton_and_chunks_restore_the_database() {
+    let db = dach();
+    let (skeleton, cities) = db.to_staged_bytes().unwrap();
+    assert!(skeleton.len() * 10 < db.to_bytes().unwrap().len());
+
+    let mut staged = GeoDb::<StandardBackend>::from_bytes(&skeleton).unwrap();
+    let stats = staged.stats();
+    assert_eq!(stats.countries, db.stats().countries);
+    assert_eq!(stats.states, db.stats().states);
+    assert_eq!(stats.cities, 0);
+    assert!(staged
+        .smart_search("Bavaria")
+        .iter()
+        .any(|h| matches!(h.item, SmartItem::State { state, .. } if state.name() == "Bavaria")));
+
+    let mut chunks = CityChunks::<StandardBackend>::new(&cities);
+    let first = chunks.next().unwrap().unwrap();
+    staged.attach_cities(first).unwrap();
+    assert_eq!(
+        staged.countries()[0].states().len(),
+        db.countries()[0].states().len()
+    );
+    assert!(staged.stats().cities > 0);
+    assert_eq!(staged.countries()[1].states()[0].cities().len(), 0);
+
+    for chunk in chunks {
+        staged.attach_cities(chunk.unwrap()).unwrap();
+    }
+    // (not byte-compared: decoding reorders the translation maps)
+    let names = |db: &GeoDb<StandardBackend>| -> Vec<Vec<Vec<String>>> {
+        db.countries()
+            .iter()
+            .map(|c| {
+                c.states()
+                    .iter()
+                    .map(|s| s.cities().iter().map(|c| c.name().to_string()).collect())
+                    .collect()
+            })
+            .collect()
+    };
+    assert_eq!(names(&staged), names(&db));
+}
+
+#[test]
+fn mismatched_or_truncated_chunks_are_rejected() {
+    let (skeleton, mut chunks) = dach().split_cities();
+    let mut db = skeleton.clone();
+
+    let wrong = CityChunk {
+        country: 0,
+        states: Vec::new(),
+    };
+    assert!(matches!(
+        db.attach_cities(wrong),
+        Err(GeoError::InvalidData(_))
+    ));
+    let unknown = CityChunk {
+        country: 99,
+        states: chunks.pop().unwrap().states,
+    };
+    assert!(db.attach_cities(unknown).is_err());
+    assert_eq!(db.stats().cities, 0);
+
+    let (_, cities) = dach().to_staged_bytes().unwrap();
+    let truncated = &cities[..cities.len() - 1];
+    let results: Vec<_> = CityChunks::<StandardBackend>::new(truncated).collect();
+    assert!(results.last().unwrap().is_err());
+    assert!(results[..results.len() - 1].iter().all(Result::is_ok));
+}
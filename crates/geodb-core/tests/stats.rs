@@ -0,0 +1,68 @@
+#![cfg(feature = "json")]
+
+use geodb_core::stats::STATS_SCHEMA;
+use geodb_core::{ChangeSet, Changelog, DatasetSource, DatasetStats, GeoDb, StandardBackend};
+
+fn dataset_path() -> std::path::PathBuf {
+    GeoDb::<StandardBackend>::default_data_dir()
+        .join(GeoDb::<StandardBackend>::default_dataset_filename())
+}
+
+#[test]
+fn report_has_counts_source_and_build_date() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let source = DatasetSource::from_path(dataset_path());
+    assert_eq!(
+        source.file.as_deref(),
+        Some("countries+states+cities.json.gz")
+    );
+    let status = GeoDb::<StandardBackend>::cache_status(dataset_path(), Some(&["LI"]));
+    assert_eq!(source.size, status.dataset_size);
+    assert_eq!(source.fingerprint, status.dataset_fingerprint);
+    assert!(source.modified.is_some());
+
+    let report = db.dataset_stats(source.with_version("2024.06.01"), &["LI"]);
+    let stats = db.stats();
+    assert_eq!(report.schema, STATS_SCHEMA);
+    assert_eq!(
+        (report.countries, report.states, report.cities),
+        (stats.countries, stats.states, stats.cities)
+    );
+    assert_eq!(report.filter, ["LI"]);
+    assert_eq!(report.dataset.version.as_deref(), Some("2024.06.01"));
+    let date = &report.build_date;
+    assert_eq!(date.len(), 10, "{date}");
+    assert_eq!(&date[4..5], "-");
+
+    // the JSON layout round-trips
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["dataset"]["version"], "2024.06.01");
+    assert_eq!(json["countries"], 1);
+    let back: DatasetStats = serde_json::from_value(json).unwrap();
+    assert_eq!(back, report);
+}
+
+#[test]
+fn version_defaults_to_the_changelog() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let missing = DatasetSource::from_path("does/not/exist.json.gz");
+    assert_eq!(missing.file.as_deref(), Some("exist.json.gz"));
+    assert_eq!(missing.size, None);
+    assert_eq!(db.dataset_stats(missing.clone(), &[]).dataset.version, None);
+
+    let mut changelog = Changelog::default();
+    changelog
+        .push(ChangeSet {
+            previous: "2024.05.01".into(),
+            version: "2024.06.01".into(),
+            changes: Vec::new(),
+        })
+        .unwrap();
+    db.set_changelog(changelog);
+    let report = db.dataset_stats(missing.clone(), &[]);
+    assert_eq!(report.dataset.version.as_deref(), Some("2024.06.01"));
+    assert!(report.filter.is_empty());
+
+    let explicit = db.dataset_stats(missing.with_version("custom"), &[]);
+    assert_eq!(explicit.dataset.version.as_deref(), Some("custom"));
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{GeoDb, StandardBackend, TimezoneIssueKind};
+
+#[test]
+fn audit_timezones_reports_both_directions() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    // upstream lists Büsingen's zone, but files the town under Europe/Berlin
+    let baseline = db.audit_timezones();
+    assert_eq!(baseline.len(), 1);
+    assert_eq!(baseline[0].kind, TimezoneIssueKind::ListedZoneUnused);
+
+    let country = &mut db.countries[0];
+    for city in country
+        .states
+        .iter_mut()
+        .flat_map(|s| s.cities.iter_mut())
+        .take(5)
+    {
+        city.timezone = Some("Europe/Paris".to_string());
+    }
+
+    let issues = db.audit_timezones();
+    let not_listed: Vec<_> = issues
+        .iter()
+        .filter(|i| i.kind == TimezoneIssueKind::CityZoneNotListed)
+        .collect();
+    let zones: Vec<&str> = not_listed.iter().map(|i| i.timezone.as_str()).collect();
+    assert_eq!(zones, ["Europe/Paris"]);
+    let paris = not_listed[0];
+    assert_eq!(paris.iso2, "DE");
+    assert_eq!(paris.cities, 5);
+    assert_eq!(paris.examples.len(), 3);
+    assert!(paris
+        .to_string()
+        .contains("Europe/Paris is used by 5 cities"));
+
+    let unused: Vec<_> = issues
+        .iter()
+        .filter(|i| i.kind == TimezoneIssueKind::ListedZoneUnused)
+        .collect();
+    assert_eq!(unused.len(), 1);
+    assert_eq!(unused[0].timezone, "Europe/Busingen");
+    assert_eq!(unused[0].cities, 0);
+}
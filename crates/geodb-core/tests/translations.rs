@@ -0,0 +1,86 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    GeoDb, RegionTranslationTable, RegionTranslations, StandardBackend, StateTranslationTable,
+    StateTranslations,
+};
+
+#[test]
+fn localized_state_names_fall_back_to_native_then_default() {
+    let mut db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["CH", "DE"]).expect("load DB");
+    let table = StateTranslationTable::load_default().expect("state translations");
+    assert!(db.apply_state_translations(&table) >= 5);
+
+    let ch = db.find_country_by_iso2("CH").unwrap();
+    let geneva = ch.states().iter().find(|s| s.name() == "Geneva").unwrap();
+    assert_eq!(geneva.localized_name("fr"), "Genève");
+    assert_eq!(geneva.localized_name("IT"), "Ginevra");
+    assert_eq!(geneva.localized_name("en"), "Geneva");
+    // no Spanish entry: native name, then the default name
+    assert_eq!(geneva.localized_name("es"), geneva.native_name().unwrap());
+
+    let de = db.find_country_by_iso2("DE").unwrap();
+    let hessen = de.states().iter().find(|s| s.name() == "Hessen").unwrap();
+    assert_eq!(hessen.localized_name("en"), "Hesse");
+
+    let options = de.state_options("de");
+    assert!(options.contains(&("BY", "Bayern")));
+    assert!(options
+        .windows(2)
+        .all(|w| w[0].1.to_lowercase() <= w[1].1.to_lowercase()));
+}
+
+#[test]
+fn translation_table_lookups_are_case_insensitive() {
+    let table = StateTranslationTable::from_entries(vec![StateTranslations {
+        iso2: "at".into(),
+        state: "Vienna".into(),
+        names: [("DE".to_string(), "Wien".to_string())].into(),
+    }]);
+    let entry = table.get("AT", "vienna").unwrap();
+    assert_eq!(entry.names.get("de").map(String::as_str), Some("Wien"));
+    assert!(table.get("DE", "Vienna").is_none());
+}
+
+#[test]
+fn regions_are_localized_from_the_sidecar() {
+    let mut db =
+        GeoDb::<StandardBackend>::load_filtered_by_iso2(&["CH", "DE", "JP"]).expect("load DB");
+    let table = RegionTranslationTable::load_default().expect("region translations");
+    assert_eq!(db.apply_region_translations(&table), 3);
+
+    // every region and subregion of the dataset has an entry
+    let all = GeoDb::<StandardBackend>::load().expect("load DB");
+    for c in all.countries() {
+        for region in [Some(c.region()), c.subregion()].into_iter().flatten() {
+            assert!(region.is_empty() || table.get(region).is_some(), "{region}");
+        }
+    }
+
+    let de = db.find_country_by_iso2("DE").unwrap();
+    assert_eq!(de.region_localized("de"), "Europa");
+    assert_eq!(de.subregion_localized("FR"), Some("Europe de l'Ouest"));
+    assert_eq!(de.region_localized("en"), "Europe");
+    assert_eq!(de.subregion_localized("xx"), Some("Western Europe"));
+    assert_eq!(de.localized_name("es"), "Alemania");
+    assert_eq!(de.localized_name("xx"), "Germany");
+
+    let groups = db.countries_by_region("de");
+    let names: Vec<&str> = groups.iter().map(|(region, _)| *region).collect();
+    assert_eq!(names, ["Asien", "Europa"]);
+    assert_eq!(groups[1].1, [("DE", "Deutschland"), ("CH", "Schweiz")]);
+}
+
+#[test]
+fn region_table_from_entries() {
+    let table = RegionTranslationTable::from_entries(vec![RegionTranslations {
+        region: "Polar".to_string(),
+        names: [("DE".to_string(), "Polargebiete".to_string())].into(),
+    }]);
+    let entry = table.get("polar").unwrap();
+    assert_eq!(
+        entry.names.get("de").map(String::as_str),
+        Some("Polargebiete")
+    );
+    assert!(table.get("Europe").is_none());
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{ChangeSet, Changelog, GeoDb, StandardBackend, VersionInfo};
+
+fn load(iso2: &[&str]) -> GeoDb<StandardBackend> {
+    GeoDb::<StandardBackend>::load_filtered_by_iso2(iso2).expect("load DB")
+}
+
+#[test]
+fn reports_crate_and_data() {
+    let db = load(&["LI"]);
+    let info = db.version_info();
+    assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.binding, None);
+    assert!(info.features.iter().any(|f| f == "json"));
+    assert_eq!(info.view_version, geodb_core::VIEW_VERSION);
+    assert_eq!(info.dataset_version, None);
+    assert_eq!(info.data_fingerprint.len(), 16);
+    let stats = db.stats();
+    assert_eq!(
+        (info.countries, info.states, info.cities),
+        (stats.countries, stats.states, stats.cities)
+    );
+
+    let info = info
+        .with_dataset_version("2024.06.01")
+        .with_binding("geodb-cli 0.1.3");
+    assert_eq!(
+        info.to_string(),
+        format!(
+            "geodb-cli 0.1.3, geodb-core {}, dataset 2024.06.01 (data {})",
+            info.crate_version, info.data_fingerprint
+        )
+    );
+
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["dataset_version"], "2024.06.01");
+    let back: VersionInfo = serde_json::from_value(json).unwrap();
+    assert_eq!(back, info);
+}
+
+#[test]
+fn fingerprint_follows_the_data() {
+    let db = load(&["LI"]);
+    let fingerprint = db.version_info().data_fingerprint;
+
+    // the same data through the binary path
+    let reloaded = GeoDb::<StandardBackend>::from_bytes(&db.to_bytes().unwrap()).unwrap();
+    assert_eq!(reloaded.version_info().data_fingerprint, fingerprint);
+
+    // other data, or the same data without coordinates
+    assert_ne!(load(&["CH"]).version_info().data_fingerprint, fingerprint);
+    assert_ne!(
+        db.without_coordinates().version_info().data_fingerprint,
+        fingerprint
+    );
+}
+
+#[test]
+fn dataset_version_defaults_to_the_changelog() {
+    let mut db = load(&["LI"]);
+    let mut changelog = Changelog::default();
+    changelog
+        .push(ChangeSet {
+            previous: "2024.05.01".into(),
+            version: "2024.06.01".into(),
+            changes: Vec::new(),
+        })
+        .unwrap();
+    db.set_changelog(changelog);
+    let info = db.version_info();
+    assert_eq!(info.dataset_version.as_deref(), Some("2024.06.01"));
+    assert_eq!(
+        info.with_dataset_version("local")
+            .dataset_version
+            .as_deref(),
+        Some("local")
+    );
+}
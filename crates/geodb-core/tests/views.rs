@@ -0,0 +1,104 @@
+#![cfg(feature = "json")]
+
+use geodb_core::{
+    CityView, CountryView, GeoDb, StandardBackend, StateView, ViewOptions, VIEW_VERSION,
+};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+fn keys(value: &Value) -> BTreeSet<&str> {
+    value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn city_and_state_views_carry_coordinates_and_parents() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["LI"]).expect("load DB");
+    let (city, state, country) = db.find_cities_by_substring("Vaduz")[0];
+
+    let json = serde_json::to_value(CityView {
+        country,
+        state,
+        city,
+    })
+    .unwrap();
+    assert_eq!(json["country_code"], "LI");
+    assert_eq!(
+        json["state_id"],
+        serde_json::to_value(country.state_id(state)).unwrap()
+    );
+    assert_eq!(json["state_code"].as_str(), Some(state.state_code()));
+    assert_eq!(json["latitude"].as_f64(), city.latitude);
+    assert_eq!(json["longitude"].as_f64(), city.longitude);
+    assert_eq!(json["timezone"].as_str(), city.timezone.as_deref());
+
+    let json = serde_json::to_value(StateView { country, state }).unwrap();
+    assert_eq!(json["country_code"], "LI");
+    assert_eq!(json["native_name"].as_str(), state.native_name());
+    assert_eq!(json["latitude"].as_f64(), state.latitude);
+    assert!(json["translations"].is_object());
+    assert_eq!(VIEW_VERSION, db.version_info().view_version);
+}
+
+#[test]
+fn every_view_of_a_kind_has_the_same_keys() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE", "LI", "AQ"]).expect("load DB");
+    let country_keys: BTreeSet<_> = db
+        .countries()
+        .iter()
+        .map(|c| {
+            let json = serde_json::to_value(CountryView(c)).unwrap();
+            keys(&json)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(country_keys.len(), 1);
+
+    let city_keys: BTreeSet<_> = db
+        .iter_cities()
+        .map(|(city, state, country)| {
+            let json = serde_json::to_value(CityView {
+                country,
+                state,
+                city,
+            })
+            .unwrap();
+            keys(&json)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(city_keys.len(), 1);
+}
+
+#[test]
+fn translations_can_be_left_out() {
+    let db = GeoDb::<StandardBackend>::load_filtered_by_iso2(&["DE"]).expect("load DB");
+    let de = &db.countries()[0];
+    let state = &de.states()[0];
+    let light = ViewOptions::default().with_translations(false);
+
+    let full = serde_json::to_value(CountryView(de)).unwrap();
+    let mut trimmed = serde_json::to_value(CountryView(de).with_options(light)).unwrap();
+    assert!(trimmed.get("translations").is_none());
+    trimmed["translations"] = full["translations"].clone();
+    assert_eq!(trimmed, full);
+
+    let view = StateView { country: de, state };
+    let json = serde_json::to_value(view.with_options(light)).unwrap();
+    assert!(json.get("translations").is_none());
+    assert_eq!(json["name"], state.name());
+
+    // search hits take the same options
+    let hit = &db.smart_search("germany")[0];
+    let json = serde_json::to_value(hit.to_view_with(light)).unwrap();
+    assert!(json.get("translations").is_none());
+    assert!(serde_json::to_value(hit.to_view()).unwrap()["translations"].is_object());
+}
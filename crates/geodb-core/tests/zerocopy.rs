@@ -0,0 +1,91 @@
+//! Also runs with `--no-default-features --features zerocopy`: the
+//! fixture comes from the bundled `ALL.bin`, which has the layout without
+//! districts.
+#![cfg(all(feature = "zerocopy", not(feature = "districts")))]
+
+use geodb_core::staged::CityChunks;
+use geodb_core::zerocopy::{is_zerocopy, ZeroCopyDb};
+use geodb_core::{GeoDb, StandardBackend};
+
+fn db() -> GeoDb<StandardBackend> {
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    GeoDb::<StandardBackend>::load_binary(&path)
+        .expect("load DB")
+        .filtered_by_iso2(&["DE", "AT", "CH", "LI"])
+}
+
+#[test]
+fn view_reads_the_hierarchy_in_place() {
+    let db = db();
+    let bytes = db.to_zerocopy_bytes().unwrap();
+    assert!(is_zerocopy(&bytes));
+    let view = ZeroCopyDb::new(&bytes).expect("valid artifact");
+
+    let stats = view.stats();
+    assert_eq!(
+        (stats.countries, stats.states, stats.cities),
+        (db.stats().countries, db.stats().states, db.stats().cities)
+    );
+    for (country, expected) in view.countries().zip(db.countries()) {
+        assert_eq!(country.iso2(), expected.iso2());
+        assert_eq!(country.name(), expected.name());
+        assert_eq!(country.states().len(), expected.states().len());
+        assert_eq!(country.state_count(), expected.state_count());
+        assert_eq!(country.city_count(), expected.city_count());
+        for (state, expected) in country.states().zip(expected.states()) {
+            assert_eq!(state.name(), expected.name());
+            assert_eq!(state.state_code(), expected.state_code.as_deref());
+            let cities: Vec<_> = state.cities().collect();
+            assert_eq!(cities.len(), expected.cities().len());
+            assert_eq!(state.city_count(), expected.city_count());
+            for (city, expected) in cities.iter().zip(expected.cities()) {
+                assert_eq!(city.name(), expected.name());
+                assert_eq!(city.latitude(), expected.latitude);
+                assert_eq!(city.longitude(), expected.longitude);
+                assert_eq!(city.timezone(), expected.timezone.as_deref());
+            }
+        }
+    }
+    assert_eq!(view.country("li").map(|c| c.name()), Some("Liechtenstein"));
+    assert!(view.country("FR").is_none());
+}
+
+#[test]
+fn city_chunks_fill_a_skeleton_like_the_city_stream() {
+    let db = db();
+    let (skeleton, stream) = db.to_staged_bytes().unwrap();
+    let zerocopy = db.to_zerocopy_bytes().unwrap();
+
+    let mut from_stream = GeoDb::<StandardBackend>::from_bytes(&skeleton).unwrap();
+    for chunk in CityChunks::<StandardBackend>::new(&stream) {
+        from_stream.attach_cities(chunk.unwrap()).unwrap();
+    }
+    let mut from_view = GeoDb::<StandardBackend>::from_bytes(&skeleton).unwrap();
+    for chunk in ZeroCopyDb::new(&zerocopy).unwrap().city_chunks() {
+        from_view.attach_cities(chunk).unwrap();
+    }
+    // the fingerprint covers every city with its coordinates
+    let fingerprint = |db: &GeoDb<StandardBackend>| db.version_info().data_fingerprint;
+    assert_eq!(fingerprint(&from_view), fingerprint(&from_stream));
+    assert_eq!(fingerprint(&from_view), fingerprint(&db));
+}
+
+#[test]
+fn damaged_artifacts_are_rejected() {
+    let bytes = db().to_zerocopy_bytes().unwrap();
+    assert!(ZeroCopyDb::new(&bytes[..bytes.len() - 1]).is_err());
+    assert!(ZeroCopyDb::new(&bytes[..10]).is_err());
+    assert!(ZeroCopyDb::new(&db().to_bytes().unwrap()).is_err());
+
+    // first country's name pointing past the strings
+    let mut bad = bytes.clone();
+    bad[24 + 8..24 + 12].copy_from_slice(&(u32::MAX - 1).to_le_bytes());
+    assert!(ZeroCopyDb::new(&bad).is_err());
+    // first country claiming more states than there are
+    let mut bad = bytes;
+    bad[24 + 16..24 + 20].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(ZeroCopyDb::new(&bad).is_err());
+}
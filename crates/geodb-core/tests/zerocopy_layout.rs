@@ -0,0 +1,169 @@
+//! Conformance of the zero-copy layout with its documentation (module docs
+//! of `geodb_core::zerocopy`). The reader below uses nothing but the
+//! documented offsets, like a reader in another language would; if this
+//! test needs changing, the layout changed and needs a new magic.
+//!
+//! The fixtures come from the bundled `ALL.bin` (layout without districts),
+//! so this also runs with `--no-default-features --features zerocopy`.
+#![cfg(all(feature = "zerocopy", not(feature = "districts")))]
+
+use geodb_core::zerocopy::{layout, ZeroCopyDb};
+use geodb_core::{GeoDb, StandardBackend};
+
+fn load(iso2: &[&str]) -> GeoDb<StandardBackend> {
+    let path = GeoDb::<StandardBackend>::default_data_dir().join(format!(
+        "{}.ALL.bin",
+        GeoDb::<StandardBackend>::default_dataset_filename()
+    ));
+    GeoDb::<StandardBackend>::load_binary(&path)
+        .expect("load DB")
+        .filtered_by_iso2(iso2)
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn f64_le(bytes: &[u8], at: usize) -> f64 {
+    f64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+/// Table offsets and counts from the header.
+struct Tables<'a> {
+    bytes: &'a [u8],
+    countries: (usize, usize),
+    states: (usize, usize),
+    cities: (usize, usize),
+    strings: usize,
+}
+
+impl<'a> Tables<'a> {
+    fn read(bytes: &'a [u8]) -> Self {
+        assert_eq!(&bytes[..8], b"GEODBZC1");
+        let [c, s, t, n] = [8, 12, 16, 20].map(|at| u32_le(bytes, at) as usize);
+        let countries = 24;
+        let states = countries + c * 20;
+        let cities = states + s * 20;
+        let strings = cities + t * 32;
+        assert_eq!(strings + n, bytes.len(), "the file ends after the strings");
+        Self {
+            bytes,
+            countries: (countries, c),
+            states: (states, s),
+            cities: (cities, t),
+            strings,
+        }
+    }
+
+    fn string(&self, at: usize) -> Option<&'a str> {
+        let (offset, len) = (u32_le(self.bytes, at), u32_le(self.bytes, at + 4));
+        if offset == 0xFFFF_FFFF {
+            return None;
+        }
+        let start = self.strings + offset as usize;
+        Some(std::str::from_utf8(&self.bytes[start..start + len as usize]).unwrap())
+    }
+
+    fn coordinate(&self, at: usize) -> Option<f64> {
+        Some(f64_le(self.bytes, at)).filter(|v| !v.is_nan())
+    }
+}
+
+#[test]
+fn independent_reader_walks_the_documented_tables() {
+    let db = load(&["LI", "AD", "MC"]);
+    let bytes = db.to_zerocopy_bytes().unwrap();
+    let tables = Tables::read(&bytes);
+    let view = ZeroCopyDb::new(&bytes).unwrap();
+    assert_eq!(tables.countries.1, db.countries().len());
+
+    let (mut state, mut city) = (0, 0);
+    for (i, (country, zc)) in db.countries().iter().zip(view.countries()).enumerate() {
+        let record = tables.countries.0 + i * 20;
+        assert_eq!(tables.string(record), Some(country.iso2()));
+        assert_eq!(tables.string(record + 8), Some(country.name()));
+        let states_end = u32_le(&bytes, record + 16) as usize;
+        assert_eq!(states_end - state, country.states().len());
+        assert_eq!(zc.index(), i);
+
+        for (s, zs) in country.states().iter().zip(zc.states()) {
+            let record = tables.states.0 + state * 20;
+            assert_eq!(zs.index(), state);
+            assert_eq!(tables.string(record), Some(s.name()));
+            assert_eq!(tables.string(record + 8), s.state_code.as_deref());
+            let cities_end = u32_le(&bytes, record + 16) as usize;
+            assert_eq!(cities_end - city, s.cities().len());
+
+            for (c, zt) in s.cities().iter().zip(zs.cities()) {
+                let record = tables.cities.0 + city * 32;
+                assert_eq!(zt.index(), city);
+                assert_eq!(tables.string(record), Some(c.name()));
+                assert_eq!(tables.string(record + 8), c.timezone.as_deref());
+                assert_eq!(tables.coordinate(record + 16), c.latitude);
+                assert_eq!(tables.coordinate(record + 24), c.longitude);
+                city += 1;
+            }
+            state += 1;
+        }
+        assert_eq!(state, states_end);
+    }
+    assert_eq!((state, city), (tables.states.1, tables.cities.1));
+}
+
+#[test]
+fn missing_coordinates_are_nan() {
+    let db = load(&["LI"]).without_coordinates();
+    let bytes = db.to_zerocopy_bytes().unwrap();
+    let tables = Tables::read(&bytes);
+    assert!(tables.cities.1 > 0);
+    for i in 0..tables.cities.1 {
+        let record = tables.cities.0 + i * 32;
+        assert!(f64_le(&bytes, record + 16).is_nan());
+        assert!(f64_le(&bytes, record + 24).is_nan());
+    }
+}
+
+#[test]
+fn layout_constants_match_the_documentation() {
+    assert_eq!(geodb_core::zerocopy::ZEROCOPY_MAGIC, b"GEODBZC1");
+    assert_eq!(
+        [
+            layout::HEADER_LEN,
+            layout::HEADER_COUNTRIES,
+            layout::HEADER_STATES,
+            layout::HEADER_CITIES,
+            layout::HEADER_STRING_BYTES,
+        ],
+        [24, 8, 12, 16, 20]
+    );
+    assert_eq!(
+        [
+            layout::COUNTRY_LEN,
+            layout::COUNTRY_ISO2,
+            layout::COUNTRY_NAME,
+            layout::COUNTRY_STATES_END,
+        ],
+        [20, 0, 8, 16]
+    );
+    assert_eq!(
+        [
+            layout::STATE_LEN,
+            layout::STATE_NAME,
+            layout::STATE_CODE,
+            layout::STATE_CITIES_END,
+        ],
+        [20, 0, 8, 16]
+    );
+    assert_eq!(
+        [
+            layout::CITY_LEN,
+            layout::CITY_NAME,
+            layout::CITY_TIMEZONE,
+            layout::CITY_LATITUDE,
+            layout::CITY_LONGITUDE,
+        ],
+        [32, 0, 8, 16, 24]
+    );
+    assert_eq!(layout::STRING_REF_LEN, 8);
+    assert_eq!(layout::ABSENT, 0xFFFF_FFFF);
+}
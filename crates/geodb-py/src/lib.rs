@@ -1,10 +1,11 @@
 #![allow(clippy::useless_conversion)]
 
 use geodb_core::{
-    CityView, CountryView, DefaultGeoDb, GeoDb, PhoneCodeSearch, SmartItem, StandardBackend,
-    StateView,
+    CityFilter, CityId, CityView, CountryId, CountryView, DefaultGeoDb, GeoDb, PhoneCodeSearch,
+    SearchLanguage, SearchOptions, SearchSessionHints, StandardBackend, StateId, StateView,
+    ViewOptions,
 };
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyModule;
 use serde::Serialize;
@@ -39,6 +40,11 @@ fn to_py<'py, T: Serialize + ?Sized>(py: Python<'py>, value: &T) -> PyResult<Bou
     Ok(obj)
 }
 
+/// Parse an entity id, raising ValueError for a malformed one.
+fn parse_id<T: std::str::FromStr<Err = String>>(id: &str) -> PyResult<T> {
+    id.parse().map_err(PyValueError::new_err)
+}
+
 /// Find the bundled data file in the Python package
 fn find_bundled_data() -> PyResult<PathBuf> {
     Python::with_gil(|py| {
@@ -123,6 +129,23 @@ impl PyGeoDb {
         Ok((s.countries, s.states, s.cities))
     }
 
+    /// Which optional data the database carries, as a dict of flags
+    /// ("coordinates", "city_aliases", "state_translations", ...)
+    pub fn capabilities<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.capabilities())
+    }
+
+    /// Code and data versions as a dict ("crate_version", "binding",
+    /// "features", "dataset_version", "data_fingerprint", counts), for bug
+    /// reports
+    pub fn version_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let info = self
+            .inner
+            .version_info()
+            .with_binding(concat!("geodb-py ", env!("CARGO_PKG_VERSION")));
+        to_py(py, &info)
+    }
+
     /// Return a list of all countries as dicts
     pub fn countries<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let items: Vec<_> = self.inner.countries().iter().map(CountryView).collect();
@@ -143,6 +166,60 @@ impl PyGeoDb {
         }
     }
 
+    /// Country by the `id` of an earlier result (its ISO2 code) as dict (or
+    /// None)
+    pub fn country_by_id<'py>(
+        &self,
+        py: Python<'py>,
+        id: &str,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let id: CountryId = parse_id(id)?;
+        self.inner
+            .country(id)
+            .map(|c| to_py(py, &CountryView(c)))
+            .transpose()
+    }
+
+    /// State by the `id` of an earlier result (e.g. "DE/3") as dict (or None)
+    pub fn state_by_id<'py>(
+        &self,
+        py: Python<'py>,
+        id: &str,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let id: StateId = parse_id(id)?;
+        let (Some(country), Some(state)) =
+            (self.inner.country(id.country()), self.inner.state_by_id(id))
+        else {
+            return Ok(None);
+        };
+        to_py(py, &StateView { country, state }).map(Some)
+    }
+
+    /// City by the `id` of an earlier result (e.g. "DE/3/17") as dict (or
+    /// None)
+    pub fn city_by_id<'py>(
+        &self,
+        py: Python<'py>,
+        id: &str,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let id: CityId = parse_id(id)?;
+        let Some(path) = self.inner.ancestors_of(id) else {
+            return Ok(None);
+        };
+        let (Some(state), Some(city)) = (path.state, path.city) else {
+            return Ok(None);
+        };
+        to_py(
+            py,
+            &CityView {
+                country: path.country,
+                state,
+                city,
+            },
+        )
+        .map(Some)
+    }
+
     /// List all states for a given country ISO2 as dicts
     pub fn states_in_country<'py>(
         &self,
@@ -162,22 +239,98 @@ impl PyGeoDb {
         }
     }
 
-    /// Find countries by phone code (e.g. "+49", "1") and return list of dicts
+    /// Find countries by phone code (e.g. "+49", "0049", "(49)", "1") and return list of dicts
     pub fn search_countries_by_phone<'py>(
         &self,
         py: Python<'py>,
         phone: &str,
     ) -> PyResult<Bound<'py, PyAny>> {
-        let code = phone.trim().trim_start_matches('+');
         let items: Vec<_> = self
             .inner
-            .find_countries_by_phone_code(code)
+            .find_countries_by_phone_code(phone)
             .iter()
             .map(|c| CountryView(*c))
             .collect();
         to_py(py, &items)
     }
 
+    /// The calling code `input` starts with ("+44", or a whole number) as a
+    /// dict with "code", "countries" (ISO2 codes, primary first) and
+    /// "primary_country" (or None)
+    pub fn lookup_dial_code<'py>(
+        &self,
+        py: Python<'py>,
+        input: &str,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.inner
+            .lookup_dial_code(input)
+            .map(|dial| to_py(py, &dial))
+            .transpose()
+    }
+
+    /// Every calling code as a list of dicts like `lookup_dial_code`, in
+    /// numeric order
+    pub fn dial_codes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.dial_codes())
+    }
+
+    /// Find countries using a currency code (case-insensitive, e.g. "EUR").
+    /// Returns list of dicts
+    pub fn find_countries_by_currency<'py>(
+        &self,
+        py: Python<'py>,
+        code: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let items: Vec<_> = self
+            .inner
+            .find_countries_by_currency(code)
+            .into_iter()
+            .map(CountryView)
+            .collect();
+        to_py(py, &items)
+    }
+
+    /// Find countries whose currency name contains `name` (case and accents
+    /// ignored, e.g. "franc"). Returns list of dicts
+    pub fn find_countries_by_currency_name<'py>(
+        &self,
+        py: Python<'py>,
+        name: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let items: Vec<_> = self
+            .inner
+            .find_countries_by_currency_name(name)
+            .into_iter()
+            .map(CountryView)
+            .collect();
+        to_py(py, &items)
+    }
+
+    /// All currencies as a list of {"code", "name", "symbol", "countries"}
+    /// dicts, sorted by code; "countries" holds ISO2 codes
+    pub fn currencies<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.currencies())
+    }
+
+    /// Find countries containing a substring (case-insensitive). With
+    /// `translations=True`, native names and translations match too
+    /// (e.g. "Alemania"). Returns list of dicts
+    #[pyo3(signature = (substr, translations = false))]
+    pub fn find_countries_by_substring<'py>(
+        &self,
+        py: Python<'py>,
+        substr: &str,
+        translations: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let items: Vec<_> = self
+            .inner
+            .find_countries_by_substring_with(substr, translations)
+            .into_iter()
+            .map(CountryView)
+            .collect();
+        to_py(py, &items)
+    }
+
     /// Find states containing a substring (ASCII, case-insensitive). Returns list of dicts
     pub fn find_states_by_substring<'py>(
         &self,
@@ -193,15 +346,21 @@ impl PyGeoDb {
         to_py(py, &items)
     }
 
-    /// Find cities containing a substring (ASCII, case-insensitive). Returns list of dicts
+    /// Find cities containing a substring (ASCII, case-insensitive); `limit`
+    /// and `offset` page through the matches. Returns list of dicts
+    #[pyo3(signature = (substr, limit = None, offset = 0))]
     pub fn find_cities_by_substring<'py>(
         &self,
         py: Python<'py>,
         substr: &str,
+        limit: Option<usize>,
+        offset: usize,
     ) -> PyResult<Bound<'py, PyAny>> {
+        let mut filter = CityFilter::default().with_offset(offset);
+        filter.limit = limit;
         let items: Vec<_> = self
             .inner
-            .find_cities_by_substring(substr)
+            .find_cities_by_substring_filtered(substr, &filter)
             .into_iter()
             .map(|(city, state, country)| CityView {
                 country,
@@ -212,40 +371,204 @@ impl PyGeoDb {
         to_py(py, &items)
     }
 
-    /// Smart search across countries, states, cities, and phone codes. Returns list of dicts
-    pub fn smart_search<'py>(&self, py: Python<'py>, query: &str) -> PyResult<Bound<'py, PyAny>> {
-        let hits = self.inner.smart_search(query);
+    /// Lazily iterate over all cities as dicts (same shape as
+    /// `find_cities_by_substring`), in database order. Dicts are made
+    /// `chunk_size` at a time, so memory stays flat, e.g. for
+    /// `pd.DataFrame(itertools.islice(db.iter_cities(), 10_000))`
+    #[pyo3(signature = (chunk_size = 1000))]
+    pub fn iter_cities(slf: Bound<'_, Self>, chunk_size: usize) -> PyResult<PyCityIter> {
+        if chunk_size == 0 {
+            return Err(PyValueError::new_err("chunk_size must be at least 1"));
+        }
+        Ok(PyCityIter {
+            db: slf.unbind(),
+            cursor: (0, 0, 0),
+            chunk_size,
+            buffer: Vec::new().into_iter(),
+        })
+    }
+
+    /// Distinct city timezones as a list of {"value", "count"} dicts
+    pub fn distinct_timezones<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.distinct_timezones())
+    }
+
+    /// Distinct country currency codes as a list of {"value", "count"} dicts
+    pub fn distinct_currencies<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.distinct_currencies())
+    }
+
+    /// Distinct country regions as a list of {"value", "count"} dicts
+    pub fn distinct_regions<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.distinct_regions())
+    }
+
+    /// Smart search across countries, states, cities, phone codes and
+    /// currencies. With `include_phone=False`, numeric queries ("41") don't
+    /// match calling codes; with `include_currency=False`, "CHF" doesn't
+    /// match the countries paying in Swiss francs. `limit`, `offset` and `min_score` page through the ranked
+    /// hits. `translations=False` leaves the translation maps out of country
+    /// and state hits. `max_edit_distance=2` also matches names a few typos
+    /// off ("berln" → Berlin). `recent_city_ids` (ids of cities the user
+    /// picked before) and `preferred_countries` (ISO2 codes) slightly boost
+    /// matching hits. `include_cities=False` skips the city scan for a quick
+    /// first list of countries and states (search again with it on for the
+    /// cities). Returns list of dicts
+    #[pyo3(signature = (query, include_phone = true, limit = None, offset = 0, min_score = None, translations = true, max_edit_distance = 0, recent_city_ids = Vec::new(), preferred_countries = Vec::new(), include_currency = true, include_cities = true))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn smart_search<'py>(
+        &self,
+        py: Python<'py>,
+        query: &str,
+        include_phone: bool,
+        limit: Option<usize>,
+        offset: usize,
+        min_score: Option<i32>,
+        translations: bool,
+        max_edit_distance: usize,
+        recent_city_ids: Vec<String>,
+        preferred_countries: Vec<String>,
+        include_currency: bool,
+        include_cities: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let session = SearchSessionHints {
+            recent_city_ids: recent_city_ids
+                .iter()
+                .map(|id| parse_id(id))
+                .collect::<PyResult<_>>()?,
+            preferred_countries: preferred_countries
+                .iter()
+                .map(|iso2| parse_id(iso2))
+                .collect::<PyResult<_>>()?,
+        };
+        let mut options = SearchOptions::default()
+            .with_include_phone(include_phone)
+            .with_include_currency(include_currency)
+            .with_include_cities(include_cities)
+            .with_offset(offset)
+            .with_max_edit_distance(max_edit_distance)
+            .with_session(session);
+        options.limit = limit;
+        options.min_score = min_score;
+        let hits = self.inner.smart_search_with(query, &options);
         // Map to a homogeneous list by emitting the view of the matched entity
-        let mut out: Vec<serde_json::Value> = Vec::with_capacity(hits.len());
-        for hit in hits {
-            let v = match hit.item {
-                SmartItem::Country(c) => serde_json::to_value(CountryView(c))
-                    .map_err(|e| PyRuntimeError::new_err(format!("serde error: {e}")))?,
-                SmartItem::State { country, state } => {
-                    serde_json::to_value(&StateView { country, state })
-                        .map_err(|e| PyRuntimeError::new_err(format!("serde error: {e}")))?
-                }
-                SmartItem::City {
-                    country,
-                    state,
-                    city,
-                } => serde_json::to_value(&CityView {
-                    country,
-                    state,
-                    city,
-                })
-                .map_err(|e| PyRuntimeError::new_err(format!("serde error: {e}")))?,
+        let view = ViewOptions::default().with_translations(translations);
+        let out: Vec<_> = hits.iter().map(|hit| hit.to_view_with(view)).collect();
+        to_py(py, &out)
+    }
+
+    /// Prefix autocomplete for search-as-you-type inputs: at most `limit`
+    /// countries, states and cities whose name starts with `prefix`, biggest
+    /// countries first, then by score. Returns list of dicts
+    #[pyo3(signature = (prefix, limit = 10))]
+    pub fn autocomplete<'py>(
+        &self,
+        py: Python<'py>,
+        prefix: &str,
+        limit: usize,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let hits = self.inner.autocomplete(prefix, limit);
+        let out: Vec<_> = hits.iter().map(|hit| hit.to_view()).collect();
+        to_py(py, &out)
+    }
+}
+
+/// Generator returned by `PyGeoDb.iter_cities`.
+#[pyclass]
+pub struct PyCityIter {
+    db: Py<PyGeoDb>,
+    /// Country, state and city index of the next city to convert.
+    cursor: (usize, usize, usize),
+    chunk_size: usize,
+    /// Converted dicts not yet handed out.
+    buffer: std::vec::IntoIter<PyObject>,
+}
+
+#[pymethods]
+impl PyCityIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        if let Some(item) = self.buffer.next() {
+            return Ok(Some(item));
+        }
+        self.refill(py)?;
+        Ok(self.buffer.next())
+    }
+}
+
+impl PyCityIter {
+    /// Convert the next `chunk_size` cities (fewer at the end).
+    fn refill(&mut self, py: Python<'_>) -> PyResult<()> {
+        let db = self.db.borrow(py);
+        let countries = db.inner.countries();
+        let (ci, si, ti) = &mut self.cursor;
+        let mut views = Vec::with_capacity(self.chunk_size);
+        while views.len() < self.chunk_size {
+            let Some(country) = countries.get(*ci) else {
+                break;
+            };
+            let Some(state) = country.states().get(*si) else {
+                (*ci, *si, *ti) = (*ci + 1, 0, 0);
+                continue;
             };
-            out.push(v);
+            let Some(city) = state.cities().get(*ti) else {
+                (*si, *ti) = (*si + 1, 0);
+                continue;
+            };
+            views.push(CityView {
+                country,
+                state,
+                city,
+            });
+            *ti += 1;
         }
-        to_py(py, &out)
+        let items: Vec<PyObject> = to_py(py, &views)?.extract()?;
+        self.buffer = items.into_iter();
+        Ok(())
     }
 }
 
+/// Bare calling-code digits of `input` ("+49 ", "0049", "(49)" -> "49"), or None
+/// if it isn't a phone code.
+#[pyfunction]
+fn normalize_phone_code(input: &str) -> Option<String> {
+    geodb_core::phone::normalize_phone_code(input)
+}
+
+/// Parse an optional search language ("de", "german", ...), raising
+/// ValueError for an unknown one; None means "simple".
+fn parse_language(language: Option<&str>) -> PyResult<SearchLanguage> {
+    language.map_or(Ok(SearchLanguage::Simple), |l| {
+        l.parse().map_err(PyValueError::new_err)
+    })
+}
+
+/// Comparison form of `text` ("  São Paulo" -> "sao paulo" with "en"), the
+/// folding smart search applies to queries and names.
+#[pyfunction]
+#[pyo3(signature = (text, language = None))]
+fn fold_key(text: &str, language: Option<&str>) -> PyResult<String> {
+    Ok(geodb_core::fold_key(text, parse_language(language)?))
+}
+
+/// True if `a` and `b` have the same `fold_key` under `language`.
+#[pyfunction]
+#[pyo3(signature = (a, b, language = None))]
+fn equals_folded(a: &str, b: &str, language: Option<&str>) -> PyResult<bool> {
+    Ok(geodb_core::equals_folded(a, b, parse_language(language)?))
+}
+
 /// Python module entry point
 /// IMPORTANT: The function name must match the module-name in pyproject.toml
 #[pymodule]
 fn geodb_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<PyGeoDb>()?;
+    m.add_class::<PyCityIter>()?;
+    m.add_function(wrap_pyfunction!(normalize_phone_code, m)?)?;
+    m.add_function(wrap_pyfunction!(fold_key, m)?)?;
+    m.add_function(wrap_pyfunction!(equals_folded, m)?)?;
     Ok(())
 }
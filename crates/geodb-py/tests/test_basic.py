@@ -25,3 +25,275 @@ def test_load_default_smoke():
     maybe_us = db.find_country("US")
     if maybe_us is not None:
         assert maybe_us["iso2"] == "US"
+
+
+def test_normalize_phone_code():
+    import geodb_rs
+
+    assert geodb_rs.normalize_phone_code("+49 ") == "49"
+    assert geodb_rs.normalize_phone_code("0049") == "49"
+    assert geodb_rs.normalize_phone_code("(49)") == "49"
+    assert geodb_rs.normalize_phone_code("berlin") is None
+
+
+def test_fold_key():
+    import geodb_rs
+
+    assert geodb_rs.fold_key("  São Paulo", "en") == "sao paulo"
+    assert geodb_rs.fold_key("Berlin") == "berlin"
+    assert geodb_rs.equals_folded("Muenchen", "München", language="de")
+    assert not geodb_rs.equals_folded("Muenchen", "München")
+    with pytest.raises(ValueError):
+        geodb_rs.fold_key("x", "xx")
+
+
+def test_distinct_values():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["DE", "AT"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    assert db.distinct_currencies() == [{"value": "EUR", "count": 2}]
+    assert {"value": "Europe", "count": 2} in db.distinct_regions()
+    zones = [tz["value"] for tz in db.distinct_timezones()]
+    assert "Europe/Berlin" in zones and "Europe/Vienna" in zones
+
+    assert sorted(c["iso2"] for c in db.find_countries_by_currency("eur")) == ["AT", "DE"]
+    assert [c["code"] for c in db.currencies()] == ["EUR"]
+    assert sorted(db.currencies()[0]["countries"]) == ["AT", "DE"]
+
+
+def test_lookup_dial_code():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["GB", "GG", "JE", "IM", "DE"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    dial = db.lookup_dial_code("0044")
+    assert dial["code"] == "44"
+    assert dial["primary_country"] == "GB"
+    assert sorted(dial["countries"]) == ["GB", "GG", "IM", "JE"]
+    assert db.lookup_dial_code("+49 30 123")["primary_country"] == "DE"
+    assert db.lookup_dial_code("berlin") is None
+    assert [d["code"] for d in db.dial_codes()] == ["44", "49"]
+
+
+def test_capabilities():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["DE"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    caps = db.capabilities()
+    assert caps["coordinates"] is True
+    assert caps["city_aliases"] is False
+    assert caps["changelog"] is False
+
+
+def test_version_info():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["DE"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    info = db.version_info()
+    assert info["binding"].startswith("geodb-py ")
+    assert info["crate_version"]
+    assert "json" in info["features"]
+    assert info["dataset_version"] is None
+    assert len(info["data_fingerprint"]) == 16
+    assert info["countries"] == 1
+    assert db.version_info() == info
+
+
+def test_paging():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["DE"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    hits = db.smart_search("berg")
+    assert db.smart_search("berg", limit=5) == hits[:5]
+    assert db.smart_search("berg", limit=5, offset=5) == hits[5:10]
+    assert db.smart_search("berg", min_score=1000) == []
+
+    cities = db.find_cities_by_substring("berg")
+    assert db.find_cities_by_substring("berg", limit=3, offset=2) == cities[2:5]
+
+
+def test_lookup_by_id():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["LI"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    city = db.find_cities_by_substring("Vaduz")[0]
+    assert db.city_by_id(city["id"]) == city
+    state = db.states_in_country("LI")[0]
+    assert db.state_by_id(state["id"]) == state
+    assert db.country_by_id("li")["iso2"] == "LI"
+    assert db.city_by_id("LI/999/0") is None
+    with pytest.raises(ValueError):
+        db.city_by_id("not an id")
+
+
+def test_iter_cities():
+    from itertools import islice
+
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["LI", "AD"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    cities = list(db.iter_cities())
+    assert len(cities) == db.stats()[2]
+    assert list(db.iter_cities(chunk_size=3)) == cities
+    assert list(islice(db.iter_cities(chunk_size=2), 5)) == cities[:5]
+    assert db.city_by_id(cities[0]["id"]) == cities[0]
+    with pytest.raises(ValueError):
+        db.iter_cities(chunk_size=0)
+
+
+def test_view_fields():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["LI"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    assert db.version_info()["view_version"] == 2
+    city = db.find_cities_by_substring("Vaduz")[0]
+    assert city["country_code"] == "LI"
+    assert city["state_id"] == city["id"].rsplit("/", 1)[0]
+    assert isinstance(city["latitude"], float)
+    assert city["timezone"] == "Europe/Vaduz"
+
+    country = db.smart_search("liechtenstein")[0]
+    assert "translations" in country
+    light = db.smart_search("liechtenstein", translations=False)[0]
+    assert "translations" not in light
+    assert {**light, "translations": country["translations"]} == country
+
+
+def test_fuzzy_search():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["LI"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    assert db.smart_search("vadzu") == []
+    names = [hit["name"] for hit in db.smart_search("vadzu", max_edit_distance=1)]
+    assert "Vaduz" in names
+
+
+def test_session_hints():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["FR", "US"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    cities = [hit for hit in db.smart_search("paris") if hit["kind"] == "city"]
+    us = next(c for c in cities if c["country_code"] == "US")
+    boosted = db.smart_search("paris", recent_city_ids=[us["id"]])
+    assert [hit for hit in boosted if hit["kind"] == "city"][0]["id"] == us["id"]
+    assert len(boosted) == len(db.smart_search("paris"))
+    with pytest.raises(ValueError):
+        db.smart_search("paris", preferred_countries=["USA"])
+
+
+def test_currency_search():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["DE", "AT", "CH", "LI"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    assert sorted(c["iso2"] for c in db.find_countries_by_currency_name("franc")) == ["CH", "LI"]
+    countries = [hit["iso2"] for hit in db.smart_search("chf") if hit["kind"] == "country"]
+    assert sorted(countries) == ["CH", "LI"]
+    assert not [hit for hit in db.smart_search("chf", include_currency=False) if hit["kind"] == "country"]
+
+
+def test_search_without_cities():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["FR"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    kinds = {hit["kind"] for hit in db.smart_search("paris", include_cities=False)}
+    assert kinds == {"state"}
+    assert "city" in {hit["kind"] for hit in db.smart_search("paris")}
+
+
+def test_autocomplete():
+    import geodb_rs
+
+    try:
+        db = geodb_rs.PyGeoDb.load_filtered(["DE", "AT", "CH"])
+    except Exception as e:
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+    hits = db.autocomplete("ber", limit=5)
+    assert 0 < len(hits) <= 5
+    assert "Berlin" in [hit["name"] for hit in db.autocomplete("berl")]
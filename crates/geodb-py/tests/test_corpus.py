@@ -0,0 +1,45 @@
+"""Shared query corpus: the same queries and expectations as the core
+(`geodb-core/tests/corpus.rs`) and WASM (`geodb-wasm/tests/corpus.rs`) tests."""
+import json
+from pathlib import Path
+
+import pytest
+
+CORPUS = Path(__file__).resolve().parents[2] / "geodb-core" / "tests" / "fixtures" / "query_corpus.json"
+
+
+def load_corpus():
+    with CORPUS.open(encoding="utf-8") as f:
+        return json.load(f)["queries"]
+
+
+@pytest.fixture(scope="module")
+def db():
+    import geodb_rs
+
+    try:
+        return geodb_rs.PyGeoDb.load_default()
+    except Exception as e:  # Data may be missing in dev mode; skip if so
+        msg = str(e).lower()
+        if "data file not found" in msg or "no such file" in msg:
+            pytest.skip("geodb data not available in this environment; skipping runtime test")
+        raise
+
+
+def run(db, op, query, options):
+    import geodb_rs
+
+    ops = {
+        "smart_search": db.smart_search,
+        "countries_by_phone": db.search_countries_by_phone,
+        "states_by_substring": db.find_states_by_substring,
+        "cities_by_substring": db.find_cities_by_substring,
+        "normalize_phone_code": geodb_rs.normalize_phone_code,
+        "fold_key": geodb_rs.fold_key,
+    }
+    return ops[op](query, **options)
+
+
+@pytest.mark.parametrize("case", load_corpus(), ids=lambda c: f"{c['op']}({c['query']})")
+def test_matches_shared_corpus(db, case):
+    assert run(db, case["op"], case["query"], case.get("options", {})) == case["expected"]
@@ -0,0 +1,166 @@
+//! Splits the prebuilt database into the two embedded stages: a skeleton
+//! with countries and states, loaded at start-up, and the city stream that
+//! is attached in the background (see `geodb_core::staged`). A prebuilt
+//! search index for the default search options comes with them (see
+//! `geodb_core::search_index`).
+//!
+//! Two environment variables trim the embedded data at compile time, so a
+//! region-specific bundle is a single `wasm-pack build`:
+//!
+//! - `GEODB_COUNTRIES=DE,AT,CH`: only these countries (ISO2, any case)
+//! - `GEODB_PROFILE`: `full` (default), `no-coords` (without coordinates,
+//!   see `GeoDb::without_coordinates`) or `states` (countries and states,
+//!   no cities)
+//!
+//! Unknown codes or profiles fail the build instead of silently embedding
+//! less than asked for.
+//!
+//! With the `zerocopy` feature the city stage is written in the zero-copy
+//! layout instead of the bincode city stream (see `geodb_core::zerocopy`).
+//!
+//! The SHA-256 of each file (`GEODB_SKELETON_SHA256`, `GEODB_CITIES_SHA256`,
+//! `GEODB_SEARCH_SHA256`), of all three in that order
+//! (`GEODB_EMBEDDED_SHA256`) and the dataset version
+//! (`GEODB_EMBEDDED_VERSION`) are compiled in; `start()` checks each file
+//! before using it.
+//!
+//! Only WASM builds embed the data; other targets (and docs.rs) get empty
+//! files so the workspace builds without decoding the database.
+use geodb_core::{GeoDb, SearchOptions, StandardBackend};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+const DATABASE: &str = "../geodb-core/data/countries+states+cities.json.gz.ALL.bin";
+
+/// How much of each embedded country to keep.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Profile {
+    Full,
+    NoCoords,
+    States,
+}
+
+impl Profile {
+    fn from_env() -> Self {
+        match std::env::var("GEODB_PROFILE").as_deref().map(str::trim) {
+            Err(_) | Ok("" | "full") => Self::Full,
+            Ok("no-coords") => Self::NoCoords,
+            Ok("states") => Self::States,
+            Ok(other) => {
+                panic!(
+                    "GEODB_PROFILE: unknown profile '{other}' (expected full, no-coords or states)"
+                )
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::NoCoords => "no-coords",
+            Self::States => "states",
+        }
+    }
+}
+
+/// ISO2 codes from `GEODB_COUNTRIES`, upper-cased; empty for all countries.
+fn countries_from_env() -> Vec<String> {
+    std::env::var("GEODB_COUNTRIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(|code| code.trim().to_ascii_uppercase())
+        .filter(|code| !code.is_empty())
+        .collect()
+}
+
+fn trimmed(
+    mut db: GeoDb<StandardBackend>,
+    countries: &[String],
+    profile: Profile,
+) -> GeoDb<StandardBackend> {
+    if !countries.is_empty() {
+        let codes: Vec<&str> = countries.iter().map(String::as_str).collect();
+        db = db.filtered_by_iso2(&codes);
+        let missing: Vec<&str> = codes
+            .iter()
+            .copied()
+            .filter(|code| db.find_country_by_iso2(code).is_none())
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "GEODB_COUNTRIES: unknown country codes {}",
+            missing.join(",")
+        );
+    }
+    match profile {
+        Profile::Full => db,
+        Profile::NoCoords => db.without_coordinates(),
+        Profile::States => {
+            for state in db.countries.iter_mut().flat_map(|c| c.states.iter_mut()) {
+                state.cities.clear();
+            }
+            db
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={DATABASE}");
+    println!("cargo:rerun-if-env-changed=DOCS_RS");
+    println!("cargo:rerun-if-env-changed=GEODB_COUNTRIES");
+    println!("cargo:rerun-if-env-changed=GEODB_PROFILE");
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR"));
+
+    let countries = countries_from_env();
+    let profile = Profile::from_env();
+    // reported by `version_info()`, so bug reports name the trimmed bundle
+    let scope = if countries.is_empty() {
+        "all".to_string()
+    } else {
+        countries.join(",")
+    };
+    println!(
+        "cargo:rustc-env=GEODB_EMBEDDED=countries={scope} profile={}",
+        profile.name()
+    );
+
+    let wasm = std::env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("wasm32");
+    let (skeleton, cities, index, version) = if wasm && std::env::var_os("DOCS_RS").is_none() {
+        let db = GeoDb::<StandardBackend>::load_binary(DATABASE)
+            .unwrap_or_else(|e| panic!("{DATABASE}: {e}"));
+        let db = trimmed(db, &countries, profile);
+        let (skeleton, mut cities) = db.to_staged_bytes().expect("encode staged database");
+        if std::env::var_os("CARGO_FEATURE_ZEROCOPY").is_some() {
+            cities = db.to_zerocopy_bytes().expect("encode zero-copy cities");
+        }
+        let index = db
+            .search_index(&SearchOptions::default())
+            .to_bytes()
+            .expect("encode search index");
+        // the fingerprint identifies the data when the changelog has no version
+        let info = db.version_info();
+        let version = info.dataset_version.unwrap_or(info.data_fingerprint);
+        (skeleton, cities, index, version)
+    } else {
+        (Vec::new(), Vec::new(), Vec::new(), "none".to_string())
+    };
+    let hex =
+        |sha: Sha256| -> String { sha.finalize().iter().map(|b| format!("{b:02x}")).collect() };
+    let mut sha = Sha256::new();
+    for (part, var) in [
+        (&skeleton, "GEODB_SKELETON_SHA256"),
+        (&cities, "GEODB_CITIES_SHA256"),
+        (&index, "GEODB_SEARCH_SHA256"),
+    ] {
+        sha.update(part);
+        println!(
+            "cargo:rustc-env={var}={}",
+            hex(Sha256::new_with_prefix(part))
+        );
+    }
+    println!("cargo:rustc-env=GEODB_EMBEDDED_SHA256={}", hex(sha));
+    println!("cargo:rustc-env=GEODB_EMBEDDED_VERSION={version}");
+    std::fs::write(out.join("geodb.skeleton.bin"), skeleton).expect("write skeleton");
+    std::fs::write(out.join("geodb.cities.bin"), cities).expect("write city stream");
+    std::fs::write(out.join("geodb.search.bin"), index).expect("write search index");
+}
@@ -6,13 +6,44 @@
 //!
 //! What it provides
 //! ----------------
-//! - Automatic initialization on module load (via `#[wasm_bindgen(start)]`)
+//! - Automatic initialization on module load (via `#[wasm_bindgen(start)]`),
+//!   checkable with `is_initialized()`. Countries and states load first;
+//!   cities follow in the background and searches widen as they arrive.
+//!   `on_ready(level => ...)` reports `"state"` and then `"city"`, and
+//!   `loaded_level()` tells how far loading got
+//! - An integrity check of the embedded database: each part is checked
+//!   against the SHA-256 taken at build time before it is loaded (countries
+//!   and states in `start()`, the cities in the background stage).
+//!   `get_db_checksum()` and `get_db_version()` return the build-time
+//!   values, for comparing against what the JS glue expects
 //! - Basic queries: `get_country_count()`, `get_country_name(iso2)`
 //! - Search helpers returning JSON-serializable objects:
-//!   - `search_countries_by_phone("+49")`
+//!   - `search_countries_by_phone("+49")` (also "0049", "(49)", "011 49")
+//!   - `lookup_dial_code("+44")` → `{ code, countries, primary_country }`
+//!     with the country to display, and `dial_codes()` for all of them
+//!   - `search_countries_by_currency("EUR")`, and `currencies()` for every
+//!     currency with its name, symbol and countries
 //!   - `search_state_substring("bavar")`
 //!   - `search_city_substring("berlin")`
-//!   - `smart_search("us" | "+1" | "berlin" | ...)`
+//!   - `search_city_substring_filtered("berg", 1e7, "DE")` and
+//!     `search_city_substring_in_bounds("berg", 47.0, 5.0, 55.0, 15.0)`:
+//!     filtered in WASM, so only the hits that are needed reach JS
+//!   - `smart_search("us" | "berlin" | ...)`, and
+//!     `smart_search("+1", { include_phone: true })` for calling codes; see
+//!     [`SmartSearchOptions`] for the options object
+//!   - `smart_search_msgpack(query, options?)`: like `smart_search`,
+//!     MessagePack-encoded (feature `msgpack`)
+//!   - `smart_search_near("springfield", lat, lng, radius_km)` (nearest first)
+//! - `normalize_phone_code("0049")` → `"49"`, the normalization the phone
+//!   searches use
+//! - `fold_key("São Paulo", "en")` → `"sao paulo"` and
+//!   `equals_folded("Muenchen", "München", "de")`: the folding smart search
+//!   compares with, for pre-normalizing input and cache keys
+//!
+//! - Debounced search for search-as-you-type inputs:
+//!   `create_search_handle(min_interval_ms)` returns a `SearchHandle` whose
+//!   `search(query)` promise resolves once typing pauses, or to `null` when
+//!   superseded by a newer query
 //!
 //! Quick start (browser)
 //! ---------------------
@@ -43,58 +74,176 @@
 //!
 //! Notes
 //! -----
-//! - The WASM build embeds a prebuilt binary database (`geodb.standard.bin`).
-//!   If you customize data, rebuild the crate to refresh the embedded bytes.
+//! - The WASM build embeds the prebuilt binary database
+//!   (`countries+states+cities.json.gz.ALL.bin`), split by `build.rs` into
+//!   countries + states and the city stream. If you customize data, rebuild
+//!   the crate to refresh the embedded bytes.
+//! - `GEODB_COUNTRIES=DE,AT,CH` and `GEODB_PROFILE=full|no-coords|states`
+//!   at build time embed a trimmed database instead, e.g.
+//!   `GEODB_COUNTRIES=DE,AT,CH GEODB_PROFILE=no-coords wasm-pack build`;
+//!   `version_info().binding` names the choice.
+//! - The `zerocopy` feature embeds the cities in the zero-copy layout of
+//!   `geodb_core::zerocopy`, attached without bincode or serde
+//!   (`wasm-pack build -- --features zerocopy`).
 //! - All exported functions are `wasm_bindgen` bindings and return plain types
-//!   or `JsValue` containing JSON-serializable arrays/objects.
+//!   or `JsValue` containing JSON-serializable arrays/objects, shaped like the
+//!   JSON of the Python bindings (`translations` is a plain object, missing
+//!   values are `null`).
+//! - Functions never abort the module: if the database isn't loaded or a
+//!   result can't be converted, they throw a JS `Error` with a descriptive
+//!   message, so wrap calls in `try`/`catch` where that matters.
 //! - See the `dist/` folder for a Trunk-based demo setup.
-use geodb_core::{CityView, CountryView, StateView};
-use geodb_core::{GeoDb, PhoneCodeSearch, SmartItem, StandardBackend};
-use serde_json::json;
-use serde_wasm_bindgen::to_value;
-use std::sync::OnceLock;
+use geodb_core::ViewOptions;
+#[cfg(feature = "msgpack")]
+use geodb_core::{render_hits_with, ResponseFormat};
+use geodb_core::{AdminPath, CityId, CountryId, StateId};
+use geodb_core::{CityFilter, CityView, CountryView, StateView};
+use geodb_core::{PhoneCodeSearch, SearchLanguage, SearchOptions, SearchSessionHints};
+use loading::with_db;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-// IMPORTANT: On docs.rs each crate is built in isolation, so paths outside
-// the crate (like pulling bytes from `../geodb-core/…`) are unavailable.
-// To ensure docs.rs builds succeed, provide a tiny stub during docs builds.
-// Normal builds (workspace/demo) still embed the real bytes.
+mod loading;
+mod search_handle;
+pub use loading::{loaded_level, on_ready};
+pub use search_handle::{create_search_handle, SearchHandle};
+
+// The prebuilt database, split by build.rs into countries + states and the
+// city stream, plus its search index. On docs.rs each crate is built in isolation, so paths outside
+// the crate (like `../geodb-core/…`) are unavailable; build.rs writes empty
+// files there and docs get these stubs instead.
+#[cfg(all(target_arch = "wasm32", not(docsrs)))]
+static EMBEDDED_SKELETON: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/geodb.skeleton.bin"));
 #[cfg(all(target_arch = "wasm32", not(docsrs)))]
-static EMBEDDED_DB: &[u8] =
-    include_bytes!("../../geodb-core/data/countries+states+cities.json.gz.ALL.bin");
+static EMBEDDED_CITIES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/geodb.cities.bin"));
+#[cfg(all(target_arch = "wasm32", not(docsrs)))]
+static EMBEDDED_SEARCH_INDEX: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/geodb.search.bin"));
 
-// Stub for docs.rs so documentation compiles without accessing external files.
+// Stubs for docs.rs so documentation compiles without accessing external files.
 #[cfg(all(target_arch = "wasm32", docsrs))]
-static EMBEDDED_DB: &[u8] = b"";
-
-static DB: OnceLock<GeoDb<StandardBackend>> = OnceLock::new();
+static EMBEDDED_SKELETON: &[u8] = b"";
+#[cfg(all(target_arch = "wasm32", docsrs))]
+static EMBEDDED_CITIES: &[u8] = b"";
+#[cfg(all(target_arch = "wasm32", docsrs))]
+static EMBEDDED_SEARCH_INDEX: &[u8] = b"";
 
 /* --------------------------------------------------------------------------
    Initialization
 -------------------------------------------------------------------------- */
 
-/// Initializes the GeoDB WASM module on startup.
+/// Load countries and states, then schedule the cities (see `on_ready`).
+///
+/// Each embedded part is checked against its build-time SHA-256 before it
+/// is used: the small skeleton right here, the search index and the cities
+/// in the background stage, so countries and states stay available first.
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen(start)]
 pub fn start() {
+    use geodb_core::{AdminLevel, GeoDb, StandardBackend};
+
     console_error_panic_hook::set_once();
+    if is_initialized() {
+        return;
+    }
     web_sys::console::log_1(&"Initializing GeoDB WASM module...".into());
 
-    DB.get_or_init(|| {
-        web_sys::console::log_1(&"Deserializing embedded DB...".into());
-        match bincode::deserialize::<GeoDb<StandardBackend>>(EMBEDDED_DB) {
-            Ok(db) => {
-                web_sys::console::log_1(
-                    &format!("✓ Loaded {} countries", db.countries().len()).into(),
+    // A truncated or mismatched payload would decode into wrong data; a
+    // failed check leaves the module uninitialized, so queries throw.
+    let (expected, checksum) = (
+        env!("GEODB_SKELETON_SHA256"),
+        loading::sha256_hex(EMBEDDED_SKELETON),
+    );
+    if checksum != expected {
+        web_sys::console::error_1(
+            &format!("✗ Embedded countries and states checksum mismatch: expected {expected}, got {checksum}")
+                .into(),
+        );
+        return;
+    }
+
+    // A failed load leaves the module usable: queries throw instead of aborting.
+    match GeoDb::<StandardBackend>::from_bytes(EMBEDDED_SKELETON) {
+        Ok(db) => {
+            web_sys::console::log_1(&format!("✓ Loaded {} countries", db.countries().len()).into());
+            loading::set_skeleton(db, AdminLevel::State);
+            let next = Closure::once_into_js(|| {
+                loading::verify_in_background(
+                    EMBEDDED_SEARCH_INDEX,
+                    env!("GEODB_SEARCH_SHA256"),
+                    "search index",
+                    Box::new(|ok: bool| {
+                        // without the index searches scan all names
+                        if ok {
+                            loading::set_search_index(EMBEDDED_SEARCH_INDEX);
+                        }
+                        loading::verify_in_background(
+                            EMBEDDED_CITIES,
+                            env!("GEODB_CITIES_SHA256"),
+                            "cities",
+                            Box::new(|ok: bool| {
+                                if ok {
+                                    load_embedded_cities();
+                                }
+                            }),
+                        );
+                    }),
                 );
-                db
-            }
-            Err(e) => {
-                web_sys::console::error_1(&format!("✗ DB load failed: {e}").into());
-                panic!("Failed to load DB: {e}");
-            }
+            });
+            search_handle::set_timeout(wasm_bindgen::JsCast::unchecked_ref(&next), 0);
+        }
+        Err(e) => {
+            web_sys::console::error_1(&format!("✗ DB load failed: {e}").into());
+        }
+    }
+}
+
+/// Attach the (verified) embedded city stream in the background.
+#[cfg(target_arch = "wasm32")]
+fn load_embedded_cities() {
+    #[cfg(not(feature = "zerocopy"))]
+    let cities = geodb_core::staged::CityChunks::new(EMBEDDED_CITIES);
+    // checked once here, then read in place without bincode
+    #[cfg(feature = "zerocopy")]
+    let cities = match geodb_core::zerocopy::ZeroCopyDb::new(EMBEDDED_CITIES) {
+        Ok(view) => view.city_chunks().map(Ok),
+        Err(e) => {
+            web_sys::console::error_1(&format!("✗ City load failed: {e}").into());
+            return;
         }
-    });
+    };
+    loading::load_cities(cities);
+}
+
+/// SHA-256 (hex) of the embedded database (skeleton, city stream and search
+/// index), computed at build time. `start()` checks each part before using
+/// it; compare this with the value your JS glue was built against to detect
+/// a stale cached `.wasm`.
+#[wasm_bindgen]
+pub fn get_db_checksum() -> String {
+    env!("GEODB_EMBEDDED_SHA256").to_string()
+}
+
+/// Version of the embedded dataset: the changelog version if the database
+/// carries one, its data fingerprint (as in `version_info()`) otherwise;
+/// `"none"` in builds without embedded data. Known before the cities load.
+#[wasm_bindgen]
+pub fn get_db_version() -> String {
+    env!("GEODB_EMBEDDED_VERSION").to_string()
+}
+
+/// `true` once countries and states have been loaded; cities may still be
+/// on their way (see `loaded_level`).
+#[wasm_bindgen]
+pub fn is_initialized() -> bool {
+    loaded_level().is_some()
+}
+
+/// Convert to the JSON shape the other bindings return: plain objects for
+/// maps (e.g. `translations`) and `null` for missing values.
+fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&format!("failed to convert result to JS: {e}")))
 }
 
 /* --------------------------------------------------------------------------
@@ -102,114 +251,452 @@ pub fn start() {
 -------------------------------------------------------------------------- */
 
 #[wasm_bindgen]
-pub fn get_country_count() -> usize {
-    DB.get().unwrap().countries().len()
+pub fn get_country_count() -> Result<usize, JsError> {
+    with_db(|db| Ok(db.countries().len()))
 }
 
 #[wasm_bindgen]
-pub fn get_country_name(iso2: &str) -> Option<String> {
-    DB.get()
-        .unwrap()
-        .find_country_by_iso2(iso2)
-        .map(|c| c.name().to_string())
+pub fn get_country_name(iso2: &str) -> Result<Option<String>, JsError> {
+    with_db(|db| Ok(db.find_country_by_iso2(iso2).map(|c| c.name().to_string())))
 }
 
 /* --------------------------------------------------------------------------
-   Country Search
+   Lookups by id (the `id` field of earlier results)
 -------------------------------------------------------------------------- */
 
+fn parse_id<T: std::str::FromStr<Err = String>>(id: &str) -> Result<T, JsError> {
+    id.parse().map_err(|e: String| JsError::new(&e))
+}
+
+/// Country by id (its ISO2 code), or `undefined`.
 #[wasm_bindgen]
-pub fn search_countries_by_phone(phone: &str) -> JsValue {
-    let code = phone.trim().trim_start_matches('+');
-    let db = DB.get().unwrap();
+pub fn country_by_id(id: &str) -> Result<JsValue, JsError> {
+    let id: CountryId = parse_id(id)?;
+    with_db(|db| match db.country(id) {
+        Some(c) => to_js(&CountryView(c)),
+        None => Ok(JsValue::UNDEFINED),
+    })
+}
 
-    let items: Vec<_> = db
-        .find_countries_by_phone_code(code)
-        .iter()
-        .map(|c| CountryView(c))
-        .collect();
+/// State by id (e.g. `"DE/3"`), or `undefined`.
+#[wasm_bindgen]
+pub fn state_by_id(id: &str) -> Result<JsValue, JsError> {
+    let id: StateId = parse_id(id)?;
+    with_db(|db| match (db.country(id.country()), db.state_by_id(id)) {
+        (Some(country), Some(state)) => to_js(&StateView { country, state }),
+        _ => Ok(JsValue::UNDEFINED),
+    })
+}
 
-    to_value(&items).unwrap()
+/// City by id (e.g. `"DE/3/17"`), or `undefined` (also while the cities are
+/// still loading).
+#[wasm_bindgen]
+pub fn city_by_id(id: &str) -> Result<JsValue, JsError> {
+    let id: CityId = parse_id(id)?;
+    with_db(|db| match db.ancestors_of(id) {
+        Some(AdminPath {
+            country,
+            state: Some(state),
+            city: Some(city),
+        }) => to_js(&CityView {
+            country,
+            state,
+            city,
+        }),
+        _ => Ok(JsValue::UNDEFINED),
+    })
 }
 
 /* --------------------------------------------------------------------------
-   State Search
+   Country Search
 -------------------------------------------------------------------------- */
 
 #[wasm_bindgen]
-pub fn search_state_substring(substr: &str) -> JsValue {
-    let db = DB.get().unwrap();
+pub fn search_countries_by_phone(phone: &str) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let items: Vec<_> = db
+            .find_countries_by_phone_code(phone)
+            .iter()
+            .map(|c| CountryView(c))
+            .collect();
 
-    let out: Vec<_> = db
-        .find_states_by_substring(substr)
-        .into_iter()
-        .map(|(state, country)| StateView { country, state })
-        .collect();
+        to_js(&items)
+    })
+}
 
-    to_value(&out).unwrap()
+/// The calling code `input` starts with (`"+44"`, or a whole number) as
+/// `{ code, countries, primary_country }`, or `undefined`. `countries` holds
+/// ISO2 codes, primary first.
+#[wasm_bindgen]
+pub fn lookup_dial_code(input: &str) -> Result<JsValue, JsError> {
+    with_db(|db| match db.lookup_dial_code(input) {
+        Some(dial) => to_js(&dial),
+        None => Ok(JsValue::UNDEFINED),
+    })
 }
 
-/* --------------------------------------------------------------------------
-   City Search
--------------------------------------------------------------------------- */
+/// Every calling code as `[{ code, countries, primary_country }]`, in
+/// numeric order.
+#[wasm_bindgen]
+pub fn dial_codes() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.dial_codes()))
+}
+
+/// Countries using the currency `code` (case-insensitive, e.g. `"EUR"`).
+#[wasm_bindgen]
+pub fn search_countries_by_currency(code: &str) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let items: Vec<_> = db
+            .find_countries_by_currency(code)
+            .into_iter()
+            .map(CountryView)
+            .collect();
+        to_js(&items)
+    })
+}
 
+/// Countries whose currency name contains `name` (case and accents ignored,
+/// e.g. `"franc"`).
 #[wasm_bindgen]
-pub fn search_city_substring(substr: &str) -> JsValue {
-    let db = DB.get().unwrap();
+pub fn search_countries_by_currency_name(name: &str) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let items: Vec<_> = db
+            .find_countries_by_currency_name(name)
+            .into_iter()
+            .map(CountryView)
+            .collect();
+        to_js(&items)
+    })
+}
 
-    let out: Vec<_> = db
-        .find_cities_by_substring(substr)
-        .into_iter()
-        .map(|(city, state, country)| CityView {
-            country,
-            state,
-            city,
-        })
-        .collect();
+/// Every currency as `[{ code, name, symbol, countries }]`, sorted by code;
+/// `countries` holds ISO2 codes.
+#[wasm_bindgen]
+pub fn currencies() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.currencies()))
+}
 
-    to_value(&out).unwrap()
+/// Bare calling-code digits of `input` ("+49 ", "0049", "(49)" → "49"), or
+/// `undefined` if it isn't a phone code.
+#[wasm_bindgen]
+pub fn normalize_phone_code(input: &str) -> Option<String> {
+    geodb_core::phone::normalize_phone_code(input)
+}
+
+/// Parse an optional search language ("de", "german", ...), defaulting to
+/// the engine's `"simple"` ASCII lowercasing.
+fn parse_language(language: Option<String>) -> Result<SearchLanguage, JsError> {
+    language
+        .map_or(Ok(SearchLanguage::Simple), |l| l.parse())
+        .map_err(|e: String| JsError::new(&e))
+}
+
+/// Comparison form of `text` ("  São Paulo" → "sao paulo" with `"en"`), the
+/// folding smart search applies to queries and names. Throws for an unknown
+/// `language`.
+#[wasm_bindgen]
+pub fn fold_key(text: &str, language: Option<String>) -> Result<String, JsError> {
+    Ok(geodb_core::fold_key(text, parse_language(language)?))
+}
+
+/// `true` if `a` and `b` have the same `fold_key` under `language`.
+#[wasm_bindgen]
+pub fn equals_folded(a: &str, b: &str, language: Option<String>) -> Result<bool, JsError> {
+    Ok(geodb_core::equals_folded(a, b, parse_language(language)?))
 }
 
 /* --------------------------------------------------------------------------
-   Smart Search (country + state + city + phone)
+   State Search
 -------------------------------------------------------------------------- */
 
 #[wasm_bindgen]
-pub fn smart_search(query: &str) -> JsValue {
-    let db = DB.get().unwrap();
-    let hits = db.smart_search(query);
+pub fn search_state_substring(substr: &str) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let out: Vec<_> = db
+            .find_states_by_substring(substr)
+            .into_iter()
+            .map(|(state, country)| StateView { country, state })
+            .collect();
+
+        to_js(&out)
+    })
+}
+
+/* --------------------------------------------------------------------------
+   City Search
+-------------------------------------------------------------------------- */
 
-    // Map to JS serializable wrappers while preserving order
-    let array = js_sys::Array::new();
-    for hit in hits {
-        let v = match hit.item {
-            SmartItem::Country(c) => to_value(&CountryView(c)).unwrap(),
-            SmartItem::State { country, state } => to_value(&StateView { country, state }).unwrap(),
-            SmartItem::City {
+#[wasm_bindgen]
+pub fn search_city_substring(substr: &str) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let out: Vec<_> = db
+            .find_cities_by_substring(substr)
+            .into_iter()
+            .map(|(city, state, country)| CityView {
                 country,
                 state,
                 city,
-            } => to_value(&CityView {
+            })
+            .collect();
+
+        to_js(&out)
+    })
+}
+
+/// `search_city_substring`, filtered before anything crosses into JS.
+///
+/// `min_pop`/`max_pop` bound the population of the city's country (the
+/// dataset has none for cities), `country` is an ISO2 code, `limit` caps
+/// the result and `offset` skips that many matches (for paging); pass
+/// `undefined` (or leave trailing arguments out) to skip a filter.
+#[wasm_bindgen]
+pub fn search_city_substring_filtered(
+    substr: &str,
+    min_pop: Option<f64>,
+    country: Option<String>,
+    max_pop: Option<f64>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<JsValue, JsError> {
+    let mut filter =
+        CityFilter::default().with_population(min_pop.map(|p| p as i64), max_pop.map(|p| p as i64));
+    filter.country = country.filter(|c| !c.trim().is_empty());
+    filter.limit = limit.map(|n| n as usize);
+    filter.offset = offset.unwrap_or(0) as usize;
+    cities_to_js(substr, &filter)
+}
+
+/// `search_city_substring` limited to the box from (`south`, `west`) to
+/// (`north`, `east`) in degrees; `west > east` crosses the antimeridian.
+/// `limit` and `offset` page through the matches.
+#[wasm_bindgen]
+pub fn search_city_substring_in_bounds(
+    substr: &str,
+    south: f64,
+    west: f64,
+    north: f64,
+    east: f64,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<JsValue, JsError> {
+    let mut filter = CityFilter::default().with_bounds(south, west, north, east);
+    filter.limit = limit.map(|n| n as usize);
+    filter.offset = offset.unwrap_or(0) as usize;
+    cities_to_js(substr, &filter)
+}
+
+fn cities_to_js(substr: &str, filter: &CityFilter) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let out: Vec<_> = db
+            .find_cities_by_substring_filtered(substr, filter)
+            .into_iter()
+            .map(|(city, state, country)| CityView {
                 country,
                 state,
                 city,
             })
-            .unwrap(),
-        };
-        array.push(&v);
+            .collect();
+
+        to_js(&out)
+    })
+}
+
+/* --------------------------------------------------------------------------
+   Smart Search (country + state + city + phone)
+-------------------------------------------------------------------------- */
+
+/// Options object accepted by `smart_search`; every field is optional.
+///
+/// Unlike the core default, phone-code hits are off: in a search-as-you-type
+/// input a number is more often a street number than a calling code.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SmartSearchOptions {
+    /// Also return countries whose calling code matches ("+41", "41").
+    pub include_phone: bool,
+    /// Also return countries whose currency code or name equals the query
+    /// ("CHF", "Swiss franc"). Off like phone codes: typing "eur" would list
+    /// the eurozone.
+    pub include_currency: bool,
+    /// Leave out cities: countries and states come back without the city
+    /// scan, quick enough to paint while the full search (without this
+    /// flag) runs on the next frame.
+    pub skip_cities: bool,
+    /// Only return hits with coordinates.
+    pub must_have_coords: bool,
+    /// Leave out territories and everything inside them.
+    pub exclude_territories: bool,
+    /// Only return countries with at least this many inhabitants.
+    pub min_population: Option<i64>,
+    /// Analyzer for query and names: "simple", "ascii"/"en", "de", "tr".
+    pub language: SearchLanguage,
+    /// Return at most this many hits, so large result sets don't cross
+    /// into JS.
+    pub limit: Option<usize>,
+    /// Skip this many of the best hits (page `n` starts at `n * limit`).
+    pub offset: usize,
+    /// Drop hits scoring below this.
+    pub min_score: Option<i32>,
+    /// Typos tolerated per name ("berln" → Berlin); 0, the default, is off.
+    pub max_edit_distance: usize,
+    /// Boosts for the current user, e.g.
+    /// `{ recent_city_ids: ["US/43/2187"], preferred_countries: ["US"] }`.
+    pub session: SearchSessionHints,
+    /// Optional fields of the hits, e.g. `{ translations: false }`.
+    pub view: ViewOptions,
+}
+
+impl SmartSearchOptions {
+    /// Read the options object; `undefined` or `null` gives the defaults.
+    fn from_js(options: JsValue) -> Result<Self, JsError> {
+        if options.is_undefined() || options.is_null() {
+            return Ok(Self::default());
+        }
+        serde_wasm_bindgen::from_value(options)
+            .map_err(|e| JsError::new(&format!("invalid smart_search options: {e}")))
+    }
+
+    fn to_search_options(&self) -> SearchOptions {
+        let mut options = SearchOptions::default()
+            .with_include_phone(self.include_phone)
+            .with_include_currency(self.include_currency)
+            .with_include_cities(!self.skip_cities)
+            .with_must_have_coords(self.must_have_coords)
+            .with_exclude_territories(self.exclude_territories)
+            .with_language(self.language);
+        options.min_population = self.min_population;
+        options.limit = self.limit;
+        options.offset = self.offset;
+        options.min_score = self.min_score;
+        options.max_edit_distance = self.max_edit_distance;
+        options.session = self.session.clone();
+        options
     }
-    array.into()
 }
 
+/// Smart search; `options` is an optional [`SmartSearchOptions`] object,
+/// e.g. `smart_search("+41", { include_phone: true })`.
 #[wasm_bindgen]
-pub fn get_stats() -> JsValue {
-    let db = DB.get().unwrap();
-    let stats = db.stats();
-    let stats = json!({
-        "countries": stats.countries,
-        "states": stats.states,
-        "cities": stats.cities
-    });
+pub fn smart_search(query: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let options = SmartSearchOptions::from_js(options)?;
+    let view = options.view;
+    let options = options.to_search_options();
+    with_db(|db| {
+        // Map to JS serializable wrappers while preserving order
+        let array = js_sys::Array::new();
+        for hit in loading::smart_search(db, query, &options) {
+            array.push(&to_js(&hit.to_view_with(view))?);
+        }
+        Ok(array.into())
+    })
+}
+
+/// Prefix autocomplete for search-as-you-type inputs: at most `limit`
+/// countries, states and cities whose name starts with `prefix`, biggest
+/// countries first. Takes the same `options` as `smart_search`; their
+/// `limit`, `offset` and phone settings don't apply.
+#[wasm_bindgen]
+pub fn autocomplete(prefix: &str, limit: usize, options: JsValue) -> Result<JsValue, JsError> {
+    let options = SmartSearchOptions::from_js(options)?;
+    let view = options.view;
+    let options = options.to_search_options();
+    with_db(|db| {
+        let array = js_sys::Array::new();
+        for hit in db.autocomplete_with(prefix, limit, &options) {
+            array.push(&to_js(&hit.to_view_with(view))?);
+        }
+        Ok(array.into())
+    })
+}
+
+/* --------------------------------------------------------------------------
+   Proximity Search (name match, nearest first)
+-------------------------------------------------------------------------- */
+
+#[wasm_bindgen]
+pub fn smart_search_near(
+    query: &str,
+    lat: f64,
+    lng: f64,
+    radius_km: f64,
+) -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let array = js_sys::Array::new();
+        for near in db.smart_search_near(query, lat, lng, radius_km) {
+            let v = to_js(&near.hit.to_view())?;
+            js_sys::Reflect::set(
+                &v,
+                &JsValue::from_str("distance_km"),
+                &JsValue::from_f64(near.distance_km),
+            )
+            .map_err(|_| JsError::new("failed to set distance_km on result"))?;
+            array.push(&v);
+        }
+        Ok(array.into())
+    })
+}
+
+/// `smart_search` results encoded as MessagePack (a `Uint8Array` in JS),
+/// with the same fields as the JSON objects but a smaller payload. Takes the
+/// same `options` as `smart_search`.
+#[cfg(feature = "msgpack")]
+#[wasm_bindgen]
+pub fn smart_search_msgpack(query: &str, options: JsValue) -> Result<Vec<u8>, JsError> {
+    let options = SmartSearchOptions::from_js(options)?;
+    let view = options.view;
+    let options = options.to_search_options();
+    with_db(|db| {
+        let hits = loading::smart_search(db, query, &options);
+        geodb_core::msgpack::to_vec(&render_hits_with(&hits, ResponseFormat::Native, view))
+            .map_err(|e| JsError::new(&e.to_string()))
+    })
+}
 
-    to_value(&stats).unwrap()
+#[wasm_bindgen]
+pub fn get_stats() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.stats()))
+}
+
+/// Optional data in the loaded database as `{ coordinates, city_aliases,
+/// ... }` flags, for feature detection. Cities load in the background, so
+/// the city flags are only final once `loaded_level()` is `"city"`.
+#[wasm_bindgen]
+pub fn capabilities() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.capabilities()))
+}
+
+/// Code and data versions as `{ crate_version, binding, features,
+/// dataset_version, data_fingerprint, countries, states, cities }`, for bug
+/// reports. Like `capabilities()`, the fingerprint and counts cover the
+/// cities only once `loaded_level()` is `"city"`.
+#[wasm_bindgen]
+pub fn version_info() -> Result<JsValue, JsError> {
+    with_db(|db| {
+        let info = db.version_info().with_binding(concat!(
+            "geodb-wasm ",
+            env!("CARGO_PKG_VERSION"),
+            " (",
+            env!("GEODB_EMBEDDED"),
+            ")"
+        ));
+        to_js(&info)
+    })
+}
+
+/// Distinct city timezones as `[{ value, count }]`, sorted by value.
+#[wasm_bindgen]
+pub fn distinct_timezones() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.distinct_timezones()))
+}
+
+/// Distinct country currency codes as `[{ value, count }]`.
+#[wasm_bindgen]
+pub fn distinct_currencies() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.distinct_currencies()))
+}
+
+/// Distinct country regions as `[{ value, count }]`.
+#[wasm_bindgen]
+pub fn distinct_regions() -> Result<JsValue, JsError> {
+    with_db(|db| to_js(&db.distinct_regions()))
 }
@@ -0,0 +1,228 @@
+//! Two-stage initialization of the embedded database.
+//!
+//! `start()` decodes a skeleton with all countries and states right away, so
+//! country pickers work as soon as `init()` resolved. The cities follow in
+//! the background, a few countries per event-loop turn; searches see them
+//! as they arrive. `on_ready(callback)` reports each stage:
+//!
+//! ```javascript
+//! await init();
+//! on_ready(level => {
+//!   if (level === 'state') renderCountryPicker(); // countries + states
+//!   if (level === 'city') enableCitySearch();     // everything
+//! });
+//! ```
+//!
+//! Smart searches go through the embedded search index (built with the
+//! default options) when the options allow it, so a query doesn't fold every
+//! name again.
+use geodb_core::{AdminLevel, GeoDb, SearchIndex, SearchOptions, SmartHit, StandardBackend};
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::prelude::*;
+
+thread_local! {
+    static DB: RefCell<Option<GeoDb<StandardBackend>>> = const { RefCell::new(None) };
+    static SEARCH_INDEX: RefCell<Option<SearchIndex>> = const { RefCell::new(None) };
+    static LEVEL: Cell<Option<AdminLevel>> = const { Cell::new(None) };
+    static GENERATION: Cell<u64> = const { Cell::new(0) };
+    static LISTENERS: RefCell<Vec<js_sys::Function>> = const { RefCell::new(Vec::new()) };
+}
+
+fn level_name(level: AdminLevel) -> &'static str {
+    match level {
+        AdminLevel::Country => "country",
+        AdminLevel::State => "state",
+        AdminLevel::City => "city",
+    }
+}
+
+/// Run `f` on the database loaded so far.
+pub(crate) fn with_db<R>(
+    f: impl FnOnce(&GeoDb<StandardBackend>) -> Result<R, JsError>,
+) -> Result<R, JsError> {
+    DB.with(|db| match db.borrow().as_ref() {
+        Some(db) => f(db),
+        None => Err(JsError::new("geodb is not initialized (the embedded database failed to load or init() was not awaited)")),
+    })
+}
+
+/// Smart search on `db`, through the search index if there is one for
+/// `options`.
+pub(crate) fn smart_search<'a>(
+    db: &'a GeoDb<StandardBackend>,
+    query: &str,
+    options: &SearchOptions,
+) -> Vec<SmartHit<'a, StandardBackend>> {
+    SEARCH_INDEX.with(|index| match index.borrow().as_ref() {
+        Some(index) => db.smart_search_indexed(query, options, index),
+        None => db.smart_search_with(query, options),
+    })
+}
+
+/// Changes whenever data is added; cached results older than that are stale.
+pub(crate) fn generation() -> u64 {
+    GENERATION.with(Cell::get)
+}
+
+/// Install the first stage and report it.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn set_skeleton(db: GeoDb<StandardBackend>, level: AdminLevel) {
+    DB.with(|slot| *slot.borrow_mut() = Some(db));
+    GENERATION.with(|g| g.set(g.get() + 1));
+    reach(level);
+}
+
+/// Decode the embedded search index; without it searches scan all names.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn set_search_index(bytes: &[u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+    match SearchIndex::from_bytes(bytes) {
+        Ok(index) => SEARCH_INDEX.with(|slot| *slot.borrow_mut() = Some(index)),
+        Err(e) => web_sys::console::error_1(&format!("✗ Search index load failed: {e}").into()),
+    }
+}
+
+/// Record that `level` is loaded and tell the listeners (outside of any
+/// borrow, so they may query right away).
+#[cfg(target_arch = "wasm32")]
+fn reach(level: AdminLevel) {
+    LEVEL.with(|l| l.set(Some(level)));
+    let listeners = LISTENERS.with(|l| {
+        if level == AdminLevel::City {
+            std::mem::take(&mut *l.borrow_mut())
+        } else {
+            l.borrow().clone()
+        }
+    });
+    let arg = JsValue::from_str(level_name(level));
+    for listener in listeners {
+        if let Err(e) = listener.call1(&JsValue::NULL, &arg) {
+            web_sys::console::error_2(&"geodb on_ready callback failed:".into(), &e);
+        }
+    }
+}
+
+/// Attach the city stream in slices of about `SLICE_MS`, yielding to the
+/// event loop in between.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn load_cities<I>(mut chunks: I)
+where
+    I: Iterator<Item = geodb_core::Result<geodb_core::staged::CityChunk<StandardBackend>>>
+        + 'static,
+{
+    use wasm_bindgen::JsCast;
+    const SLICE_MS: f64 = 8.0;
+
+    let deadline = js_sys::Date::now() + SLICE_MS;
+    let mut attached = false;
+    while js_sys::Date::now() < deadline {
+        let result = match chunks.next() {
+            Some(Ok(chunk)) => DB.with(|db| match db.borrow_mut().as_mut() {
+                Some(db) => db.attach_cities(chunk),
+                None => Ok(()),
+            }),
+            Some(Err(e)) => Err(e),
+            None => {
+                GENERATION.with(|g| g.set(g.get() + 1));
+                web_sys::console::log_1(&"✓ Loaded all cities".into());
+                reach(AdminLevel::City);
+                return;
+            }
+        };
+        if let Err(e) = result {
+            web_sys::console::error_1(&format!("✗ Loading cities failed: {e}").into());
+            return;
+        }
+        attached = true;
+    }
+    if attached {
+        GENERATION.with(|g| g.set(g.get() + 1));
+    }
+    let next = Closure::once_into_js(move || load_cities(chunks));
+    crate::search_handle::set_timeout(next.unchecked_ref(), 0);
+}
+
+/// SHA-256 of `bytes` as lowercase hex.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Check `data` against the SHA-256 `expected` (hex) in slices of about
+/// `SLICE_MS`, yielding to the event loop in between like `load_cities`,
+/// then call `then` with the result; a mismatch is logged.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn verify_in_background(
+    data: &'static [u8],
+    expected: &'static str,
+    what: &'static str,
+    then: Box<dyn FnOnce(bool)>,
+) {
+    use sha2::{Digest, Sha256};
+
+    fn step(
+        mut sha: Sha256,
+        mut rest: &'static [u8],
+        expected: &'static str,
+        what: &'static str,
+        then: Box<dyn FnOnce(bool)>,
+    ) {
+        use wasm_bindgen::JsCast;
+        const SLICE_MS: f64 = 8.0;
+        const CHUNK: usize = 64 * 1024;
+
+        let deadline = js_sys::Date::now() + SLICE_MS;
+        while !rest.is_empty() && js_sys::Date::now() < deadline {
+            let (head, tail) = rest.split_at(CHUNK.min(rest.len()));
+            sha.update(head);
+            rest = tail;
+        }
+        if rest.is_empty() {
+            let checksum: String = sha.finalize().iter().map(|b| format!("{b:02x}")).collect();
+            let ok = checksum == expected;
+            if !ok {
+                web_sys::console::error_1(
+                    &format!(
+                        "✗ Embedded {what} checksum mismatch: expected {expected}, got {checksum}"
+                    )
+                    .into(),
+                );
+            }
+            then(ok);
+            return;
+        }
+        let next = Closure::once_into_js(move || step(sha, rest, expected, what, then));
+        crate::search_handle::set_timeout(next.unchecked_ref(), 0);
+    }
+
+    step(Sha256::new(), data, expected, what, then);
+}
+
+/// Level loaded so far: `"state"` once countries and states are available,
+/// `"city"` once the cities are too, `undefined` before (or if the database
+/// failed to load).
+#[wasm_bindgen]
+pub fn loaded_level() -> Option<String> {
+    LEVEL.with(Cell::get).map(|l| level_name(l).to_string())
+}
+
+/// Call `callback(level)` for every loaded level: right away for the levels
+/// already reached, later for the rest (`"state"`, then `"city"`).
+#[wasm_bindgen]
+pub fn on_ready(callback: js_sys::Function) {
+    let reached = LEVEL.with(Cell::get);
+    for level in [AdminLevel::State, AdminLevel::City] {
+        if reached.is_some_and(|r| r >= level) {
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(level_name(level)));
+        }
+    }
+    if reached != Some(AdminLevel::City) {
+        LISTENERS.with(|l| l.borrow_mut().push(callback));
+    }
+}
@@ -0,0 +1,155 @@
+//! Debounced search for search-as-you-type inputs.
+//!
+//! Calling `smart_search` on every keystroke scans the whole database each
+//! time. A [`SearchHandle`] waits until the input has been quiet for
+//! `min_interval_ms` before running the query; every newer call supersedes
+//! the pending one. Freeing the handle cancels the pending query.
+//!
+//! ```javascript
+//! const handle = create_search_handle(150);
+//! input.addEventListener('input', async () => {
+//!   const results = await handle.search(input.value);
+//!   if (results === null) return; // superseded by a newer keystroke
+//!   render(results);
+//! });
+//! ```
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    pub(crate) fn set_timeout(handler: &js_sys::Function, timeout: i32) -> i32;
+
+    #[wasm_bindgen(js_name = clearTimeout)]
+    fn clear_timeout(id: i32);
+}
+
+/// A scheduled query that hasn't run yet.
+struct Pending {
+    timer: i32,
+    resolve: js_sys::Function,
+    // kept alive until the timer fires or is cleared
+    callback: Closure<dyn FnMut()>,
+}
+
+#[derive(Default)]
+struct State {
+    pending: Option<Pending>,
+    // callback of the last fired timer; can't be dropped while it runs
+    fired: Option<Closure<dyn FnMut()>>,
+    // last executed query, the data generation it ran on, and its result
+    last: Option<(String, u64, JsValue)>,
+}
+
+impl State {
+    /// Drop the pending query, resolving its promise with `null`.
+    fn cancel(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            clear_timeout(pending.timer);
+            let _ = pending.resolve.call1(&JsValue::NULL, &JsValue::NULL);
+        }
+    }
+
+    fn run(&mut self, query: &str) -> Result<JsValue, JsError> {
+        // cities arriving in the background widen the results
+        let generation = crate::loading::generation();
+        if let Some((last_query, last_generation, result)) = &self.last {
+            if last_query == query && *last_generation == generation {
+                return Ok(result.clone());
+            }
+        }
+        let result = crate::smart_search(query, JsValue::UNDEFINED)?;
+        self.last = Some((query.to_string(), generation, result.clone()));
+        Ok(result)
+    }
+}
+
+/// Debounced `smart_search`; create with [`create_search_handle`].
+#[wasm_bindgen]
+pub struct SearchHandle {
+    min_interval_ms: u32,
+    state: Rc<RefCell<State>>,
+}
+
+/// Create a [`SearchHandle`] that waits `min_interval_ms` of quiet before
+/// running a query.
+#[wasm_bindgen]
+pub fn create_search_handle(min_interval_ms: u32) -> SearchHandle {
+    SearchHandle::new(min_interval_ms)
+}
+
+#[wasm_bindgen]
+impl SearchHandle {
+    #[wasm_bindgen(constructor)]
+    pub fn new(min_interval_ms: u32) -> SearchHandle {
+        SearchHandle {
+            min_interval_ms,
+            state: Rc::new(RefCell::new(State::default())),
+        }
+    }
+
+    /// Schedule `query`, superseding any pending one.
+    ///
+    /// Returns a promise resolving to the `smart_search` results, or to
+    /// `null` if a newer call (or [`SearchHandle::cancel`]) superseded it.
+    /// Repeating the last executed query reuses its results, unless cities
+    /// arrived in the meantime (see `on_ready`).
+    pub fn search(&self, query: String) -> js_sys::Promise {
+        self.state.borrow_mut().cancel();
+
+        let delay = i32::try_from(self.min_interval_ms).unwrap_or(i32::MAX);
+        let state = Rc::clone(&self.state);
+        js_sys::Promise::new(&mut |resolve: js_sys::Function, reject: js_sys::Function| {
+            let weak: Weak<RefCell<State>> = Rc::downgrade(&state);
+            let query = query.clone();
+            let settle = resolve.clone();
+            let callback = Closure::once(move || {
+                let Some(state) = weak.upgrade() else { return };
+                let mut state = state.borrow_mut();
+                if let Some(pending) = state.pending.take() {
+                    state.fired = Some(pending.callback);
+                }
+                let _ = match state.run(&query) {
+                    Ok(results) => settle.call1(&JsValue::NULL, &results),
+                    Err(e) => reject.call1(&JsValue::NULL, &JsValue::from(e)),
+                };
+            });
+            let timer = set_timeout(callback.as_ref().unchecked_ref(), delay);
+            state.borrow_mut().pending = Some(Pending {
+                timer,
+                resolve,
+                callback,
+            });
+        })
+    }
+
+    /// Cancel the pending query, if any; its promise resolves to `null`.
+    pub fn cancel(&self) {
+        self.state.borrow_mut().cancel();
+    }
+
+    /// `true` while a query is waiting to run.
+    #[wasm_bindgen(getter)]
+    pub fn pending(&self) -> bool {
+        self.state.borrow().pending.is_some()
+    }
+
+    /// Quiet period in milliseconds.
+    #[wasm_bindgen(getter)]
+    pub fn min_interval_ms(&self) -> u32 {
+        self.min_interval_ms
+    }
+}
+
+impl Drop for SearchHandle {
+    // `free()` from JavaScript: the timer must not call the closure dropped
+    // with the state, and the pending promise still settles (to `null`)
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.try_borrow_mut() {
+            state.cancel();
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Shared query corpus: the same queries and expectations as the core
+//! (`geodb-core/tests/corpus.rs`) and Python (`geodb-py/tests/test_corpus.py`)
+//! tests. Run with `wasm-pack test --node crates/geodb-wasm`.
+use serde::Serialize;
+use serde_json::Value;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+static CORPUS: &str = include_str!("../../geodb-core/tests/fixtures/query_corpus.json");
+
+fn from_js(value: JsValue) -> Value {
+    serde_wasm_bindgen::from_value(value).expect("JSON-compatible result")
+}
+
+fn to_js(value: &Value) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap()
+}
+
+fn run(op: &str, query: &str, options: &Value) -> Value {
+    let result = match op {
+        "smart_search" => geodb_wasm::smart_search(query, to_js(options)),
+        "countries_by_phone" => geodb_wasm::search_countries_by_phone(query),
+        "states_by_substring" => geodb_wasm::search_state_substring(query),
+        "cities_by_substring" => geodb_wasm::search_city_substring(query),
+        "normalize_phone_code" => return geodb_wasm::normalize_phone_code(query).into(),
+        "fold_key" => {
+            let language = options["language"].as_str().map(String::from);
+            let key = geodb_wasm::fold_key(query, language);
+            return key
+                .unwrap_or_else(|_| panic!("fold_key({query:?}) threw"))
+                .into();
+        }
+        other => panic!("unknown corpus op {other:?}"),
+    };
+    from_js(result.unwrap_or_else(|_| panic!("{op}({query:?}) threw")))
+}
+
+#[wasm_bindgen_test]
+fn wasm_matches_the_shared_corpus() {
+    #[cfg(target_arch = "wasm32")]
+    geodb_wasm::start();
+
+    let corpus: Value = serde_json::from_str(CORPUS).unwrap();
+    for case in corpus["queries"].as_array().unwrap() {
+        let (op, query) = (
+            case["op"].as_str().unwrap(),
+            case["query"].as_str().unwrap(),
+        );
+        let options = case.get("options").unwrap_or(&Value::Null);
+        assert_eq!(run(op, query, options), case["expected"], "{op}({query:?})");
+    }
+}
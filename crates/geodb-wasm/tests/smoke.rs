@@ -1,7 +1,7 @@
 use wasm_bindgen_test::*;
 
 // Import the wasm functions from this crate
-use geodb_wasm::{get_country_count, get_country_name};
+use geodb_wasm::{get_country_count, get_country_name, is_initialized};
 
 #[wasm_bindgen_test]
 fn can_get_country_count() {
@@ -9,7 +9,8 @@ fn can_get_country_count() {
     #[cfg(target_arch = "wasm32")]
     geodb_wasm::start();
 
-    let count = get_country_count();
+    assert!(is_initialized());
+    let count = get_country_count().expect("database loaded");
     assert!(count > 0, "expected at least one country, got {count}");
 }
 
@@ -18,6 +19,91 @@ fn can_lookup_country_name() {
     #[cfg(target_arch = "wasm32")]
     geodb_wasm::start();
 
-    let name = get_country_name("US");
+    let name = get_country_name("US").ok().flatten();
     assert!(name.is_some());
 }
+
+#[wasm_bindgen_test]
+fn embedded_database_has_checksum_and_version() {
+    #[cfg(target_arch = "wasm32")]
+    geodb_wasm::start();
+
+    // start() only initializes when the payload matches the checksum
+    #[cfg(target_arch = "wasm32")]
+    assert!(is_initialized());
+    let checksum = geodb_wasm::get_db_checksum();
+    assert_eq!(checksum.len(), 64);
+    assert!(checksum.bytes().all(|b| b.is_ascii_hexdigit()));
+    assert!(!geodb_wasm::get_db_version().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn search_handle_runs_only_the_last_query() {
+    use wasm_bindgen_futures::JsFuture;
+
+    #[cfg(target_arch = "wasm32")]
+    geodb_wasm::start();
+
+    let handle = geodb_wasm::create_search_handle(50);
+    let started = js_sys::Date::now();
+    let first = handle.search("Ber".into());
+    let second = handle.search("Berlin".into());
+    assert!(handle.pending());
+
+    // superseded right away by the second call
+    let superseded = JsFuture::from(first).await.expect("first search");
+    assert!(superseded.is_null());
+    assert!(handle.pending());
+
+    // the second one waits out the quiet period, then runs
+    let results = JsFuture::from(second).await.expect("second search");
+    assert!(js_sys::Date::now() - started >= 50.0);
+    assert!(!handle.pending());
+    let results = js_sys::Array::from(&results);
+    assert!(results.length() > 0);
+}
+
+#[wasm_bindgen_test]
+async fn cancelled_and_freed_search_handles_resolve_to_null() {
+    use wasm_bindgen_futures::JsFuture;
+
+    let handle = geodb_wasm::create_search_handle(1_000);
+    let cancelled = handle.search("Berlin".into());
+    handle.cancel();
+    assert!(!handle.pending());
+    assert!(JsFuture::from(cancelled).await.expect("cancel").is_null());
+
+    // free() while the query is waiting: the timer is cleared with it
+    let dropped = handle.search("Berlin".into());
+    drop(handle);
+    assert!(JsFuture::from(dropped).await.expect("drop").is_null());
+}
+
+#[wasm_bindgen_test]
+fn countries_are_ready_before_cities() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::prelude::*;
+
+    #[cfg(target_arch = "wasm32")]
+    geodb_wasm::start();
+
+    let level = geodb_wasm::loaded_level();
+    assert!(
+        matches!(level.as_deref(), Some("state" | "city")),
+        "{level:?}"
+    );
+
+    // levels already reached are reported right away
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let record = Rc::clone(&seen);
+    let callback = Closure::<dyn FnMut(String)>::new(move |level| record.borrow_mut().push(level));
+    geodb_wasm::on_ready(
+        callback
+            .as_ref()
+            .unchecked_ref::<js_sys::Function>()
+            .clone(),
+    );
+    callback.forget();
+    assert_eq!(seen.borrow().first().map(String::as_str), Some("state"));
+}
@@ -0,0 +1,356 @@
+// web/geodb-autocomplete.js
+//
+// <geodb-autocomplete>: a drop-in place picker around the geodb-wasm bindings.
+//
+//   <script type="module">
+//     import init, * as geodb from "./geodb_wasm.js";
+//     import { GeodbAutocomplete } from "./geodb-autocomplete.js";
+//     await init();
+//     GeodbAutocomplete.bindings = geodb;
+//   </script>
+//   <geodb-autocomplete placeholder="City or country" kinds="city"></geodb-autocomplete>
+//
+// With `trunk serve` no wiring is needed: the element picks up
+// `window.wasmBindings` once the Trunk loader has started the module.
+//
+// Attributes:
+//   placeholder   input placeholder
+//   debounce      quiet period in ms before searching (default 150)
+//   min-chars     minimum query length (default 2)
+//   max-results   maximum options shown (default 8)
+//   kinds         comma-separated filter: "country", "state", "city"
+//
+// Keyboard: ArrowDown/ArrowUp move, Enter selects, Escape closes.
+//
+// Events (bubbling, composed):
+//   geodb-select  detail: { item }   an option was chosen
+//   geodb-error   detail: { error }  a search failed
+//
+// Theming: CSS custom properties on the element
+//   --geodb-ac-font, --geodb-ac-fg, --geodb-ac-bg, --geodb-ac-muted,
+//   --geodb-ac-border, --geodb-ac-radius, --geodb-ac-active-bg,
+//   --geodb-ac-active-fg, --geodb-ac-max-height
+// and the parts `input`, `listbox`, `option`, `option-active`, `label`,
+// `detail`, `empty`. Override `renderOption(item, li)` to change the markup
+// of an option entirely.
+
+const TEMPLATE = document.createElement("template");
+TEMPLATE.innerHTML = `
+<style>
+  :host {
+    display: block;
+    position: relative;
+    font: var(--geodb-ac-font, inherit);
+    color: var(--geodb-ac-fg, inherit);
+  }
+  input {
+    box-sizing: border-box;
+    width: 100%;
+    padding: 8px;
+    font: inherit;
+    color: inherit;
+    background: var(--geodb-ac-bg, #fff);
+    border: 1px solid var(--geodb-ac-border, #ccc);
+    border-radius: var(--geodb-ac-radius, 4px);
+  }
+  ul {
+    position: absolute;
+    z-index: 10;
+    left: 0;
+    right: 0;
+    margin: 2px 0 0;
+    padding: 0;
+    list-style: none;
+    max-height: var(--geodb-ac-max-height, 320px);
+    overflow-y: auto;
+    background: var(--geodb-ac-bg, #fff);
+    border: 1px solid var(--geodb-ac-border, #ccc);
+    border-radius: var(--geodb-ac-radius, 4px);
+  }
+  ul[hidden] { display: none; }
+  li {
+    padding: 6px 8px;
+    cursor: pointer;
+  }
+  li[aria-selected="true"] {
+    background: var(--geodb-ac-active-bg, #eef3ff);
+    color: var(--geodb-ac-active-fg, inherit);
+  }
+  .detail, .empty {
+    color: var(--geodb-ac-muted, #555);
+    font-size: 0.875em;
+  }
+</style>
+<input part="input" role="combobox" aria-autocomplete="list" aria-expanded="false"
+       autocomplete="off" spellcheck="false"/>
+<ul part="listbox" role="listbox" hidden></ul>
+`;
+
+let nextId = 0;
+
+/** Subtitle for an option: where the place is. */
+function describe(item) {
+    switch (item.kind) {
+        case "city":
+            return [item.state, item.country].filter(Boolean).join(", ");
+        case "state":
+            return item.country || "";
+        default:
+            return item.iso2 || "";
+    }
+}
+
+export class GeodbAutocomplete extends HTMLElement {
+    /** Bindings used by all instances unless `element.bindings` is set. */
+    static bindings = null;
+
+    static get observedAttributes() {
+        return ["placeholder", "debounce"];
+    }
+
+    constructor() {
+        super();
+        this.attachShadow({ mode: "open" }).appendChild(TEMPLATE.content.cloneNode(true));
+        this._input = this.shadowRoot.querySelector("input");
+        this._list = this.shadowRoot.querySelector("ul");
+        this._listId = `geodb-ac-${nextId++}`;
+        this._list.id = this._listId;
+        this._input.setAttribute("aria-controls", this._listId);
+
+        this._bindings = null;
+        this._handle = null;
+        this._items = [];
+        this._active = -1;
+        this._selected = null;
+
+        this._onInput = this._onInput.bind(this);
+        this._onKeyDown = this._onKeyDown.bind(this);
+        this._onBlur = () => this.close();
+        this._onTrunkStarted = () => this._reset();
+    }
+
+    connectedCallback() {
+        this._input.placeholder = this.getAttribute("placeholder") || "";
+        this._input.addEventListener("input", this._onInput);
+        this._input.addEventListener("keydown", this._onKeyDown);
+        this._input.addEventListener("blur", this._onBlur);
+        // mousedown would blur the input before the click lands
+        this._list.addEventListener("mousedown", e => e.preventDefault());
+        this._list.addEventListener("click", e => {
+            const li = e.target.closest("li[data-index]");
+            if (li) this.select(Number(li.dataset.index));
+        });
+        window.addEventListener("TrunkApplicationStarted", this._onTrunkStarted);
+    }
+
+    disconnectedCallback() {
+        this._input.removeEventListener("input", this._onInput);
+        this._input.removeEventListener("keydown", this._onKeyDown);
+        this._input.removeEventListener("blur", this._onBlur);
+        window.removeEventListener("TrunkApplicationStarted", this._onTrunkStarted);
+        this._reset();
+    }
+
+    attributeChangedCallback(name, _old, value) {
+        if (name === "placeholder") this._input.placeholder = value || "";
+        if (name === "debounce") this._reset();
+    }
+
+    /** geodb-wasm bindings for this instance (overrides the static default). */
+    get bindings() {
+        return this._bindings || GeodbAutocomplete.bindings || window.wasmBindings || null;
+    }
+
+    set bindings(value) {
+        this._bindings = value;
+        this._reset();
+    }
+
+    /** The last selected item, or `null`. */
+    get value() {
+        return this._selected;
+    }
+
+    /** Text currently in the input. */
+    get query() {
+        return this._input.value;
+    }
+
+    set query(text) {
+        this._input.value = text;
+    }
+
+    focus(options) {
+        this._input.focus(options);
+    }
+
+    /** Select the option at `index` and fire `geodb-select`. */
+    select(index) {
+        const item = this._items[index];
+        if (!item) return;
+        this._selected = item;
+        this._input.value = item.name;
+        this.close();
+        this.dispatchEvent(new CustomEvent("geodb-select", {
+            detail: { item },
+            bubbles: true,
+            composed: true,
+        }));
+    }
+
+    /** Hide the option list and drop any pending search. */
+    close() {
+        if (this._handle) this._handle.cancel();
+        this._list.hidden = true;
+        this._input.setAttribute("aria-expanded", "false");
+        this._input.removeAttribute("aria-activedescendant");
+        this._active = -1;
+    }
+
+    /**
+     * Fill `li` for `item`. Override (on the class or an instance) to change
+     * the option markup; use `textContent`, the data is not HTML.
+     */
+    renderOption(item, li) {
+        const label = document.createElement("div");
+        label.setAttribute("part", "label");
+        label.textContent = item.emoji ? `${item.emoji} ${item.name}` : item.name;
+        li.appendChild(label);
+
+        const detail = describe(item);
+        if (detail) {
+            const small = document.createElement("div");
+            small.className = "detail";
+            small.setAttribute("part", "detail");
+            small.textContent = detail;
+            li.appendChild(small);
+        }
+    }
+
+    _number(name, fallback) {
+        const value = Number(this.getAttribute(name));
+        return Number.isFinite(value) && this.hasAttribute(name) ? value : fallback;
+    }
+
+    /** Drop the debounce handle so it's recreated with current settings. */
+    _reset() {
+        if (this._handle) {
+            this._handle.cancel();
+            if (typeof this._handle.free === "function") this._handle.free();
+        }
+        this._handle = null;
+    }
+
+    _search(query) {
+        const b = this.bindings;
+        if (!b) return Promise.reject(new Error("geodb-autocomplete: bindings are not loaded yet"));
+        if (typeof b.create_search_handle !== "function") {
+            return Promise.resolve(b.smart_search(query));
+        }
+        if (!this._handle) this._handle = b.create_search_handle(this._number("debounce", 150));
+        return this._handle.search(query);
+    }
+
+    async _onInput() {
+        const query = this._input.value.trim();
+        if (query.length < this._number("min-chars", 2)) {
+            this.close();
+            return;
+        }
+        let results;
+        try {
+            results = await this._search(query);
+        } catch (error) {
+            this.dispatchEvent(new CustomEvent("geodb-error", {
+                detail: { error },
+                bubbles: true,
+                composed: true,
+            }));
+            return;
+        }
+        // superseded by a newer keystroke, or the input changed meanwhile
+        if (results === null || this._input.value.trim() !== query) return;
+        this._render(Array.from(results));
+    }
+
+    _render(results) {
+        const kinds = (this.getAttribute("kinds") || "")
+            .split(",")
+            .map(k => k.trim())
+            .filter(Boolean);
+        this._items = results
+            .filter(item => !kinds.length || kinds.includes(item.kind))
+            .slice(0, this._number("max-results", 8));
+        this._active = -1;
+        this._list.replaceChildren();
+
+        if (!this._items.length) {
+            const li = document.createElement("li");
+            li.className = "empty";
+            li.setAttribute("part", "empty");
+            li.setAttribute("aria-disabled", "true");
+            li.textContent = "No matches";
+            this._list.appendChild(li);
+        }
+        this._items.forEach((item, index) => {
+            const li = document.createElement("li");
+            li.id = `${this._listId}-${index}`;
+            li.dataset.index = String(index);
+            li.setAttribute("role", "option");
+            li.setAttribute("part", "option");
+            li.setAttribute("aria-selected", "false");
+            this.renderOption(item, li);
+            this._list.appendChild(li);
+        });
+
+        this._list.hidden = false;
+        this._input.setAttribute("aria-expanded", "true");
+        this._input.removeAttribute("aria-activedescendant");
+    }
+
+    _move(delta) {
+        const count = this._items.length;
+        if (!count) return;
+        const next = this._active < 0 && delta < 0 ? count - 1 : (this._active + delta + count) % count;
+        const options = this._list.querySelectorAll("li[data-index]");
+        options.forEach((li, i) => {
+            const active = i === next;
+            li.setAttribute("aria-selected", String(active));
+            li.setAttribute("part", active ? "option option-active" : "option");
+        });
+        this._active = next;
+        this._input.setAttribute("aria-activedescendant", options[next].id);
+        options[next].scrollIntoView({ block: "nearest" });
+    }
+
+    _onKeyDown(e) {
+        const open = !this._list.hidden;
+        switch (e.key) {
+            case "ArrowDown":
+                if (open) this._move(1);
+                else this._onInput();
+                e.preventDefault();
+                break;
+            case "ArrowUp":
+                if (open) this._move(-1);
+                e.preventDefault();
+                break;
+            case "Enter":
+                if (open && this._active >= 0) {
+                    this.select(this._active);
+                    e.preventDefault();
+                }
+                break;
+            case "Escape":
+                if (open) {
+                    this.close();
+                    e.preventDefault();
+                }
+                break;
+        }
+    }
+}
+
+if (!customElements.get("geodb-autocomplete")) {
+    customElements.define("geodb-autocomplete", GeodbAutocomplete);
+}
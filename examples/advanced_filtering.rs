@@ -12,9 +12,9 @@ fn main() -> Result<()> {
     // Example 1: Find all countries in a specific region with a specific currency
     println!("--- Example 1: Countries in Europe using Euro ---");
     let euro_countries: Vec<_> = db
-        .countries()
-        .iter()
-        .filter(|c| c.region() == "Europe" && c.currency() == "EUR")
+        .find_countries_by_currency("EUR")
+        .into_iter()
+        .filter(|c| c.region() == "Europe")
         .collect();
 
     println!("Found {} countries:", euro_countries.len());
@@ -49,7 +49,7 @@ fn main() -> Result<()> {
         })
         .collect();
 
-    state_city_counts.sort_by(|a, b| b.2.cmp(&a.2));
+    state_city_counts.sort_by_key(|s| std::cmp::Reverse(s.2));
 
     for (i, (country, state, count)) in state_city_counts.iter().take(10).enumerate() {
         println!("{}. {} ({}) - {} cities", i + 1, state, country, count);
@@ -118,7 +118,7 @@ fn main() -> Result<()> {
         .map(|c| (c.name(), c.states().len()))
         .collect();
 
-    country_state_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    country_state_counts.sort_by_key(|c| std::cmp::Reverse(c.1));
 
     for (i, (country, count)) in country_state_counts.iter().take(5).enumerate() {
         println!("{}. {} - {} states", i + 1, country, count);
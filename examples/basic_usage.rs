@@ -92,14 +92,15 @@ fn main() -> Result<()> {
 
     // Example 7: Using the cache
     println!("--- Example 7: Cache usage ---");
+    let cache = GeoDbCache::<StandardBackend>::new();
     println!("First load (will cache):");
     let start = std::time::Instant::now();
-    let _db1 = GeoDb::<StandardBackend>::load()?;
+    let _db1 = cache.load_default()?;
     println!("Time: {:?}", start.elapsed());
 
     println!("Second load (from cache):");
     let start = std::time::Instant::now();
-    let _db2 = GeoDb::<StandardBackend>::load()?;
+    let _db2 = cache.load_default()?;
     println!("Time: {:?}", start.elapsed());
     println!();
 
@@ -0,0 +1,59 @@
+//! Regenerate `crates/geodb-core/src/consts.rs` from the bundled dataset.
+//!
+//! Run after updating the dataset:
+//!
+//! ```text
+//! cargo run --example gen_country_consts
+//! ```
+
+use geodb_core::prelude::*;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+fn main() -> Result<()> {
+    let db = GeoDb::<StandardBackend>::load()?;
+
+    let mut countries: Vec<_> = db.countries().iter().filter(|c| c.id().is_some()).collect();
+    countries.sort_by_key(|c| c.iso2().to_ascii_uppercase());
+
+    let mut out = String::new();
+    out.push_str(
+        "// @generated by `cargo run --example gen_country_consts`; do not edit.\n\
+         //! Country constants, one per country in the bundled dataset.\n\
+         //!\n\
+         //! ```\n\
+         //! use geodb_core::consts;\n\
+         //!\n\
+         //! assert_eq!(consts::DE.as_str(), \"DE\");\n\
+         //! ```\n\
+         use crate::country_id::CountryId;\n",
+    );
+    for c in &countries {
+        let iso2 = c.iso2().to_ascii_uppercase();
+        let _ = write!(
+            out,
+            "\n/// {}\npub const {iso2}: CountryId = CountryId::from_ascii(*b\"{iso2}\");\n",
+            c.name(),
+        );
+    }
+    out.push_str("\n/// All constants above, sorted by code.\npub const ALL: &[CountryId] = &[\n");
+    // 24 codes per line, like rustfmt
+    for line in countries.chunks(24) {
+        let codes: Vec<String> = line.iter().map(|c| c.iso2().to_ascii_uppercase()).collect();
+        let _ = writeln!(out, "    {},", codes.join(", "));
+    }
+    out.push_str("];\n");
+
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "crates",
+        "geodb-core",
+        "src",
+        "consts.rs",
+    ]
+    .iter()
+    .collect();
+    std::fs::write(&path, out)?;
+    println!("Wrote {} constants to {}", countries.len(), path.display());
+    Ok(())
+}
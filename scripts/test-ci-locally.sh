@@ -121,6 +121,25 @@ else
     exit 1
 fi
 
+# geodb-core feature matrix: the binary read path must build without `json`
+echo -e "${YELLOW}Step 6a: Checking geodb-core feature matrix...${NC}"
+if cargo check --locked -p geodb-core --no-default-features \
+    && cargo check --locked -p geodb-core --all-features \
+    && cargo test --locked -p geodb-core --no-default-features --test read_path \
+    && cargo test --locked -p geodb-core --features districts --test districts \
+    && cargo test --locked -p geodb-core --features multilingual-cities --test labels \
+    && cargo test --locked -p geodb-core --features embed-data --test embedded \
+    && cargo test --locked -p geodb-core --features msgpack --test msgpack \
+    && cargo test --locked -p geodb-core --features signed --test signed \
+    && cargo test --locked -p geodb-core --features geonames --test geonames \
+    && cargo test --locked -p geodb-core --features geo-interop --test geo_interop \
+    && cargo test --locked -p geodb-core --features bundle,signed --test bundle; then
+    echo -e "${GREEN}✓ feature matrix passed${NC}\n"
+else
+    echo -e "${RED}✗ feature matrix failed${NC}\n"
+    exit 1
+fi
+
 # Run WASM tests for geodb-wasm if tooling is available
 echo -e "${YELLOW}Step 6b: Running geodb-wasm tests (wasm32, Node)...${NC}"
 if command -v wasm-pack &> /dev/null; then